@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Args;
+use itertools::Itertools;
+
+use solver_core::prelude::{
+    Action, Advisor, Blind, Card, Deck, DeckTracker, GameState, JokerSlot, Score,
+    TranspositionTable,
+};
+
+use crate::discard_ev::parse_cards;
+use crate::render::render;
+use crate::stats::parse_jokers;
+
+#[derive(Debug, Args)]
+pub struct BestPlayArgs {
+    /// The cards in hand, like "AS KD 7C 7H 2S 9D JC 4H"
+    #[arg(required_unless_present = "save")]
+    hand: Option<String>,
+
+    /// Read the hand, deck, jokers and the rest of the round from one of
+    /// Balatro's save files, like `save.jkr`, instead
+    #[arg(
+        long = "save",
+        conflicts_with_all = ["hand", "hands", "discards", "seen", "jokers"]
+    )]
+    save: Option<PathBuf>,
+
+    /// How many hands are left this round, counting the next one
+    #[arg(long = "hands", default_value = "4")]
+    hands: u32,
+
+    /// How many discards are left this round
+    #[arg(long = "discards", default_value = "3")]
+    discards: u32,
+
+    /// Cards already played or discarded this round, which can't be drawn
+    #[arg(long = "seen", default_value = "")]
+    seen: String,
+
+    /// The jokers held, by name, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// How many of the best actions to list
+    #[arg(short = 'k', long = "top", default_value = "1")]
+    top: usize,
+
+    /// How many draws to sample for each discard, when there are too many to
+    /// try them all
+    #[arg(short = 's', long = "samples", default_value = "200")]
+    samples: usize,
+}
+
+/// The cards an action plays or discards, by name.
+fn describe(action: &Action, hand: &[Card]) -> String {
+    let (verb, indices) = match action {
+        Action::Play(indices) => ("Play", indices),
+        Action::Discard(indices) => ("Discard", indices),
+    };
+    let cards = indices.iter().map(|index| hand[*index]).collect_vec();
+    format!("{verb} {}", render(&cards))
+}
+
+/// The round as the arguments give it, and what's left to draw.
+fn round(args: &BestPlayArgs) -> Result<(GameState, DeckTracker)> {
+    if let Some(path) = &args.save {
+        let context = || format!("couldn't read {}", path.display());
+        let state =
+            GameState::from_save(&fs::read(path).with_context(context)?).with_context(context)?;
+        if state.hand.is_empty() {
+            bail!("the save isn't in the middle of a round, so there's no hand to play");
+        }
+        let tracker = DeckTracker::from_state(&state);
+        return Ok((state, tracker));
+    }
+
+    let hand = parse_cards(args.hand.as_deref().unwrap_or_default())?;
+    let jokers = parse_jokers(&args.jokers)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect();
+    let state = GameState::from_parts(
+        Deck::base_deck(),
+        hand,
+        args.hands,
+        args.discards,
+        0,
+        1,
+        Blind::Small,
+        jokers,
+        Vec::new(),
+    );
+
+    let mut tracker = DeckTracker::from_state(&state);
+    for card in parse_cards(&args.seen)? {
+        if !tracker.see(card) {
+            return Err(anyhow!("{card} was seen, but isn't left in the deck"));
+        }
+    }
+    Ok((state, tracker))
+}
+
+pub fn run(args: &BestPlayArgs) -> Result<()> {
+    let (state, tracker) = round(args)?;
+    let hand = &state.hand;
+
+    // Many discards leave the same cards in hand, and draw the same ones
+    let advisor = Advisor::new()
+        .with_samples(args.samples)
+        .with_table(Arc::new(TranspositionTable::new()));
+    for recommendation in advisor.recommend(hand, &tracker, &state, args.top.max(1)) {
+        let expected = Score::from(recommendation.expected);
+        if recommendation.margin > 0.0 {
+            let margin = Score::from(recommendation.margin);
+            println!(
+                "{}: {expected} expected ({margin} behind)",
+                describe(&recommendation.action, hand)
+            );
+        } else {
+            println!(
+                "{}: {expected} expected",
+                describe(&recommendation.action, hand)
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::output::write_atomically;
+
+#[derive(Clone, Debug, Args)]
+pub struct CheckpointArgs {
+    /// Save progress to this file every so often, and pick up from it if it
+    /// already holds progress on the same job
+    #[arg(long = "checkpoint")]
+    path: Option<PathBuf>,
+
+    /// How often to save progress, like `90s`, `5m` or `1h`
+    #[arg(
+        long = "checkpoint-interval",
+        default_value = "60s",
+        value_parser = parse_interval
+    )]
+    interval: Duration,
+}
+
+/// A length of time in seconds, minutes or hours, like `90s`, `5m` or `1h`.
+/// Bare numbers are seconds.
+fn parse_interval(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let (number, unit) = match text.char_indices().last() {
+        Some((index, 's')) => (&text[..index], 1),
+        Some((index, 'm')) => (&text[..index], 60),
+        Some((index, 'h')) => (&text[..index], 60 * 60),
+        _ => (text, 1),
+    };
+    let number = number
+        .trim()
+        .parse::<u64>()
+        .map_err(|err| err.to_string())?;
+    if number == 0 {
+        return Err("must be more than zero".to_owned());
+    }
+    Ok(Duration::from_secs(number * unit))
+}
+
+/// What every checkpoint file starts with, before the version of its format.
+const MAGIC: &[u8; 4] = b"BSCK";
+
+/// The version of the format checkpoints are written in.
+const VERSION: u8 = 1;
+
+/// What a checkpoint file holds.
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct Saved {
+    /// The job the progress is on, so it isn't picked up by a different one
+    pub(crate) job: String,
+    /// The progress on each stage of the job, by name
+    pub(crate) stages: BTreeMap<String, Value>,
+}
+
+impl Saved {
+    /// Read the checkpoint file at `path`, or `None` if there isn't one.
+    pub(crate) fn load(path: &Path) -> Result<Option<Self>> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(anyhow!("couldn't read {}: {err}", path.display())),
+        };
+        Self::decode(&bytes)
+            .map(Some)
+            .map_err(|err| anyhow!("{} isn't a checkpoint: {err}", path.display()))
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let Some(compressed) = bytes.strip_prefix(MAGIC.as_slice()) else {
+            return Err(anyhow!("it doesn't start with {MAGIC:?}"));
+        };
+        match compressed.split_first() {
+            Some((&VERSION, compressed)) => {
+                let mut json = Vec::new();
+                DeflateDecoder::new(compressed).read_to_end(&mut json)?;
+                Ok(serde_json::from_slice(&json)?)
+            }
+            Some((version, _)) => Err(anyhow!("it's in version {version} of the format")),
+            None => Err(anyhow!("it stops after the header")),
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        let mut encoder = DeflateEncoder::new(bytes, Compression::default());
+        serde_json::to_writer(&mut encoder, self)?;
+        Ok(encoder.finish()?)
+    }
+
+    /// Write to `path`, replacing it whole, so being interrupted leaves the
+    /// last save intact.
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        write_atomically(path, self.encode()?)
+    }
+}
+
+/// Saves a job's progress to a file every so often, and hands back what an
+/// interrupted run of the same job had saved.
+///
+/// A job is made of stages, each saving its own progress. The file is
+/// binary: [`MAGIC`], a version byte, then the progress as deflated JSON,
+/// which keeps the tallies small without a serialization format of its own.
+/// It's replaced whole on each save, so being interrupted mid-save leaves
+/// the last one intact.
+#[derive(Debug)]
+pub(crate) struct Checkpointer {
+    path: Option<PathBuf>,
+    interval: Duration,
+    job: String,
+    stages: Mutex<BTreeMap<String, Value>>,
+    last_saved: Mutex<Instant>,
+}
+
+impl Checkpointer {
+    /// Load the progress saved to the file in `args` on `job`, which should
+    /// describe everything that changes the results. Fails if the file holds
+    /// progress on another job.
+    pub(crate) fn open(args: &CheckpointArgs, job: String) -> Result<Self> {
+        let saved = match &args.path {
+            Some(path) => Saved::load(path)?,
+            None => None,
+        };
+        let stages = match saved {
+            Some(saved) if saved.job != job => {
+                return Err(anyhow!(
+                    "{} holds progress on a different job: {}",
+                    args.path.as_ref().unwrap().display(),
+                    saved.job
+                ));
+            }
+            Some(saved) => saved.stages,
+            None => BTreeMap::new(),
+        };
+        Ok(Self {
+            path: args.path.clone(),
+            interval: args.interval,
+            job,
+            stages: Mutex::new(stages),
+            last_saved: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// A checkpointer that never saves anything, for jobs run without one.
+    pub(crate) fn disabled() -> Self {
+        Self {
+            path: None,
+            interval: Duration::MAX,
+            job: String::new(),
+            stages: Mutex::default(),
+            last_saved: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Whether there was progress on the job to pick up.
+    pub(crate) fn is_resuming(&self) -> bool {
+        !self.stages.lock().unwrap().is_empty()
+    }
+
+    /// Whether progress is being saved at all.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// The progress saved on `stage`, if there is any.
+    pub(crate) fn resume<T: DeserializeOwned>(&self, stage: &str) -> Result<Option<T>> {
+        self.stages
+            .lock()
+            .unwrap()
+            .get(stage)
+            .map(|progress| serde_json::from_value(progress.clone()))
+            .transpose()
+            .map_err(|err| anyhow!("the checkpoint of {stage} is damaged: {err}"))
+    }
+
+    /// Record the progress on `stage`, and save everything if it's been long
+    /// enough since the last save, or if `now`.
+    pub(crate) fn update<T: Serialize>(&self, stage: &str, progress: &T, now: bool) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        self.stages
+            .lock()
+            .unwrap()
+            .insert(stage.to_owned(), serde_json::to_value(progress)?);
+        if !now && self.last_saved.lock().unwrap().elapsed() < self.interval {
+            return Ok(());
+        }
+
+        let saved = Saved {
+            job: self.job.clone(),
+            stages: self.stages.lock().unwrap().clone(),
+        };
+        saved.save(path)?;
+        *self.last_saved.lock().unwrap() = Instant::now();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let saved = Saved {
+            job: "hand-stats, seed 1, iterations 10".to_owned(),
+            stages: BTreeMap::from([("5 cards with []".to_owned(), Value::from(42))]),
+        };
+        let bytes = saved.encode().unwrap();
+        assert!(bytes.starts_with(MAGIC));
+
+        let loaded = Saved::decode(&bytes).unwrap();
+        assert_eq!(loaded.job, saved.job);
+        assert_eq!(loaded.stages, saved.stages);
+    }
+
+    #[test]
+    fn decode_test() {
+        assert!(Saved::decode(br#"{"job":"","stages":{}}"#).is_err());
+        assert!(Saved::decode(MAGIC).is_err());
+        assert!(Saved::decode(b"BSCK\x02").is_err());
+        assert!(Saved::decode(b"BSCK\x01not deflated").is_err());
+    }
+}
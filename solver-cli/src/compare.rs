@@ -0,0 +1,147 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use itertools::Itertools;
+use rand::prelude::*;
+use rayon::prelude::*;
+
+use solver_core::prelude::{
+    FlushHunter, GameState, Greedy, Mcts, Observer, RandomPlayer, RoundPlanner, RunSimulator,
+    RunSummary, ScoreBreakdown, Strategy,
+};
+
+/// The strategies that can be compared, by name.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, ValueEnum)]
+pub(crate) enum StrategyName {
+    /// Plays the highest-scoring hand every time
+    Greedy,
+    /// Discards towards flushes
+    FlushHunter,
+    /// Does everything at random
+    Random,
+    /// Plans each round with expectimax (slow)
+    Planner,
+    /// Plans each round with Monte Carlo Tree Search (very slow)
+    Mcts,
+}
+
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    /// The strategies to compare, separated by commas
+    #[arg(
+        short = 's',
+        long = "strategies",
+        value_delimiter = ',',
+        default_value = "greedy,flush-hunter,random"
+    )]
+    strategies: Vec<StrategyName>,
+
+    /// How many runs each strategy plays
+    #[arg(short = 'n', long = "runs", default_value = "100")]
+    runs: u64,
+
+    /// The seed of the first run; each run after uses the next seed, so every
+    /// strategy plays the same runs
+    #[arg(long = "seed", default_value = "0")]
+    seed: u64,
+
+    /// Run on a single thread (for profiling)
+    #[arg(long = "single-threaded", default_value = "false")]
+    single_threaded: bool,
+}
+
+/// Collects the score of every hand played.
+#[derive(Debug, Default)]
+struct HandScores(Mutex<Vec<f64>>);
+
+impl Observer for HandScores {
+    fn on_hand_played(&self, state: &mut GameState, breakdown: &ScoreBreakdown) {
+        self.0.lock().unwrap().push(breakdown.total.value());
+    }
+}
+
+fn simulate(strategy: impl Strategy, seed: u64) -> Result<(RunSummary, Vec<f64>)> {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let scores = Arc::new(HandScores::default());
+    let summary = RunSimulator::new(strategy)
+        .with_observer(Arc::clone(&scores) as Arc<dyn Observer>)
+        .run(GameState::fresh_run(&mut rng), &mut rng)?;
+    let scores = scores.0.lock().unwrap().clone();
+    Ok((summary, scores))
+}
+
+fn simulate_named(name: StrategyName, seed: u64) -> Result<(RunSummary, Vec<f64>)> {
+    match name {
+        StrategyName::Greedy => simulate(Greedy::default(), seed),
+        StrategyName::FlushHunter => simulate(FlushHunter::default(), seed),
+        StrategyName::Random => simulate(RandomPlayer, seed),
+        StrategyName::Planner => simulate(RoundPlanner::default(), seed),
+        StrategyName::Mcts => simulate(Mcts::default(), seed),
+    }
+}
+
+/// The value `fraction` of the way through sorted `values`.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(values: &[f64], fraction: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let index = ((values.len() - 1) as f64 * fraction).round() as usize;
+    values[index]
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn run(args: &CompareArgs) -> Result<()> {
+    let seeds = args.seed..args.seed + args.runs;
+
+    println!("Over {} runs each, from seed {}:", args.runs, args.seed);
+    println!(
+        "{:14} {:>8} {:>10} {:>10}   {:>10} {:>10} {:>10}",
+        "Strategy", "Win rate", "Mean ante", "Mean hands", "Hand p10", "Hand p50", "Hand p90"
+    );
+    for name in args.strategies.iter().copied().unique() {
+        let results: Vec<(RunSummary, Vec<f64>)> = if args.single_threaded {
+            seeds
+                .clone()
+                .map(|seed| simulate_named(name, seed))
+                .collect::<Result<_>>()?
+        } else {
+            seeds
+                .clone()
+                .into_par_iter()
+                .map(|seed| simulate_named(name, seed))
+                .collect::<Result<_>>()?
+        };
+
+        let runs = results.len().max(1) as f64;
+        let wins = results.iter().filter(|(summary, _)| summary.won).count() as f64;
+        let antes: u32 = results.iter().map(|(summary, _)| summary.final_ante).sum();
+        let hands: u32 = results
+            .iter()
+            .map(|(summary, _)| summary.hands_played)
+            .sum();
+        let scores = results
+            .into_iter()
+            .flat_map(|(_, scores)| scores)
+            .sorted_by(f64::total_cmp)
+            .collect_vec();
+
+        println!(
+            "{:14} {:>7.1}% {:>10.2} {:>10.1}   {:>10.0} {:>10.0} {:>10.0}",
+            name.to_possible_value().unwrap().get_name(),
+            wins / runs * 100.0,
+            f64::from(antes) / runs,
+            f64::from(hands) / runs,
+            percentile(&scores, 0.1),
+            percentile(&scores, 0.5),
+            percentile(&scores, 0.9),
+        );
+    }
+
+    Ok(())
+}
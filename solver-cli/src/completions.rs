@@ -0,0 +1,26 @@
+//! Tab completion scripts for shells, written from the commands' own
+//! descriptions so they keep up as commands and flags are added.
+
+use std::io::{self, Write};
+
+use anyhow::Result;
+use clap::{Args, Command};
+use clap_complete::Shell;
+
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    /// The shell to complete in. Source the script printed from its
+    /// startup file, like `source <(solver-cli completions bash)` in
+    /// ~/.bashrc
+    shell: Shell,
+}
+
+pub fn run(args: &CompletionsArgs, mut command: Command) -> Result<()> {
+    // Written to a buffer first, since clap_complete panics on a failed
+    // write, like a closed pipe, rather than returning the error
+    let name = command.get_name().to_owned();
+    let mut script = Vec::new();
+    clap_complete::generate(args.shell, &mut command, name, &mut script);
+    io::stdout().write_all(&script)?;
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Defaults for flags, read from a TOML file so long strings of flags don't
+//! have to be repeated for every invocation.
+//!
+//! Keys are flags' long names. Those at the top level apply to every command
+//! with that flag, and those in a table named after a command apply to it
+//! alone, overriding them:
+//!
+//! ```toml
+//! threads = 4
+//! format = "json"
+//!
+//! [stats.hand-stats]
+//! iterations = 500
+//! jokers = ["Shortcut", "Four Fingers"]
+//! ```
+//!
+//! Flags on the command line override both. A switch turned on in the file
+//! can't be turned off on the command line.
+
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgMatches, Command, CommandFactory, FromArgMatches, ValueHint};
+use itertools::Itertools;
+use toml::{Table, Value};
+
+/// The file defaults are read from without `--config`, if there is one
+const DEFAULT_PATH: &str = "balatro-solver.toml";
+
+/// Parse `args` as a `T`, with defaults from the file given with `--config`,
+/// or else `balatro-solver.toml` in the current directory.
+pub(crate) fn parse<T: CommandFactory + FromArgMatches>(args: Vec<OsString>) -> Result<T> {
+    let path = match config_path(&args) {
+        Some(path) => Some(path),
+        None => Some(PathBuf::from(DEFAULT_PATH)).filter(|path| path.exists()),
+    };
+
+    let mut command = command::<T>();
+    if let Some(path) = path {
+        let table = load(&path)?;
+        check(&command, &table, "").map_err(|err| anyhow!("{}: {err}", path.display()))?;
+        command = with_defaults(command, &table, &[]);
+    }
+    let matches: ArgMatches = command.get_matches_from(args);
+    Ok(T::from_arg_matches(&matches).unwrap_or_else(|err| err.exit()))
+}
+
+/// The command `T` describes, with the `--config` flag.
+pub(crate) fn command<T: CommandFactory>() -> Command {
+    T::command().arg(
+        Arg::new("config")
+            .long("config")
+            .value_name("PATH")
+            .value_hint(ValueHint::FilePath)
+            .global(true)
+            .help(format!(
+                "Read defaults for flags from this TOML file, rather than {DEFAULT_PATH} if it exists"
+            )),
+    )
+}
+
+/// The path given with `--config`, wherever it is among `args`, before
+/// they're parsed for real.
+fn config_path(args: &[OsString]) -> Option<PathBuf> {
+    let mut args = args.iter().take_while(|arg| *arg != "--");
+    while let Some(arg) = args.next() {
+        let arg = arg.to_string_lossy();
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+fn load(path: &Path) -> Result<Table> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| anyhow!("couldn't read {}: {err}", path.display()))?;
+    text.parse()
+        .map_err(|err| anyhow!("{} isn't valid TOML: {err}", path.display()))
+}
+
+/// The long names of the flags of `command` and every command under it.
+fn flags(command: &Command) -> BTreeSet<String> {
+    command
+        .get_arguments()
+        .filter_map(|arg| arg.get_long().map(str::to_owned))
+        .chain(command.get_subcommands().flat_map(flags))
+        .collect()
+}
+
+/// `value` as it would be written after a flag, with lists separated by
+/// commas, or `None` if it can't be.
+fn flag_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => Some(value.to_string()),
+        Value::Array(items) => items
+            .iter()
+            .map(flag_value)
+            .collect::<Option<Vec<_>>>()
+            .map(|items| items.join(",")),
+        Value::Datetime(_) | Value::Table(_) => None,
+    }
+}
+
+/// Fail unless every key in `table` is a flag of `command` or a command
+/// under it, and every table is named after a command under it. `name` is
+/// the table's, like `stats.hand-stats`, or empty at the top level.
+fn check(command: &Command, table: &Table, name: &str) -> Result<()> {
+    let flags = flags(command);
+    for (key, value) in table {
+        match value {
+            Value::Table(table) => {
+                let name = if name.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{name}.{key}")
+                };
+                let subcommand = command
+                    .find_subcommand(key)
+                    .ok_or_else(|| anyhow!("[{name}] isn't a command"))?;
+                check(subcommand, table, &name)?;
+            }
+            _ if !flags.contains(key) && name.is_empty() => {
+                return Err(anyhow!("--{key} isn't a flag of any command"));
+            }
+            _ if !flags.contains(key) => {
+                return Err(anyhow!("--{key} isn't a flag of [{name}]"));
+            }
+            _ if flag_value(value).is_none() => {
+                return Err(anyhow!("--{key} can't be {value}"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// `command` with the defaults in `table`, over those `inherited` from the
+/// commands above it, given to its flags and those of every command under
+/// it. `table` must have been checked.
+fn with_defaults(mut command: Command, table: &Table, inherited: &[(String, String)]) -> Command {
+    let mut defaults = inherited
+        .iter()
+        .filter(|(flag, _)| !table.contains_key(flag))
+        .cloned()
+        .collect_vec();
+    defaults.extend(
+        table
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), flag_value(value)?))),
+    );
+
+    for (flag, value) in &defaults {
+        let id = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(flag.as_str()))
+            .map(|arg| arg.get_id().clone());
+        if let Some(id) = id {
+            // Clap holds on to defaults for good, as the command does. A
+            // default fills in a required flag, too.
+            let value: &'static str = value.clone().leak();
+            command = command.mut_arg(id, |arg| arg.default_value(value).required(false));
+        }
+    }
+
+    let subcommands = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_owned())
+        .collect_vec();
+    let empty = Table::new();
+    for name in subcommands {
+        let table = match table.get(&name) {
+            Some(Value::Table(table)) => table,
+            _ => &empty,
+        };
+        command = command.mut_subcommand(&name, |subcommand| {
+            with_defaults(subcommand, table, &defaults)
+        });
+    }
+    command
+}
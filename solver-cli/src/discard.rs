@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+use itertools::Itertools;
+use rayon::prelude::*;
+
+use solver_core::prelude::{
+    Advisor, Blind, Deck, DeckTracker, GameState, JokerSlot, Score, TranspositionTable,
+};
+
+use crate::discard_ev::parse_cards;
+use crate::render::{render, render_padded};
+use crate::stats::parse_jokers;
+
+#[derive(Debug, Args)]
+pub struct DiscardArgs {
+    /// The cards in hand, like "AS KD 7C 7H 2S 9D JC 4H"
+    hand: String,
+
+    /// Cards already played or discarded this round, which can't be drawn
+    #[arg(long = "seen", default_value = "")]
+    seen: String,
+
+    /// The jokers held, by name, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// How many hands are left this round, counting the next one
+    #[arg(long = "hands", default_value = "4")]
+    hands: u32,
+
+    /// The most cards to discard at once
+    #[arg(long = "limit", default_value = "5")]
+    limit: usize,
+
+    /// How many of the best discards to list; all of them by default
+    #[arg(short = 'k', long = "top")]
+    top: Option<usize>,
+
+    /// How many draws to sample for each discard, when there are too many to
+    /// try them all
+    #[arg(short = 's', long = "samples", default_value = "200")]
+    samples: usize,
+}
+
+pub fn run(args: &DiscardArgs) -> Result<()> {
+    let hand = parse_cards(&args.hand)?;
+    let jokers = parse_jokers(&args.jokers)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect();
+    let state = GameState::from_parts(
+        Deck::base_deck(),
+        hand.clone(),
+        args.hands,
+        1,
+        0,
+        1,
+        Blind::Small,
+        jokers,
+        Vec::new(),
+    );
+
+    let mut tracker = DeckTracker::from_state(&state);
+    for card in parse_cards(&args.seen)? {
+        if !tracker.see(card) {
+            return Err(anyhow!("{card} was seen, but isn't left in the deck"));
+        }
+    }
+
+    // Many discards leave the same cards in hand, and draw the same ones
+    let advisor = Advisor::new()
+        .with_samples(args.samples)
+        .with_table(Arc::new(TranspositionTable::new()));
+    let discards = (1..=args.limit.min(hand.len()))
+        .flat_map(|size| (0..hand.len()).combinations(size))
+        .collect_vec();
+    let mut ranked: Vec<(Vec<usize>, f64)> = discards
+        .into_par_iter()
+        .map(|discard| {
+            let expected = advisor.discard_ev(&hand, &discard, &tracker, &state);
+            (discard, expected)
+        })
+        .collect();
+    // Stable, so fewer cards come first among equals
+    ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    let (play, score) = advisor.best_play(&hand, &state);
+    let play = play.iter().map(|index| hand[*index]).collect_vec();
+    println!("Best play now: {} ({score})", render(&play));
+    println!();
+
+    let width = hand.len() * 3;
+    println!(
+        "{:>4}  {:<width$}  {:<width$}  {:>10}",
+        "#", "Discard", "Keep", "Expected"
+    );
+    for (rank, (discard, expected)) in ranked
+        .iter()
+        .take(args.top.unwrap_or(usize::MAX))
+        .enumerate()
+    {
+        let discarded = discard.iter().map(|index| hand[*index]).collect_vec();
+        let kept = (0..hand.len())
+            .filter(|index| !discard.contains(index))
+            .map(|index| hand[index])
+            .collect_vec();
+        println!(
+            "{:>4}  {}  {}  {:>10}",
+            rank + 1,
+            render_padded(&discarded, width),
+            render_padded(&kept, width),
+            Score::from(*expected).to_string(),
+        );
+    }
+    Ok(())
+}
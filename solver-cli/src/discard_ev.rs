@@ -0,0 +1,110 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use itertools::Itertools;
+
+use solver_core::prelude::{Advisor, Blind, Card, Deck, DeckTracker, GameState, JokerSlot, Score};
+
+use crate::render::render;
+use crate::stats::parse_jokers;
+
+#[derive(Debug, Args)]
+pub struct DiscardEvArgs {
+    /// The cards in hand, like "AS KD 7C 7H"
+    #[arg(long = "hand")]
+    hand: String,
+
+    /// The cards to discard, which must all be in hand
+    #[arg(short = 'd', long = "discard")]
+    discard: String,
+
+    /// Cards already played or discarded this round, which can't be drawn
+    #[arg(long = "seen", default_value = "")]
+    seen: String,
+
+    /// The jokers held, by name, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// How many hands are left this round, counting the next one
+    #[arg(long = "hands", default_value = "4")]
+    hands: u32,
+
+    /// How many draws to sample when there are too many to try them all
+    #[arg(short = 's', long = "samples", default_value = "10000")]
+    samples: usize,
+
+    /// Try every possible draw when there are at most this many
+    #[arg(long = "exact-limit", default_value = "100000")]
+    exact_limit: usize,
+}
+
+pub(crate) fn parse_cards(idents: &str) -> Result<Vec<Card>> {
+    idents
+        .split_whitespace()
+        .map(|ident| Ok(ident.parse::<Card>()?))
+        .collect()
+}
+
+pub(crate) fn show(cards: &[Card]) -> String {
+    cards.iter().join(" ")
+}
+
+pub fn run(args: &DiscardEvArgs) -> Result<()> {
+    let hand = parse_cards(&args.hand)?;
+    let jokers = parse_jokers(&args.jokers)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect();
+    let state = GameState::from_parts(
+        Deck::base_deck(),
+        hand.clone(),
+        args.hands,
+        1,
+        0,
+        1,
+        Blind::Small,
+        jokers,
+        Vec::new(),
+    );
+
+    // Each discarded card has to be a different card from hand
+    let mut discard = Vec::new();
+    for card in parse_cards(&args.discard)? {
+        let index = (0..hand.len())
+            .find(|index| hand[*index] == card && !discard.contains(index))
+            .ok_or_else(|| anyhow!("{card} isn't in hand"))?;
+        discard.push(index);
+    }
+
+    let mut tracker = DeckTracker::from_state(&state);
+    for card in parse_cards(&args.seen)? {
+        if !tracker.see(card) {
+            return Err(anyhow!("{card} was seen, but isn't left in the deck"));
+        }
+    }
+
+    let advisor = Advisor::new()
+        .with_samples(args.samples)
+        .with_exact_limit(args.exact_limit);
+
+    let (play, score) = advisor.best_play(&hand, &state);
+    let play = play.iter().map(|index| hand[*index]).collect_vec();
+    println!("Best play now: {} ({score})", render(&play));
+
+    let expected = Score::from(advisor.discard_ev(&hand, &discard, &tracker, &state));
+    let method = if advisor.is_exact(tracker.count(), discard.len()) {
+        "exact".to_owned()
+    } else {
+        format!("estimated from {} draws", args.samples)
+    };
+    let kept = (0..hand.len())
+        .filter(|index| !discard.contains(index))
+        .map(|index| hand[index])
+        .collect_vec();
+    println!(
+        "Discarding {}, keeping {}: {expected} expected ({method})",
+        render(&discard.iter().map(|index| hand[*index]).collect_vec()),
+        render(&kept),
+    );
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+
+use solver_core::prelude::{
+    CardCollection, CardView, HandEvaluator, Options, Scorer, ScoringContext, ScoringOptions,
+};
+
+use crate::discard_ev::{parse_cards, show};
+use crate::render::render;
+
+#[derive(Debug, Args)]
+pub struct EvalArgs {
+    /// The cards played, one to five of them, like "AS KS QS JS TS"
+    hand: String,
+
+    /// Whether the "Shortcut" joker is enabled, allowing straights to have a gap
+    #[arg(long = "shortcut", default_value = "false")]
+    shortcut: bool,
+
+    /// Whether the "Four Fingers" joker is enabled, allowing straights/flushes to consist of 4 cards
+    #[arg(long = "four-fingers", default_value = "false")]
+    four_fingers: bool,
+
+    /// Whether to score as the Plasma deck, balancing chips and mult before multiplying
+    #[arg(long = "plasma", default_value = "false")]
+    plasma: bool,
+}
+
+pub fn run(args: &EvalArgs) -> Result<()> {
+    let cards = parse_cards(&args.hand)?;
+    if !(1..=5).contains(&cards.len()) {
+        return Err(anyhow!(
+            "a hand is one to five cards, but {} were given",
+            cards.len()
+        ));
+    }
+
+    let mut options = Options::empty();
+    if args.shortcut {
+        options |= Options::GappedStraights;
+    }
+    if args.four_fingers {
+        options |= Options::FourCardStraightsAndFlushes;
+    }
+    let scoring_options = if args.plasma {
+        ScoringOptions::Plasma
+    } else {
+        ScoringOptions::empty()
+    };
+
+    let (kind, scoring, _) =
+        HandEvaluator::evaluate_played_hand(CardCollection::from(cards.as_slice()), options)
+            .ok_or_else(|| anyhow!("{} isn't a poker hand", show(&cards)))?;
+    let context = ScoringContext::new(kind, &scoring)
+        .with_all_played(&cards)
+        .with_options(scoring_options);
+
+    println!("Hand:    {kind:?}");
+    println!("Scoring: {}", render(scoring.view()));
+    println!("Score:   {}", Scorer::score_hand(&context));
+    Ok(())
+}
@@ -1,4 +1,25 @@
+mod best_play;
+mod checkpoint;
+mod compare;
+mod completions;
+mod config;
+mod discard;
+mod discard_ev;
+mod eval;
+mod odds;
+mod output;
+mod render;
+mod repl;
+mod report;
+mod serve;
+mod shop;
+mod simulate;
 mod stats;
+mod tui;
+mod verify;
+mod win_probability;
+
+use std::num::NonZeroUsize;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -8,21 +29,95 @@ use clap::{Parser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: CliCommands,
+
+    /// How many threads to run on, where a command runs in parallel (all
+    /// cores by default)
+    #[arg(long = "threads", global = true)]
+    threads: Option<NonZeroUsize>,
+
+    /// Print cards without colour, which is also left out when stdout isn't
+    /// a terminal or `NO_COLOR` is set
+    #[arg(long = "no-color", global = true)]
+    no_color: bool,
+
+    /// Print cards' suits as letters, like "AS KH", rather than symbols
+    #[arg(long = "ascii", global = true)]
+    ascii: bool,
 }
 
 #[derive(Debug, Subcommand)]
 enum CliCommands {
+    /// Work out what poker hand some cards make, and what they score
+    Eval(eval::EvalArgs),
+    /// Recommend what to play or discard from a dealt hand
+    #[command(visible_alias = "advise")]
+    BestPlay(best_play::BestPlayArgs),
     /// Generate statistics
     Stats {
         #[command(subcommand)]
-        command: stats::CliCommands,
+        command: Box<stats::CliCommands>,
     },
+    /// Simulate whole runs
+    Simulate {
+        #[command(subcommand)]
+        command: simulate::CliCommands,
+    },
+    /// Rank every discard from a hand by what the best hand is worth on
+    /// average afterwards
+    Discard(discard::DiscardArgs),
+    /// Work out the chance of holding at least each kind of hand after
+    /// discarding some cards and drawing their replacements
+    Odds(odds::OddsArgs),
+    /// Work out what the best hand is worth on average after discarding
+    /// some cards from a hand
+    DiscardEv(discard_ev::DiscardEvArgs),
+    /// Play the same seeded runs with several strategies, and compare how
+    /// they do
+    CompareStrategies(compare::CompareArgs),
+    /// Estimate how likely a run is to clear its next blind and its ante,
+    /// by playing it out many times
+    WinProbability(win_probability::WinProbabilityArgs),
+    /// Play along with a round interactively, getting advice each turn
+    Repl(repl::ReplArgs),
+    /// Play along with a round full-screen, with the hand, deck and advice
+    /// kept in view
+    Tui(tui::TuiArgs),
+    /// Rank what can be bought in a shop by how much it improves the run
+    Shop(shop::ShopArgs),
+    /// Check the hand evaluator against a slow, plain reference, listing
+    /// any hands they disagree on
+    Verify(verify::VerifyArgs),
+    /// Answer evaluate, advise and simulate requests over HTTP, as JSON
+    Serve(serve::ServeArgs),
+    /// Print a script that completes commands and flags in a shell
+    Completions(completions::CompletionsArgs),
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let cli: Cli = config::parse(std::env::args_os().collect())?;
+    render::configure(cli.no_color, cli.ascii);
+
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.get())
+            .build_global()?;
+    }
 
     match &cli.command {
+        CliCommands::Eval(args) => eval::run(args),
+        CliCommands::BestPlay(args) => best_play::run(args),
         CliCommands::Stats { command } => stats::run(command),
+        CliCommands::Simulate { command } => simulate::run(command),
+        CliCommands::Discard(args) => discard::run(args),
+        CliCommands::Odds(args) => odds::run(args),
+        CliCommands::DiscardEv(args) => discard_ev::run(args),
+        CliCommands::CompareStrategies(args) => compare::run(args),
+        CliCommands::WinProbability(args) => win_probability::run(args),
+        CliCommands::Repl(args) => repl::run(args),
+        CliCommands::Tui(args) => tui::run(args),
+        CliCommands::Shop(args) => shop::run(args),
+        CliCommands::Verify(args) => verify::run(args),
+        CliCommands::Serve(args) => serve::run(args),
+        CliCommands::Completions(args) => completions::run(args, config::command::<Cli>()),
     }
 }
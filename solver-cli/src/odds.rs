@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+
+use solver_core::prelude::{kind_odds, Blind, Deck, DeckTracker, GameState, Options};
+
+use crate::discard_ev::parse_cards;
+use crate::render::render;
+
+#[derive(Debug, Args)]
+pub struct OddsArgs {
+    /// The cards in hand, like "AS KD 7C 7H 2S 9D JC 4H"
+    hand: String,
+
+    /// The cards to discard, which must all be in hand
+    #[arg(short = 'd', long = "discard", default_value = "")]
+    discard: String,
+
+    /// Cards already played or discarded this round, which can't be drawn
+    #[arg(long = "seen", default_value = "")]
+    seen: String,
+
+    /// Whether the "Shortcut" joker is enabled, allowing straights to have a gap
+    #[arg(long = "shortcut", default_value = "false")]
+    shortcut: bool,
+
+    /// Whether the "Four Fingers" joker is enabled, allowing straights/flushes to consist of 4 cards
+    #[arg(long = "four-fingers", default_value = "false")]
+    four_fingers: bool,
+
+    /// How many draws to sample when there are too many to try them all
+    #[arg(short = 's', long = "samples", default_value = "100000")]
+    samples: usize,
+
+    /// Try every possible draw when there are at most this many
+    #[arg(long = "exact-limit", default_value = "2000000")]
+    exact_limit: usize,
+}
+
+pub fn run(args: &OddsArgs) -> Result<()> {
+    let hand = parse_cards(&args.hand)?;
+    let state = GameState::from_parts(
+        Deck::base_deck(),
+        hand.clone(),
+        1,
+        1,
+        0,
+        1,
+        Blind::Small,
+        Vec::new(),
+        Vec::new(),
+    );
+
+    // Each discarded card has to be a different card from hand
+    let mut kept = hand.clone();
+    for card in parse_cards(&args.discard)? {
+        let index = kept
+            .iter()
+            .position(|held| *held == card)
+            .ok_or_else(|| anyhow!("{card} isn't in hand"))?;
+        kept.remove(index);
+    }
+
+    let mut tracker = DeckTracker::from_state(&state);
+    for card in parse_cards(&args.seen)? {
+        if !tracker.see(card) {
+            return Err(anyhow!("{card} was seen, but isn't left in the deck"));
+        }
+    }
+
+    let mut options = Options::empty();
+    if args.shortcut {
+        options |= Options::GappedStraights;
+    }
+    if args.four_fingers {
+        options |= Options::FourCardStraightsAndFlushes;
+    }
+
+    let drawn = hand.len() - kept.len();
+    println!(
+        "Keeping {}, drawing {drawn} of {} unseen cards:",
+        render(&kept),
+        tracker.count()
+    );
+    let odds = kind_odds(
+        &kept,
+        &tracker,
+        drawn,
+        options,
+        args.exact_limit,
+        args.samples,
+    );
+    for (kind, probability) in odds {
+        println!("{:<14} {:>7.3}%", format!("{kind:?}"), probability * 100.0);
+    }
+    Ok(())
+}
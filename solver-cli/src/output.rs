@@ -0,0 +1,136 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use clap::{Args, ValueEnum};
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::Value;
+
+/// How a command prints its results.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Prose and aligned tables, for reading
+    #[default]
+    Text,
+    /// An array with an object per record
+    Json,
+    /// A header row naming the columns, then a row per record
+    Csv,
+}
+
+/// Where and how a command prints its results.
+#[derive(Clone, Debug, Args)]
+pub struct OutputArgs {
+    /// Write the results to this file instead of printing them,
+    /// replacing it whole once they're all in
+    #[arg(long = "output")]
+    file: Option<PathBuf>,
+
+    /// How to print the results
+    #[arg(long = "format", default_value = "text")]
+    pub(crate) format: Format,
+}
+
+impl OutputArgs {
+    /// Run `command` with where to print its results: stdout, or if there's
+    /// an `--output` file, that file. The file is only replaced once the
+    /// command succeeds, and then whole, so nothing reading it sees half the
+    /// results.
+    pub(crate) fn run(&self, command: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+        let Some(file) = &self.file else {
+            return command(&mut io::stdout().lock());
+        };
+        let mut results = Vec::new();
+        command(&mut results)?;
+        write_atomically(file, &results)
+    }
+}
+
+/// Write `contents` to `path`, replacing it whole, so being interrupted
+/// leaves what was there before intact.
+pub(crate) fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    fs::write(&partial, contents)
+        .and_then(|()| fs::rename(&partial, path))
+        .map_err(|err| anyhow!("couldn't write {}: {err}", path.display()))
+}
+
+/// One row of a command's machine-readable results.
+pub(crate) trait Record: Serialize {
+    /// The names of the fields, in the order they're serialized.
+    const COLUMNS: &'static [&'static str];
+}
+
+/// Print `records` to `out` as JSON or CSV. Text is left to each command,
+/// which knows how to lay its results out.
+pub(crate) fn print_records<R: Record>(
+    out: &mut dyn Write,
+    records: &[R],
+    format: Format,
+) -> Result<()> {
+    match format {
+        Format::Text => {}
+        Format::Json => writeln!(out, "{}", serde_json::to_string_pretty(records)?)?,
+        Format::Csv => {
+            writeln!(out, "{}", R::COLUMNS.join(","))?;
+            for record in records {
+                let value = serde_json::to_value(record)?;
+                let row = R::COLUMNS
+                    .iter()
+                    .map(|column| csv_field(&value[*column]))
+                    .join(",");
+                writeln!(out, "{row}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `value` as a CSV field, quoted if it would otherwise be misread.
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) if text.contains([',', '"', '\n']) => {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        }
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// A file of JSON records, one to a line, written as they're produced.
+#[derive(Debug)]
+pub(crate) struct JsonLines {
+    path: String,
+    file: Mutex<BufWriter<File>>,
+}
+
+impl JsonLines {
+    /// Start the file at `path` afresh, or if `append`, carry on from the
+    /// end of it.
+    pub(crate) fn open(path: &Path, append: bool) -> Result<Self> {
+        let file = File::options()
+            .write(true)
+            .create(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)
+            .map_err(|err| anyhow!("couldn't open {}: {err}", path.display()))?;
+        Ok(Self {
+            path: path.display().to_string(),
+            file: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    /// Append `lines`, each a record followed by a newline.
+    pub(crate) fn write(&self, lines: &str) -> Result<()> {
+        let mut file = self.file.lock().unwrap();
+        file.write_all(lines.as_bytes())
+            .and_then(|()| file.flush())
+            .map_err(|err| anyhow!("couldn't write to {}: {err}", self.path))
+    }
+}
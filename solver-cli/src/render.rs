@@ -0,0 +1,70 @@
+//! Cards as they're printed for people to read, like `A♠ K♥`, with hearts and
+//! diamonds in red and spades and clubs in the terminal's own colour.
+
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::OnceLock;
+
+use itertools::Itertools;
+
+use solver_core::prelude::{Card, Suit};
+
+use crate::discard_ev::show;
+
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy, Debug)]
+struct Style {
+    /// Whether to colour the red suits
+    color: bool,
+    /// Whether to print the suits as letters, like the idents cards are
+    /// entered as
+    ascii: bool,
+}
+
+static STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Set how cards are rendered for the rest of the program. Colour is left out
+/// when asked, when `NO_COLOR` is set, and when stdout isn't a terminal.
+pub(crate) fn configure(no_color: bool, ascii: bool) {
+    let color = !no_color && env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal();
+    STYLE.set(Style { color, ascii }).unwrap();
+}
+
+/// `cards` as they're printed for people to read, separated by spaces.
+pub(crate) fn render(cards: &[Card]) -> String {
+    let style = *STYLE.get_or_init(|| Style {
+        color: false,
+        ascii: true,
+    });
+    if style.ascii {
+        return show(cards);
+    }
+    cards
+        .iter()
+        .map(|card| {
+            // The ident is the rank's character followed by the suit's
+            let rank = &card.to_string()[..1];
+            let (symbol, red) = match card.suit {
+                Suit::Spades => ('♠', false),
+                Suit::Clubs => ('♣', false),
+                Suit::Hearts => ('♥', true),
+                Suit::Diamonds => ('♦', true),
+            };
+            if red && style.color {
+                format!("{RED}{rank}{symbol}{RESET}")
+            } else {
+                format!("{rank}{symbol}")
+            }
+        })
+        .join(" ")
+}
+
+/// `cards` rendered, then padded with spaces to `width` columns, since the
+/// colour codes throw off `format!`'s own padding.
+pub(crate) fn render_padded(cards: &[Card], width: usize) -> String {
+    // Every card takes two columns, however it's rendered
+    let columns = show(cards).len();
+    render(cards) + " ".repeat(width.saturating_sub(columns)).as_str()
+}
@@ -0,0 +1,350 @@
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+use itertools::Itertools;
+
+use solver_core::prelude::{
+    Action, Advisor, Blind, Card, CardView, Deck, DeckTracker, GameState, JokerSlot,
+    Recommendation, Score, TranspositionTable,
+};
+
+use crate::discard_ev::{parse_cards, show};
+use crate::render::render;
+use crate::stats::parse_jokers;
+
+pub(crate) const HELP: &str = "\
+Commands:
+  hand <cards>      Start a turn with these cards dealt, like \"AS KD 7C 7H\"
+  draw <cards>      Add cards drawn to the hand
+  play <cards>      Play cards from hand, using up a hand and scoring them
+  discard <cards>   Discard cards from hand, using up a discard
+  seen <cards>      Mark cards as gone from the deck without being in hand
+  hands <n>         Set how many hands are left
+  discards <n>      Set how many discards are left
+  target <chips>    Set the score the blind needs
+  jokers <names>    Set the jokers held, by name, separated by commas
+  advise [k]        List the k best actions (3 by default)
+  status            Show the hand, what's left, and the deck
+  reset             Start a new round with a full deck
+  help              Show this message
+  quit              Leave";
+
+#[derive(Debug, Args)]
+pub struct ReplArgs {
+    /// How many hands each round starts with
+    #[arg(long = "hands", default_value = "4")]
+    hands: u32,
+
+    /// How many discards each round starts with
+    #[arg(long = "discards", default_value = "3")]
+    discards: u32,
+
+    /// The jokers held, by name, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// How many draws to sample for each discard, when there are too many to
+    /// try them all
+    #[arg(short = 's', long = "samples", default_value = "200")]
+    samples: usize,
+}
+
+/// A round being played along with, as it's entered: what's in hand, what's
+/// been seen, and what's left.
+pub(crate) struct Session {
+    pub(crate) hand: Vec<Card>,
+    pub(crate) tracker: DeckTracker,
+    pub(crate) hands: u32,
+    pub(crate) discards: u32,
+    pub(crate) jokers: Vec<JokerSlot>,
+    /// The score the blind needs, if it's been given
+    pub(crate) target: Option<f64>,
+    /// The score of the hands played so far this round
+    pub(crate) scored: f64,
+    round_hands: u32,
+    round_discards: u32,
+    advisor: Advisor,
+}
+
+impl Session {
+    pub(crate) fn new(hands: u32, discards: u32, jokers: Vec<JokerSlot>, samples: usize) -> Self {
+        Self {
+            hand: Vec::new(),
+            tracker: DeckTracker::new(Deck::base_deck().view().to_vec()),
+            hands,
+            discards,
+            jokers,
+            target: None,
+            scored: 0.0,
+            round_hands: hands,
+            round_discards: discards,
+            // Turns often leave the same cards in hand, and draw the same ones
+            advisor: Advisor::new()
+                .with_samples(samples)
+                .with_table(Arc::new(TranspositionTable::new())),
+        }
+    }
+
+    /// Forget the round, and start a new one with a full deck.
+    pub(crate) fn reset(&mut self) {
+        self.hand.clear();
+        self.tracker = DeckTracker::new(Deck::base_deck().view().to_vec());
+        self.hands = self.round_hands;
+        self.discards = self.round_discards;
+        self.scored = 0.0;
+    }
+
+    /// Put `cards` in hand, seeing them in the deck.
+    pub(crate) fn draw(&mut self, cards: &[Card]) -> Result<()> {
+        self.see(cards)?;
+        self.hand.extend_from_slice(cards);
+        Ok(())
+    }
+
+    /// Replace the hand with `cards`, seeing them in the deck. Leaves the hand
+    /// as it was if one of them isn't left.
+    pub(crate) fn deal(&mut self, cards: Vec<Card>) -> Result<()> {
+        self.see(&cards)?;
+        self.hand = cards;
+        Ok(())
+    }
+
+    /// Mark `cards` as gone from the deck. Fails without seeing any if one
+    /// of them isn't left.
+    pub(crate) fn see(&mut self, cards: &[Card]) -> Result<()> {
+        let mut tracker = self.tracker.clone();
+        for card in cards {
+            if !tracker.see(*card) {
+                return Err(anyhow!("{card} isn't left in the deck"));
+            }
+        }
+        self.tracker = tracker;
+        Ok(())
+    }
+
+    /// Take `cards` out of hand, using up a discard if `discard` is set and a
+    /// hand if not. Fails without changing anything if one of them isn't in
+    /// hand, or there's nothing left to use up.
+    pub(crate) fn remove(&mut self, cards: &[Card], discard: bool) -> Result<()> {
+        let left = if discard { self.discards } else { self.hands };
+        if left == 0 {
+            let kind = if discard { "discards" } else { "hands" };
+            return Err(anyhow!("there are no {kind} left"));
+        }
+        let mut hand = self.hand.clone();
+        for card in cards {
+            let index = hand
+                .iter()
+                .position(|held| held == card)
+                .ok_or_else(|| anyhow!("{card} isn't in hand"))?;
+            hand.remove(index);
+        }
+        self.hand = hand;
+        if discard {
+            self.discards -= 1;
+        } else {
+            self.hands -= 1;
+        }
+        Ok(())
+    }
+
+    /// Play `cards` from hand, adding what they score to the round's.
+    pub(crate) fn play(&mut self, cards: &[Card]) -> Result<()> {
+        let mut indices = Vec::new();
+        for card in cards {
+            let index = (0..self.hand.len())
+                .find(|index| self.hand[*index] == *card && !indices.contains(index))
+                .ok_or_else(|| anyhow!("{card} isn't in hand"))?;
+            indices.push(index);
+        }
+        indices.sort_unstable();
+        let score = self
+            .advisor
+            .plays(&self.hand, &self.state())
+            .into_iter()
+            .find(|(play, _)| *play == indices)
+            .map_or(0.0, |(_, score)| score.value());
+        self.remove(cards, false)?;
+        self.scored += score;
+        Ok(())
+    }
+
+    pub(crate) fn state(&self) -> GameState {
+        GameState::from_parts(
+            Deck::base_deck(),
+            self.hand.clone(),
+            self.hands,
+            self.discards,
+            0,
+            1,
+            Blind::Small,
+            self.jokers.clone(),
+            Vec::new(),
+        )
+    }
+
+    /// The `k` best actions for the hand, best first.
+    pub(crate) fn recommend(&self, k: usize) -> Vec<Recommendation> {
+        self.advisor
+            .recommend(&self.hand, &self.tracker, &self.state(), k)
+    }
+
+    /// The cards an action plays or discards, by name, written with `show`.
+    pub(crate) fn describe(&self, action: &Action, show: fn(&[Card]) -> String) -> String {
+        let (verb, indices) = match action {
+            Action::Play(indices) => ("Play", indices),
+            Action::Discard(indices) => ("Discard", indices),
+        };
+        let cards = indices.iter().map(|index| self.hand[*index]).collect_vec();
+        format!("{verb} {}", show(&cards))
+    }
+}
+
+fn print_recommendations(session: &Session, k: usize) {
+    if session.hand.is_empty() {
+        println!("Nothing in hand; enter it with `hand`.");
+        return;
+    }
+    for (rank, recommendation) in session.recommend(k).iter().enumerate() {
+        println!(
+            "{:>2}. {}: {} expected",
+            rank + 1,
+            session.describe(&recommendation.action, render),
+            Score::from(recommendation.expected),
+        );
+    }
+}
+
+fn print_status(session: &Session) {
+    println!("Hand:     {}", render(&session.hand));
+    println!(
+        "Left:     {} hands, {} discards",
+        session.hands, session.discards
+    );
+    match session.target {
+        Some(target) => println!(
+            "Score:    {} of {}",
+            Score::from(session.scored),
+            Score::from(target)
+        ),
+        None => println!("Score:    {}", Score::from(session.scored)),
+    }
+    let suits = session
+        .tracker
+        .view()
+        .iter()
+        .counts_by(|card| card.suit)
+        .into_iter()
+        .sorted()
+        .map(|(suit, count)| format!("{count} {suit:?}"))
+        .join(", ");
+    println!("Unseen:   {} cards ({suits})", session.tracker.count());
+}
+
+/// What a line of input asks to see, once it's been carried out.
+pub(crate) enum Outcome {
+    Nothing,
+    /// New cards are in hand, so the best action has changed
+    Dealt,
+    Advice(usize),
+    Status,
+    Help,
+    Quit,
+}
+
+/// Carry out one line of input.
+pub(crate) fn execute(session: &mut Session, line: &str) -> Result<Outcome> {
+    let (command, rest) = line
+        .trim()
+        .split_once(char::is_whitespace)
+        .unwrap_or((line.trim(), ""));
+    let outcome = match command {
+        "" => Outcome::Nothing,
+        "hand" => {
+            session.deal(parse_cards(rest)?)?;
+            Outcome::Dealt
+        }
+        "draw" => {
+            session.draw(&parse_cards(rest)?)?;
+            Outcome::Dealt
+        }
+        "play" => {
+            session.play(&parse_cards(rest)?)?;
+            Outcome::Nothing
+        }
+        "discard" => {
+            session.remove(&parse_cards(rest)?, true)?;
+            Outcome::Nothing
+        }
+        "seen" => {
+            session.see(&parse_cards(rest)?)?;
+            Outcome::Nothing
+        }
+        "hands" => {
+            session.hands = rest.trim().parse()?;
+            Outcome::Nothing
+        }
+        "discards" => {
+            session.discards = rest.trim().parse()?;
+            Outcome::Nothing
+        }
+        "target" => {
+            session.target = Some(rest.trim().parse()?);
+            Outcome::Nothing
+        }
+        "jokers" => {
+            let names = rest.split(',').map(str::to_owned).collect_vec();
+            session.jokers = parse_jokers(&names)?
+                .into_iter()
+                .map(|id| JokerSlot::new(id.joker()))
+                .collect();
+            Outcome::Nothing
+        }
+        "advise" => {
+            if rest.trim().is_empty() {
+                Outcome::Advice(3)
+            } else {
+                Outcome::Advice(rest.trim().parse()?)
+            }
+        }
+        "status" => Outcome::Status,
+        "reset" => {
+            session.reset();
+            Outcome::Nothing
+        }
+        "help" => Outcome::Help,
+        "quit" | "exit" => Outcome::Quit,
+        _ => return Err(anyhow!("unknown command `{command}`; try `help`")),
+    };
+    Ok(outcome)
+}
+
+pub fn run(args: &ReplArgs) -> Result<()> {
+    let jokers = parse_jokers(&args.jokers)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect();
+    let mut session = Session::new(args.hands, args.discards, jokers, args.samples);
+
+    println!("Type `help` for commands.");
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(());
+        };
+        match execute(&mut session, &line?) {
+            Ok(Outcome::Nothing) => {}
+            Ok(Outcome::Dealt) => print_recommendations(&session, 1),
+            Ok(Outcome::Advice(k)) => print_recommendations(&session, k),
+            Ok(Outcome::Status) => print_status(&session),
+            Ok(Outcome::Help) => println!("{HELP}"),
+            Ok(Outcome::Quit) => return Ok(()),
+            Err(err) => println!("Error: {err}"),
+        }
+    }
+}
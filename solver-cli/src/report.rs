@@ -0,0 +1,221 @@
+//! Reports of a command's results, with tables and bar charts, written as
+//! HTML or Markdown so they can be shared as they are.
+
+use std::path::Path;
+
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::output::write_atomically;
+
+/// How many characters wide the longest bar of a chart is in Markdown
+const MARKDOWN_BAR_WIDTH: f64 = 40.0;
+
+/// One bar of a chart.
+pub(crate) struct Bar {
+    pub(crate) label: String,
+    /// How long the bar is, relative to the others
+    pub(crate) value: f64,
+    /// The value as it's written beside the bar
+    pub(crate) text: String,
+}
+
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    Table {
+        header: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    Chart {
+        caption: String,
+        bars: Vec<Bar>,
+    },
+}
+
+/// A report built up a block at a time, then written out whole.
+pub(crate) struct Report {
+    title: String,
+    blocks: Vec<Block>,
+}
+
+impl Report {
+    pub(crate) fn new(title: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            blocks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn heading(&mut self, text: &str) {
+        self.blocks.push(Block::Heading(text.to_owned()));
+    }
+
+    pub(crate) fn paragraph(&mut self, text: &str) {
+        self.blocks.push(Block::Paragraph(text.to_owned()));
+    }
+
+    /// A table of `rows`, each with a cell per column of `header`.
+    pub(crate) fn table(&mut self, header: &[&str], rows: Vec<Vec<String>>) {
+        self.blocks.push(Block::Table {
+            header: header.iter().map(|column| (*column).to_owned()).collect(),
+            rows,
+        });
+    }
+
+    /// A horizontal bar chart, with the longest bar the largest value.
+    pub(crate) fn chart(&mut self, caption: &str, bars: Vec<Bar>) {
+        self.blocks.push(Block::Chart {
+            caption: caption.to_owned(),
+            bars,
+        });
+    }
+
+    /// Write the report to `path`: as Markdown if it ends in `.md` or
+    /// `.markdown`, and otherwise as HTML.
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let markdown = path
+            .extension()
+            .is_some_and(|extension| extension == "md" || extension == "markdown");
+        let text = if markdown {
+            self.markdown()
+        } else {
+            self.html()
+        };
+        write_atomically(path, &text)
+    }
+
+    fn markdown(&self) -> String {
+        let row = |cells: &[String]| {
+            format!(
+                "| {} |",
+                cells
+                    .iter()
+                    .map(|cell| cell.replace('|', "\\|"))
+                    .join(" | ")
+            )
+        };
+        let mut parts = vec![format!("# {}", self.title)];
+        for block in &self.blocks {
+            let part = match block {
+                Block::Heading(text) => format!("## {text}"),
+                Block::Paragraph(text) => text.clone(),
+                Block::Table { header, rows } => {
+                    // Names on the left, and figures on the right
+                    let rule = std::iter::once("---".to_owned())
+                        .chain(std::iter::repeat_n("---:".to_owned(), header.len() - 1))
+                        .collect_vec();
+                    [row(header), row(&rule)]
+                        .into_iter()
+                        .chain(rows.iter().map(|cells| row(cells)))
+                        .join("\n")
+                }
+                Block::Chart { caption, bars } => {
+                    let lengths = bar_lengths(bars);
+                    let lines = bars.iter().zip(lengths).map(|(bar, length)| {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        let width = (length * MARKDOWN_BAR_WIDTH).round() as usize;
+                        row(&[bar.label.clone(), "█".repeat(width), bar.text.clone()])
+                    });
+                    [
+                        row(&[caption.clone(), String::new(), String::new()]),
+                        row(&["---".to_owned(), "---".to_owned(), "---:".to_owned()]),
+                    ]
+                    .into_iter()
+                    .chain(lines)
+                    .join("\n")
+                }
+            };
+            parts.push(part);
+        }
+        parts.join("\n\n") + "\n"
+    }
+
+    fn html(&self) -> String {
+        let mut parts = vec![
+            "<!DOCTYPE html>".to_owned(),
+            "<html>".to_owned(),
+            "<head>".to_owned(),
+            "<meta charset=\"utf-8\">".to_owned(),
+            format!("<title>{}</title>", escape(&self.title)),
+            format!("<style>{STYLE}</style>"),
+            "</head>".to_owned(),
+            "<body>".to_owned(),
+            format!("<h1>{}</h1>", escape(&self.title)),
+        ];
+        for block in &self.blocks {
+            let part = match block {
+                Block::Heading(text) => format!("<h2>{}</h2>", escape(text)),
+                Block::Paragraph(text) => format!("<p>{}</p>", escape(text)),
+                Block::Table { header, rows } => {
+                    let cells = |tag: &str, cells: &[String]| {
+                        cells
+                            .iter()
+                            .map(|cell| format!("<{tag}>{}</{tag}>", escape(cell)))
+                            .join("")
+                    };
+                    let rows = rows
+                        .iter()
+                        .map(|row| format!("<tr>{}</tr>", cells("td", row)))
+                        .join("\n");
+                    format!(
+                        "<table>\n<tr>{}</tr>\n{rows}\n</table>",
+                        cells("th", header)
+                    )
+                }
+                Block::Chart { caption, bars } => {
+                    let rows = bars
+                        .iter()
+                        .zip(bar_lengths(bars))
+                        .map(|(bar, length)| {
+                            format!(
+                                "<tr><th>{}</th><td class=\"bar\"><div style=\"width: {:.1}%\"></div></td><td>{}</td></tr>",
+                                escape(&bar.label),
+                                length * 100.0,
+                                escape(&bar.text)
+                            )
+                        })
+                        .join("\n");
+                    format!(
+                        "<table class=\"chart\">\n<caption>{}</caption>\n{rows}\n</table>",
+                        escape(caption)
+                    )
+                }
+            };
+            parts.push(part);
+        }
+        parts.push("</body>".to_owned());
+        parts.push("</html>".to_owned());
+        parts.join("\n") + "\n"
+    }
+}
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; } \
+table { border-collapse: collapse; margin: 1em 0; } \
+th, td { padding: 0.2em 0.6em; text-align: right; border-bottom: 1px solid #ddd; } \
+th:first-child, td:first-child { text-align: left; } \
+caption { font-weight: bold; text-align: left; } \
+.chart td.bar { width: 30em; } \
+.chart td.bar div { background: #4a7ebb; height: 1em; }";
+
+/// Each bar's length as a share of the longest.
+fn bar_lengths(bars: &[Bar]) -> Vec<f64> {
+    let longest = bars.iter().map(|bar| bar.value).fold(0.0, f64::max);
+    bars.iter()
+        .map(|bar| {
+            if longest > 0.0 {
+                bar.value.max(0.0) / longest
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// `text` with the characters that mean something in HTML escaped.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
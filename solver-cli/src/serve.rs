@@ -0,0 +1,362 @@
+//! The solver as an HTTP service, so overlays and bots can use it without
+//! starting the CLI for every question. Every endpoint takes a JSON body by
+//! `POST` and answers with JSON, with cards as their idents, like `"KH+glass"`:
+//!
+//! - `/evaluate` takes `{"cards": [...], "jokers": [...]}` and gives the kind
+//!   of hand, the cards that score, and the score
+//! - `/advise` takes `{"hand": [...], "jokers": [...], "hands": 4,
+//!   "discards": 3, "seen": [...], "samples": 200}`, where only the hand is
+//!   required, and gives `{"action": "play", "cards": [...], "expected": ...}`.
+//!   The hand can be at most 16 cards
+//! - `/simulate` takes `{"strategy": "greedy", "deck": "plain", "stake":
+//!   "white", "runs": 100, "seed": 0}`, all optional, and sums up the runs
+//!
+//! Failures are answered with `{"error": "..."}`. Requests are answered by a
+//! fixed number of workers, and the rest wait for one to be free.
+
+use std::io::Read;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use solver_core::prelude::{Card, Hand, HandKind, Stake, StartingDeck};
+use solver_core::solver::bindings::{self, parse_jokers, AdviseRequest};
+
+use crate::compare::StrategyName;
+use crate::simulate::{play_runs, Tally};
+
+/// The largest request body read, in bytes.
+const MAX_BODY: u64 = 1 << 20;
+
+/// The most cards `/advise` will look for a play in. Every subset of the
+/// hand is tried, so each card past this doubles the work.
+const MAX_HAND: usize = 16;
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// The port to listen on
+    #[arg(short = 'p', long = "port", default_value = "8080")]
+    port: u16,
+
+    /// The address to listen on; 0.0.0.0 accepts connections from other
+    /// machines
+    #[arg(long = "host", default_value = "127.0.0.1")]
+    host: IpAddr,
+
+    /// The most runs a single `/simulate` request can ask for
+    #[arg(long = "max-runs", default_value = "10000")]
+    max_runs: u64,
+
+    /// The most draws a single `/advise` request can sample for each discard
+    #[arg(long = "max-samples", default_value = "1000")]
+    max_samples: usize,
+
+    /// How many requests to answer at once, with the rest waiting their turn
+    /// (as many as there are threads by default)
+    #[arg(long = "workers")]
+    workers: Option<NonZeroUsize>,
+}
+
+/// How much work a single request can ask for.
+#[derive(Copy, Clone, Debug)]
+struct Limits {
+    max_runs: u64,
+    max_samples: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Endpoint {
+    Evaluate,
+    Advise,
+    Simulate,
+}
+
+impl Endpoint {
+    fn from_path(path: &str) -> Option<Self> {
+        match path.split('?').next().unwrap_or_default() {
+            "/evaluate" => Some(Self::Evaluate),
+            "/advise" => Some(Self::Advise),
+            "/simulate" => Some(Self::Simulate),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EvaluateRequest {
+    cards: Vec<Card>,
+    /// The jokers held, by name
+    #[serde(default)]
+    jokers: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Evaluation {
+    kind: HandKind,
+    /// The cards that score, in the order they were played
+    scoring: Hand,
+    score: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SimulateRequest {
+    strategy: String,
+    deck: String,
+    stake: String,
+    runs: u64,
+    /// The seed of the first run; each run after uses the next seed
+    seed: u64,
+}
+
+impl Default for SimulateRequest {
+    fn default() -> Self {
+        Self {
+            strategy: "greedy".to_owned(),
+            deck: StartingDeck::default().to_string(),
+            stake: Stake::default().to_string(),
+            runs: 100,
+            seed: 0,
+        }
+    }
+}
+
+fn evaluate(request: &EvaluateRequest) -> Result<Evaluation> {
+    let (kind, scoring, _) = bindings::evaluate(&request.cards)?;
+    let jokers = parse_jokers(request.jokers.iter().map(String::as_str))?;
+    let score = bindings::score(&request.cards, &jokers)?;
+    Ok(Evaluation {
+        kind,
+        scoring,
+        score: score.value(),
+    })
+}
+
+fn advise(request: &AdviseRequest, max_samples: usize) -> Result<bindings::Advice> {
+    if request.hand.len() > MAX_HAND {
+        bail!("at most {MAX_HAND} cards can be advised on at once");
+    }
+    if request.samples > max_samples {
+        bail!("at most {max_samples} draws can be sampled for each discard");
+    }
+    Ok(bindings::advise(request)?)
+}
+
+fn simulate(request: &SimulateRequest, max_runs: u64) -> Result<Tally> {
+    if request.runs > max_runs {
+        bail!("at most {max_runs} runs can be simulated at once");
+    }
+    let strategy = StrategyName::from_str(&request.strategy, true)
+        .map_err(|_| anyhow!("unknown strategy `{}`", request.strategy))?;
+    let deck: StartingDeck = request
+        .deck
+        .parse()
+        .map_err(|_| anyhow!("unknown deck `{}`", request.deck))?;
+    let stake: Stake = request
+        .stake
+        .parse()
+        .map_err(|_| anyhow!("unknown stake `{}`", request.stake))?;
+    let end = request.seed.checked_add(request.runs).ok_or_else(|| {
+        anyhow!(
+            "{} runs from seed {} run out of seeds",
+            request.runs,
+            request.seed
+        )
+    })?;
+    let seeds = request.seed..end;
+    Ok(Tally::new(&play_runs(strategy, deck, stake, seeds, false)?))
+}
+
+/// The JSON answer to a request's `body`.
+fn answer(endpoint: Endpoint, body: &str, limits: Limits) -> Result<String> {
+    let json = match endpoint {
+        Endpoint::Evaluate => serde_json::to_string(&evaluate(&serde_json::from_str(body)?)?)?,
+        Endpoint::Advise => {
+            serde_json::to_string(&advise(&serde_json::from_str(body)?, limits.max_samples)?)?
+        }
+        Endpoint::Simulate => {
+            serde_json::to_string(&simulate(&serde_json::from_str(body)?, limits.max_runs)?)?
+        }
+    };
+    Ok(json)
+}
+
+fn read_body(request: &mut Request) -> Result<String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY + 1)
+        .read_to_string(&mut body)?;
+    if body.len() as u64 > MAX_BODY {
+        bail!("the body is over {MAX_BODY} bytes");
+    }
+    Ok(body)
+}
+
+fn respond(mut request: Request, limits: Limits) {
+    let url = request.url().to_owned();
+    let method = request.method().clone();
+    let (status, body) = match (Endpoint::from_path(&url), &method) {
+        (Some(endpoint), Method::Post) => {
+            match read_body(&mut request).and_then(|body| answer(endpoint, &body, limits)) {
+                Ok(json) => (200, json),
+                Err(err) => (
+                    400,
+                    serde_json::json!({ "error": err.to_string() }).to_string(),
+                ),
+            }
+        }
+        (Some(_), _) => (
+            405,
+            serde_json::json!({ "error": format!("{url} only takes POST") }).to_string(),
+        ),
+        (None, _) => (
+            404,
+            serde_json::json!({ "error": format!("there's nothing at {url}") }).to_string(),
+        ),
+    };
+    eprintln!("{method} {url} {status}");
+
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("the header is valid");
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(content_type);
+    if let Err(err) = request.respond(response) {
+        eprintln!("Couldn't answer {url}: {err}");
+    }
+}
+
+pub fn run(args: &ServeArgs) -> Result<()> {
+    let address = SocketAddr::new(args.host, args.port);
+    let server =
+        Server::http(address).map_err(|err| anyhow!("couldn't listen on {address}: {err}"))?;
+    println!("Listening on http://{address}");
+
+    // Advising and simulating can take a while, so a few workers take turns
+    // at the requests rather than one holding up the rest. Their number is
+    // fixed so a flood of requests queues up instead of starting a thread
+    // for each
+    let server = Arc::new(server);
+    let limits = Limits {
+        max_runs: args.max_runs,
+        max_samples: args.max_samples,
+    };
+    let workers = args
+        .workers
+        .map_or_else(rayon::current_num_threads, NonZeroUsize::get);
+    let workers = (0..workers)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    respond(request, limits);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for worker in workers {
+        worker.join().map_err(|_| anyhow!("a worker panicked"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: Limits = Limits {
+        max_runs: 10,
+        max_samples: 500,
+    };
+
+    #[test]
+    fn from_path_test() {
+        assert!(matches!(
+            Endpoint::from_path("/evaluate"),
+            Some(Endpoint::Evaluate)
+        ));
+        assert!(matches!(
+            Endpoint::from_path("/advise?verbose"),
+            Some(Endpoint::Advise)
+        ));
+        assert!(matches!(
+            Endpoint::from_path("/simulate"),
+            Some(Endpoint::Simulate)
+        ));
+        assert!(Endpoint::from_path("/").is_none());
+        assert!(Endpoint::from_path("/evaluate/more").is_none());
+    }
+
+    #[test]
+    fn answer_test() {
+        let json = answer(
+            Endpoint::Evaluate,
+            r#"{"cards": ["AS", "AH", "KD"]}"#,
+            LIMITS,
+        )
+        .unwrap();
+        let evaluation: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(evaluation["kind"], "Pair");
+        assert_eq!(evaluation["scoring"], serde_json::json!(["AS", "AH"]));
+
+        let json = answer(
+            Endpoint::Advise,
+            r#"{"hand": ["AS", "AH", "AD", "AC", "KD"], "discards": 0, "hands": 1}"#,
+            LIMITS,
+        )
+        .unwrap();
+        let advice: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(advice["action"], "play");
+
+        for (endpoint, body, expected) in [
+            (Endpoint::Evaluate, "{", "EOF while parsing"),
+            (
+                Endpoint::Evaluate,
+                r#"{"cards": [], "tags": []}"#,
+                "unknown field `tags`",
+            ),
+            (
+                Endpoint::Advise,
+                r#"{"hand": ["AS","2S","3S","4S","5S","6S","7S","8S","9S","TS","JS","QS","KS","AH","2H","3H","4H"]}"#,
+                "at most 16 cards",
+            ),
+            (
+                Endpoint::Advise,
+                r#"{"hand": ["AS"], "samples": 501}"#,
+                "at most 500 draws",
+            ),
+            (
+                Endpoint::Advise,
+                r#"{"hand": ["AS", "KD"], "hands": 0}"#,
+                "no hands left",
+            ),
+            (
+                Endpoint::Advise,
+                r#"{"hand": ["AS", "AS", "KD"]}"#,
+                "AS is in hand more than once",
+            ),
+            (Endpoint::Simulate, r#"{"runs": 11}"#, "at most 10 runs"),
+            (
+                Endpoint::Simulate,
+                r#"{"strategy": "lucky", "runs": 1}"#,
+                "unknown strategy `lucky`",
+            ),
+            (
+                Endpoint::Simulate,
+                r#"{"runs": 2, "seed": 18446744073709551615}"#,
+                "run out of seeds",
+            ),
+        ] {
+            let err = answer(endpoint, body, LIMITS).unwrap_err().to_string();
+            assert!(err.contains(expected), "{body}: {err}");
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use rand::prelude::*;
+
+use solver_core::prelude::{
+    Blind, GameState, JokerSlot, Shop, ShopItem, ShopOptimizer, ShopOption, Stake, Stickers,
+    Valuation, WeightedHeuristic,
+};
+
+use crate::stats::parse_jokers;
+
+/// How to judge the run after each option.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum ValuationName {
+    /// Score the state with the default heuristic (fast)
+    Heuristic,
+    /// Play rollouts through the next ante (slow)
+    Rollouts,
+}
+
+#[derive(Debug, Args)]
+pub struct ShopArgs {
+    /// The ante being played
+    #[arg(short = 'a', long = "ante", default_value = "1")]
+    ante: u32,
+
+    /// The blind just beaten: small, big or boss
+    #[arg(short = 'b', long = "blind", default_value = "small")]
+    blind: Blind,
+
+    /// The stake the run is on
+    #[arg(long = "stake", default_value = "white")]
+    stake: Stake,
+
+    /// The money held
+    #[arg(short = 'm', long = "money", default_value = "10")]
+    money: i64,
+
+    /// The jokers held, by name, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// The jokers for sale in the card slots, by name, separated by commas.
+    /// Without this, the card slots are stocked at random
+    #[arg(long = "for-sale", value_delimiter = ',')]
+    for_sale: Vec<String>,
+
+    /// The seed to stock the shop with
+    #[arg(long = "seed", default_value = "0")]
+    seed: u64,
+
+    /// How to judge the run after each option
+    #[arg(long = "valuation", default_value = "heuristic")]
+    valuation: ValuationName,
+
+    /// How many rollouts to judge each state by, with rollout valuation
+    #[arg(short = 'r', long = "rollouts", default_value = "200")]
+    rollouts: usize,
+
+    /// How many ways to open each pack, or reroll the shop
+    #[arg(short = 's', long = "samples", default_value = "8")]
+    samples: usize,
+}
+
+fn describe(option: ShopOption, shop: &Shop) -> String {
+    match option {
+        ShopOption::Buy(index) => match shop.cards[index] {
+            ShopItem::Joker { id, .. } => format!("Buy {}", id.name()),
+            ShopItem::Consumable(consumable) => format!("Buy {consumable}"),
+            ShopItem::Card(card) => format!("Buy {card}"),
+        },
+        ShopOption::BuyPack(index) => {
+            let pack = shop.packs[index];
+            format!("Buy {:?} {:?} pack", pack.size, pack.kind)
+        }
+        ShopOption::BuyVoucher(index) => format!("Buy {}", shop.vouchers[index]),
+        ShopOption::Reroll => "Reroll".to_owned(),
+        ShopOption::Leave => "Leave".to_owned(),
+    }
+}
+
+pub fn run(args: &ShopArgs) -> Result<()> {
+    let mut rng = SmallRng::seed_from_u64(args.seed);
+    let mut state = GameState::fresh_run(&mut rng).with_stake(args.stake);
+    state.ante = args.ante;
+    state.blind = args.blind;
+    state.money = args.money;
+    state.jokers = parse_jokers(&args.jokers)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect();
+
+    let mut shop = Shop::generate(&state, &mut rng);
+    if !args.for_sale.is_empty() {
+        shop.cards = parse_jokers(&args.for_sale)?
+            .into_iter()
+            .map(|id| ShopItem::Joker {
+                id,
+                edition: None,
+                stickers: Stickers::empty(),
+            })
+            .collect();
+    }
+
+    let valuation = match args.valuation {
+        ValuationName::Heuristic => Valuation::Heuristic(WeightedHeuristic::default()),
+        ValuationName::Rollouts => Valuation::Rollouts(args.rollouts),
+    };
+    let optimizer = ShopOptimizer::new()
+        .with_valuation(valuation)
+        .with_samples(args.samples);
+
+    println!("With ${} to spend:", state.money);
+    for ranked in optimizer.rank(&shop, &state)? {
+        println!(
+            "{:>+10.3}  {}",
+            ranked.uplift,
+            describe(ranked.option, &shop)
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueEnum};
+use itertools::Itertools;
+use rand::prelude::*;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use solver_core::prelude::{
+    FlushHunter, GameState, Greedy, Mcts, RandomPlayer, RoundPlanner, RunSimulator, RunSummary,
+    Stake, StartingDeck, Strategy,
+};
+
+use crate::compare::StrategyName;
+
+#[derive(Debug, Subcommand)]
+pub enum CliCommands {
+    /// Play many full runs with one strategy, and sum up how they went
+    Run(RunArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// The deck to start each run with
+    #[arg(long = "deck", default_value = "plain")]
+    deck: StartingDeck,
+
+    /// The stake to play each run on
+    #[arg(long = "stake", default_value = "white")]
+    stake: Stake,
+
+    /// The strategy to play with
+    #[arg(long = "strategy", default_value = "greedy")]
+    strategy: StrategyName,
+
+    /// How many runs to play
+    #[arg(short = 'n', long = "runs", default_value = "1000")]
+    runs: u64,
+
+    /// The seed of the first run; each run after uses the next seed
+    #[arg(long = "seed", default_value = "0")]
+    seed: u64,
+
+    /// Run on a single thread (for profiling)
+    #[arg(long = "single-threaded", default_value = "false")]
+    single_threaded: bool,
+}
+
+fn play(
+    strategy: impl Strategy,
+    deck: StartingDeck,
+    stake: Stake,
+    seed: u64,
+) -> Result<RunSummary> {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let fresh = GameState::fresh_run_with(deck, &mut rng).with_stake(stake);
+    Ok(RunSimulator::new(strategy).run(fresh, &mut rng)?)
+}
+
+fn play_named(
+    name: StrategyName,
+    deck: StartingDeck,
+    stake: Stake,
+    seed: u64,
+) -> Result<RunSummary> {
+    match name {
+        StrategyName::Greedy => play(Greedy::default(), deck, stake, seed),
+        StrategyName::FlushHunter => play(FlushHunter::default(), deck, stake, seed),
+        StrategyName::Random => play(RandomPlayer, deck, stake, seed),
+        StrategyName::Planner => play(RoundPlanner::default(), deck, stake, seed),
+        StrategyName::Mcts => play(Mcts::default(), deck, stake, seed),
+    }
+}
+
+/// Play a run for each of `seeds` with the named strategy, in seed order.
+pub(crate) fn play_runs(
+    name: StrategyName,
+    deck: StartingDeck,
+    stake: Stake,
+    seeds: Range<u64>,
+    single_threaded: bool,
+) -> Result<Vec<RunSummary>> {
+    if single_threaded {
+        seeds
+            .map(|seed| play_named(name, deck, stake, seed))
+            .collect()
+    } else {
+        seeds
+            .into_par_iter()
+            .map(|seed| play_named(name, deck, stake, seed))
+            .collect()
+    }
+}
+
+pub fn run(command: &CliCommands) -> Result<()> {
+    match command {
+        CliCommands::Run(args) => simulate_runs(args),
+    }
+}
+
+/// How a batch of runs went, summed up.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct Tally {
+    pub(crate) runs: usize,
+    /// The share of runs won, from 0 to 1
+    pub(crate) win_rate: f64,
+    pub(crate) mean_blinds_beaten: f64,
+    pub(crate) mean_final_money: f64,
+    /// How many runs ended on each ante
+    pub(crate) final_antes: BTreeMap<u32, usize>,
+}
+
+impl Tally {
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn new(summaries: &[RunSummary]) -> Self {
+        let runs = summaries.len().max(1) as f64;
+        let wins = summaries.iter().filter(|summary| summary.won).count() as f64;
+        let money: i64 = summaries
+            .iter()
+            .map(|summary| summary.money.last().copied().unwrap_or(0))
+            .sum();
+        let blinds: u32 = summaries.iter().map(|summary| summary.blinds_beaten).sum();
+        Self {
+            runs: summaries.len(),
+            win_rate: wins / runs,
+            mean_blinds_beaten: f64::from(blinds) / runs,
+            mean_final_money: money as f64 / runs,
+            final_antes: summaries
+                .iter()
+                .counts_by(|summary| summary.final_ante)
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn simulate_runs(args: &RunArgs) -> Result<()> {
+    let summaries = play_runs(
+        args.strategy,
+        args.deck,
+        args.stake,
+        args.seed..args.seed + args.runs,
+        args.single_threaded,
+    )?;
+    let tally = Tally::new(&summaries);
+
+    println!(
+        "Over {} runs with {}, on the {} Deck at {} Stake, from seed {}:",
+        args.runs,
+        args.strategy.to_possible_value().unwrap().get_name(),
+        args.deck,
+        args.stake,
+        args.seed
+    );
+    println!("Win rate:             {:>7.2}%", tally.win_rate * 100.0);
+    println!("Mean blinds beaten:   {:>8.2}", tally.mean_blinds_beaten);
+    println!("Mean final money:     {:>8.2}", tally.mean_final_money);
+    println!();
+    println!("{:>4} {:>7} {:>8}", "Ante", "Runs", "Share");
+    let runs = tally.runs.max(1) as f64;
+    for (ante, count) in tally.final_antes {
+        println!("{ante:>4} {count:>7} {:>7.2}%", count as f64 / runs * 100.0);
+    }
+
+    Ok(())
+}
@@ -1,195 +1,3090 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
-
-use anyhow::Result;
-use clap::Subcommand;
-use itertools::Itertools;
-use rand::prelude::*;
-use rayon::prelude::*;
-use strum::IntoEnumIterator;
-
-use solver_core::prelude::{
-    CardCollection, CardView, Deck, Hand, HandEvaluator, HandKind, Options, Scorer,
-};
-
-#[derive(Debug, Subcommand)]
-pub enum CliCommands {
-    /// Generate statistics for the 12 different types of Balatro hands
-    HandStats {
-        /// Run on a single thread (for profiling)
-        #[arg(long = "single-threaded", default_value = "false")]
-        single_threaded: bool,
-
-        /// Perform this many iterations, in tens of thousands
-        #[arg(short = 'i', long = "iterations", default_value = "100")]
-        iterations: usize,
-
-        /// Whether the "Shortcut" joker is enabled, allowing straights to have a gap
-        #[arg(long = "shortcut", default_value = "false")]
-        shortcut: bool,
-
-        /// Whether the "Four Fingers" joker is enabled, allowing straights/flushes to consist of 4 cards
-        #[arg(long = "four-fingers", default_value = "false")]
-        four_fingers: bool,
-    },
-}
-
-struct HandStats {
-    frequency: f32,
-    average_score: f32,
-}
-
-#[allow(clippy::cast_precision_loss)]
-fn generate_hand_stats<G>(
-    single_threaded: bool,
-    iterations: usize,
-    generate_hand: G,
-) -> HashMap<HandKind, HandStats>
-where
-    G: Fn() -> (HandKind, Hand) + std::marker::Sync,
-{
-    let hand_map: HashMap<HandKind, (usize, f32)> = if single_threaded {
-        (0..iterations)
-            .map(|_| generate_hand())
-            .fold(HashMap::new(), |mut map, (kind, hand)| {
-                let entry = map.entry(kind).or_insert((0, 0.0));
-                entry.0 += 1;
-                entry.1 += Scorer::score_hand(kind, &hand);
-                map
-            })
-    } else {
-        (0..iterations)
-            .into_par_iter()
-            .map(|_| generate_hand())
-            .fold(HashMap::new, |mut map, (kind, hand)| {
-                let entry = map.entry(kind).or_insert((0, 0.0));
-                entry.0 += 1;
-                entry.1 += Scorer::score_hand(kind, &hand);
-                map
-            })
-            .reduce(HashMap::new, |mut left, right| {
-                for (hand, (count, score)) in right {
-                    let entry = left.entry(hand).or_insert((0, 0.0));
-                    entry.0 += count;
-                    entry.1 += score;
-                }
-
-                left
-            })
-    };
-
-    let total = hand_map.values().map(|(count, _)| count).sum::<usize>() as f32;
-
-    let frequencies: HashMap<_, _> = hand_map
-        .into_iter()
-        .map(|(hand, (count, score))| {
-            (
-                hand,
-                HandStats {
-                    frequency: (count as f32) / total,
-                    average_score: score / (count as f32),
-                },
-            )
-        })
-        .collect();
-
-    frequencies
-}
-
-fn print_card_stats(stats: HashMap<HandKind, HandStats>) {
-    let hand_name_columns = HandKind::iter()
-        .map(|h| format!("{h:?}").len())
-        .max()
-        .unwrap();
-    for (
-        hand,
-        HandStats {
-            frequency,
-            average_score,
-        },
-    ) in stats.into_iter().sorted_by_key(|(hand, _)| *hand)
-    {
-        println!(
-            " - {:hand_width$} {:>6.3}% (avg: {average_score:>6.1}, ev: {:>6.1})",
-            format!("{:?}", hand),
-            frequency * 100.0,
-            average_score * frequency,
-            hand_width = hand_name_columns
-        );
-    }
-}
-
-fn fresh_draw_stats(single_threaded: bool, iterations: usize, options: Options) {
-    thread_local! {
-        static RNG: RefCell<SmallRng> = RefCell::new(rand::rngs::SmallRng::from_entropy());
-    }
-
-    let generate_hand = || {
-        let mut deck = RNG.with_borrow_mut(Deck::shuffled);
-        let hand = deck.draw_hand().unwrap();
-
-        HandEvaluator::evaluate_poker_hand(hand, options).unwrap()
-    };
-
-    let stats = generate_hand_stats(single_threaded, iterations, generate_hand);
-
-    println!("When drawing 5 cards from a shuffled 52-card standard deck, the frequencies of each hand are:");
-    print_card_stats(stats);
-}
-
-fn eight_card_draw_stats(single_threaded: bool, iterations: usize, options: Options) {
-    thread_local! {
-        static RNG: RefCell<SmallRng> = RefCell::new(rand::rngs::SmallRng::from_entropy());
-    }
-
-    let generate_hand = || {
-        let mut deck = RNG.with_borrow_mut(Deck::shuffled);
-        let cards = deck.draw_n(8).unwrap();
-
-        let mut best_hand: Option<(HandKind, Hand)> = None;
-        for hand in cards.view().iter().copied().combinations(5) {
-            let (kind, hand) =
-                HandEvaluator::evaluate_poker_hand(Hand::from_slice(&hand).unwrap(), options)
-                    .unwrap();
-
-            if best_hand.is_none() || kind > best_hand.as_ref().unwrap().0 {
-                best_hand = Some((kind, hand));
-            }
-        }
-
-        best_hand.unwrap()
-    };
-
-    let stats = generate_hand_stats(single_threaded, iterations, generate_hand);
-
-    println!("When drawing 8 cards from a shuffled 52-card standard deck, the frequencies of each best hand are:");
-    print_card_stats(stats);
-}
-
-#[allow(clippy::unnecessary_wraps)]
-fn hand_stats(single_threaded: bool, iterations: usize, options: Options) -> Result<()> {
-    fresh_draw_stats(single_threaded, iterations, options);
-    eight_card_draw_stats(single_threaded, iterations, options);
-
-    Ok(())
-}
-
-pub fn run(command: &CliCommands) -> Result<()> {
-    match command {
-        CliCommands::HandStats {
-            single_threaded,
-            iterations,
-            shortcut,
-            four_fingers,
-        } => hand_stats(*single_threaded, *iterations * 10_000, {
-            let mut options = Options::empty();
-            if *shortcut {
-                options |= Options::GappedStraights;
-            }
-            if *four_fingers {
-                options |= Options::FourCardStraightsAndFlushes;
-            }
-            options
-        }),
-    }
-}
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::ops::{AddAssign, Range, RangeInclusive};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use itertools::Itertools;
+use rand::prelude::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use solver_core::prelude::{
+    draw_classes, Action, Advisor, Blind, BossBlind, Card, CardCollection, CardView, Deck,
+    DeckTracker, Edition, Enhancement, GameState, Hand, HandEvaluator, HandKind, HandLevels,
+    ImportanceSampler, JokerId, JokerSlot, Options, Rank, Scorer, ScoringContext, ScoringOptions,
+    Seal, Stake, StartingDeck, Suit, TranspositionTable,
+};
+
+use crate::checkpoint::{CheckpointArgs, Checkpointer, Saved};
+use crate::compare::StrategyName;
+use crate::discard_ev::parse_cards;
+use crate::output::{print_records, Format, JsonLines, OutputArgs, Record};
+use crate::report::{Bar, Report};
+use crate::simulate::play_runs;
+
+#[derive(Debug, Subcommand)]
+pub enum CliCommands {
+    /// Generate statistics for the 12 different types of Balatro hands
+    HandStats(HandStatsArgs),
+    /// Measure how much each pair of jokers scores together compared to the
+    /// sum of what they score separately
+    JokerSynergy {
+        /// Run on a single thread (for profiling)
+        #[arg(long = "single-threaded", default_value = "false")]
+        single_threaded: bool,
+
+        /// Perform this many iterations, in tens of thousands
+        #[arg(short = 'i', long = "iterations", default_value = "10")]
+        iterations: usize,
+
+        /// Seed each worker's random number generator with this plus its
+        /// index, so the results can be reproduced exactly
+        #[arg(long = "seed")]
+        seed: Option<u64>,
+
+        /// The jokers to compare, by name, separated by commas
+        #[arg(short = 'j', long = "jokers", value_delimiter = ',', required = true)]
+        jokers: Vec<String>,
+
+        /// Play every hand against this Boss Blind, like `club`, `plant`,
+        /// `flint`, `manacle` or `house`
+        #[arg(long = "boss")]
+        boss: Option<BossBlind>,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Compare the hand statistics with and without a joker
+    JokerImpact {
+        /// Run on a single thread (for profiling)
+        #[arg(long = "single-threaded", default_value = "false")]
+        single_threaded: bool,
+
+        /// Perform this many iterations, in tens of thousands
+        #[arg(short = 'i', long = "iterations", default_value = "100")]
+        iterations: usize,
+
+        /// Seed each worker's random number generator with this plus its
+        /// index, so the results can be reproduced exactly
+        #[arg(long = "seed")]
+        seed: Option<u64>,
+
+        /// The joker to measure, by name
+        #[arg(short = 'j', long = "joker")]
+        joker: String,
+
+        /// Play every hand against this Boss Blind, like `club`, `plant`,
+        /// `flint`, `manacle` or `house`
+        #[arg(long = "boss")]
+        boss: Option<BossBlind>,
+
+        /// Perform only this share of the iterations, like `2/4` for the
+        /// second quarter, drawing from the generators the whole job would, so
+        /// the shards' checkpoints merge into the same counts
+        #[arg(long = "shard", value_parser = parse_shard)]
+        shard: Option<Shard>,
+
+        #[command(flatten)]
+        checkpoint: CheckpointArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Measure how a kind of hand's average score and EV grow as it's
+    /// levelled up, to value its planet
+    LevelSweep(LevelSweepArgs),
+    /// Measure how often cards of each rank and suit score in the best hand,
+    /// to see which are worth removing from the deck
+    CardStats(CardStatsArgs),
+    /// Measure how often each card is drawn but left out of the best hand,
+    /// most often first, to see which are dead weight in the deck
+    DeadWeight(CardStatsArgs),
+    /// Combine the hands counted in the checkpoints of several runs of the
+    /// same job, like on different machines, into one report
+    Merge(MergeArgs),
+    /// Measure how far runs get: the distribution of the final ante reached
+    /// over many simulated runs
+    AnteDistribution {
+        /// Run on a single thread (for profiling)
+        #[arg(long = "single-threaded", default_value = "false")]
+        single_threaded: bool,
+
+        /// How many runs to play
+        #[arg(
+            short = 'n',
+            long = "runs",
+            default_value = "1000",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        runs: u64,
+
+        /// The seed of the first run; each run after uses the next seed
+        #[arg(long = "seed", default_value = "0")]
+        seed: u64,
+
+        /// The strategy to play with
+        #[arg(long = "strategy", default_value = "greedy")]
+        strategy: StrategyName,
+
+        /// The deck to start each run with
+        #[arg(long = "deck", default_value = "plain")]
+        deck: StartingDeck,
+
+        /// The stake to play each run on
+        #[arg(long = "stake", default_value = "white")]
+        stake: Stake,
+
+        #[command(flatten)]
+        checkpoint: CheckpointArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+// Each flag is a switch of its own, so they're plain bools
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Args)]
+pub struct HandStatsArgs {
+    /// Run on a single thread (for profiling)
+    #[arg(long = "single-threaded", default_value = "false")]
+    single_threaded: bool,
+
+    /// Perform this many iterations, in tens of thousands
+    #[arg(short = 'i', long = "iterations", default_value = "100")]
+    iterations: usize,
+
+    /// Seed each worker's random number generator with this plus its
+    /// index, so the results can be reproduced exactly
+    #[arg(long = "seed")]
+    seed: Option<u64>,
+
+    /// Keep sampling until every frequency's standard error, and the
+    /// average score's relative to it, are within this, like `0.1%`,
+    /// performing at most the given iterations
+    #[arg(long = "precision", value_parser = parse_proportion)]
+    precision: Option<f64>,
+
+    /// Draw cards that share suits and ranks more often than chance, and
+    /// weight each hand by how much likelier it is to be drawn fairly,
+    /// for tighter estimates of the rarest hands
+    #[arg(long = "importance", default_value = "false")]
+    importance: bool,
+
+    /// Count every distinct 5-card draw once, instead of sampling them,
+    /// for exact 5-card frequencies
+    #[arg(long = "exhaustive", default_value = "false")]
+    exhaustive: bool,
+
+    /// Work out the 5-card frequencies from the deck's ranks and suits,
+    /// scoring one draw for each class of equivalent draws, instead of
+    /// sampling them. Only for decks of plain cards, without jokers or a
+    /// Boss Blind
+    #[arg(
+        long = "analytic",
+        default_value = "false",
+        conflicts_with = "exhaustive"
+    )]
+    analytic: bool,
+
+    /// Whether the "Shortcut" joker is enabled, allowing straights to have a gap
+    #[arg(long = "shortcut", default_value = "false")]
+    shortcut: bool,
+
+    /// Whether the "Four Fingers" joker is enabled, allowing straights/flushes to consist of 4 cards
+    #[arg(long = "four-fingers", default_value = "false")]
+    four_fingers: bool,
+
+    /// The jokers held, by name, separated by commas. Shortcut, Four
+    /// Fingers, Splash and Pareidolia change which hands are made and which
+    /// cards score; the rest add their scoring effects
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// Run with every combination of Shortcut and Four Fingers, alongside
+    /// any other jokers held, and compare each hand's frequency with neither
+    #[arg(
+        long = "all-options",
+        default_value = "false",
+        conflicts_with_all = ["shortcut", "four_fingers"]
+    )]
+    all_options: bool,
+
+    /// Whether to score as the Plasma deck, balancing chips and mult before multiplying
+    #[arg(long = "plasma", default_value = "false")]
+    plasma: bool,
+
+    /// How many cards to draw for the best hand, like 11 with Juggler and
+    /// Troubadour, before The Manacle takes one away
+    #[arg(long = "hand-size", default_value = "8")]
+    hand_size: usize,
+
+    /// Play every hand against this Boss Blind, like `club`, `plant`,
+    /// `flint`, `manacle` or `house`
+    #[arg(long = "boss")]
+    boss: Option<BossBlind>,
+
+    /// Discard from the cards drawn for the best hand and draw replacements
+    /// up to this many times first, whenever the advisor expects it to
+    /// score more. Each discard is searched for, so this is far slower
+    #[arg(long = "discards", default_value = "0", conflicts_with = "importance")]
+    discards: u32,
+
+    /// The deck to draw from: a starting deck like `checkered`, `abandoned`
+    /// or `erratic`, whose cards are dealt afresh for every draw, or the
+    /// cards, like "AS AS+glass KD+foil+red-seal 7C ...", or a file listing
+    /// them
+    #[arg(
+        long = "deck",
+        visible_alias = "deck-file",
+        default_value = "plain",
+        value_parser = parse_deck
+    )]
+    deck: DrawDeck,
+
+    /// Give each card of the deck a random enhancement with this chance,
+    /// like `0.2` or `20%`, afresh for every draw, unless it has one
+    #[arg(long = "random-enhancements", value_parser = parse_chance)]
+    random_enhancements: Option<f64>,
+
+    /// Give each card of the deck a random edition with this chance, afresh
+    /// for every draw, unless it has one
+    #[arg(long = "random-editions", value_parser = parse_chance)]
+    random_editions: Option<f64>,
+
+    /// Give each card of the deck a random seal with this chance, afresh for
+    /// every draw, unless it has one
+    #[arg(long = "random-seals", value_parser = parse_chance)]
+    random_seals: Option<f64>,
+
+    /// Perform only this share of the iterations, like `2/4` for the
+    /// second quarter, drawing from the generators the whole job would, so
+    /// the shards' checkpoints merge into the same counts
+    #[arg(long = "shard", value_parser = parse_shard)]
+    shard: Option<Shard>,
+
+    #[command(flatten)]
+    checkpoint: CheckpointArgs,
+
+    /// Write each hand sampled to this file, as a line of JSON with the
+    /// cards scored and held, the kind, the score and the weight
+    #[arg(long = "record")]
+    record: Option<PathBuf>,
+
+    /// Also write the results to this file as a report, with tables and
+    /// charts of the frequencies and scores: Markdown if it ends in `.md`,
+    /// and otherwise HTML
+    #[arg(long = "report")]
+    report: Option<PathBuf>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct LevelSweepArgs {
+    /// Run on a single thread (for profiling)
+    #[arg(long = "single-threaded", default_value = "false")]
+    single_threaded: bool,
+
+    /// Perform this many iterations, in tens of thousands
+    #[arg(short = 'i', long = "iterations", default_value = "10")]
+    iterations: usize,
+
+    /// Seed each worker's random number generator with this plus its
+    /// index, so the results can be reproduced exactly
+    #[arg(long = "seed")]
+    seed: Option<u64>,
+
+    /// The kind of hand to level up, like `Flush` or `full-house`
+    #[arg(long = "kind", value_parser = parse_kind)]
+    kind: HandKind,
+
+    /// The levels to compare, like `1..10`, including both ends
+    #[arg(long = "levels", default_value = "1..10", value_parser = parse_levels)]
+    levels: RangeInclusive<u32>,
+
+    /// The jokers held, by name, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// Whether the "Shortcut" joker is enabled, allowing straights to have a gap
+    #[arg(long = "shortcut", default_value = "false")]
+    shortcut: bool,
+
+    /// Whether the "Four Fingers" joker is enabled, allowing straights/flushes to consist of 4 cards
+    #[arg(long = "four-fingers", default_value = "false")]
+    four_fingers: bool,
+
+    /// Play every hand against this Boss Blind, like `club`, `plant`,
+    /// `flint`, `manacle` or `house`
+    #[arg(long = "boss")]
+    boss: Option<BossBlind>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct CardStatsArgs {
+    /// Run on a single thread (for profiling)
+    #[arg(long = "single-threaded", default_value = "false")]
+    single_threaded: bool,
+
+    /// Perform this many iterations, in tens of thousands
+    #[arg(short = 'i', long = "iterations", default_value = "10")]
+    iterations: usize,
+
+    /// Seed each worker's random number generator with this plus its
+    /// index, so the results can be reproduced exactly
+    #[arg(long = "seed")]
+    seed: Option<u64>,
+
+    /// The jokers held that change which cards score: Shortcut, Four
+    /// Fingers or Splash, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// How many cards to draw for the best hand, before The Manacle takes one
+    /// away
+    #[arg(long = "hand-size", default_value = "8")]
+    hand_size: usize,
+
+    /// Play every hand against this Boss Blind, like `club`, `plant` or
+    /// `house`
+    #[arg(long = "boss")]
+    boss: Option<BossBlind>,
+
+    /// The deck to draw from: a starting deck like `checkered`, `abandoned`
+    /// or `erratic`, or the cards, like "AS AS+glass KD+foil+red-seal 7C
+    /// ...", or a file listing them
+    #[arg(
+        long = "deck",
+        visible_alias = "deck-file",
+        default_value = "plain",
+        value_parser = parse_deck
+    )]
+    deck: DrawDeck,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct MergeArgs {
+    /// The checkpoint files to merge, saved with `--checkpoint` by runs of
+    /// the same job with different seeds, or none
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Save the merged counts to this file, as a checkpoint that can be
+    /// merged again
+    #[arg(long = "checkpoint")]
+    checkpoint: Option<PathBuf>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// How many standard errors either side of an estimate its 95% confidence
+/// interval reaches
+const Z_95: f64 = 1.96;
+
+struct HandStats {
+    frequency: f64,
+    /// The standard error of the frequency
+    frequency_error: f64,
+    average_score: f64,
+    /// The sample variance of the scores of hands of this kind
+    score_variance: f64,
+    /// How many hands of this kind were sampled, or as many fair draws as
+    /// they're worth if they were weighted
+    count: f64,
+    /// How many hands of every kind were sampled, or as many fair draws as
+    /// they're worth
+    samples: f64,
+    /// Whether every possible hand was counted, rather than a sample
+    exact: bool,
+    /// The share of hands of this kind that scored in each of
+    /// `score_bucket`'s buckets
+    score_shares: [f64; SCORE_BUCKETS],
+}
+
+impl HandStats {
+    /// Half the width of the 95% confidence interval around the frequency.
+    fn frequency_margin(&self) -> f64 {
+        if self.exact {
+            0.0
+        } else {
+            Z_95 * self.frequency_error
+        }
+    }
+
+    /// Half the width of the 95% confidence interval around the average
+    /// score.
+    fn score_margin(&self) -> f64 {
+        if self.count > 0.0 && !self.exact {
+            Z_95 * (self.score_variance / self.count).sqrt()
+        } else {
+            0.0
+        }
+    }
+}
+
+/// The average score over hands of every kind, and half the width of its 95%
+/// confidence interval.
+fn expected_score(stats: &HashMap<HandKind, HandStats>) -> (f64, f64) {
+    let mean = stats
+        .values()
+        .map(|stats| stats.frequency * stats.average_score)
+        .sum::<f64>();
+    let square = stats
+        .values()
+        .map(|stats| stats.frequency * (stats.score_variance + stats.average_score.powi(2)))
+        .sum::<f64>();
+    let samples = stats
+        .values()
+        .map(|stats| stats.samples)
+        .fold(0.0, f64::max);
+    let exact = stats.values().any(|stats| stats.exact);
+    let margin = if samples > 0.0 && !exact {
+        Z_95 * ((square - mean * mean).max(0.0) / samples).sqrt()
+    } else {
+        0.0
+    };
+    (mean, margin)
+}
+
+/// How often a kind of hand is the best one drawn, and what it scores.
+#[derive(Clone, Serialize)]
+struct HandRecord {
+    /// How many cards the hand is drawn from
+    drawn: usize,
+    /// The jokers held, separated by commas
+    jokers: String,
+    hand: String,
+    frequency: f64,
+    /// Half the width of the frequency's 95% confidence interval
+    frequency_margin: f64,
+    average_score: f64,
+    /// Half the width of the average score's 95% confidence interval
+    score_margin: f64,
+    ev: f64,
+}
+
+impl Record for HandRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "drawn",
+        "jokers",
+        "hand",
+        "frequency",
+        "frequency_margin",
+        "average_score",
+        "score_margin",
+        "ev",
+    ];
+}
+
+fn hand_records(
+    drawn: usize,
+    jokers: &str,
+    stats: &HashMap<HandKind, HandStats>,
+) -> Vec<HandRecord> {
+    stats
+        .iter()
+        .sorted_by_key(|(hand, _)| **hand)
+        .map(|(hand, stats)| HandRecord {
+            drawn,
+            jokers: jokers.to_owned(),
+            hand: format!("{hand:?}"),
+            frequency: stats.frequency,
+            frequency_margin: stats.frequency_margin(),
+            average_score: stats.average_score,
+            score_margin: stats.score_margin(),
+            ev: stats.average_score * stats.frequency,
+        })
+        .collect()
+}
+
+/// What a lineup of no jokers, one joker or a pair of them scores on the
+/// best hand from 8 cards.
+#[derive(Clone, Serialize)]
+struct SynergyRecord {
+    first: Option<String>,
+    second: Option<String>,
+    average_score: f64,
+    /// How much the lineup raises the average score over no jokers
+    uplift: f64,
+    /// A pair's uplift relative to the sum of its jokers' separate uplifts
+    synergy: Option<f64>,
+}
+
+impl Record for SynergyRecord {
+    const COLUMNS: &'static [&'static str] =
+        &["first", "second", "average_score", "uplift", "synergy"];
+}
+
+/// What the best hand from 8 cards scores with a kind of hand at a level.
+#[derive(Clone, Serialize)]
+struct LevelRecord {
+    kind: String,
+    level: u32,
+    /// How often the kind is the best hand
+    best_rate: f64,
+    /// The kind's average score, when it's the best hand
+    average_score: f64,
+    /// The kind's share of the average score over every hand
+    ev: f64,
+    /// The average score over every hand
+    overall: f64,
+    /// How much the overall average score rises over the first level
+    uplift: f64,
+}
+
+impl Record for LevelRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "kind",
+        "level",
+        "best_rate",
+        "average_score",
+        "ev",
+        "overall",
+        "uplift",
+    ];
+}
+
+/// How often cards of a rank or a suit are drawn, and score in the best
+/// hand.
+#[derive(Clone, Serialize)]
+struct CardRecord {
+    /// Whether `card` is a rank or a suit
+    group: &'static str,
+    card: String,
+    /// How many are drawn a hand, on average
+    drawn: f64,
+    /// How many score a hand, on average
+    scored: f64,
+    /// The share of those drawn that score
+    rate: f64,
+    /// Their share of every card that scores
+    share: f64,
+}
+
+impl Record for CardRecord {
+    const COLUMNS: &'static [&'static str] = &["group", "card", "drawn", "scored", "rate", "share"];
+}
+
+/// How often a card is drawn but doesn't score in the best hand.
+#[derive(Clone, Serialize)]
+struct DeadWeightRecord {
+    card: String,
+    /// How many are drawn a hand, on average
+    drawn: f64,
+    /// How many are drawn and don't score a hand, on average
+    unused: f64,
+    /// The share of those drawn that don't score
+    rate: f64,
+}
+
+impl Record for DeadWeightRecord {
+    const COLUMNS: &'static [&'static str] = &["card", "drawn", "unused", "rate"];
+}
+
+/// How often a kind of hand came up in a stage of merged runs, and what it
+/// scored.
+#[derive(Clone, Serialize)]
+struct MergedRecord {
+    /// What was sampled, like `8 cards with [Shortcut]`
+    stage: String,
+    hand: String,
+    frequency: f64,
+    /// Half the width of the frequency's 95% confidence interval
+    frequency_margin: f64,
+    average_score: f64,
+    /// Half the width of the average score's 95% confidence interval
+    score_margin: f64,
+    ev: f64,
+}
+
+impl Record for MergedRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "stage",
+        "hand",
+        "frequency",
+        "frequency_margin",
+        "average_score",
+        "score_margin",
+        "ev",
+    ];
+}
+
+/// How many runs ended on an ante.
+#[derive(Clone, Serialize)]
+struct AnteRecord {
+    ante: u32,
+    ended: usize,
+    /// How many of the runs that ended there won
+    won: usize,
+    share: f64,
+    /// The share of runs that got at least this far
+    reached: f64,
+}
+
+impl Record for AnteRecord {
+    const COLUMNS: &'static [&'static str] = &["ante", "ended", "won", "share", "reached"];
+}
+
+/// How many iterations each worker performs with its own random number
+/// generator
+const WORKER_ITERATIONS: usize = 1_000;
+
+/// How many iterations to perform between checks on the precision, between
+/// checkpoints, and between writes of the hands recorded
+const BATCH_ITERATIONS: usize = 100 * WORKER_ITERATIONS;
+
+/// How many runs to play between checkpoints
+const BATCH_RUNS: u64 = 100;
+
+/// How many hands to sample, and how.
+#[derive(Copy, Clone, Debug)]
+struct Sampling<'a> {
+    single_threaded: bool,
+    /// How many iterations to perform, or the most to if there's a precision
+    /// target
+    iterations: usize,
+    /// The first iteration to perform, past 0 for a shard of a larger job
+    start: usize,
+    /// Seed each worker's generator with this plus its index
+    seed: Option<u64>,
+    /// Stop once every frequency's standard error, and the average score's
+    /// relative to it, are within this
+    precision: Option<f64>,
+    /// Draw with an `ImportanceSampler` rather than fairly
+    importance: bool,
+    /// The cards to draw from
+    deck: &'a DrawDeck,
+    /// How many cards to draw for the best hand
+    hand_size: usize,
+    /// How many times the cards drawn for the best hand can be discarded
+    /// from and replaced first, as an `Advisor` says
+    discards: u32,
+    /// Where to save the tallies every so often, and resume them from
+    checkpoint: &'a Checkpointer,
+    /// Where to write every hand sampled
+    record: Option<&'a JsonLines>,
+}
+
+impl<'a> Sampling<'a> {
+    /// Sample fairly from the standard deck, `iterations` tens of thousands
+    /// of times.
+    fn new(
+        single_threaded: bool,
+        iterations: usize,
+        seed: Option<u64>,
+        checkpoint: &'a Checkpointer,
+    ) -> Self {
+        Self {
+            single_threaded,
+            iterations: iterations * 10_000,
+            start: 0,
+            seed,
+            precision: None,
+            importance: false,
+            deck: &STANDARD_DECK,
+            hand_size: 8,
+            discards: 0,
+            checkpoint,
+            record: None,
+        }
+    }
+
+    /// The discards made before the best hand as a phrase for prose, like
+    /// ` and discarding up to 2 times`, or nothing without any.
+    fn discarding(&self) -> String {
+        match self.discards {
+            0 => String::new(),
+            1 => " and discarding up to once".to_owned(),
+            discards => format!(" and discarding up to {discards} times"),
+        }
+    }
+
+    /// Perform just the iterations in `shard`, if there is one.
+    fn sharded(self, shard: Option<Range<usize>>) -> Self {
+        match shard {
+            Some(shard) => Self {
+                iterations: shard.len(),
+                start: shard.start,
+                ..self
+            },
+            None => self,
+        }
+    }
+}
+
+/// One of several parts of a job's iterations, the `index`th from 1.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Shard {
+    index: usize,
+    count: usize,
+}
+
+/// A shard like `2/4`, for the second of four.
+fn parse_shard(text: &str) -> Result<Shard, String> {
+    let parse = |number: &str| {
+        number
+            .trim()
+            .parse::<usize>()
+            .map_err(|err| format!("{number}: {err}"))
+    };
+    let (index, count) = text
+        .split_once('/')
+        .ok_or_else(|| "a shard is like 2/4, for the second of four".to_owned())?;
+    let (index, count) = (parse(index)?, parse(count)?);
+    if index == 0 || index > count {
+        return Err("shards are numbered from 1 up to how many there are".to_owned());
+    }
+    Ok(Shard { index, count })
+}
+
+/// The iterations `shard` performs of a job's `iterations`, if it's sharded:
+/// its share of the workers, in order, so the shards perform every worker's
+/// between them.
+fn shard_iterations(shard: Option<Shard>, iterations: usize) -> Result<Option<Range<usize>>> {
+    let Some(Shard { index, count }) = shard else {
+        return Ok(None);
+    };
+    let workers = (iterations * 10_000).div_ceil(WORKER_ITERATIONS);
+    if count > workers {
+        return Err(anyhow!(
+            "there are only {workers} workers' iterations to split between {count} shards"
+        ));
+    }
+    let start = (index - 1) * workers / count * WORKER_ITERATIONS;
+    let end = index * workers / count * WORKER_ITERATIONS;
+    Ok(Some(start..end.min(iterations * 10_000)))
+}
+
+/// A seed, for telling jobs apart. A shard of a seeded job lists the
+/// generators its workers draw from instead, as `merge` does.
+fn describe_seed(seed: Option<u64>, shard: Option<&Range<usize>>) -> String {
+    match (seed, shard) {
+        (None, _) => "no seed".to_owned(),
+        (Some(seed), None) => format!("seed {seed}"),
+        (Some(seed), Some(shard)) => {
+            let worker = |iteration: usize| seed.saturating_add(iteration as u64);
+            format!(
+                "generators {}..{}",
+                worker(shard.start / WORKER_ITERATIONS),
+                worker(shard.end.div_ceil(WORKER_ITERATIONS))
+            )
+        }
+    }
+}
+
+/// `name` without case, spaces or dashes, for matching names loosely.
+fn squash(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// A kind of hand by name, ignoring case, spaces and dashes, like `Flush` or
+/// `full-house`.
+fn parse_kind(text: &str) -> Result<HandKind, String> {
+    HandKind::iter()
+        .find(|kind| squash(&format!("{kind:?}")) == squash(text))
+        .ok_or_else(|| format!("there's no kind of hand called {text}"))
+}
+
+/// A range of levels like `1..10` or `1..=10`, both including both ends, or
+/// a single level.
+fn parse_levels(text: &str) -> Result<RangeInclusive<u32>, String> {
+    let parse = |level: &str| {
+        level
+            .trim()
+            .parse::<u32>()
+            .map_err(|err| format!("{level}: {err}"))
+    };
+    let (first, last) = match text.split_once("..") {
+        Some((first, last)) => (parse(first)?, parse(last.trim_start_matches('='))?),
+        None => (parse(text)?, parse(text)?),
+    };
+    if first == 0 || first > last {
+        return Err("levels start from 1, and the first can't be past the last".to_owned());
+    }
+    Ok(first..=last)
+}
+
+/// The cards hands are drawn from.
+#[derive(Clone, Debug)]
+struct DrawDeck {
+    /// What to call the deck, like `52-card Checkered deck`
+    name: String,
+    cards: Vec<Card>,
+    /// Whether each draw is from a fresh Erratic deck, rather than `cards`
+    erratic: bool,
+    /// The modifiers given to the cards at random for each draw
+    modifiers: RandomModifiers,
+}
+
+impl DrawDeck {
+    /// Whether every draw is from the same cards.
+    fn is_fixed(&self) -> bool {
+        !self.erratic && self.modifiers.is_none()
+    }
+
+    /// This deck with `modifiers` given to its cards at random for each
+    /// draw.
+    fn with_modifiers(&self, modifiers: RandomModifiers) -> Self {
+        let mut deck = self.clone();
+        if !modifiers.is_none() {
+            deck.name = format!("{} with {}", deck.name, modifiers.describe());
+            deck.modifiers = modifiers;
+        }
+        deck
+    }
+}
+
+static STANDARD_DECK: LazyLock<DrawDeck> = LazyLock::new(|| DrawDeck {
+    name: "52-card standard deck".to_owned(),
+    cards: Deck::base_deck().view().to_vec(),
+    erratic: false,
+    modifiers: RandomModifiers::default(),
+});
+
+/// The chances of each card being given a random enhancement, edition and
+/// seal, like it was bought from a Standard pack.
+#[derive(Clone, Copy, Debug, Default)]
+struct RandomModifiers {
+    enhancements: f64,
+    editions: f64,
+    seals: f64,
+}
+
+impl RandomModifiers {
+    fn is_none(self) -> bool {
+        self.enhancements == 0.0 && self.editions == 0.0 && self.seals == 0.0
+    }
+
+    /// What's given, like `20% enhanced and 5% sealed cards`.
+    fn describe(self) -> String {
+        [
+            (self.enhancements, "enhanced"),
+            (self.editions, "editioned"),
+            (self.seals, "sealed"),
+        ]
+        .into_iter()
+        .filter(|(chance, _)| *chance > 0.0)
+        .map(|(chance, modified)| format!("{}% {modified}", chance * 100.0))
+        .join(" and ")
+            + " cards"
+    }
+
+    /// `cards`, each given a random enhancement, edition and seal by chance,
+    /// unless it has one already.
+    fn apply(self, cards: &[Card], rng: &mut SmallRng) -> Vec<Card> {
+        static ENHANCEMENTS: LazyLock<Vec<Enhancement>> =
+            LazyLock::new(|| Enhancement::iter().collect());
+        static EDITIONS: LazyLock<Vec<Edition>> = LazyLock::new(|| Edition::iter().collect());
+        static SEALS: LazyLock<Vec<Seal>> = LazyLock::new(|| Seal::iter().collect());
+        cards
+            .iter()
+            .map(|card| {
+                let mut card = *card;
+                if card.enhancement.is_none() && rng.gen_bool(self.enhancements) {
+                    card.enhancement = ENHANCEMENTS.choose(rng).copied();
+                }
+                if card.edition.is_none() && rng.gen_bool(self.editions) {
+                    card.edition = EDITIONS.choose(rng).copied();
+                }
+                if card.seal.is_none() && rng.gen_bool(self.seals) {
+                    card.seal = SEALS.choose(rng).copied();
+                }
+                card
+            })
+            .collect()
+    }
+}
+
+/// A starting deck by name, like `checkered`, or else the path of a file
+/// listing the cards of a deck, or the cards themselves, like
+/// "AS AS+glass KD+foil+red-seal 7C ...".
+fn parse_deck(text: &str) -> Result<DrawDeck, String> {
+    if let Ok(deck) = text.parse::<StartingDeck>() {
+        // Only the Erratic deck's cards are random, and those are dealt
+        // afresh for every draw
+        let cards = deck.cards(&mut SmallRng::seed_from_u64(0)).view().to_vec();
+        let name = match deck {
+            StartingDeck::Abandoned | StartingDeck::Checkered | StartingDeck::Erratic => {
+                format!("{}-card {deck} deck", cards.len())
+            }
+            _ => STANDARD_DECK.name.clone(),
+        };
+        return Ok(DrawDeck {
+            name,
+            cards,
+            erratic: deck == StartingDeck::Erratic,
+            modifiers: RandomModifiers::default(),
+        });
+    }
+
+    let (cards, name) = match fs::read_to_string(text) {
+        Ok(contents) => {
+            let cards = parse_cards(&contents).map_err(|err| format!("{text}: {err}"))?;
+            let name = format!("{}-card deck from {text}", cards.len());
+            (cards, name)
+        }
+        Err(err) => {
+            let cards = parse_cards(text)
+                .ok()
+                .filter(|cards| !cards.is_empty())
+                .ok_or_else(|| {
+                    format!("{text} is neither a starting deck, a file of cards nor a list of them: {err}")
+                })?;
+            let name = format!("{}-card deck", cards.len());
+            (cards, name)
+        }
+    };
+    Ok(DrawDeck {
+        name,
+        cards,
+        erratic: false,
+        modifiers: RandomModifiers::default(),
+    })
+}
+
+/// A proportion, given as a fraction like `0.001` or a percentage like `0.1%`.
+fn parse_proportion(text: &str) -> Result<f64, String> {
+    let (number, scale) = match text.strip_suffix('%') {
+        Some(number) => (number, 100.0),
+        None => (text, 1.0),
+    };
+    let value = number
+        .trim()
+        .parse::<f64>()
+        .map_err(|err| err.to_string())?;
+    if value.is_finite() && value > 0.0 {
+        Ok(value / scale)
+    } else {
+        Err("must be more than zero".to_owned())
+    }
+}
+
+/// A chance, given like a proportion, of at most 100%.
+fn parse_chance(text: &str) -> Result<f64, String> {
+    let value = parse_proportion(text)?;
+    if value <= 1.0 {
+        Ok(value)
+    } else {
+        Err("must be at most 100%".to_owned())
+    }
+}
+
+/// Split the `iterations` between workers, and return what each produced, in
+/// order. Each worker draws from its own generator, seeded with the seed plus
+/// its index if there's a seed, so a seeded run comes out the same however
+/// the workers are scheduled, even single-threaded.
+fn run_workers<T, W>(sampling: &Sampling, iterations: Range<usize>, work: W) -> Vec<T>
+where
+    T: Send,
+    W: Fn(&mut SmallRng, usize) -> T + Sync,
+{
+    // A shard's iterations start part way through the job's
+    let end = sampling.start + iterations.end;
+    let run = |worker: usize| {
+        let mut rng = sampling.seed.map_or_else(SmallRng::from_entropy, |seed| {
+            SmallRng::seed_from_u64(seed.wrapping_add(worker as u64))
+        });
+        let start = worker * WORKER_ITERATIONS;
+        work(&mut rng, WORKER_ITERATIONS.min(end - start))
+    };
+    let workers =
+        (sampling.start + iterations.start) / WORKER_ITERATIONS..end.div_ceil(WORKER_ITERATIONS);
+    if sampling.single_threaded {
+        workers.map(run).collect()
+    } else {
+        workers.into_par_iter().map(run).collect()
+    }
+}
+
+/// Whether every frequency's standard error, and the average score's
+/// relative to it, are within `precision`.
+fn precise_enough(tally: &Tally, precision: f64) -> bool {
+    let stats = summarize(tally, false);
+    let (mean, margin) = expected_score(&stats);
+    stats
+        .values()
+        .all(|stats| stats.frequency_error <= precision)
+        && margin / Z_95 <= precision * mean
+}
+
+/// How many buckets scores are counted in for their distribution
+const SCORE_BUCKETS: usize = 32;
+
+/// The bucket `score` is counted in: the first for scores below 1, and each
+/// after for scores up to double the last's, from 1 up to 2, 2 up to 4, and
+/// so on, with the last taking any beyond.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn score_bucket(score: f64) -> usize {
+    if score < 1.0 {
+        0
+    } else {
+        (score.log2().floor() as usize + 1).min(SCORE_BUCKETS - 1)
+    }
+}
+
+/// The scores counted in `bucket`, like `32–63`.
+fn describe_bucket(bucket: usize) -> String {
+    match bucket {
+        0 => "0".to_owned(),
+        _ if bucket == SCORE_BUCKETS - 1 => format!("{}+", 1_u64 << (bucket - 1)),
+        _ => format!("{}–{}", 1_u64 << (bucket - 1), (1_u64 << bucket) - 1),
+    }
+}
+
+/// The hands of one kind sampled, each weighted by how much likelier it is
+/// to be drawn fairly than by the sampler, which is 1 for fair draws.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+struct KindTally {
+    count: usize,
+    /// The total of the weights, and of their squares
+    weight: f64,
+    squared_weight: f64,
+    /// The total of the weighted scores, and of the weighted squared scores
+    score: f64,
+    squared_score: f64,
+    /// The total of the weights of the scores in each of `score_bucket`'s
+    /// buckets
+    histogram: [f64; SCORE_BUCKETS],
+}
+
+impl KindTally {
+    /// Count `count` hands of `weight` each that all scored `score`.
+    #[allow(clippy::cast_precision_loss)]
+    fn add(&mut self, count: usize, weight: f64, score: f64) {
+        let count_f64 = count as f64;
+        self.count += count;
+        self.weight += count_f64 * weight;
+        self.squared_weight += count_f64 * weight * weight;
+        self.score += count_f64 * weight * score;
+        self.squared_score += count_f64 * weight * score * score;
+        self.histogram[score_bucket(score)] += count_f64 * weight;
+    }
+}
+
+impl AddAssign for KindTally {
+    fn add_assign(&mut self, other: Self) {
+        self.count += other.count;
+        self.weight += other.weight;
+        self.squared_weight += other.squared_weight;
+        self.score += other.score;
+        self.squared_score += other.squared_score;
+        for (bucket, other_bucket) in self.histogram.iter_mut().zip(other.histogram) {
+            *bucket += other_bucket;
+        }
+    }
+}
+
+type Tally = HashMap<HandKind, KindTally>;
+
+/// How far sampling got, as saved in a checkpoint.
+#[derive(Serialize, Deserialize)]
+struct SamplingProgress {
+    /// How many iterations were performed
+    done: usize,
+    /// Each kind's tally, from High Card up
+    kinds: Vec<KindTally>,
+}
+
+impl SamplingProgress {
+    fn new(tally: &Tally, done: usize) -> Self {
+        Self {
+            done,
+            kinds: HandKind::iter()
+                .map(|kind| tally.get(&kind).copied().unwrap_or_default())
+                .collect(),
+        }
+    }
+
+    /// Count the hands in `other` too.
+    fn merge(&mut self, other: &Self) {
+        self.done += other.done;
+        for (kind, other_kind) in self.kinds.iter_mut().zip(&other.kinds) {
+            *kind += *other_kind;
+        }
+    }
+
+    fn tally(&self) -> Tally {
+        HandKind::iter()
+            .zip(&self.kinds)
+            .filter(|(_, kind)| kind.count > 0)
+            .map(|(hand, kind)| (hand, *kind))
+            .collect()
+    }
+}
+
+/// Score a hand under `rules`, count it and its score towards its kind with
+/// `weight`, and return the score.
+fn tally_hand(tally: &mut Tally, played: &PlayedHand, weight: f64, rules: Rules) -> f64 {
+    let score = rules.score(played);
+    tally.entry(played.kind).or_default().add(1, weight, score);
+    score
+}
+
+/// A hand sampled, as written with `--record`.
+#[derive(Serialize)]
+struct HandLine<'a> {
+    /// What was being sampled, like `8 cards with [Shortcut]`
+    stage: &'a str,
+    /// The cards that make the hand
+    scoring: Vec<String>,
+    /// The cards played alongside them that don't, unless with Splash
+    unscored: Vec<String>,
+    held: Vec<String>,
+    kind: String,
+    score: f64,
+    /// How much likelier the hand is to be drawn fairly than it was to be
+    /// sampled, 1 unless importance sampling
+    weight: f64,
+}
+
+/// Add the tallies one after another, so seeded runs match to the last bit.
+fn merge_tallies(tallies: impl IntoIterator<Item = Tally>) -> Tally {
+    let mut merged = Tally::new();
+    for tally in tallies {
+        for (hand, kind_tally) in tally {
+            *merged.entry(hand).or_default() += kind_tally;
+        }
+    }
+    merged
+}
+
+/// Each kind's frequency and average score, with their errors. The hands
+/// count for as many fair draws as their weights are worth: all of them
+/// when they're weighted evenly, and fewer the more unevenly they are.
+fn summarize(tally: &Tally, exact: bool) -> HashMap<HandKind, HandStats> {
+    let weight = tally.values().map(|kind| kind.weight).sum::<f64>();
+    let squared_weight = tally.values().map(|kind| kind.squared_weight).sum::<f64>();
+
+    tally
+        .iter()
+        .map(|(hand, kind)| {
+            let frequency = kind.weight / weight;
+            // Every hand sampled sways the frequency: towards it for hands of
+            // this kind, and away for the rest
+            let frequency_error = (kind.squared_weight * (1.0 - frequency).powi(2)
+                + (squared_weight - kind.squared_weight) * frequency.powi(2))
+            .sqrt()
+                / weight;
+            let average_score = kind.score / kind.weight;
+            let count = kind.weight.powi(2) / kind.squared_weight;
+            let score_variance = if count > 1.0 {
+                ((kind.squared_score / kind.weight - average_score.powi(2)) * count / (count - 1.0))
+                    .max(0.0)
+            } else {
+                0.0
+            };
+            (
+                *hand,
+                HandStats {
+                    frequency,
+                    frequency_error,
+                    average_score,
+                    score_variance,
+                    count,
+                    samples: weight.powi(2) / squared_weight,
+                    exact,
+                    score_shares: kind.histogram.map(|bucket| bucket / kind.weight),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Each kind of hand's frequency and average score, and how many hands were
+/// sampled to find them. Checkpoints save the tallies as `stage`.
+fn generate_hand_stats<G>(
+    sampling: &Sampling,
+    stage: &str,
+    rules: Rules,
+    generate_hand: G,
+) -> Result<(HashMap<HandKind, HandStats>, usize)>
+where
+    G: Fn(&mut SmallRng) -> (PlayedHand, f64) + std::marker::Sync,
+{
+    let work = |rng: &mut SmallRng, count| {
+        let mut tally = Tally::new();
+        let mut lines = String::new();
+        for _ in 0..count {
+            let (played, weight) = generate_hand(rng);
+            let score = tally_hand(&mut tally, &played, weight, rules);
+            if sampling.record.is_some() {
+                let names = |cards: &[Card]| cards.iter().map(ToString::to_string).collect();
+                let line = HandLine {
+                    stage,
+                    scoring: names(played.hand.view()),
+                    unscored: names(&played.unscored),
+                    held: names(&played.held),
+                    kind: format!("{:?}", played.kind),
+                    score,
+                    weight,
+                };
+                lines += &serde_json::to_string(&line).unwrap();
+                lines.push('\n');
+            }
+        }
+        (tally, lines)
+    };
+
+    let (mut tally, mut done) = match sampling.checkpoint.resume::<SamplingProgress>(stage)? {
+        Some(progress) => (progress.tally(), progress.done),
+        None => (Tally::new(), 0),
+    };
+    let precise = |tally: &Tally, done| {
+        done > 0
+            && sampling
+                .precision
+                .is_some_and(|precision| precise_enough(tally, precision))
+    };
+    while done < sampling.iterations && !precise(&tally, done) {
+        let batch = if sampling.precision.is_some()
+            || sampling.checkpoint.is_enabled()
+            || sampling.record.is_some()
+        {
+            BATCH_ITERATIONS.min(sampling.iterations - done)
+        } else {
+            sampling.iterations - done
+        };
+        let (tallies, lines): (Vec<_>, Vec<_>) = run_workers(sampling, done..done + batch, work)
+            .into_iter()
+            .unzip();
+        if let Some(record) = sampling.record {
+            record.write(&lines.concat())?;
+        }
+        tally = merge_tallies(std::iter::once(tally).chain(tallies));
+        done += batch;
+        // Progress is saved with every batch recorded, so a resumed job
+        // doesn't record the same hands twice
+        sampling.checkpoint.update(
+            stage,
+            &SamplingProgress::new(&tally, done),
+            sampling.record.is_some(),
+        )?;
+    }
+    sampling
+        .checkpoint
+        .update(stage, &SamplingProgress::new(&tally, done), true)?;
+
+    Ok((summarize(&tally, false), done))
+}
+
+/// Each kind of hand's exact frequency and average score over every distinct
+/// 5 cards that can be drawn from `cards`, which are all equally likely.
+fn every_fresh_draw_stats(
+    single_threaded: bool,
+    cards: &[Card],
+    rules: Rules,
+) -> (HashMap<HandKind, HandStats>, usize) {
+    // Split up by the first card of each draw, in deck order
+    let draws_from = |first: usize| {
+        let mut tally = Tally::new();
+        for rest in (first + 1..cards.len()).combinations(4) {
+            let drawn = std::iter::once(first)
+                .chain(rest)
+                .map(|index| cards[index])
+                .collect_vec();
+            let played = PlayedHand::new(&drawn, Vec::new(), rules.options);
+            tally_hand(&mut tally, &played, 1.0, rules);
+        }
+        tally
+    };
+    let tallies: Vec<Tally> = if single_threaded {
+        (0..cards.len()).map(draws_from).collect()
+    } else {
+        (0..cards.len()).into_par_iter().map(draws_from).collect()
+    };
+
+    let tally = merge_tallies(tallies);
+    let draws = tally.values().map(|kind| kind.count).sum();
+    (summarize(&tally, true), draws)
+}
+
+/// Like `every_fresh_draw_stats`, but scoring just one draw for each class
+/// of draws that make the same hand from cards of the same ranks, which
+/// score the same. See `draw_classes`.
+///
+/// Fails with jokers that add scoring effects, which may depend on the suits
+/// the classes leave out.
+fn analytic_fresh_draw_stats(
+    cards: &[Card],
+    rules: Rules,
+) -> Result<(HashMap<HandKind, HandStats>, usize)> {
+    if !rules.jokers.is_empty() {
+        return Err(anyhow!(
+            "--analytic only tells draws apart by suit as far as flushes do, so it can't score {}; use --exhaustive instead",
+            rules.jokers.iter().map(JokerSlot::name).join(", ")
+        ));
+    }
+    if let Some(boss) = rules.boss {
+        return Err(anyhow!(
+            "--analytic only tells draws apart by suit as far as flushes do, so it can't score against {boss}; use --exhaustive instead"
+        ));
+    }
+    let mut tally = Tally::new();
+    for class in draw_classes(CardCollection::from(cards), rules.options) {
+        let played = PlayedHand::new(&class.cards, Vec::new(), rules.options);
+        tally.entry(played.kind).or_default().add(
+            usize::try_from(class.ways).unwrap(),
+            1.0,
+            rules.score(&played),
+        );
+    }
+
+    let draws = tally.values().map(|kind| kind.count).sum();
+    Ok((summarize(&tally, true), draws))
+}
+
+fn print_card_stats(out: &mut dyn Write, stats: &HashMap<HandKind, HandStats>) -> Result<()> {
+    let hand_name_columns = HandKind::iter()
+        .map(|h| format!("{h:?}").len())
+        .max()
+        .unwrap();
+    for (hand, stats) in stats.iter().sorted_by_key(|(hand, _)| **hand) {
+        writeln!(
+            out,
+            " - {:hand_width$} {:>6.3}% ±{:.3}% (avg: {:>6.1} ±{:.1}, ev: {:>6.1})",
+            format!("{:?}", hand),
+            stats.frequency * 100.0,
+            stats.frequency_margin() * 100.0,
+            stats.average_score,
+            stats.score_margin(),
+            stats.average_score * stats.frequency,
+            hand_width = hand_name_columns
+        )?;
+    }
+    Ok(())
+}
+
+fn fresh_draw_stats(
+    sampling: &Sampling,
+    rules: Rules,
+) -> Result<(HashMap<HandKind, HandStats>, usize)> {
+    let generate_hand = |rng: &mut SmallRng| {
+        let (cards, weight) = draw_cards(sampling, rng, 5);
+        (PlayedHand::new(&cards, Vec::new(), rules.options), weight)
+    };
+
+    let stage = format!("5 cards with [{}]", rules.describe_jokers());
+    generate_hand_stats(sampling, &stage, rules, generate_hand)
+}
+
+fn hand_draw_stats(
+    sampling: &Sampling,
+    rules: Rules,
+) -> Result<(HashMap<HandKind, HandStats>, usize)> {
+    let generate_hand = |rng: &mut SmallRng| {
+        if sampling.discards == 0 {
+            let (cards, weight) = draw_cards(sampling, rng, sampling.hand_size);
+            return (rules.play(&cards), weight);
+        }
+        let (cards, weight) = draw_cards(sampling, rng, sampling.deck.cards.len());
+        (play_after_discards(sampling, rules, cards), weight)
+    };
+
+    let stage = format!(
+        "{} cards with [{}]",
+        sampling.hand_size,
+        rules.describe_jokers()
+    );
+    generate_hand_stats(sampling, &stage, rules, generate_hand)
+}
+
+/// The hand statistics of 5 cards, and of the best hand from a full hand,
+/// under `rules`, and how many draws each were found from.
+fn draw_stats(
+    sampling: &Sampling,
+    exhaustive: bool,
+    analytic: bool,
+    rules: Rules,
+) -> Result<[(HashMap<HandKind, HandStats>, usize); 2]> {
+    if (exhaustive || analytic) && !sampling.deck.is_fixed() {
+        return Err(anyhow!(
+            "--exhaustive and --analytic need the same cards for every draw, unlike the Erratic deck's or randomly modified ones"
+        ));
+    }
+    if analytic
+        && sampling
+            .deck
+            .cards
+            .iter()
+            .any(|card| card.enhancement.is_some() || card.edition.is_some() || card.seal.is_some())
+    {
+        return Err(anyhow!(
+            "--analytic scores a plain card of each rank and suit, so it can't score the deck's enhancements, editions and seals; use --exhaustive instead"
+        ));
+    }
+    let fresh = if exhaustive {
+        every_fresh_draw_stats(sampling.single_threaded, &sampling.deck.cards, rules)
+    } else if analytic {
+        analytic_fresh_draw_stats(&sampling.deck.cards, rules)?
+    } else {
+        fresh_draw_stats(sampling, rules)?
+    };
+    Ok([fresh, hand_draw_stats(sampling, rules)?])
+}
+
+/// Every combination of the options Shortcut and Four Fingers turn on.
+fn option_combinations() -> [Options; 4] {
+    [
+        Options::empty(),
+        Options::GappedStraights,
+        Options::FourCardStraightsAndFlushes,
+        Options::GappedStraights | Options::FourCardStraightsAndFlushes,
+    ]
+}
+
+/// The options turned on by the Shortcut and Four Fingers jokers.
+fn evaluator_options(shortcut: bool, four_fingers: bool) -> Options {
+    let mut options = Options::empty();
+    if shortcut {
+        options |= Options::GappedStraights;
+    }
+    if four_fingers {
+        options |= Options::FourCardStraightsAndFlushes;
+    }
+    options
+}
+
+/// The jokers the scorer models as options rather than as scoring effects,
+/// and what they turn on.
+const OPTION_JOKERS: [(&str, Options, ScoringOptions); 4] = [
+    (
+        "Shortcut",
+        Options::GappedStraights,
+        ScoringOptions::empty(),
+    ),
+    (
+        "Four Fingers",
+        Options::FourCardStraightsAndFlushes,
+        ScoringOptions::empty(),
+    ),
+    (
+        "Splash",
+        Options::empty(),
+        ScoringOptions::AllPlayedCardsScore,
+    ),
+    (
+        "Pareidolia",
+        Options::empty(),
+        ScoringOptions::AllCardsAreFace,
+    ),
+];
+
+/// What changes which hands are made from the cards drawn, and how they
+/// score.
+#[derive(Copy, Clone, Debug)]
+struct Rules<'a> {
+    options: Options,
+    scoring_options: ScoringOptions,
+    /// The jokers held that add scoring effects of their own
+    jokers: &'a [JokerSlot],
+    /// The Boss Blind the hands are played against
+    boss: Option<BossBlind>,
+}
+
+/// The jokers named in `names`: the options the option jokers among them
+/// turn on, matching their names loosely, and the rest.
+fn parse_held_jokers(names: &[String]) -> Result<(Options, ScoringOptions, Vec<JokerSlot>)> {
+    let mut options = Options::empty();
+    let mut scoring_options = ScoringOptions::empty();
+    let mut others = Vec::new();
+    for name in names {
+        match OPTION_JOKERS
+            .iter()
+            .find(|(joker, ..)| squash(joker) == squash(name))
+        {
+            Some((_, joker_options, joker_scoring_options)) => {
+                options |= *joker_options;
+                scoring_options |= *joker_scoring_options;
+            }
+            None => others.push(name.clone()),
+        }
+    }
+    let jokers = parse_jokers(&others)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect();
+    Ok((options, scoring_options, jokers))
+}
+
+impl Rules<'_> {
+    /// Every joker held, the option jokers first, separated by commas.
+    fn describe_jokers(&self) -> String {
+        OPTION_JOKERS
+            .iter()
+            .filter(|(_, options, scoring_options)| {
+                (!options.is_empty() && self.options.contains(*options))
+                    || (!scoring_options.is_empty()
+                        && self.scoring_options.contains(*scoring_options))
+            })
+            .map(|(joker, ..)| *joker)
+            .chain(self.jokers.iter().map(JokerSlot::name))
+            .join(",")
+    }
+
+    /// The jokers held and the Boss Blind as a phrase for prose, like
+    /// ` holding Splash, Baron against The Club`, or nothing without either.
+    fn holding(&self) -> String {
+        let jokers = self.describe_jokers();
+        let holding = if jokers.is_empty() {
+            jokers
+        } else {
+            format!(" holding {}", jokers.replace(',', ", "))
+        };
+        match self.boss {
+            Some(boss) => format!("{holding} against {boss}"),
+            None => holding,
+        }
+    }
+
+    /// The hand played from `cards`, the best one unless the Boss Blind has
+    /// them drawn face down, when it's the first 5 of them.
+    fn play(&self, cards: &[Card]) -> PlayedHand {
+        if self.boss.is_some_and(BossBlind::first_hand_face_down) {
+            PlayedHand::new(&cards[..5], cards[5..].to_vec(), self.options)
+        } else {
+            best_hand(cards, self.options)
+        }
+    }
+
+    /// What `played` scores.
+    fn score(&self, played: &PlayedHand) -> f64 {
+        let context = played
+            .context()
+            .with_options(self.scoring_options)
+            .with_jokers(self.jokers)
+            .with_boss(self.boss);
+        Scorer::score_hand(&context).value()
+    }
+}
+
+/// Each score's share of every hand, in `score_bucket`'s buckets.
+fn score_distribution(stats: &HashMap<HandKind, HandStats>) -> [f64; SCORE_BUCKETS] {
+    let mut distribution = [0.0; SCORE_BUCKETS];
+    for stats in stats.values() {
+        for (share, kind_share) in distribution.iter_mut().zip(stats.score_shares) {
+            *share += stats.frequency * kind_share;
+        }
+    }
+    distribution
+}
+
+/// Add a section on `stats` to `report`: a table of each kind of hand, and
+/// charts of their frequencies and of the scores of every hand.
+fn report_hand_stats(
+    report: &mut Report,
+    heading: &str,
+    description: &str,
+    stats: &HashMap<HandKind, HandStats>,
+) {
+    report.heading(heading);
+    report.paragraph(description);
+    let sorted = stats.iter().sorted_by_key(|(kind, _)| **kind).collect_vec();
+    let rows = sorted
+        .iter()
+        .map(|(kind, stats)| {
+            vec![
+                format!("{kind:?}"),
+                format!("{:.3}%", stats.frequency * 100.0),
+                format!("±{:.3}%", stats.frequency_margin() * 100.0),
+                format!("{:.1}", stats.average_score),
+                format!("±{:.1}", stats.score_margin()),
+                format!("{:.1}", stats.average_score * stats.frequency),
+            ]
+        })
+        .collect();
+    report.table(
+        &["Hand", "Frequency", "±", "Average score", "±", "EV"],
+        rows,
+    );
+    let (mean, margin) = expected_score(stats);
+    report.paragraph(&format!(
+        "Over every hand, the average score is {mean:.1} ±{margin:.1}."
+    ));
+
+    let frequencies = sorted
+        .iter()
+        .map(|(kind, stats)| Bar {
+            label: format!("{kind:?}"),
+            value: stats.frequency,
+            text: format!("{:.3}%", stats.frequency * 100.0),
+        })
+        .collect();
+    report.chart("Frequency of each hand", frequencies);
+
+    // From the lowest bucket any score fell in to the highest
+    let distribution = score_distribution(stats);
+    let buckets = distribution
+        .iter()
+        .positions(|share| *share > 0.0)
+        .minmax()
+        .into_option()
+        .map_or(0..0, |(first, last)| first..last + 1);
+    let scores = buckets
+        .map(|bucket| Bar {
+            label: describe_bucket(bucket),
+            value: distribution[bucket],
+            text: format!("{:.3}%", distribution[bucket] * 100.0),
+        })
+        .collect();
+    report.chart("Share of hands scoring", scores);
+}
+
+fn hand_stats(
+    out: &mut dyn Write,
+    sampling: &Sampling,
+    exhaustive: bool,
+    analytic: bool,
+    rules: Rules,
+    format: Format,
+    report: Option<&Path>,
+) -> Result<()> {
+    let [(fresh, fresh_samples), (dealt, dealt_samples)] =
+        draw_stats(sampling, exhaustive, analytic, rules)?;
+
+    let sampled = |samples: usize| match (sampling.precision, sampling.importance) {
+        (Some(_), false) => format!(" ({samples} samples)"),
+        (Some(_), true) => format!(" ({samples} importance-weighted samples)"),
+        (None, false) => String::new(),
+        (None, true) => " (importance-weighted)".to_owned(),
+    };
+    let fresh_note = if exhaustive || analytic {
+        format!(" (all {fresh_samples} draws, exactly)")
+    } else {
+        sampled(fresh_samples)
+    };
+    let holding = rules.holding();
+    let fresh_description = format!(
+        "When drawing 5 cards from a shuffled {}{holding}, the frequencies of each hand are{fresh_note}:",
+        sampling.deck.name
+    );
+    let dealt_description = format!(
+        "When drawing {} cards from a shuffled {}{holding}{}, the frequencies of each best hand are{}:",
+        sampling.hand_size,
+        sampling.deck.name,
+        sampling.discarding(),
+        sampled(dealt_samples)
+    );
+
+    if let Some(path) = report {
+        let mut report = Report::new("Hand statistics");
+        report_hand_stats(&mut report, "5 cards", &fresh_description, &fresh);
+        report_hand_stats(
+            &mut report,
+            &format!("The best hand from {} cards", sampling.hand_size),
+            &dealt_description,
+            &dealt,
+        );
+        report.write(path)?;
+    }
+
+    if format == Format::Text {
+        writeln!(out, "{fresh_description}")?;
+        print_card_stats(out, &fresh)?;
+        writeln!(out, "{dealt_description}")?;
+        print_card_stats(out, &dealt)?;
+    } else {
+        let jokers = rules.describe_jokers();
+        let records = [
+            hand_records(5, &jokers, &fresh),
+            hand_records(sampling.hand_size, &jokers, &dealt),
+        ]
+        .concat();
+        print_records(out, &records, format)?;
+    }
+
+    Ok(())
+}
+
+/// Add a section to `report` comparing the hand statistics under each of
+/// the combinations of options named in `columns`: a table of `rows`, each
+/// a name and a cell per combination, and a chart of the `averages`.
+fn report_comparison(
+    report: &mut Report,
+    heading: &str,
+    description: &str,
+    columns: &[&str],
+    rows: &[(String, Vec<String>)],
+    averages: &[f64],
+) {
+    report.heading(heading);
+    report.paragraph(description);
+    let header = std::iter::once("Hand")
+        .chain(columns.iter().copied())
+        .collect_vec();
+    let table = rows
+        .iter()
+        .map(|(name, cells)| std::iter::once(name.clone()).chain(cells.clone()).collect())
+        .collect();
+    report.table(&header, table);
+    let bars = columns
+        .iter()
+        .zip(averages)
+        .map(|(column, average)| Bar {
+            label: (*column).to_owned(),
+            value: *average,
+            text: format!("{average:.1}"),
+        })
+        .collect();
+    report.chart("Average score", bars);
+}
+
+/// Like `hand_stats`, but under every combination of options, comparing each
+/// hand's frequency with its frequency under none.
+fn compare_options(
+    out: &mut dyn Write,
+    sampling: &Sampling,
+    exhaustive: bool,
+    analytic: bool,
+    rules: Rules,
+    format: Format,
+    report: Option<&Path>,
+) -> Result<()> {
+    let combinations = option_combinations().map(|options| Rules { options, ..rules });
+    let results = combinations
+        .iter()
+        .map(|rules| draw_stats(sampling, exhaustive, analytic, *rules))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut report = report.map(|path| {
+        (
+            path,
+            Report::new("Hand statistics with Shortcut and Four Fingers"),
+        )
+    });
+    if format != Format::Text {
+        let records = combinations
+            .iter()
+            .zip(&results)
+            .flat_map(|(rules, [(fresh, _), (dealt, _)])| {
+                let jokers = rules.describe_jokers();
+                [
+                    hand_records(5, &jokers, fresh),
+                    hand_records(sampling.hand_size, &jokers, dealt),
+                ]
+            })
+            .concat();
+        print_records(out, &records, format)?;
+    }
+
+    let hand_width = HandKind::iter()
+        .map(|kind| format!("{kind:?}").len())
+        .max()
+        .unwrap();
+    let columns = ["Neither", "Shortcut", "Four Fingers", "Both"];
+    // The jokers held alongside each combination
+    let holding = combinations[0].holding();
+    let draws = [(5, ""), (sampling.hand_size, "best ")];
+    for (index, (drawn, best)) in draws.into_iter().enumerate() {
+        let description = format!(
+            "When drawing {drawn} cards from a shuffled {}{holding}{}, the frequencies of each {best}hand with Shortcut and Four Fingers, and their changes from neither:",
+            sampling.deck.name,
+            if index == 0 { String::new() } else { sampling.discarding() }
+        );
+        // The value under each combination, with its change from the first
+        let cells = |values: &[f64], precision: usize, unit: &str| {
+            std::iter::once(format!("{:.precision$}{unit}", values[0]))
+                .chain(values[1..].iter().map(|value| {
+                    format!(
+                        "{value:.precision$}{unit} ({:+.precision$})",
+                        value - values[0]
+                    )
+                }))
+                .collect_vec()
+        };
+        let mut rows = Vec::new();
+        for kind in HandKind::iter() {
+            let frequencies = results
+                .iter()
+                .map(|stats| {
+                    stats[index]
+                        .0
+                        .get(&kind)
+                        .map_or(0.0, |stats| stats.frequency * 100.0)
+                })
+                .collect_vec();
+            if frequencies.iter().any(|frequency| *frequency > 0.0) {
+                rows.push((format!("{kind:?}"), cells(&frequencies, 3, "%")));
+            }
+        }
+        let averages = results
+            .iter()
+            .map(|stats| expected_score(&stats[index].0).0)
+            .collect_vec();
+        rows.push(("Average score".to_owned(), cells(&averages, 1, "")));
+
+        if let Some((_, report)) = &mut report {
+            let heading = if best.is_empty() {
+                format!("{drawn} cards")
+            } else {
+                format!("The best hand from {drawn} cards")
+            };
+            report_comparison(report, &heading, &description, &columns, &rows, &averages);
+        }
+
+        if format == Format::Text {
+            writeln!(out, "{description}")?;
+            writeln!(
+                out,
+                "   {:hand_width$} {:>8} {:>19} {:>19} {:>19}",
+                "", columns[0], columns[1], columns[2], columns[3]
+            )?;
+            for (name, cells) in &rows {
+                let changes = cells[1..]
+                    .iter()
+                    .map(|cell| format!("{cell:>19}"))
+                    .join(" ");
+                writeln!(out, " - {name:hand_width$} {:>8} {changes}", cells[0])?;
+            }
+        }
+    }
+
+    if let Some((path, report)) = report {
+        report.write(path)?;
+    }
+    Ok(())
+}
+
+/// Draw `n` cards from the deck, fairly or with an `ImportanceSampler`,
+/// along with the draw's weight.
+fn draw_cards(sampling: &Sampling, rng: &mut SmallRng, n: usize) -> (Vec<Card>, f64) {
+    let erratic;
+    let mut cards = if sampling.deck.erratic {
+        erratic = StartingDeck::Erratic.cards(rng);
+        erratic.view()
+    } else {
+        &sampling.deck.cards
+    };
+    let modified;
+    if !sampling.deck.modifiers.is_none() {
+        modified = sampling.deck.modifiers.apply(cards, rng);
+        cards = &modified;
+    }
+    if sampling.importance {
+        ImportanceSampler::new().draw(cards, n, rng)
+    } else {
+        let mut deck = Deck::from_cards(cards.to_vec());
+        deck.shuffle(rng);
+        (deck.draw_n(n).unwrap().view().to_vec(), 1.0)
+    }
+}
+
+/// A poker hand played from the cards drawn.
+struct PlayedHand {
+    kind: HandKind,
+    /// The cards that make the hand
+    hand: Hand,
+    /// Every card played, in the order played
+    played: Vec<Card>,
+    /// The cards played alongside them that don't, unless with Splash
+    unscored: Vec<Card>,
+    /// The cards drawn but not played
+    held: Vec<Card>,
+}
+
+impl PlayedHand {
+    /// Play `played` as the best poker hand they make, holding `held`.
+    fn new(played: &[Card], held: Vec<Card>, options: Options) -> Self {
+        let (kind, hand) =
+            HandEvaluator::evaluate_poker_hand(Hand::from_slice(played).unwrap(), options).unwrap();
+        let mut unscored = played.to_vec();
+        for card in hand.view() {
+            if let Some(index) = unscored.iter().position(|other| other == card) {
+                unscored.remove(index);
+            }
+        }
+        Self {
+            kind,
+            hand,
+            played: played.to_vec(),
+            unscored,
+            held,
+        }
+    }
+
+    /// A context for scoring the hand, to add options, jokers and levels to.
+    fn context(&self) -> ScoringContext<'_> {
+        ScoringContext::new(self.kind, &self.hand)
+            .with_all_played(&self.played)
+            .with_held(&self.held)
+    }
+}
+
+/// Hold the first of `cards`, shuffled, and discard from them and draw
+/// replacements from the rest in order as an `Advisor` says, as many times
+/// as `sampling` allows, then play the hand left.
+fn play_after_discards(sampling: &Sampling, rules: Rules, mut cards: Vec<Card>) -> PlayedHand {
+    let advisor = Advisor::new()
+        .with_evaluator_options(rules.options)
+        .with_scoring_options(rules.scoring_options)
+        .with_table(Arc::new(TranspositionTable::new()));
+    let mut pile = cards.split_off(sampling.hand_size);
+    pile.reverse();
+    let mut hand = cards;
+    let mut tracker = DeckTracker::new(pile.clone());
+    let mut state = GameState::from_parts(
+        Deck::from_cards(Vec::new()),
+        Vec::new(),
+        4,
+        sampling.discards,
+        0,
+        1,
+        Blind::Small,
+        rules.jokers.to_vec(),
+        Vec::new(),
+    );
+    while state.discards > 0 {
+        let Action::Discard(discard) = advisor.advise(&hand, &tracker, &state) else {
+            break;
+        };
+        if discard.len() > pile.len() {
+            break;
+        }
+        // The indices are in order, so removing from the back keeps the
+        // rest in place
+        for index in discard.iter().rev() {
+            hand.remove(*index);
+        }
+        for card in pile.drain(pile.len() - discard.len()..).rev() {
+            tracker.see(card);
+            hand.push(card);
+        }
+        state.discards -= 1;
+    }
+    rules.play(&hand)
+}
+
+/// Draw 8 cards, or fewer against a Boss Blind that shrinks the hand, and
+/// play the hand `rules` would from them, holding the rest.
+fn draw_played_hand(rng: &mut SmallRng, rules: Rules) -> PlayedHand {
+    let drawn = Deck::shuffled(rng)
+        .draw_n(boss_hand_size(8, rules.boss))
+        .unwrap();
+    rules.play(drawn.view())
+}
+
+/// The best poker hand among `cards`, holding the rest of them.
+fn best_hand(cards: &[Card], options: Options) -> PlayedHand {
+    let mut best: Option<(HandKind, Vec<usize>)> = None;
+    for indices in (0..cards.len()).combinations(5) {
+        let played: Vec<Card> = indices.iter().map(|index| cards[*index]).collect();
+        let (kind, _) =
+            HandEvaluator::evaluate_poker_hand(Hand::from_slice(&played).unwrap(), options)
+                .unwrap();
+        if best.as_ref().is_none_or(|(best_kind, _)| kind > *best_kind) {
+            best = Some((kind, indices));
+        }
+    }
+
+    let (_, indices) = best.unwrap();
+    let played = indices.iter().map(|index| cards[*index]).collect_vec();
+    let held = (0..cards.len())
+        .filter(|index| !indices.contains(index))
+        .map(|index| cards[index])
+        .collect();
+    PlayedHand::new(&played, held, options)
+}
+
+/// Every 5 cards that could be played from `cards`, holding the rest.
+fn every_play(cards: &[Card], options: Options) -> Vec<PlayedHand> {
+    (0..cards.len())
+        .combinations(5)
+        .map(|indices| {
+            let played = indices.iter().map(|index| cards[*index]).collect_vec();
+            let held = (0..cards.len())
+                .filter(|index| !indices.contains(index))
+                .map(|index| cards[index])
+                .collect();
+            PlayedHand::new(&played, held, options)
+        })
+        .collect()
+}
+
+/// Score the best hand from 8 cards with each lineup of jokers, against
+/// `boss` if there is one, over many draws, and return each lineup's average
+/// score. Every lineup is scored on exactly the same hands.
+#[allow(clippy::cast_precision_loss)]
+fn average_lineup_scores(
+    sampling: &Sampling,
+    lineups: &[Vec<JokerSlot>],
+    boss: Option<BossBlind>,
+) -> Vec<f64> {
+    let rules = Rules {
+        options: Options::empty(),
+        scoring_options: ScoringOptions::empty(),
+        jokers: &[],
+        boss,
+    };
+    let worker_totals = run_workers(sampling, 0..sampling.iterations, |rng, count| {
+        let mut totals = vec![0.0; lineups.len()];
+        for _ in 0..count {
+            let played = draw_played_hand(rng, rules);
+            for (total, jokers) in totals.iter_mut().zip(lineups) {
+                *total += Rules { jokers, ..rules }.score(&played);
+            }
+        }
+        totals
+    });
+    let mut totals = vec![0.0; lineups.len()];
+    for worker in worker_totals {
+        for (total, score) in totals.iter_mut().zip(worker) {
+            *total += score;
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|total| total / sampling.iterations as f64)
+        .collect()
+}
+
+pub(crate) fn parse_jokers(names: &[String]) -> Result<Vec<JokerId>> {
+    names
+        .iter()
+        .map(|name| {
+            name.trim()
+                .parse::<JokerId>()
+                .map_err(|_| anyhow!("unknown joker `{name}`"))
+        })
+        .collect()
+}
+
+fn joker_synergy(
+    out: &mut dyn Write,
+    sampling: &Sampling,
+    names: &[String],
+    boss: Option<BossBlind>,
+    format: Format,
+) -> Result<()> {
+    let ids = parse_jokers(names)?;
+    let slot = |id: JokerId| JokerSlot::new(id.joker());
+
+    // Score with no jokers, each joker alone, and each pair
+    let pairs: Vec<(usize, usize)> = (0..ids.len()).tuple_combinations().collect();
+    let lineups: Vec<Vec<JokerSlot>> = std::iter::once(Vec::new())
+        .chain(ids.iter().map(|id| vec![slot(*id)]))
+        .chain(
+            pairs
+                .iter()
+                .map(|(a, b)| vec![slot(ids[*a]), slot(ids[*b])]),
+        )
+        .collect();
+    let averages = average_lineup_scores(sampling, &lineups, boss);
+
+    let baseline = averages[0];
+    let single_uplift = |joker: usize| averages[1 + joker] - baseline;
+    let pair_uplift = |pair: usize| averages[1 + ids.len() + pair] - baseline;
+    // How much better (above 1) or worse (below 1) a pair does than its
+    // jokers do separately
+    let synergy = |pair: usize| {
+        let (a, b) = pairs[pair];
+        let independent = single_uplift(a) + single_uplift(b);
+        (independent > 0.0).then(|| pair_uplift(pair) / independent)
+    };
+
+    if format != Format::Text {
+        let members = std::iter::once(Vec::new())
+            .chain((0..ids.len()).map(|joker| vec![joker]))
+            .chain(pairs.iter().map(|(a, b)| vec![*a, *b]));
+        let name = |joker: &usize| ids[*joker].to_string();
+        let records = members
+            .zip(&averages)
+            .enumerate()
+            .map(|(lineup, (members, average))| SynergyRecord {
+                first: members.first().map(name),
+                second: members.get(1).map(name),
+                average_score: *average,
+                uplift: average - baseline,
+                synergy: lineup.checked_sub(1 + ids.len()).and_then(synergy),
+            })
+            .collect_vec();
+        return print_records(out, &records, format);
+    }
+
+    writeln!(
+        out,
+        "Average score of the best hand from {} cards{}, without jokers: {baseline:.1}",
+        boss_hand_size(8, boss),
+        boss.map_or_else(String::new, |boss| format!(" against {boss}")),
+    )?;
+    writeln!(out, "Average uplift of each joker on its own:")?;
+    for (joker, id) in ids.iter().enumerate() {
+        writeln!(out, " - {id}: +{:.1}", single_uplift(joker))?;
+    }
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "Uplift of each pair relative to the sum of their separate uplifts:"
+    )?;
+    let name_width = ids.iter().map(|id| id.name().len()).max().unwrap();
+    let column_width = name_width.max(6);
+    write!(out, "{:name_width$}", "")?;
+    for id in &ids {
+        write!(out, "  {:>column_width$}", id.name())?;
+    }
+    writeln!(out)?;
+    for (row, row_id) in ids.iter().enumerate() {
+        write!(out, "{:name_width$}", row_id.name())?;
+        for column in 0..ids.len() {
+            let pair = pairs
+                .iter()
+                .position(|pair| *pair == (row.min(column), row.max(column)));
+            let cell = match pair.map(synergy) {
+                Some(Some(ratio)) => format!("x{ratio:.2}"),
+                _ => "-".to_owned(),
+            };
+            write!(out, "  {cell:>column_width$}")?;
+        }
+        writeln!(out)?;
+    }
+
+    writeln!(out)?;
+    let synergistic = (0..pairs.len())
+        .filter_map(|pair| Some((pair, synergy(pair)?)))
+        .filter(|(_, ratio)| *ratio > 1.05)
+        .sorted_by(|(_, left), (_, right)| right.total_cmp(left))
+        .collect_vec();
+    if synergistic.is_empty() {
+        writeln!(
+            out,
+            "No pair does more than 5% better together than separately."
+        )?;
+    } else {
+        writeln!(out, "Synergistic pairs:")?;
+        for (pair, ratio) in synergistic {
+            let (a, b) = pairs[pair];
+            writeln!(
+                out,
+                " - {} + {}: +{:.1} together vs +{:.1} separately (x{ratio:.2})",
+                ids[a],
+                ids[b],
+                pair_uplift(pair),
+                single_uplift(a) + single_uplift(b),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn joker_impact(
+    out: &mut dyn Write,
+    sampling: &Sampling,
+    name: &str,
+    boss: Option<BossBlind>,
+    format: Format,
+) -> Result<()> {
+    let id = parse_jokers(&[name.to_owned()])?[0];
+    let hand_size = boss_hand_size(8, boss);
+    let run = |stage: &str, jokers: &[JokerSlot]| {
+        let rules = Rules {
+            options: Options::empty(),
+            scoring_options: ScoringOptions::empty(),
+            jokers,
+            boss,
+        };
+        let generate_hand = |rng: &mut SmallRng| (draw_played_hand(rng, rules), 1.0);
+        generate_hand_stats(sampling, stage, rules, generate_hand).map(|(stats, _)| stats)
+    };
+    let without = run("without", &[])?;
+    let with = run("with", &[JokerSlot::new(id.joker())])?;
+
+    if format != Format::Text {
+        let records = [
+            hand_records(hand_size, "", &without),
+            hand_records(hand_size, id.name(), &with),
+        ]
+        .concat();
+        return print_records(out, &records, format);
+    }
+
+    let empty = HandStats {
+        frequency: 0.0,
+        frequency_error: 0.0,
+        average_score: 0.0,
+        score_variance: 0.0,
+        count: 0.0,
+        samples: 0.0,
+        exact: false,
+        score_shares: [0.0; SCORE_BUCKETS],
+    };
+    // The margins of a difference, were the two runs independent. With a
+    // seed they see the same hands, so the true margin is narrower.
+    let difference_margin = |before: f64, after: f64| before.hypot(after);
+
+    writeln!(
+        out,
+        "Playing the best hand from {hand_size} cards{}, without and with {id}:",
+        boss.map_or_else(String::new, |boss| format!(" against {boss}")),
+    )?;
+    let hand_width = HandKind::iter()
+        .map(|kind| format!("{kind:?}").len())
+        .max()
+        .unwrap();
+    for kind in HandKind::iter() {
+        let before = without.get(&kind).unwrap_or(&empty);
+        let after = with.get(&kind).unwrap_or(&empty);
+        if before.frequency == 0.0 && after.frequency == 0.0 {
+            continue;
+        }
+        writeln!(
+            out,
+            " - {:hand_width$} {:>6.3}% -> {:>6.3}%  (avg: {:>8.1} -> {:>8.1}, {:>+8.1} ±{:.1})",
+            format!("{kind:?}"),
+            before.frequency * 100.0,
+            after.frequency * 100.0,
+            before.average_score,
+            after.average_score,
+            after.average_score - before.average_score,
+            difference_margin(before.score_margin(), after.score_margin()),
+        )?;
+    }
+
+    let (before, before_margin) = expected_score(&without);
+    let (after, after_margin) = expected_score(&with);
+    writeln!(
+        out,
+        "Average score: {before:.1} ±{before_margin:.1} -> {after:.1} ±{after_margin:.1} ({:+.1} ±{:.1}, {:+.1}%)",
+        after - before,
+        difference_margin(before_margin, after_margin),
+        (after - before) / before * 100.0
+    )?;
+
+    Ok(())
+}
+
+/// How a run ended, as saved in a checkpoint.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+struct RunOutcome {
+    final_ante: u32,
+    won: bool,
+}
+
+/// For `kind` at each of `hand_levels`, over many draws of 8 cards, fewer
+/// against a Boss Blind that shrinks the hand: how often it's the best
+/// scoring hand, its total score when it is, and the total score of the
+/// other kinds when one of them is.
+fn tally_levels(
+    sampling: &Sampling,
+    kind: HandKind,
+    hand_levels: &[HandLevels],
+    rules: Rules,
+) -> Vec<(usize, f64, f64)> {
+    let workers = run_workers(sampling, 0..sampling.iterations, |rng, count| {
+        let mut tallies = vec![(0_usize, 0.0, 0.0); hand_levels.len()];
+        for _ in 0..count {
+            let drawn = Deck::shuffled(rng)
+                .draw_n(boss_hand_size(8, rules.boss))
+                .unwrap();
+            // Cards drawn face down can't be picked from
+            let plays = if rules.boss.is_some_and(BossBlind::first_hand_face_down) {
+                vec![rules.play(drawn.view())]
+            } else {
+                every_play(drawn.view(), rules.options)
+            };
+            let score = |played: &PlayedHand, hand_levels: &HandLevels| {
+                let context = played
+                    .context()
+                    .with_jokers(rules.jokers)
+                    .with_hand_levels(hand_levels)
+                    .with_boss(rules.boss);
+                Scorer::score_hand(&context).value()
+            };
+            // Levelling the kind only changes what its own hands score, so
+            // the best of the others only has to be found once
+            let best_other = plays
+                .iter()
+                .filter(|played| played.kind != kind)
+                .map(|played| score(played, &HandLevels::NEW))
+                .fold(0.0, f64::max);
+            for (tally, hand_levels) in tallies.iter_mut().zip(hand_levels) {
+                let best_kind = plays
+                    .iter()
+                    .filter(|played| played.kind == kind)
+                    .map(|played| score(played, hand_levels))
+                    .max_by(f64::total_cmp);
+                match best_kind {
+                    Some(score) if score >= best_other => {
+                        tally.0 += 1;
+                        tally.1 += score;
+                    }
+                    _ => tally.2 += best_other,
+                }
+            }
+        }
+        tallies
+    });
+    let mut tallies = vec![(0_usize, 0.0, 0.0); hand_levels.len()];
+    for worker_tallies in workers {
+        for (tally, worker_tally) in tallies.iter_mut().zip(worker_tallies) {
+            tally.0 += worker_tally.0;
+            tally.1 += worker_tally.1;
+            tally.2 += worker_tally.2;
+        }
+    }
+    tallies
+}
+
+/// Score the best scoring hand from 8 cards with `args.kind` at each level,
+/// over many draws. Every level is scored on the same draws, and the hand
+/// played is picked again at each one, since a levelled kind can come to
+/// outscore kinds that beat it at the first level.
+#[allow(clippy::cast_precision_loss)]
+fn level_sweep(out: &mut dyn Write, sampling: &Sampling, args: &LevelSweepArgs) -> Result<()> {
+    let jokers = parse_jokers(&args.jokers)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect_vec();
+    let rules = Rules {
+        options: evaluator_options(args.shortcut, args.four_fingers),
+        scoring_options: ScoringOptions::empty(),
+        jokers: &jokers,
+        boss: args.boss,
+    };
+    let hand_levels = args
+        .levels
+        .clone()
+        .map(|level| {
+            let mut hand_levels = HandLevels::NEW;
+            hand_levels.set_level(args.kind, level);
+            hand_levels
+        })
+        .collect_vec();
+
+    let tallies = tally_levels(sampling, args.kind, &hand_levels, rules);
+
+    let samples = sampling.iterations as f64;
+    let first = (tallies[0].1 + tallies[0].2) / samples;
+    let records = args
+        .levels
+        .clone()
+        .zip(tallies)
+        .map(|(level, (found, total, others))| LevelRecord {
+            kind: format!("{:?}", args.kind),
+            level,
+            best_rate: found as f64 / samples,
+            average_score: if found > 0 { total / found as f64 } else { 0.0 },
+            ev: total / samples,
+            overall: (total + others) / samples,
+            uplift: (total + others) / samples - first,
+        })
+        .collect_vec();
+    if args.output.format != Format::Text {
+        return print_records(out, &records, args.output.format);
+    }
+
+    writeln!(
+        out,
+        "Levelling up {:?}, {}{}:",
+        args.kind,
+        if jokers.is_empty() {
+            "with no jokers".to_owned()
+        } else {
+            format!("with {}", args.jokers.join(", "))
+        },
+        args.boss
+            .map_or_else(String::new, |boss| format!(" against {boss}")),
+    )?;
+    writeln!(
+        out,
+        "{:>5} {:>8} {:>9} {:>8} {:>8} {:>18}",
+        "Level", "Best", "Avg score", "EV", "Overall", "Uplift"
+    )?;
+    for record in &records {
+        writeln!(
+            out,
+            "{:>5} {:>7.3}% {:>9.1} {:>8.1} {:>8.1} {:>+8.1} ({:>+5.1}%)",
+            record.level,
+            record.best_rate * 100.0,
+            record.average_score,
+            record.ev,
+            record.overall,
+            record.uplift,
+            record.uplift / first * 100.0,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Which of `count_cards`' counts a card is in: one for each rank and suit,
+/// ranks first.
+fn card_slot(card: Card) -> usize {
+    card.rank as usize * Suit::iter().len() + card.suit as usize
+}
+
+/// How many cards of each rank and suit, by `card_slot`, are drawn, and how
+/// many of those score in the hand played from them under `rules`.
+fn count_cards(sampling: &Sampling, rules: &Rules) -> (Vec<usize>, Vec<usize>) {
+    let splash = rules
+        .scoring_options
+        .contains(ScoringOptions::AllPlayedCardsScore);
+    let all_face = rules
+        .scoring_options
+        .contains(ScoringOptions::AllCardsAreFace);
+    let slots = Rank::iter().len() * Suit::iter().len();
+    let workers = run_workers(sampling, 0..sampling.iterations, |rng, count| {
+        let mut drawn = vec![0_usize; slots];
+        let mut scored = drawn.clone();
+        for _ in 0..count {
+            let (cards, _) = draw_cards(sampling, rng, sampling.hand_size);
+            let played = rules.play(&cards);
+            for card in &cards {
+                drawn[card_slot(*card)] += 1;
+            }
+            let unscored: &[Card] = if splash { &played.unscored } else { &[] };
+            for card in played.hand.view().iter().chain(unscored) {
+                let is_face = all_face || card.is_face();
+                if !rules.boss.is_some_and(|boss| boss.debuffs(*card, is_face)) {
+                    scored[card_slot(*card)] += 1;
+                }
+            }
+        }
+        (drawn, scored)
+    });
+
+    let mut drawn = vec![0_usize; slots];
+    let mut scored = drawn.clone();
+    for (worker_drawn, worker_scored) in workers {
+        for (total, count) in drawn.iter_mut().zip(worker_drawn) {
+            *total += count;
+        }
+        for (total, count) in scored.iter_mut().zip(worker_scored) {
+            *total += count;
+        }
+    }
+    (drawn, scored)
+}
+
+/// The cards drawn and scored over many draws, by `card_slot`.
+struct CardCounts {
+    rules: Rules<'static>,
+    /// How many hands were drawn
+    samples: usize,
+    /// How many cards each hand was drawn from
+    hand_size: usize,
+    drawn: Vec<usize>,
+    scored: Vec<usize>,
+}
+
+/// Draw from the deck `args` asks for, holding only the jokers that change
+/// which cards score, and count the cards drawn and scored.
+fn sample_cards(args: &CardStatsArgs) -> Result<CardCounts> {
+    let hand_size = boss_hand_size(args.hand_size, args.boss);
+    check_hand_size(&args.deck, hand_size)?;
+    let (options, scoring_options, jokers) = parse_held_jokers(&args.jokers)?;
+    if !jokers.is_empty() {
+        return Err(anyhow!(
+            "only Shortcut, Four Fingers and Splash change which cards score"
+        ));
+    }
+    let checkpoint = Checkpointer::disabled();
+    let sampling = Sampling {
+        deck: &args.deck,
+        hand_size,
+        ..Sampling::new(
+            args.single_threaded,
+            args.iterations,
+            args.seed,
+            &checkpoint,
+        )
+    };
+    let rules = Rules {
+        options,
+        scoring_options,
+        jokers: &[],
+        boss: args.boss,
+    };
+    let (drawn, scored) = count_cards(&sampling, &rules);
+    Ok(CardCounts {
+        rules,
+        samples: sampling.iterations,
+        hand_size,
+        drawn,
+        scored,
+    })
+}
+
+/// Count the cards of each rank and suit drawn, and those that score in the
+/// best hand among them, over many draws.
+#[allow(clippy::cast_precision_loss)]
+fn card_stats(out: &mut dyn Write, args: &CardStatsArgs) -> Result<()> {
+    let counts = sample_cards(args)?;
+    let ranks = Rank::iter().collect_vec();
+    let suits = Suit::iter().collect_vec();
+    // Each rank's counts, from Deuce up, and then each suit's
+    let mut drawn = vec![0_usize; ranks.len() + suits.len()];
+    let mut scored = drawn.clone();
+    for rank in &ranks {
+        for suit in &suits {
+            let slot = card_slot(Card::new(*rank, *suit));
+            for group in [*rank as usize, ranks.len() + *suit as usize] {
+                drawn[group] += counts.drawn[slot];
+                scored[group] += counts.scored[slot];
+            }
+        }
+    }
+
+    let samples = counts.samples as f64;
+    let total_scored = scored[..ranks.len()].iter().sum::<usize>().max(1) as f64;
+    let names = ranks
+        .iter()
+        .map(|rank| ("rank", format!("{rank:?}")))
+        .chain(suits.iter().map(|suit| ("suit", format!("{suit:?}"))));
+    let records = names
+        .zip(drawn.iter().zip(&scored))
+        .filter(|(_, (drawn, _))| **drawn > 0)
+        .map(|((group, card), (drawn, scored))| CardRecord {
+            group,
+            card,
+            drawn: *drawn as f64 / samples,
+            scored: *scored as f64 / samples,
+            rate: *scored as f64 / *drawn as f64,
+            share: *scored as f64 / total_scored,
+        })
+        .collect_vec();
+    if args.output.format != Format::Text {
+        return print_records(out, &records, args.output.format);
+    }
+
+    writeln!(
+        out,
+        "Drawing {} cards from a shuffled {}{} and playing the best hand, the cards of each rank and suit drawn and scored a hand:",
+        counts.hand_size,
+        args.deck.name,
+        counts.rules.holding()
+    )?;
+    for group in ["rank", "suit"] {
+        writeln!(
+            out,
+            "{:<8} {:>6} {:>6} {:>7} {:>7}",
+            if group == "rank" { "Rank" } else { "Suit" },
+            "Drawn",
+            "Scored",
+            "Rate",
+            "Share"
+        )?;
+        for record in records.iter().filter(|record| record.group == group) {
+            writeln!(
+                out,
+                "{:<8} {:>6.3} {:>6.3} {:>6.1}% {:>6.1}%",
+                record.card,
+                record.drawn,
+                record.scored,
+                record.rate * 100.0,
+                record.share * 100.0
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Count how often each card is drawn and left out of the best hand among
+/// those drawn with it, over many draws, listing the most often left out
+/// first.
+#[allow(clippy::cast_precision_loss)]
+fn dead_weight(out: &mut dyn Write, args: &CardStatsArgs) -> Result<()> {
+    let CardCounts {
+        rules,
+        samples,
+        hand_size,
+        drawn,
+        scored,
+    } = sample_cards(args)?;
+    let samples = samples as f64;
+    let records = Rank::iter()
+        .cartesian_product(Suit::iter())
+        .map(|(rank, suit)| Card::new(rank, suit))
+        .filter(|card| drawn[card_slot(*card)] > 0)
+        .map(|card| {
+            let drawn = drawn[card_slot(card)];
+            let unused = drawn - scored[card_slot(card)];
+            DeadWeightRecord {
+                card: card.to_string(),
+                drawn: drawn as f64 / samples,
+                unused: unused as f64 / samples,
+                rate: unused as f64 / drawn as f64,
+            }
+        })
+        .sorted_by(|left, right| right.rate.total_cmp(&left.rate))
+        .collect_vec();
+    if args.output.format != Format::Text {
+        return print_records(out, &records, args.output.format);
+    }
+
+    writeln!(
+        out,
+        "Drawing {hand_size} cards from a shuffled {}{} and playing the best hand, how often each card drawn doesn't score, most often first:",
+        args.deck.name,
+        rules.holding()
+    )?;
+    writeln!(
+        out,
+        "{:<4} {:>6} {:>6} {:>7}",
+        "Card", "Drawn", "Unused", "Rate"
+    )?;
+    for record in &records {
+        writeln!(
+            out,
+            "{:<4} {:>6.3} {:>6.3} {:>6.1}%",
+            record.card,
+            record.drawn,
+            record.unused,
+            record.rate * 100.0
+        )?;
+    }
+
+    Ok(())
+}
+
+/// A job without its seed or how many iterations it was set to perform, which
+/// runs being merged can differ in, and the generators it drew from: each
+/// seeded with its seed plus the index of one of its `workers`, or those
+/// listed by the merge that made it.
+fn split_generators(job: &str, workers: u64) -> (String, Vec<Range<u64>>) {
+    let mut generators = Vec::new();
+    let unseeded = job
+        .split(", ")
+        .filter(|part| {
+            if let Some(Ok(seed)) = part.strip_prefix("seed ").map(str::parse::<u64>) {
+                generators.push(seed..seed.saturating_add(workers));
+            } else if let Some(listed) = part.strip_prefix("generators ") {
+                generators.extend(listed.split(' ').filter_map(|range| {
+                    let (start, end) = range.split_once("..")?;
+                    Some(start.parse().ok()?..end.parse().ok()?)
+                }));
+            } else {
+                return *part != "no seed" && !part.starts_with("iterations ");
+            }
+            false
+        })
+        .join(", ");
+    (unseeded, generators)
+}
+
+/// Add up the hands counted in the checkpoints of runs of the same job, and
+/// report them as one run.
+fn merge(out: &mut dyn Write, args: &MergeArgs) -> Result<()> {
+    let mut job: Option<String> = None;
+    let mut stages: BTreeMap<String, SamplingProgress> = BTreeMap::new();
+    // The generators each seeded run drew from, which no two may share, or
+    // they'd have drawn some of the same hands
+    let mut generators: Vec<(&PathBuf, Range<u64>)> = Vec::new();
+    for path in &args.files {
+        let saved = Saved::load(path)?.ok_or_else(|| anyhow!("there's no {}", path.display()))?;
+        let mut most_done = 0;
+        for (stage, progress) in saved.stages {
+            let progress: SamplingProgress = serde_json::from_value(progress).map_err(|_| {
+                anyhow!(
+                    "{} doesn't count hands in {stage}; only hand-stats and joker-impact checkpoints merge",
+                    path.display()
+                )
+            })?;
+            most_done = most_done.max(progress.done);
+            match stages.get_mut(&stage) {
+                Some(merged) => merged.merge(&progress),
+                None => {
+                    stages.insert(stage, progress);
+                }
+            }
+        }
+
+        let workers = most_done.div_ceil(WORKER_ITERATIONS) as u64;
+        let (unseeded, used) = split_generators(&saved.job, workers);
+        if job.as_ref().is_some_and(|job| *job != unseeded) {
+            return Err(anyhow!(
+                "{} holds progress on a different job: {}",
+                path.display(),
+                saved.job
+            ));
+        }
+        job = Some(unseeded);
+        for used in used {
+            if let Some((other, _)) = generators
+                .iter()
+                .find(|(_, other)| other.start < used.end && used.start < other.end)
+            {
+                return Err(anyhow!(
+                    "{} and {} drew from some of the same generators, so they sampled some of the same hands; seed runs at least {} apart for each ten thousand iterations",
+                    other.display(),
+                    path.display(),
+                    10_000 / WORKER_ITERATIONS
+                ));
+            }
+            generators.push((path, used));
+        }
+    }
+    let mut job = job.unwrap_or_default();
+    if !generators.is_empty() {
+        let listed = generators
+            .iter()
+            .map(|(_, used)| format!("{}..{}", used.start, used.end))
+            .join(" ");
+        job = format!("{job}, generators {listed}");
+    }
+
+    if let Some(checkpoint) = &args.checkpoint {
+        let saved = Saved {
+            job: job.clone(),
+            stages: stages
+                .iter()
+                .map(|(stage, progress)| Ok((stage.clone(), serde_json::to_value(progress)?)))
+                .collect::<Result<_>>()?,
+        };
+        saved.save(checkpoint)?;
+    }
+
+    if args.output.format != Format::Text {
+        let records = stages
+            .iter()
+            .flat_map(|(stage, progress)| {
+                summarize(&progress.tally(), false)
+                    .into_iter()
+                    .sorted_by_key(|(hand, _)| *hand)
+                    .map(|(hand, stats)| MergedRecord {
+                        stage: stage.clone(),
+                        hand: format!("{hand:?}"),
+                        frequency: stats.frequency,
+                        frequency_margin: stats.frequency_margin(),
+                        average_score: stats.average_score,
+                        score_margin: stats.score_margin(),
+                        ev: stats.average_score * stats.frequency,
+                    })
+            })
+            .collect_vec();
+        return print_records(out, &records, args.output.format);
+    }
+
+    writeln!(out, "Merging {} runs of {job}:", args.files.len())?;
+    for (stage, progress) in &stages {
+        writeln!(out, "{stage} ({} hands):", progress.done)?;
+        print_card_stats(out, &summarize(&progress.tally(), false))?;
+    }
+
+    Ok(())
+}
+
+/// Play the runs seeded by `seeds`, or resume them from a checkpoint, and
+/// say how each one ended.
+fn play_ante_runs(
+    single_threaded: bool,
+    seeds: Range<u64>,
+    strategy: StrategyName,
+    deck: StartingDeck,
+    stake: Stake,
+    checkpoint: &CheckpointArgs,
+) -> Result<Vec<RunOutcome>> {
+    let job = format!(
+        "ante-distribution, seed {}, runs {}, {}, {deck} Deck, {stake} Stake",
+        seeds.start,
+        seeds.end - seeds.start,
+        strategy.to_possible_value().unwrap().get_name()
+    );
+    let checkpoint = Checkpointer::open(checkpoint, job)?;
+    let mut outcomes: Vec<RunOutcome> = checkpoint.resume("runs")?.unwrap_or_default();
+    while (outcomes.len() as u64) < seeds.end - seeds.start {
+        let start = seeds.start + outcomes.len() as u64;
+        let end = if checkpoint.is_enabled() {
+            (start + BATCH_RUNS).min(seeds.end)
+        } else {
+            seeds.end
+        };
+        let summaries = play_runs(strategy, deck, stake, start..end, single_threaded)?;
+        outcomes.extend(summaries.iter().map(|summary| RunOutcome {
+            final_ante: summary.final_ante,
+            won: summary.won,
+        }));
+        checkpoint.update("runs", &outcomes, end == seeds.end)?;
+    }
+    Ok(outcomes)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ante_distribution(
+    out: &mut dyn Write,
+    outcomes: &[RunOutcome],
+    first_seed: u64,
+    strategy: StrategyName,
+    deck: StartingDeck,
+    stake: Stake,
+    format: Format,
+) -> Result<()> {
+    let runs = outcomes.len().max(1) as f64;
+    let ended = outcomes.iter().counts_by(|outcome| outcome.final_ante);
+    let last = ended.keys().copied().max().unwrap_or(1);
+    let wins = outcomes.iter().filter(|outcome| outcome.won).count();
+
+    if format != Format::Text {
+        let mut reached = outcomes.len();
+        let records = (1..=last)
+            .map(|ante| {
+                let count = ended.get(&ante).copied().unwrap_or(0);
+                let record = AnteRecord {
+                    ante,
+                    ended: count,
+                    won: outcomes
+                        .iter()
+                        .filter(|outcome| outcome.won && outcome.final_ante == ante)
+                        .count(),
+                    share: count as f64 / runs,
+                    reached: reached as f64 / runs,
+                };
+                reached -= count;
+                record
+            })
+            .collect_vec();
+        return print_records(out, &records, format);
+    }
+
+    writeln!(
+        out,
+        "Over {} runs with {} on the {deck} Deck at {stake} Stake, from seed {}:",
+        outcomes.len(),
+        strategy.to_possible_value().unwrap().get_name(),
+        first_seed,
+    )?;
+    writeln!(
+        out,
+        "{:>4} {:>7} {:>8} {:>9}",
+        "Ante", "Ended", "Share", "Reached"
+    )?;
+    let mut reached = outcomes.len();
+    for ante in 1..=last {
+        let count = ended.get(&ante).copied().unwrap_or(0);
+        let row = format!(
+            "{ante:>4} {count:>7} {:>7.2}% {:>8.2}%  {}",
+            count as f64 / runs * 100.0,
+            reached as f64 / runs * 100.0,
+            "#".repeat(count * 50 / outcomes.len()),
+        );
+        writeln!(out, "{}", row.trim_end())?;
+        reached -= count;
+    }
+    writeln!(out, "Won: {wins} ({:.2}%)", wins as f64 / runs * 100.0)?;
+
+    Ok(())
+}
+
+/// Fail unless there are enough cards in `deck` to draw `hand_size`, and
+/// enough of those to play a hand of 5.
+fn check_hand_size(deck: &DrawDeck, hand_size: usize) -> Result<()> {
+    if hand_size < 5 || hand_size > deck.cards.len() {
+        return Err(anyhow!(
+            "--hand-size must be from 5 up to the {} cards in the deck",
+            deck.cards.len()
+        ));
+    }
+    Ok(())
+}
+
+/// How many cards are drawn for `hand_size` against `boss`.
+fn boss_hand_size(hand_size: usize, boss: Option<BossBlind>) -> usize {
+    hand_size.saturating_sub(boss.map_or(0, BossBlind::hand_size_penalty))
+}
+
+fn run_hand_stats(out: &mut dyn Write, args: &HandStatsArgs) -> Result<()> {
+    let shard = shard_iterations(args.shard, args.iterations)?;
+    let deck = args.deck.with_modifiers(RandomModifiers {
+        enhancements: args.random_enhancements.unwrap_or_default(),
+        editions: args.random_editions.unwrap_or_default(),
+        seals: args.random_seals.unwrap_or_default(),
+    });
+    let hand_size = boss_hand_size(args.hand_size, args.boss);
+    let job = format!(
+        "hand-stats, {}, iterations {}{}, importance {}, plasma {}, shortcut {}, four fingers {}, jokers [{}], {}, hand size {hand_size}{}{}",
+        describe_seed(args.seed, shard.as_ref()),
+        args.iterations,
+        args.precision
+            .map_or_else(String::new, |precision| format!(", precision {precision}")),
+        args.importance,
+        args.plasma,
+        args.shortcut,
+        args.four_fingers,
+        args.jokers.join(","),
+        deck.name,
+        args.boss
+            .map_or_else(String::new, |boss| format!(", against {boss}")),
+        match args.discards {
+            0 => String::new(),
+            discards => format!(", discards {discards}"),
+        }
+    );
+    check_hand_size(&deck, hand_size)?;
+    let checkpoint = Checkpointer::open(&args.checkpoint, job)?;
+    // A resumed job adds to the hands recorded before it was interrupted
+    let record = args
+        .record
+        .as_deref()
+        .map(|path| JsonLines::open(path, checkpoint.is_resuming()))
+        .transpose()?;
+    let sampling = Sampling {
+        precision: args.precision,
+        importance: args.importance,
+        deck: &deck,
+        hand_size,
+        discards: args.discards,
+        record: record.as_ref(),
+        ..Sampling::new(
+            args.single_threaded,
+            args.iterations,
+            args.seed,
+            &checkpoint,
+        )
+        .sharded(shard)
+    };
+    let (options, mut scoring_options, jokers) = parse_held_jokers(&args.jokers)?;
+    if args.plasma {
+        scoring_options |= ScoringOptions::Plasma;
+    }
+    let rules = Rules {
+        options: options | evaluator_options(args.shortcut, args.four_fingers),
+        scoring_options,
+        jokers: &jokers,
+        boss: args.boss,
+    };
+    if args.all_options {
+        return compare_options(
+            out,
+            &sampling,
+            args.exhaustive,
+            args.analytic,
+            rules,
+            args.output.format,
+            args.report.as_deref(),
+        );
+    }
+    hand_stats(
+        out,
+        &sampling,
+        args.exhaustive,
+        args.analytic,
+        rules,
+        args.output.format,
+        args.report.as_deref(),
+    )
+}
+
+pub fn run(command: &CliCommands) -> Result<()> {
+    let output = match command {
+        CliCommands::HandStats(args) => &args.output,
+        CliCommands::JokerSynergy { output, .. }
+        | CliCommands::JokerImpact { output, .. }
+        | CliCommands::AnteDistribution { output, .. } => output,
+        CliCommands::LevelSweep(args) => &args.output,
+        CliCommands::CardStats(args) | CliCommands::DeadWeight(args) => &args.output,
+        CliCommands::Merge(args) => &args.output,
+    };
+    output.run(|out| run_command(out, command))
+}
+
+fn run_command(out: &mut dyn Write, command: &CliCommands) -> Result<()> {
+    match command {
+        CliCommands::HandStats(args) => run_hand_stats(out, args),
+        CliCommands::JokerSynergy {
+            single_threaded,
+            iterations,
+            seed,
+            jokers,
+            boss,
+            output,
+            ..
+        } => joker_synergy(
+            out,
+            &Sampling::new(
+                *single_threaded,
+                *iterations,
+                *seed,
+                &Checkpointer::disabled(),
+            ),
+            jokers,
+            *boss,
+            output.format,
+        ),
+        CliCommands::JokerImpact {
+            single_threaded,
+            iterations,
+            seed,
+            joker,
+            boss,
+            shard,
+            checkpoint,
+            output,
+            ..
+        } => {
+            let shard = shard_iterations(*shard, *iterations)?;
+            let checkpoint = Checkpointer::open(
+                checkpoint,
+                format!(
+                    "joker-impact, {}, iterations {iterations}, {joker}{}",
+                    describe_seed(*seed, shard.as_ref()),
+                    boss.map_or_else(String::new, |boss| format!(", against {boss}")),
+                ),
+            )?;
+            joker_impact(
+                out,
+                &Sampling::new(*single_threaded, *iterations, *seed, &checkpoint).sharded(shard),
+                joker,
+                *boss,
+                output.format,
+            )
+        }
+        CliCommands::LevelSweep(args) => level_sweep(
+            out,
+            &Sampling::new(
+                args.single_threaded,
+                args.iterations,
+                args.seed,
+                &Checkpointer::disabled(),
+            ),
+            args,
+        ),
+        CliCommands::CardStats(args) => card_stats(out, args),
+        CliCommands::DeadWeight(args) => dead_weight(out, args),
+        CliCommands::Merge(args) => merge(out, args),
+        CliCommands::AnteDistribution {
+            single_threaded,
+            runs,
+            seed,
+            strategy,
+            deck,
+            stake,
+            checkpoint,
+            output,
+            ..
+        } => ante_distribution(
+            out,
+            &play_ante_runs(
+                *single_threaded,
+                *seed..*seed + *runs,
+                *strategy,
+                *deck,
+                *stake,
+                checkpoint,
+            )?,
+            *seed,
+            *strategy,
+            *deck,
+            *stake,
+            output.format,
+        ),
+    }
+}
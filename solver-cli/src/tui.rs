@@ -0,0 +1,252 @@
+use anyhow::Result;
+use clap::Args;
+use itertools::Itertools;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use strum::IntoEnumIterator;
+
+use solver_core::prelude::{Card, CardView, JokerSlot, Rank, Score, Suit};
+
+use crate::discard_ev::show;
+use crate::repl::{execute, Outcome, Session, HELP};
+use crate::stats::parse_jokers;
+
+#[derive(Debug, Args)]
+pub struct TuiArgs {
+    /// How many hands each round starts with
+    #[arg(long = "hands", default_value = "4")]
+    hands: u32,
+
+    /// How many discards each round starts with
+    #[arg(long = "discards", default_value = "3")]
+    discards: u32,
+
+    /// The score the blind needs
+    #[arg(short = 't', long = "target")]
+    target: Option<f64>,
+
+    /// The jokers held, by name, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// How many actions to rank
+    #[arg(short = 'k', long = "top", default_value = "5")]
+    top: usize,
+
+    /// How many draws to sample for each discard, when there are too many to
+    /// try them all
+    #[arg(short = 's', long = "samples", default_value = "200")]
+    samples: usize,
+}
+
+/// A card as a span, with hearts and diamonds in red.
+fn card_span(card: Card) -> Span<'static> {
+    let style = match card.suit {
+        Suit::Hearts | Suit::Diamonds => Style::new().fg(Color::Red),
+        Suit::Spades | Suit::Clubs => Style::new(),
+    };
+    Span::styled(card.to_string(), style)
+}
+
+fn card_line(cards: impl IntoIterator<Item = Option<Card>>) -> Line<'static> {
+    let spans = cards.into_iter().map(|card| match card {
+        Some(card) => card_span(card),
+        None => Span::styled("··", Style::new().fg(Color::DarkGray)),
+    });
+    Line::from(Itertools::intersperse(spans, Span::raw(" ")).collect_vec())
+}
+
+/// Every card of the standard deck, by suit, with the ones already seen
+/// blanked out.
+fn deck_lines(session: &Session) -> Vec<Line<'static>> {
+    let mut unseen = session.tracker.view().to_vec();
+    Suit::iter()
+        .map(|suit| {
+            card_line(Rank::iter().rev().map(|rank| {
+                let card = Card::new(rank, suit);
+                let index = unseen.iter().position(|left| *left == card)?;
+                unseen.swap_remove(index);
+                Some(card)
+            }))
+        })
+        .collect()
+}
+
+fn round_lines(session: &Session) -> Vec<Line<'static>> {
+    let score = match session.target {
+        Some(target) => format!(
+            "Score {} of {} ({} to go)",
+            Score::from(session.scored),
+            Score::from(target),
+            Score::from((target - session.scored).max(0.0)),
+        ),
+        None => format!("Score {} (no target set)", Score::from(session.scored)),
+    };
+    vec![
+        Line::from(score),
+        Line::from(format!(
+            "{} hands and {} discards left",
+            session.hands, session.discards
+        )),
+    ]
+}
+
+fn recommendation_lines(session: &Session, top: usize) -> Vec<Line<'static>> {
+    if session.hand.is_empty() {
+        return vec![Line::from("Enter the dealt cards with `hand`")];
+    }
+    let to_go = session.target.map(|target| target - session.scored);
+    session
+        .recommend(top)
+        .iter()
+        .enumerate()
+        .map(|(rank, recommendation)| {
+            let wins = to_go.is_some_and(|to_go| recommendation.expected >= to_go);
+            Line::from(format!(
+                "{:>2}. {}: {} expected{}",
+                rank + 1,
+                session.describe(&recommendation.action, show),
+                Score::from(recommendation.expected),
+                if wins { ", beats the blind" } else { "" },
+            ))
+        })
+        .collect()
+}
+
+/// The session and what's shown of it. Ranking the actions takes a while, so
+/// it's only done again when a command changes something, rather than on
+/// every key.
+struct App {
+    session: Session,
+    top: usize,
+    recommendations: Vec<Line<'static>>,
+    input: String,
+    message: String,
+}
+
+impl App {
+    fn refresh(&mut self) {
+        self.recommendations = recommendation_lines(&self.session, self.top);
+    }
+
+    /// Carry out the line typed so far, returning whether to quit.
+    fn submit(&mut self) -> bool {
+        let line = std::mem::take(&mut self.input);
+        self.message = match execute(&mut self.session, &line) {
+            Ok(Outcome::Advice(k)) => {
+                self.top = k;
+                String::new()
+            }
+            Ok(Outcome::Help) => HELP.to_owned(),
+            Ok(Outcome::Quit) => return true,
+            Ok(_) => String::new(),
+            Err(err) => format!("Error: {err}"),
+        };
+        self.refresh();
+        false
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let deck_title = format!("Deck ({} unseen)", self.session.tracker.count());
+        let message_height = u16::try_from(self.message.lines().count() + 2).unwrap_or(u16::MAX);
+        let [hand, round, deck, recommendations, message, input] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(4),
+            Constraint::Length(6),
+            Constraint::Min(3),
+            Constraint::Length(if self.message.is_empty() {
+                0
+            } else {
+                message_height
+            }),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+
+        let panel = |title: &str| Block::bordered().title(format!(" {title} "));
+        frame.render_widget(
+            Paragraph::new(card_line(self.session.hand.iter().copied().map(Some)))
+                .block(panel("Hand")),
+            hand,
+        );
+        frame.render_widget(
+            Paragraph::new(round_lines(&self.session)).block(panel("Round")),
+            round,
+        );
+        frame.render_widget(
+            Paragraph::new(deck_lines(&self.session)).block(panel(&deck_title)),
+            deck,
+        );
+        frame.render_widget(
+            Paragraph::new(self.recommendations.clone()).block(panel("Recommendations")),
+            recommendations,
+        );
+        frame.render_widget(
+            Paragraph::new(self.message.as_str())
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered()),
+            message,
+        );
+        frame.render_widget(
+            Paragraph::new(format!("> {}", self.input)).block(panel("Command")),
+            input,
+        );
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position((input.x + 3 + self.input.chars().count() as u16, input.y + 1));
+    }
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let quit = match key.code {
+            KeyCode::Char('c' | 'd') if key.modifiers.contains(KeyModifiers::CONTROL) => true,
+            KeyCode::Esc => true,
+            KeyCode::Enter => app.submit(),
+            KeyCode::Backspace => {
+                app.input.pop();
+                false
+            }
+            KeyCode::Char(char) => {
+                app.input.push(char);
+                false
+            }
+            _ => false,
+        };
+        if quit {
+            return Ok(());
+        }
+    }
+}
+
+pub fn run(args: &TuiArgs) -> Result<()> {
+    let jokers = parse_jokers(&args.jokers)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect();
+    let mut session = Session::new(args.hands, args.discards, jokers, args.samples);
+    session.target = args.target;
+    let mut app = App {
+        session,
+        top: args.top,
+        recommendations: Vec::new(),
+        input: String::new(),
+        message: "Type `help` for commands.".to_owned(),
+    };
+    app.refresh();
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+use itertools::Itertools;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use solver_core::prelude::{
+    Card, CardCollection, CardView, Deck, Hand, HandEvaluator, HandKind, Options,
+};
+use solver_core::solver::hand_evaluator::reference;
+
+use crate::discard_ev::show;
+use crate::output::{print_records, Format, OutputArgs, Record};
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// How many random hands of one to five cards to check. Each card is
+    /// drawn from the whole of a standard deck, so a hand can hold copies of
+    /// a card, as hands from some decks can
+    #[arg(short = 's', long = "samples", default_value = "100000")]
+    samples: usize,
+
+    /// Check every hand of one to five different cards from a standard deck,
+    /// rather than random ones
+    #[arg(long = "exhaustive")]
+    exhaustive: bool,
+
+    /// Seed the random hands, to check the same ones again
+    #[arg(long = "seed")]
+    seed: Option<u64>,
+
+    /// List this many of the hands disagreed on
+    #[arg(long = "show", default_value = "20")]
+    show: usize,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Which of the Shortcut and Four Fingers jokers turn on `options`,
+/// separated by commas.
+fn jokers(options: Options) -> String {
+    let mut names = Vec::new();
+    if options.contains(Options::GappedStraights) {
+        names.push("Shortcut");
+    }
+    if options.contains(Options::FourCardStraightsAndFlushes) {
+        names.push("Four Fingers");
+    }
+    names.join(",")
+}
+
+/// `jokers`, separated by commas, for reading.
+fn describe_jokers(jokers: &str) -> String {
+    if jokers.is_empty() {
+        "no jokers".to_owned()
+    } else {
+        jokers.replace(',', ", ")
+    }
+}
+
+/// A hand the evaluator and the reference make different things of.
+struct Disagreement {
+    cards: Vec<Card>,
+    options: Options,
+    evaluated: Option<(HandKind, Hand)>,
+    reference: Option<(HandKind, Hand)>,
+}
+
+impl Disagreement {
+    fn record(&self) -> DisagreementRecord {
+        let kind =
+            |found: &Option<(HandKind, Hand)>| found.as_ref().map(|(kind, _)| format!("{kind:?}"));
+        let scoring = |found: &Option<(HandKind, Hand)>| {
+            found
+                .as_ref()
+                .map_or_else(String::new, |(_, scoring)| show(scoring.view()))
+        };
+        DisagreementRecord {
+            cards: show(&self.cards),
+            jokers: jokers(self.options),
+            kind: kind(&self.evaluated),
+            scoring: scoring(&self.evaluated),
+            reference_kind: kind(&self.reference),
+            reference_scoring: scoring(&self.reference),
+        }
+    }
+}
+
+/// A hand the evaluator and the reference make different things of, and
+/// what each makes of it.
+#[derive(Clone, Serialize)]
+struct DisagreementRecord {
+    cards: String,
+    /// The jokers held, separated by commas
+    jokers: String,
+    kind: Option<String>,
+    /// The cards that score, going by the evaluator
+    scoring: String,
+    reference_kind: Option<String>,
+    /// The cards that score, going by the reference
+    reference_scoring: String,
+}
+
+impl Record for DisagreementRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "cards",
+        "jokers",
+        "kind",
+        "scoring",
+        "reference_kind",
+        "reference_scoring",
+    ];
+}
+
+/// Evaluate `cards` both ways with each combination of the jokers that
+/// change which hands they make, keeping what's disagreed on.
+fn check(cards: &[Card]) -> Vec<Disagreement> {
+    let combinations = [
+        Options::empty(),
+        Options::GappedStraights,
+        Options::FourCardStraightsAndFlushes,
+        Options::GappedStraights | Options::FourCardStraightsAndFlushes,
+    ];
+    combinations
+        .into_iter()
+        .filter_map(|options| {
+            let evaluated =
+                HandEvaluator::evaluate_poker_hand(CardCollection::from(cards), options);
+            let reference = reference::evaluate_poker_hand(CardCollection::from(cards), options);
+            (evaluated != reference).then(|| Disagreement {
+                cards: cards.to_vec(),
+                options,
+                evaluated,
+                reference,
+            })
+        })
+        .collect()
+}
+
+/// Check every hand of one to five different cards from `deck`, returning
+/// how many there were and those disagreed on, in order.
+fn check_every_hand(deck: &[Card]) -> (usize, Vec<Disagreement>) {
+    // Split up by hand size and first card, so the work spreads evenly
+    let starts = (1..=5).cartesian_product(0..deck.len()).collect_vec();
+    let (counts, disagreements): (Vec<usize>, Vec<Vec<Disagreement>>) = starts
+        .into_par_iter()
+        .map(|(size, first)| {
+            let mut count = 0;
+            let mut disagreements = Vec::new();
+            for rest in deck[first + 1..].iter().copied().combinations(size - 1) {
+                let cards = [&[deck[first]], &rest[..]].concat();
+                count += 1;
+                disagreements.extend(check(&cards));
+            }
+            (count, disagreements)
+        })
+        .unzip();
+    (
+        counts.into_iter().sum(),
+        disagreements.into_iter().flatten().collect(),
+    )
+}
+
+/// Check `samples` random hands of one to five cards, each drawn from the
+/// whole of `deck`, returning those disagreed on, in order.
+fn check_random_hands(deck: &[Card], samples: usize, seed: u64) -> Vec<Disagreement> {
+    (0..samples)
+        .into_par_iter()
+        .flat_map_iter(|sample| {
+            // Each hand is drawn from its own generator, so a seeded run
+            // comes out the same however many threads it's on
+            let mut rng = SmallRng::seed_from_u64(seed.wrapping_add(sample as u64));
+            let size = rng.gen_range(1..=5);
+            let cards = (0..size)
+                .map(|_| deck[rng.gen_range(0..deck.len())])
+                .collect_vec();
+            check(&cards)
+        })
+        .collect()
+}
+
+/// How many of `disagreements` there are of each combination of jokers and
+/// kinds made by the evaluator and the reference.
+fn print_summary(out: &mut dyn Write, disagreements: &[Disagreement]) -> Result<()> {
+    let kind = |found: &Option<(HandKind, Hand)>| {
+        found
+            .as_ref()
+            .map_or_else(|| "nothing".to_owned(), |(kind, _)| format!("{kind:?}"))
+    };
+    let counts: BTreeMap<(String, String, String), usize> = disagreements
+        .iter()
+        .map(|disagreement| {
+            (
+                describe_jokers(&jokers(disagreement.options)),
+                kind(&disagreement.evaluated),
+                kind(&disagreement.reference),
+            )
+        })
+        .counts()
+        .into_iter()
+        .collect();
+    writeln!(
+        out,
+        "{:<24} {:<15} {:<15} {:>9}",
+        "Jokers", "Evaluator", "Reference", "Hands"
+    )?;
+    for ((jokers, evaluated, reference), count) in counts {
+        writeln!(
+            out,
+            "{jokers:<24} {evaluated:<15} {reference:<15} {count:>9}"
+        )?;
+    }
+    Ok(())
+}
+
+/// Check the hands `args` asks for and print the results to `out`,
+/// returning how many were disagreed on.
+fn check_hands(out: &mut dyn Write, args: &VerifyArgs) -> Result<usize> {
+    let format = args.output.format;
+    let deck = Deck::base_deck();
+    let (checked, disagreements) = if args.exhaustive {
+        check_every_hand(deck.view())
+    } else {
+        let seed = args.seed.unwrap_or_else(rand::random);
+        if format == Format::Text {
+            writeln!(out, "Drawing random hands with seed {seed}")?;
+        }
+        (
+            args.samples,
+            check_random_hands(deck.view(), args.samples, seed),
+        )
+    };
+
+    let records = disagreements
+        .iter()
+        .take(args.show)
+        .map(Disagreement::record)
+        .collect_vec();
+    if format == Format::Text {
+        writeln!(
+            out,
+            "Checked {checked} hands with each combination of Shortcut and Four Fingers against the reference evaluator"
+        )?;
+        if !disagreements.is_empty() {
+            writeln!(out)?;
+            print_summary(out, &disagreements)?;
+            writeln!(out)?;
+            writeln!(out, "The first {} disagreed on:", records.len())?;
+            for record in &records {
+                let made = |kind: &Option<String>, scoring: &str| {
+                    kind.as_ref().map_or_else(
+                        || "nothing".to_owned(),
+                        |kind| format!("{kind} ({scoring})"),
+                    )
+                };
+                writeln!(
+                    out,
+                    "  {:<15} {:<24} {} vs {}",
+                    record.cards,
+                    describe_jokers(&record.jokers),
+                    made(&record.kind, &record.scoring),
+                    made(&record.reference_kind, &record.reference_scoring)
+                )?;
+            }
+        }
+    }
+    print_records(out, &records, format)?;
+    Ok(disagreements.len())
+}
+
+pub fn run(args: &VerifyArgs) -> Result<()> {
+    // The hands disagreed on are written out too, so they can be looked into
+    let mut disagreed = 0;
+    args.output.run(|out| {
+        disagreed = check_hands(out, args)?;
+        Ok(())
+    })?;
+    if disagreed == 0 {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "the evaluator disagreed with the reference {disagreed} times"
+        ))
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use clap::Args;
+use rand::prelude::*;
+
+use solver_core::prelude::{
+    win_probability_with, Blind, FlushHunter, GameState, Greedy, JokerSlot, Mcts, RandomPlayer,
+    RoundPlanner, Stake, WinProbability,
+};
+
+use crate::compare::StrategyName;
+use crate::stats::parse_jokers;
+
+#[derive(Debug, Args)]
+pub struct WinProbabilityArgs {
+    /// The ante being played
+    #[arg(short = 'a', long = "ante", default_value = "1")]
+    ante: u32,
+
+    /// The blind up next: small, big or boss
+    #[arg(short = 'b', long = "blind", default_value = "small")]
+    blind: Blind,
+
+    /// The stake the run is on
+    #[arg(long = "stake", default_value = "white")]
+    stake: Stake,
+
+    /// The money held
+    #[arg(short = 'm', long = "money", default_value = "4")]
+    money: i64,
+
+    /// The jokers held, by name, separated by commas
+    #[arg(short = 'j', long = "jokers", value_delimiter = ',')]
+    jokers: Vec<String>,
+
+    /// The strategy to play the rollouts with
+    #[arg(long = "strategy", default_value = "greedy")]
+    strategy: StrategyName,
+
+    /// How many rollouts to play
+    #[arg(short = 's', long = "samples", default_value = "1000")]
+    samples: usize,
+}
+
+pub fn run(args: &WinProbabilityArgs) -> Result<()> {
+    let mut state = GameState::fresh_run(&mut SmallRng::seed_from_u64(0)).with_stake(args.stake);
+    state.ante = args.ante;
+    state.blind = args.blind;
+    state.money = args.money;
+    state.jokers = parse_jokers(&args.jokers)?
+        .into_iter()
+        .map(|id| JokerSlot::new(id.joker()))
+        .collect();
+
+    let samples = args.samples;
+    let odds: WinProbability = match args.strategy {
+        StrategyName::Greedy => win_probability_with(&Greedy::default(), &state, samples),
+        StrategyName::FlushHunter => win_probability_with(&FlushHunter::default(), &state, samples),
+        StrategyName::Random => win_probability_with(&RandomPlayer, &state, samples),
+        StrategyName::Planner => win_probability_with(&RoundPlanner::default(), &state, samples),
+        StrategyName::Mcts => win_probability_with(&Mcts::default(), &state, samples),
+    }?;
+
+    println!(
+        "Over {samples} rollouts, from the {} Blind of ante {}:",
+        args.blind, args.ante
+    );
+    println!("Clears the blind: {:>6.2}%", odds.blind * 100.0);
+    println!("Clears the ante:  {:>6.2}%", odds.ante * 100.0);
+
+    Ok(())
+}
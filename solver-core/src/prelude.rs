@@ -1,4 +1,47 @@
-pub use crate::solver::cards::{Card, CardCollection, CardView, Deck, Hand, HandKind, Rank, Suit};
-pub use crate::solver::error::{Error, Result};
-pub use crate::solver::hand_evaluator::{HandEvaluator, Options};
-pub use crate::solver::scorer::Scorer;
+pub use crate::solver::advisor::{advise, Advisor, Recommendation};
+pub use crate::solver::beam::{BeamPlan, BeamSearch, Decision};
+pub use crate::solver::blind::Blind;
+pub use crate::solver::boss::BossBlind;
+pub use crate::solver::cards::{
+    Card, CardCollection, CardView, Deck, Edition, Enhancement, Hand, HandKind, Rank, Seal, Suit,
+};
+pub use crate::solver::combinatorics::{draw_classes, kind_probabilities, DrawClass};
+pub use crate::solver::consumable::{Consumable, Tarot};
+pub use crate::solver::consumable_planner::{ConsumablePlanner, ConsumableUse};
+pub use crate::solver::deck_tracker::DeckTracker;
+pub use crate::solver::determinize::Determinizer;
+pub use crate::solver::economy::{CashOut, EconomyRules};
+pub use crate::solver::error::{Error, Result};
+pub use crate::solver::game_state::GameState;
+pub use crate::solver::hand_evaluator::{HandEvaluator, Options};
+pub use crate::solver::hand_levels::HandLevels;
+pub use crate::solver::heuristic::{Heuristic, WeightedHeuristic};
+pub use crate::solver::importance_sampler::ImportanceSampler;
+pub use crate::solver::joker::registry::JokerId;
+pub use crate::solver::joker::{Joker, JokerSlot, JokerState, Rarity, Stickers};
+pub use crate::solver::mcts::{ActionNode, DecisionNode, Mcts, MctsResult, SearchTree};
+pub use crate::solver::observer::Observer;
+pub use crate::solver::outs::{kind_odds, outs, Outs};
+pub use crate::solver::planner::{Plan, RoundPlanner};
+pub use crate::solver::rollout::{win_probability, win_probability_with, WinProbability};
+pub use crate::solver::round::{Action, Round, RoundOutcome};
+pub use crate::solver::ruleset::Ruleset;
+pub use crate::solver::run::{RunSimulator, RunSummary};
+pub use crate::solver::score::Score;
+pub use crate::solver::scorer::{
+    AppliedEffect, Chance, ChancePath, Effect, EffectSource, Randomness, ScoreBreakdown, ScoreStep,
+    Scorer, ScoringContext, ScoringOptions,
+};
+pub use crate::solver::shop::{BoosterPack, OpenPack, PackKind, PackSize, Shop, ShopItem};
+pub use crate::solver::shop_optimizer::{RankedOption, ShopOptimizer, ShopOption, Valuation};
+pub use crate::solver::stake::Stake;
+pub use crate::solver::starting_deck::StartingDeck;
+pub use crate::solver::strategy::flush_hunter::FlushHunter;
+pub use crate::solver::strategy::greedy::Greedy;
+pub use crate::solver::strategy::random::RandomPlayer;
+pub use crate::solver::strategy::shopper::Shopper;
+pub use crate::solver::strategy::Strategy;
+pub use crate::solver::tag::Tag;
+pub use crate::solver::target::{TargetPlan, TargetPlanner};
+pub use crate::solver::transposition::TranspositionTable;
+pub use crate::solver::voucher::Voucher;
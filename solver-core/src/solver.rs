@@ -1,5 +1,42 @@
-pub mod cards;
-pub mod cardset;
-pub mod error;
-pub mod hand_evaluator;
-pub mod scorer;
+pub mod advisor;
+pub mod beam;
+pub mod bindings;
+pub mod blind;
+pub mod boss;
+pub mod cards;
+pub mod cardset;
+pub mod combinatorics;
+pub mod consumable;
+pub mod consumable_planner;
+pub mod deck_tracker;
+pub mod determinize;
+pub mod economy;
+pub mod error;
+pub mod game_state;
+pub mod hand_evaluator;
+pub mod hand_levels;
+pub mod heuristic;
+pub mod importance_sampler;
+pub mod joker;
+pub mod mcts;
+pub mod observer;
+pub mod outs;
+pub mod planner;
+pub mod rollout;
+pub mod round;
+pub mod ruleset;
+pub mod run;
+pub mod score;
+pub mod scorer;
+pub mod shop;
+pub mod shop_optimizer;
+pub mod stake;
+pub mod starting_deck;
+pub mod strategy;
+pub mod tag;
+pub mod target;
+pub mod transposition;
+pub mod voucher;
+
+pub use advisor::advise;
+pub use rollout::win_probability;
@@ -0,0 +1,488 @@
+//! Deciding what to do with a dealt hand: which cards to play, or whether
+//! discarding some of them is likely to lead to a better play.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::solver::cards::{Card, CardCollection, CardView, Hand};
+use crate::solver::deck_tracker::DeckTracker;
+use crate::solver::game_state::GameState;
+use crate::solver::hand_evaluator::{HandEvaluator, Options};
+use crate::solver::round::Action;
+use crate::solver::score::Score;
+use crate::solver::scorer::{Randomness, ScoringContext, ScoringOptions};
+use crate::solver::transposition::TranspositionTable;
+
+/// One of the actions `Advisor::recommend` suggests.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recommendation {
+    pub action: Action,
+    /// The score of a play, or the expected score of a discard
+    pub expected: f64,
+    /// How much less this is expected to score than the best action
+    pub margin: f64,
+}
+
+/// Recommends the action that maximizes the expected score of the next hand
+/// played. Every possible play is scored exactly; every possible discard is
+/// scored by what the best hand afterwards would score on average, over every
+/// possible draw of replacements when there are few enough of them, or over
+/// a sample of draws otherwise.
+///
+/// Draws are sampled from a fixed seed, so the same situation always gets
+/// the same advice.
+#[derive(Clone, Debug)]
+pub struct Advisor {
+    pub evaluator_options: Options,
+    pub scoring_options: ScoringOptions,
+    /// How many draws to try for each possible discard, when they aren't all
+    /// tried
+    pub samples: usize,
+    /// The most possible draws a discard can have for all of them to be tried
+    pub exact_limit: usize,
+    /// The most combinations of chance outcomes `play_outcomes` goes through
+    /// before settling for the expected score
+    pub outcome_limit: usize,
+    /// Remembers best hands and discard EVs, if set
+    pub table: Option<Arc<TranspositionTable>>,
+}
+
+impl Advisor {
+    pub const DEFAULT_SAMPLES: usize = 32;
+    pub const DEFAULT_EXACT_LIMIT: usize = 256;
+    pub const DEFAULT_OUTCOME_LIMIT: usize = 64;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_evaluator_options(self, evaluator_options: Options) -> Self {
+        Self {
+            evaluator_options,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_scoring_options(self, scoring_options: ScoringOptions) -> Self {
+        Self {
+            scoring_options,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_samples(self, samples: usize) -> Self {
+        Self { samples, ..self }
+    }
+
+    #[must_use]
+    pub fn with_exact_limit(self, exact_limit: usize) -> Self {
+        Self {
+            exact_limit,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_outcome_limit(self, outcome_limit: usize) -> Self {
+        Self {
+            outcome_limit,
+            ..self
+        }
+    }
+
+    /// Remember best hands and discard EVs in `table`. See
+    /// `TranspositionTable` for which states can share one.
+    #[must_use]
+    pub fn with_table(self, table: Arc<TranspositionTable>) -> Self {
+        Self {
+            table: Some(table),
+            ..self
+        }
+    }
+
+    /// The best thing to do with `hand`, given the cards that might be drawn
+    /// and the hands, discards and jokers in `state`. Discarding is only
+    /// advised when it's expected to beat the best play available now.
+    pub fn advise(&self, hand: &[Card], tracker: &DeckTracker, state: &GameState) -> Action {
+        self.recommend(hand, tracker, state, 1)
+            .into_iter()
+            .next()
+            .map_or(Action::Play(Vec::new()), |recommendation| {
+                recommendation.action
+            })
+    }
+
+    /// The `k` best things to do with `hand`, best first, each with its
+    /// expected score and how far that falls short of the best. Plays are
+    /// ranked by their score and discards by `discard_ev`. Of actions that are
+    /// expected to score the same, plays come before discards, and fewer cards
+    /// before more.
+    pub fn recommend(
+        &self,
+        hand: &[Card],
+        tracker: &DeckTracker,
+        state: &GameState,
+        k: usize,
+    ) -> Vec<Recommendation> {
+        let mut candidates = self
+            .plays(hand, state)
+            .into_iter()
+            .map(|(indices, score)| (Action::Play(indices), score.value()))
+            .collect_vec();
+        if state.discards > 0 && tracker.count() > 0 {
+            candidates.extend(selections(hand.len()).map(|discard| {
+                let expected = self.discard_ev(hand, &discard, tracker, state);
+                (Action::Discard(discard), expected)
+            }));
+        }
+        // The sort is stable, so ties keep the order they were listed in
+        candidates.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        let best = candidates.first().map_or(0.0, |(_, expected)| *expected);
+        candidates
+            .into_iter()
+            .take(k)
+            .map(|(action, expected)| Recommendation {
+                action,
+                expected,
+                margin: best - expected,
+            })
+            .collect()
+    }
+
+    /// The highest-scoring selection of cards to play from `hand`, by index,
+    /// and what it scores. Every selection of up to five cards is tried, and
+    /// cards that wouldn't score are left in hand.
+    pub fn best_play(&self, hand: &[Card], state: &GameState) -> (Vec<usize>, Score) {
+        self.plays(hand, state)
+            .into_iter()
+            .next()
+            .unwrap_or((Vec::new(), Score::ZERO))
+    }
+
+    /// Every selection of up to five cards to play from `hand`, by index, with
+    /// what it scores, best first. Of plays that score the same, the one with
+    /// the fewest cards comes first.
+    pub fn plays(&self, hand: &[Card], state: &GameState) -> Vec<(Vec<usize>, Score)> {
+        let mut plays = selections(hand.len())
+            .map(|indices| {
+                let score = self.score_play(hand, &indices, state);
+                (indices, score)
+            })
+            .collect_vec();
+        plays.sort_by(|(a_indices, a), (b_indices, b)| {
+            b.value()
+                .total_cmp(&a.value())
+                .then(a_indices.len().cmp(&b_indices.len()))
+        });
+        plays
+    }
+
+    /// Every score playing the cards at `indices` from `hand` can get, with its
+    /// probability, lowest first. Lucky cards and the like make for more than
+    /// one.
+    pub fn play_outcomes(
+        &self,
+        hand: &[Card],
+        indices: &[usize],
+        state: &GameState,
+    ) -> Vec<(Score, f64)> {
+        self.preview_play(hand, indices, state, |context| {
+            state.preview_outcomes(context, self.outcome_limit)
+        })
+        .unwrap_or_else(|| vec![(Score::ZERO, 1.0)])
+    }
+
+    /// The expected score of the best hand left after discarding the cards
+    /// at `discard` and drawing as many replacements. See `is_exact` for
+    /// whether this is exact or estimated.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn discard_ev(
+        &self,
+        hand: &[Card],
+        discard: &[usize],
+        tracker: &DeckTracker,
+        state: &GameState,
+    ) -> f64 {
+        let kept = hand
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !discard.contains(index))
+            .map(|(_, card)| *card)
+            .collect_vec();
+        let score = |drawn: &[Card]| {
+            let mut cards = kept.clone();
+            cards.extend_from_slice(drawn);
+            self.score_best(&cards, state).value()
+        };
+
+        let replacements = discard.len().min(tracker.count());
+        let expected = || self.estimate(score, tracker, replacements);
+        match &self.table {
+            Some(table) => table.discard_ev(
+                &kept,
+                tracker,
+                replacements,
+                state,
+                self.evaluator_options,
+                self.options(state),
+                expected,
+            ),
+            None => expected(),
+        }
+    }
+
+    /// The average of `score` over draws of `replacements` unseen cards.
+    #[allow(clippy::cast_precision_loss)]
+    fn estimate(
+        &self,
+        score: impl Fn(&[Card]) -> f64,
+        tracker: &DeckTracker,
+        replacements: usize,
+    ) -> f64 {
+        if self.is_exact(tracker.count(), replacements) {
+            // Each unseen card is as likely to be drawn as any other, so every
+            // combination of them is an equally likely draw
+            let (total, draws) = tracker
+                .view()
+                .iter()
+                .copied()
+                .combinations(replacements)
+                .fold((0.0, 0_usize), |(total, draws), drawn| {
+                    (total + score(&drawn), draws + 1)
+                });
+            return total / draws as f64;
+        }
+
+        // Every discard sees the same sequence of draws, so they're compared
+        // on equal footing
+        let samples = self.samples.max(1);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let total: f64 = (0..samples)
+            .map(|_| score(&tracker.sample(replacements, &mut rng)))
+            .sum();
+        total / samples as f64
+    }
+
+    /// Whether `discard_ev` tries every possible draw of `drawn` cards from
+    /// `unseen` of them, rather than sampling.
+    pub fn is_exact(&self, unseen: usize, drawn: usize) -> bool {
+        binomial(unseen, drawn).is_some_and(|draws| draws <= self.exact_limit)
+    }
+
+    pub(crate) fn score_play(&self, hand: &[Card], indices: &[usize], state: &GameState) -> Score {
+        self.preview_play(hand, indices, state, |context| {
+            state.preview_hand(context, Randomness::Expected)
+        })
+        .unwrap_or(Score::ZERO)
+    }
+
+    /// `preview` the play of the cards at `indices` from `hand`, if they make
+    /// a hand.
+    fn preview_play<T>(
+        &self,
+        hand: &[Card],
+        indices: &[usize],
+        state: &GameState,
+        preview: impl FnOnce(ScoringContext) -> T,
+    ) -> Option<T> {
+        let played = indices.iter().map(|&index| hand[index]).collect_vec();
+        let held = hand
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !indices.contains(index))
+            .map(|(_, card)| *card)
+            .collect_vec();
+        let (kind, scoring, _) = Hand::from_slice(&played).ok().and_then(|played| {
+            HandEvaluator::evaluate_played_hand(played, self.evaluator_options)
+        })?;
+        Some(preview(
+            ScoringContext::new(kind, &scoring)
+                .with_all_played(&played)
+                .with_held(&held)
+                .with_options(self.options(state)),
+        ))
+    }
+
+    /// What the best poker hand in `cards` scores, holding the rest. This is
+    /// much cheaper than `best_play`, and only differs from it when jokers or
+    /// held cards favour a weaker hand.
+    fn score_best(&self, cards: &[Card], state: &GameState) -> Score {
+        let best = match &self.table {
+            Some(table) => table.find_best_poker_hand(cards, self.evaluator_options),
+            None => HandEvaluator::find_best_poker_hand(
+                CardCollection::from(cards),
+                self.evaluator_options,
+            ),
+        };
+        let Some((kind, scoring)) = best else {
+            return Score::ZERO;
+        };
+        let mut held = cards.to_vec();
+        for card in scoring.view() {
+            if let Some(index) = held.iter().position(|held| held == card) {
+                held.swap_remove(index);
+            }
+        }
+        state.preview_hand(
+            ScoringContext::new(kind, &scoring)
+                .with_held(&held)
+                .with_options(self.options(state)),
+            Randomness::Expected,
+        )
+    }
+
+    fn options(&self, state: &GameState) -> ScoringOptions {
+        if state.hands == 1 {
+            self.scoring_options | ScoringOptions::FinalHand
+        } else {
+            self.scoring_options
+        }
+    }
+}
+
+impl Default for Advisor {
+    fn default() -> Self {
+        Self {
+            evaluator_options: Options::empty(),
+            scoring_options: ScoringOptions::empty(),
+            samples: Self::DEFAULT_SAMPLES,
+            exact_limit: Self::DEFAULT_EXACT_LIMIT,
+            outcome_limit: Self::DEFAULT_OUTCOME_LIMIT,
+            table: None,
+        }
+    }
+}
+
+/// Every selection of one to five of `len` cards, by index.
+pub(crate) fn selections(len: usize) -> impl Iterator<Item = Vec<usize>> {
+    (1..=len.min(5)).flat_map(move |n| (0..len).combinations(n))
+}
+
+/// How many ways there are to pick `k` of `n` things, if it fits in a `usize`.
+pub(crate) fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    (0..k.min(n - k)).try_fold(1_usize, |ways, i| Some(ways.checked_mul(n - i)? / (i + 1)))
+}
+
+/// The best thing to do with `hand`, with the default `Advisor`.
+pub fn advise(hand: &[Card], tracker: &DeckTracker, state: &GameState) -> Action {
+    Advisor::default().advise(hand, tracker, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::Deck;
+
+    fn dealt(hand: &str, discards: u32) -> GameState {
+        GameState::from_parts(
+            Deck::base_deck(),
+            CardCollection::from_idents(hand).view().to_vec(),
+            4,
+            discards,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn best_play_test() {
+        let state = dealt("2S 7D 9C 9H 4S 3D KC 8H", 3);
+        let (indices, score) = Advisor::new().best_play(&state.hand, &state);
+        // Pair of nines: (10 + 9 + 9) * 2
+        assert_eq!(indices, vec![2, 3]);
+        assert_relative_eq!(score.value(), 56.0);
+    }
+
+    #[test]
+    fn advise_test() {
+        // Nothing can beat a royal flush in a standard deck, so just play it
+        let state = dealt("AS KS QS JS TS 2D 3C 4H", 3);
+        let tracker = DeckTracker::from_state(&state);
+        assert_eq!(
+            advise(&state.hand, &tracker, &state),
+            Action::Play(vec![0, 1, 2, 3, 4])
+        );
+
+        // A hand with nothing in it is worth throwing away
+        let state = dealt("2S 7D 9C JH 4S 3D KC 8H", 3);
+        let tracker = DeckTracker::from_state(&state);
+        assert!(matches!(
+            advise(&state.hand, &tracker, &state),
+            Action::Discard(_)
+        ));
+
+        // ...but not without any discards. The jack scores as much as the king
+        let state = dealt("2S 7D 9C JH 4S 3D KC 8H", 0);
+        assert_eq!(advise(&state.hand, &tracker, &state), Action::Play(vec![3]));
+    }
+
+    #[test]
+    fn recommend_test() {
+        let state = dealt("AS AD 7C", 3);
+        let tracker = DeckTracker::new(vec![Card::from_ident("AH"), Card::from_ident("3C")]);
+        let advisor = Advisor::new();
+        let top = advisor.recommend(&state.hand, &tracker, &state, 3);
+        // Fishing for the third ace, then the pair with or without the kicker
+        let expected = [
+            (Action::Discard(vec![2]), 126.5, 0.0),
+            (Action::Play(vec![0, 1]), 64.0, 62.5),
+            (Action::Play(vec![0, 1, 2]), 64.0, 62.5),
+        ];
+        assert_eq!(top.len(), expected.len());
+        for (recommendation, (action, score, margin)) in top.iter().zip(expected) {
+            assert_eq!(recommendation.action, action);
+            assert_relative_eq!(recommendation.expected, score);
+            assert_relative_eq!(recommendation.margin, margin);
+        }
+
+        // Seven plays and seven discards of three cards
+        assert_eq!(
+            advisor.recommend(&state.hand, &tracker, &state, 100).len(),
+            14
+        );
+        assert_eq!(advisor.advise(&state.hand, &tracker, &state), top[0].action);
+    }
+
+    #[test]
+    fn discard_ev_test() {
+        let state = dealt("AS AD 7C", 3);
+        let tracker = DeckTracker::new(vec![Card::from_ident("AH"), Card::from_ident("3C")]);
+        let advisor = Advisor::new();
+        assert!(advisor.is_exact(2, 1));
+        // Either three aces, (30 + 33) * 3 = 189, or still a pair,
+        // (10 + 22) * 2 = 64
+        assert_relative_eq!(
+            advisor.discard_ev(&state.hand, &[2], &tracker, &state),
+            126.5
+        );
+
+        let advisor = advisor.with_exact_limit(0);
+        assert!(!advisor.is_exact(2, 1));
+        let estimate = advisor.discard_ev(&state.hand, &[2], &tracker, &state);
+        assert!((64.0..=189.0).contains(&estimate));
+    }
+
+    #[test]
+    fn binomial_test() {
+        assert_eq!(binomial(52, 5), Some(2_598_960));
+        assert_eq!(binomial(5, 0), Some(1));
+        assert_eq!(binomial(3, 5), Some(0));
+        assert_eq!(binomial(500, 250), None);
+    }
+}
@@ -0,0 +1,253 @@
+//! Beam search over the next few blinds of a run, and the shopping and
+//! skipping between them. Much cheaper than searching every line of play, at
+//! the cost of only following the few that look best so far.
+
+use rand::RngCore;
+
+use crate::solver::blind::Blind;
+use crate::solver::game_state::GameState;
+use crate::solver::heuristic::Heuristic;
+use crate::solver::round::{Round, RoundOutcome};
+use crate::solver::shop::Shop;
+use crate::solver::strategy::{take_turn, Strategy};
+use crate::solver::tag::Tag;
+
+/// One step along a line of play.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Decision {
+    /// Bought the shop card in this slot
+    Buy(usize),
+    /// Bought and opened the pack in this slot
+    BuyPack(usize),
+    /// Redeemed the voucher in this slot
+    BuyVoucher(usize),
+    /// Skipped the blind for this tag
+    Skip(Tag),
+    /// Played the blind, and beat it
+    Play,
+}
+
+/// The best line of play found, and where it leads.
+#[derive(Clone, Debug)]
+pub struct BeamPlan {
+    pub decisions: Vec<Decision>,
+    /// The state after the last blind of the line
+    pub state: GameState,
+    /// What the heuristic made of `state`
+    pub value: f64,
+}
+
+/// Searches a blind at a time. From each line in the beam, every single
+/// purchase in the shop (or none) is tried, followed by skipping or playing
+/// the next blind; the `width` lines whose states the heuristic likes best
+/// go on to the next blind. Lines that lose a blind are dropped.
+///
+/// Blinds are played by `strategy`, once per line, so a line can get lucky or
+/// unlucky with its draws.
+pub struct BeamSearch<S, H> {
+    /// Plays the blinds, and takes what it wants from packs
+    pub strategy: S,
+    /// How good a state is, higher being better
+    pub heuristic: H,
+    /// How many lines are kept after each blind
+    pub width: usize,
+    /// How many blinds to look ahead
+    pub horizon: usize,
+}
+
+/// A line of play in the beam.
+#[derive(Clone)]
+struct Line {
+    decisions: Vec<Decision>,
+    state: GameState,
+    /// The shop after the last blind beaten, until it's left
+    shop: Option<Shop>,
+    value: f64,
+}
+
+impl<S: Strategy, H: Heuristic> BeamSearch<S, H> {
+    pub const DEFAULT_WIDTH: usize = 4;
+    pub const DEFAULT_HORIZON: usize = 3;
+
+    pub fn new(strategy: S, heuristic: H) -> Self {
+        Self {
+            strategy,
+            heuristic,
+            width: Self::DEFAULT_WIDTH,
+            horizon: Self::DEFAULT_HORIZON,
+        }
+    }
+
+    #[must_use]
+    pub fn with_width(self, width: usize) -> Self {
+        Self { width, ..self }
+    }
+
+    #[must_use]
+    pub fn with_horizon(self, horizon: usize) -> Self {
+        Self { horizon, ..self }
+    }
+
+    /// Search from `state`, before its current blind. The plan found stops
+    /// early if every line loses, and is `None` if even the first blind
+    /// can't be beaten.
+    pub fn plan(&mut self, state: &GameState, rng: &mut dyn RngCore) -> Option<BeamPlan> {
+        let mut beam = vec![Line {
+            decisions: Vec::new(),
+            state: state.clone(),
+            shop: None,
+            value: self.heuristic.evaluate(state),
+        }];
+        for step in 0..self.horizon {
+            let mut next = Vec::new();
+            for line in &beam {
+                self.expand(line, rng, &mut next);
+            }
+            if next.is_empty() {
+                if step == 0 {
+                    return None;
+                }
+                break;
+            }
+            next.sort_by(|a, b| b.value.total_cmp(&a.value));
+            next.truncate(self.width.max(1));
+            beam = next;
+        }
+
+        beam.into_iter().next().map(|line| BeamPlan {
+            decisions: line.decisions,
+            state: line.state,
+            value: line.value,
+        })
+    }
+
+    /// Push every way `line` can get through its next blind onto `next`.
+    fn expand(&mut self, line: &Line, rng: &mut dyn RngCore, next: &mut Vec<Line>) {
+        for (mut decisions, mut state) in self.purchases(line, rng) {
+            if line.shop.is_some() {
+                state.advance_blind();
+                if state.blind == Blind::Small {
+                    state.restock_voucher(rng);
+                }
+            }
+
+            if state.blind != Blind::Boss {
+                let tag = Tag::random(state.ante, rng);
+                let mut skipped = state.clone();
+                if let Ok(packs) = skipped.skip_blind(tag, rng) {
+                    for mut pack in packs {
+                        self.strategy.open_pack(&mut pack, &mut skipped, rng);
+                    }
+                    let mut decisions = decisions.clone();
+                    decisions.push(Decision::Skip(tag));
+                    next.push(self.line(decisions, skipped, None));
+                }
+            }
+
+            let mut round = Round::start(state, rng);
+            while !round.is_over() {
+                if take_turn(&mut self.strategy, &mut round, rng).is_err() {
+                    break;
+                }
+            }
+            if round.outcome() == Some(RoundOutcome::Won) {
+                let mut state = round.into_state();
+                state.cash_out();
+                let shop = Shop::enter(&mut state, rng);
+                decisions.push(Decision::Play);
+                next.push(self.line(decisions, state, Some(shop)));
+            }
+        }
+    }
+
+    /// Every way to leave `line`'s shop having bought at most one thing.
+    fn purchases(&mut self, line: &Line, rng: &mut dyn RngCore) -> Vec<(Vec<Decision>, GameState)> {
+        let mut purchases = vec![(line.decisions.clone(), line.state.clone())];
+        let Some(shop) = &line.shop else {
+            return purchases;
+        };
+
+        let mut buy = |decision: Decision, purchases: &mut Vec<_>| {
+            let mut state = line.state.clone();
+            let mut shop = shop.clone();
+            let bought = match decision {
+                Decision::Buy(index) => shop.buy(index, &mut state).is_ok(),
+                Decision::BuyVoucher(index) => shop.buy_voucher(index, &mut state).is_ok(),
+                Decision::BuyPack(index) => match shop.buy_pack(index, &mut state, rng) {
+                    Ok(mut pack) => {
+                        self.strategy.open_pack(&mut pack, &mut state, rng);
+                        true
+                    }
+                    Err(_) => false,
+                },
+                Decision::Skip(_) | Decision::Play => unreachable!(),
+            };
+            if bought {
+                let mut decisions = line.decisions.clone();
+                decisions.push(decision);
+                purchases.push((decisions, state));
+            }
+        };
+        for index in 0..shop.cards.len() {
+            buy(Decision::Buy(index), &mut purchases);
+        }
+        for index in 0..shop.packs.len() {
+            buy(Decision::BuyPack(index), &mut purchases);
+        }
+        for index in 0..shop.vouchers.len() {
+            buy(Decision::BuyVoucher(index), &mut purchases);
+        }
+        purchases
+    }
+
+    fn line(&self, decisions: Vec<Decision>, state: GameState, shop: Option<Shop>) -> Line {
+        Line {
+            decisions,
+            value: self.heuristic.evaluate(&state),
+            state,
+            shop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::advisor::Advisor;
+    use crate::solver::round::Action;
+
+    /// Plays the best hand every time.
+    struct BestPlay;
+
+    impl Strategy for BestPlay {
+        fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+            Action::Play(Advisor::new().best_play(&round.state.hand, &round.state).0)
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn plan_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let state = GameState::fresh_run(&mut rng);
+        let money = |state: &GameState| state.money as f64;
+        let plan = BeamSearch::new(BestPlay, money)
+            .with_width(2)
+            .with_horizon(2)
+            .plan(&state, &mut rng)
+            .unwrap();
+
+        // Each blind was either skipped or beaten, with purchases in between
+        let blinds = plan
+            .decisions
+            .iter()
+            .filter(|decision| matches!(decision, Decision::Skip(_) | Decision::Play))
+            .count();
+        assert_eq!(blinds, 2);
+        assert!(plan.value >= money(&state));
+        assert!((plan.value - money(&plan.state)).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,283 @@
+//! The calls every language binding exposes: evaluating, scoring and advising
+//! on cards given as text, the way people type them. Cards are their idents
+//! separated by whitespace, like `"AS KD+glass 7C"`, and jokers are their
+//! names. With the `serde` feature, `AdviseRequest` and `Advice` are plain
+//! JSON, with cards as their idents.
+
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::solver::advisor::Advisor;
+use crate::solver::blind::Blind;
+use crate::solver::cards::{Card, CardCollection, CardView, Deck, Hand, HandKind};
+use crate::solver::deck_tracker::DeckTracker;
+use crate::solver::error::{Error, Result};
+use crate::solver::game_state::GameState;
+use crate::solver::hand_evaluator::{HandEvaluator, Options};
+use crate::solver::joker::registry::JokerId;
+use crate::solver::joker::JokerSlot;
+use crate::solver::round::Action;
+use crate::solver::score::Score;
+use crate::solver::scorer::{Scorer, ScoringContext};
+use crate::solver::transposition::TranspositionTable;
+
+/// A hand to be advised on, and what's known about the round. Only the hand
+/// is required; the rest default to the start of a round with no jokers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct AdviseRequest {
+    pub hand: Vec<Card>,
+    /// The jokers held, by name
+    pub jokers: Vec<String>,
+    /// How many hands are left this round, counting the next one
+    pub hands: u32,
+    /// How many discards are left this round
+    pub discards: u32,
+    /// Cards already played or discarded this round, which can't be drawn
+    pub seen: Vec<Card>,
+    /// How many draws to sample for each discard, when there are too many to
+    /// try them all
+    pub samples: usize,
+}
+
+impl Default for AdviseRequest {
+    fn default() -> Self {
+        Self {
+            hand: Vec::new(),
+            jokers: Vec::new(),
+            hands: 4,
+            discards: 3,
+            seen: Vec::new(),
+            samples: 200,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Move {
+    Play,
+    Discard,
+}
+
+/// The best thing to do with an `AdviseRequest`'s hand.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Advice {
+    pub action: Move,
+    /// The cards to play or discard, in order
+    pub cards: Vec<Card>,
+    /// The score of a play, or the expected score of a discard
+    pub expected: f64,
+}
+
+#[cfg(feature = "serde")]
+impl Advice {
+    /// The advice as JSON, like
+    /// `{"action":"discard","cards":["2C","7D"],"expected":312.5}`.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("advice is always valid JSON")
+    }
+}
+
+/// Cards from their idents, separated by whitespace.
+pub fn parse_cards(idents: &str) -> Result<Vec<Card>> {
+    idents.split_whitespace().map(str::parse).collect()
+}
+
+/// Jokers from their names, ignoring blank ones, so a list split on commas
+/// can have a trailing comma.
+pub fn parse_jokers<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<Vec<JokerSlot>> {
+    names
+        .into_iter()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let id: JokerId = name
+                .parse()
+                .map_err(|_| Error::UnknownJoker(name.to_owned()))?;
+            Ok(JokerSlot::new(id.joker()))
+        })
+        .collect()
+}
+
+/// Jokers from their names separated by commas, like `"Joker, Greedy Joker"`.
+pub fn parse_joker_list(names: &str) -> Result<Vec<JokerSlot>> {
+    parse_jokers(names.split(','))
+}
+
+/// The kind of poker hand one to five played cards make, the cards that
+/// score, and the ones that don't.
+pub fn evaluate(cards: &[Card]) -> Result<(HandKind, Hand, Hand)> {
+    if !(1..=5).contains(&cards.len()) {
+        return Err(Error::PlayedHandSize(cards.len()));
+    }
+    HandEvaluator::evaluate_played_hand(CardCollection::from(cards), Options::empty())
+        .ok_or(Error::NotAHand)
+}
+
+/// What one to five played cards score with `jokers` held.
+pub fn score(cards: &[Card], jokers: &[JokerSlot]) -> Result<Score> {
+    let (kind, scoring, _) = evaluate(cards)?;
+    let context = ScoringContext::new(kind, &scoring)
+        .with_all_played(cards)
+        .with_jokers(jokers);
+    Ok(Scorer::score_hand(&context))
+}
+
+/// What `Advisor` recommends for the request's hand, drawing from a standard
+/// deck less the hand and the cards seen.
+pub fn advise(request: &AdviseRequest) -> Result<Advice> {
+    if request.hand.is_empty() {
+        return Err(Error::EmptyHand);
+    }
+    if request.hands == 0 {
+        return Err(Error::NoHandsLeft);
+    }
+    if let Some((_, card)) = request
+        .hand
+        .iter()
+        .enumerate()
+        .find(|(index, card)| request.hand[..*index].contains(card))
+    {
+        return Err(Error::DuplicateCard(*card));
+    }
+    let jokers = parse_jokers(request.jokers.iter().map(String::as_str))?;
+    let state = GameState::from_parts(
+        Deck::base_deck(),
+        request.hand.clone(),
+        request.hands,
+        request.discards,
+        0,
+        1,
+        Blind::Small,
+        jokers,
+        Vec::new(),
+    );
+    let mut tracker = DeckTracker::from_state(&state);
+    for card in &request.seen {
+        if !tracker.see(*card) {
+            return Err(Error::SeenCardNotInDeck(*card));
+        }
+    }
+
+    // Many discards leave the same cards in hand, and draw the same ones
+    let advisor = Advisor::new()
+        .with_samples(request.samples)
+        .with_table(Arc::new(TranspositionTable::new()));
+    let recommendation = advisor
+        .recommend(&request.hand, &tracker, &state, 1)
+        .into_iter()
+        .next()
+        .ok_or(Error::EmptyHand)?;
+    let (action, indices) = match recommendation.action {
+        Action::Play(indices) => (Move::Play, indices),
+        Action::Discard(indices) => (Move::Discard, indices),
+    };
+    Ok(Advice {
+        action,
+        cards: indices.iter().map(|index| request.hand[*index]).collect(),
+        expected: recommendation.expected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn evaluate_test() {
+        let cards = parse_cards("AS AH AD KS KH").unwrap();
+        assert_eq!(evaluate(&cards).unwrap().0, HandKind::FullHouse);
+        let cards = parse_cards("AS AH AD KS KH 2C").unwrap();
+        assert!(matches!(evaluate(&cards), Err(Error::PlayedHandSize(6))));
+        assert!(matches!(parse_cards("AS ZZ"), Err(Error::InvalidCard(_))));
+    }
+
+    #[test]
+    fn score_test() {
+        let cards = parse_cards("AS AH").unwrap();
+        // (10 + 11 + 11) chips * 2 mult
+        assert_relative_eq!(score(&cards, &[]).unwrap().value(), 64.0);
+        let jokers = parse_joker_list("Joker,").unwrap();
+        assert_relative_eq!(score(&cards, &jokers).unwrap().value(), 192.0);
+
+        // A Stone card adds its 50 chips to the pair, whatever its rank
+        for played in ["AS AH AD+stone", "AS AH KD+stone"] {
+            let cards = parse_cards(played).unwrap();
+            assert_eq!(evaluate(&cards).unwrap().0, HandKind::Pair);
+            // (10 + 11 + 11 + 50) chips * 2 mult
+            assert_relative_eq!(score(&cards, &[]).unwrap().value(), 164.0);
+        }
+        // The Wild ten completes a royal flush: (100 + 11 + 10 * 4) * 8
+        let cards = parse_cards("AS KS QS JS TH+wild").unwrap();
+        assert_eq!(evaluate(&cards).unwrap().0, HandKind::StraightFlush);
+        assert_relative_eq!(score(&cards, &[]).unwrap().value(), 1208.0);
+        assert!(matches!(
+            parse_jokers(["Not A Joker"]),
+            Err(Error::UnknownJoker(_))
+        ));
+    }
+
+    #[test]
+    fn advise_test() {
+        let request = AdviseRequest {
+            hand: parse_cards("AS KS QS JS TS 2C 3D 4H").unwrap(),
+            ..AdviseRequest::default()
+        };
+        let advice = advise(&request).unwrap();
+        assert_eq!(advice.action, Move::Play);
+        assert_eq!(advice.cards, parse_cards("AS KS QS JS TS").unwrap());
+        // (100 + 11 + 10 * 4) chips * 8 mult
+        assert_relative_eq!(advice.expected, 1208.0);
+
+        let request = AdviseRequest {
+            seen: parse_cards("AS").unwrap(),
+            ..request
+        };
+        assert!(matches!(advise(&request), Err(Error::SeenCardNotInDeck(_))));
+
+        let request = AdviseRequest {
+            hand: parse_cards("AS AS KD").unwrap(),
+            ..AdviseRequest::default()
+        };
+        assert!(matches!(advise(&request), Err(Error::DuplicateCard(_))));
+        let request = AdviseRequest {
+            hand: parse_cards("AS KD").unwrap(),
+            hands: 0,
+            ..AdviseRequest::default()
+        };
+        assert!(matches!(advise(&request), Err(Error::NoHandsLeft)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let request: AdviseRequest = serde_json::from_str(r#"{"hand": ["AS", "KD"]}"#).unwrap();
+        assert_eq!(request.hand, parse_cards("AS KD").unwrap());
+        assert_eq!(request.discards, 3);
+        assert!(serde_json::from_str::<AdviseRequest>(r#"{"stake": "gold"}"#).is_err());
+
+        let advice = Advice {
+            action: Move::Discard,
+            cards: parse_cards("2C 7D").unwrap(),
+            expected: 312.5,
+        };
+        assert_eq!(
+            advice.to_json(),
+            r#"{"action":"discard","cards":["2C","7D"],"expected":312.5}"#
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::solver::score::Score;
+use crate::solver::stake::Stake;
+
+/// The base chip requirement of antes 1 through 8, before the blind's own
+/// multiplier.
+const ANTE_BASE_CHIPS: [f64; 8] = [
+    300.0, 800.0, 2_000.0, 5_000.0, 11_000.0, 20_000.0, 35_000.0, 50_000.0,
+];
+
+/// The three blinds of every ante, in the order they're played.
+#[derive(
+    Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Display, EnumIter, EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Blind {
+    #[default]
+    Small,
+    Big,
+    Boss,
+}
+
+impl Blind {
+    /// How much the ante's base chips are multiplied by for this blind.
+    pub fn chip_multiplier(self) -> f64 {
+        match self {
+            Self::Small => 1.0,
+            Self::Big => 1.5,
+            Self::Boss => 2.0,
+        }
+    }
+
+    /// The money paid out for beating this blind, not counting interest or
+    /// leftover hands.
+    pub fn reward(self) -> u32 {
+        match self {
+            Self::Small => 3,
+            Self::Big => 4,
+            Self::Boss => 5,
+        }
+    }
+
+    /// The score needed to beat this blind in the given ante, at White
+    /// Stake.
+    pub fn target(self, ante: u32) -> Score {
+        self.target_at(ante, Stake::White)
+    }
+
+    /// The score needed to beat this blind in the given ante and stake.
+    pub fn target_at(self, ante: u32, stake: Stake) -> Score {
+        Score::from(stake.ante_base_chips(ante) * self.chip_multiplier())
+    }
+
+    /// The blind that comes after this one, and whether it starts a new ante.
+    pub fn next(self) -> (Self, bool) {
+        match self {
+            Self::Small => (Self::Big, false),
+            Self::Big => (Self::Boss, false),
+            Self::Boss => (Self::Small, true),
+        }
+    }
+}
+
+/// The base chip requirement of an ante, at White Stake. Past ante 8 this
+/// grows faster than exponentially, and is rounded down to its two leading
+/// digits the same way Balatro does it.
+pub fn ante_base_chips(ante: u32) -> f64 {
+    scaled_base_chips(&ANTE_BASE_CHIPS, ante)
+}
+
+/// The base chip requirement of an ante, given the requirements of antes 1
+/// through 8.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub(crate) fn scaled_base_chips(amounts: &[f64; 8], ante: u32) -> f64 {
+    match ante {
+        0 => 100.0,
+        1..=8 => amounts[ante as usize - 1],
+        _ => {
+            let extra = f64::from(ante - 8);
+            let growth = 1.6 + (0.75 * extra).powf(1.0 + 0.2 * extra);
+            let amount = (amounts[7] * growth.powf(extra)).floor();
+            if !amount.is_finite() {
+                return amount;
+            }
+            let precision = 10_f64.powi(amount.log10().floor() as i32 - 1);
+            amount - amount % precision
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn target_test() {
+        assert_relative_eq!(Blind::Small.target(1).value(), 300.0);
+        assert_relative_eq!(Blind::Big.target(1).value(), 450.0);
+        assert_relative_eq!(Blind::Boss.target(1).value(), 600.0);
+        assert_relative_eq!(Blind::Boss.target(8).value(), 100_000.0);
+        assert_relative_eq!(Blind::Small.target(9).value(), 110_000.0);
+        assert_relative_eq!(Blind::Small.target(10).value(), 560_000.0);
+        assert_relative_eq!(Blind::Small.target(11).value(), 7_200_000.0);
+        assert!(Blind::Small.target(100).is_naneinf());
+    }
+
+    #[test]
+    fn next_test() {
+        assert_eq!(Blind::Small.next(), (Blind::Big, false));
+        assert_eq!(Blind::Boss.next(), (Blind::Small, true));
+    }
+}
@@ -0,0 +1,94 @@
+//! Boss Blinds whose rules change what a hand scores, or which hand can be
+//! played. Bosses that only matter over a whole round, like The Needle or
+//! The Water, aren't modeled.
+
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::solver::cards::{Card, Suit};
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Display, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum BossBlind {
+    /// All Clubs are debuffed
+    #[strum(to_string = "The Club", serialize = "Club")]
+    Club,
+    /// All Spades are debuffed
+    #[strum(to_string = "The Goad", serialize = "Goad")]
+    Goad,
+    /// All Hearts are debuffed
+    #[strum(to_string = "The Head", serialize = "Head")]
+    Head,
+    /// All Diamonds are debuffed
+    #[strum(to_string = "The Window", serialize = "Window")]
+    Window,
+    /// All face cards are debuffed
+    #[strum(to_string = "The Plant", serialize = "Plant")]
+    Plant,
+    /// Base chips and mult are halved
+    #[strum(to_string = "The Flint", serialize = "Flint")]
+    Flint,
+    /// -1 hand size
+    #[strum(to_string = "The Manacle", serialize = "Manacle")]
+    Manacle,
+    /// The first hand is drawn face down
+    #[strum(to_string = "The House", serialize = "House")]
+    House,
+}
+
+impl BossBlind {
+    /// Whether `card` is debuffed, so it scores nothing and triggers nothing
+    /// when it's played or held, though it still counts towards the hand.
+    /// `is_face` is whether it counts as a face card, which Pareidolia can
+    /// make true of any card.
+    pub fn debuffs(self, card: Card, is_face: bool) -> bool {
+        match self {
+            Self::Club => card.suit == Suit::Clubs,
+            Self::Goad => card.suit == Suit::Spades,
+            Self::Head => card.suit == Suit::Hearts,
+            Self::Window => card.suit == Suit::Diamonds,
+            Self::Plant => is_face,
+            Self::Flint | Self::Manacle | Self::House => false,
+        }
+    }
+
+    /// Whether the hand's base chips and mult are halved, rounding to the
+    /// nearest, with at least 1 mult.
+    pub fn halves_base(self) -> bool {
+        self == Self::Flint
+    }
+
+    /// How many fewer cards are held in hand.
+    pub fn hand_size_penalty(self) -> usize {
+        usize::from(self == Self::Manacle)
+    }
+
+    /// Whether the first hand of the round is drawn face down, so it can't
+    /// be chosen by what the cards are.
+    pub fn first_hand_face_down(self) -> bool {
+        self == Self::House
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card;
+
+    #[test]
+    fn parse_test() {
+        assert_eq!("the club".parse::<BossBlind>().unwrap(), BossBlind::Club);
+        assert_eq!("flint".parse::<BossBlind>().unwrap(), BossBlind::Flint);
+        assert_eq!(BossBlind::Window.to_string(), "The Window");
+    }
+
+    #[test]
+    fn debuffs_test() {
+        assert!(BossBlind::Club.debuffs(card!("AC"), false));
+        assert!(!BossBlind::Club.debuffs(card!("AS"), false));
+        assert!(BossBlind::Plant.debuffs(card!("KD"), true));
+        assert!(!BossBlind::Plant.debuffs(card!("AD"), false));
+        // Under Pareidolia every card is a face card
+        assert!(BossBlind::Plant.debuffs(card!("AD"), true));
+        assert!(!BossBlind::Flint.debuffs(card!("KD"), true));
+    }
+}
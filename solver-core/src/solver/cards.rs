@@ -1,360 +1,699 @@
-use heapless;
-use itertools::Itertools;
-use lazy_static::lazy_static;
-use rand::{
-    prelude::{Rng, SeedableRng, SliceRandom},
-    rngs::SmallRng,
-};
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-
-use crate::solver::{
-    cardset::CardSet,
-    error::{Error, Result},
-};
-
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
-#[repr(u8)]
-pub enum Suit {
-    Spades = 0,
-    Clubs = 1,
-    Hearts = 2,
-    Diamonds = 3,
-}
-
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
-#[repr(u8)]
-pub enum Rank {
-    Deuce = 0,
-    Three = 1,
-    Four = 2,
-    Five = 3,
-    Six = 4,
-    Seven = 5,
-    Eight = 6,
-    Nine = 7,
-    Ten = 8,
-    Jack = 9,
-    Queen = 10,
-    King = 11,
-    Ace = 12,
-}
-
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Card {
-    pub rank: Rank,
-    pub suit: Suit,
-    // TODO(pixlark): Card modifiers
-}
-
-impl Card {
-    /// Convert a shorthand identifier into a card. Panics if the identifier
-    /// is incorrect. This exists only for test-writing.
-    ///
-    /// ```
-    /// # use solver_core::prelude::{Card, Suit, Rank};
-    /// let a = Card::from_ident("KH");
-    /// let b = Card {
-    ///     rank: Rank::King,
-    ///     suit: Suit::Hearts,
-    /// };
-    /// assert_eq!(a, b);
-    /// ```
-    pub fn from_ident(ident: &str) -> Self {
-        assert!(ident.chars().count() == 2);
-
-        let rank = ident.chars().nth(0).unwrap();
-        let rank = match rank.to_ascii_uppercase() {
-            '2' => Rank::Deuce,
-            '3' => Rank::Three,
-            '4' => Rank::Four,
-            '5' => Rank::Five,
-            '6' => Rank::Six,
-            '7' => Rank::Seven,
-            '8' => Rank::Eight,
-            '9' => Rank::Nine,
-            'T' => Rank::Ten,
-            'J' => Rank::Jack,
-            'Q' => Rank::Queen,
-            'K' => Rank::King,
-            'A' => Rank::Ace,
-            _ => panic!(),
-        };
-
-        let suit = ident.chars().nth(1).unwrap();
-        let suit = match suit.to_ascii_uppercase() {
-            'S' => Suit::Spades,
-            'C' => Suit::Clubs,
-            'H' => Suit::Hearts,
-            'D' => Suit::Diamonds,
-            _ => panic!(),
-        };
-
-        Self { rank, suit }
-    }
-}
-
-#[macro_export]
-macro_rules! card {
-    ($ident:literal) => {
-        $crate::solver::cards::Card::from_ident($ident)
-    };
-}
-
-pub trait CardView {
-    fn view(&self) -> &[Card];
-}
-
-#[derive(Clone, Debug)]
-pub struct Deck {
-    cards: Vec<Card>,
-}
-
-lazy_static! {
-    static ref BASE_DECK_CARDS: Vec<Card> = {
-        let mut cards = Vec::with_capacity(52);
-        for suit in Suit::iter() {
-            for rank in Rank::iter() {
-                cards.push(Card { rank, suit });
-            }
-        }
-        cards
-    };
-}
-
-impl Deck {
-    pub fn base_deck() -> Self {
-        Self {
-            cards: BASE_DECK_CARDS.clone(),
-        }
-    }
-
-    pub fn shuffle(&mut self, rng: &mut impl Rng) {
-        self.cards.shuffle(rng);
-    }
-
-    pub fn shuffled(rng: &mut impl Rng) -> Self {
-        let mut deck = Self::base_deck();
-        deck.shuffle(rng);
-        deck
-    }
-
-    pub fn peek_top_card(&self) -> Option<Card> {
-        self.cards.last().copied()
-    }
-
-    pub fn draw(&mut self) -> Option<Card> {
-        if self.count() == 0 {
-            None
-        } else {
-            Some(self.cards.remove(self.count() - 1))
-        }
-    }
-
-    pub fn draw_hand(&mut self) -> Option<Hand> {
-        if self.count() < 5 {
-            None
-        } else {
-            let mut hand = Hand::empty();
-            for _ in 0..5 {
-                hand.cards.push(self.draw().unwrap()).unwrap();
-            }
-            Some(hand)
-        }
-    }
-
-    pub fn draw_n(&mut self, n: usize) -> Option<CardCollection> {
-        if self.count() < n {
-            None
-        } else {
-            let mut cards = Vec::with_capacity(n);
-            for _ in 0..n {
-                cards.push(self.draw().unwrap());
-            }
-            Some(CardCollection { cards })
-        }
-    }
-
-    pub fn count(&self) -> usize {
-        self.cards.len()
-    }
-}
-
-impl CardView for Deck {
-    fn view(&self) -> &[Card] {
-        &self.cards
-    }
-}
-
-#[derive(Clone, Debug)]
-pub struct Hand {
-    pub(crate) cards: heapless::Vec<Card, 5>,
-}
-
-impl Hand {
-    pub fn empty() -> Self {
-        Self {
-            cards: heapless::Vec::new(),
-        }
-    }
-    pub fn from_slice(cards: &[Card]) -> Result<Self> {
-        Ok(Self {
-            cards: heapless::Vec::from_slice(cards).map_err(|()| Error::OverfullHand)?,
-        })
-    }
-    /// Convert a series of shorthand identifiers into a `Hand`.
-    /// Panics if the input is incorrect. This exists only for test-writing.
-    pub fn from_idents(idents: &str) -> Self {
-        Self::from_slice(CardCollection::from_idents(idents).view()).unwrap()
-    }
-}
-
-#[macro_export]
-macro_rules! hand {
-    ($ident:literal) => {
-        $crate::solver::cards::Hand::from_idents($ident)
-    };
-}
-
-impl PartialEq for Hand {
-    fn eq(&self, other: &Self) -> bool {
-        self.cards == other.cards
-        // let mut a = self.cards.clone();
-        // a.sort();
-        // let mut b = other.cards.clone();
-        // b.sort();
-        // a == b
-    }
-}
-
-impl From<heapless::Vec<Card, 5>> for Hand {
-    fn from(value: heapless::Vec<Card, 5>) -> Self {
-        Self { cards: value }
-    }
-}
-
-impl std::iter::FromIterator<Card> for Hand {
-    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
-        Self {
-            cards: iter.into_iter().take(5).collect::<heapless::Vec<_, 5>>(),
-        }
-    }
-}
-
-impl CardView for Hand {
-    fn view(&self) -> &[Card] {
-        &self.cards
-    }
-}
-
-pub struct CardCollection {
-    cards: Vec<Card>,
-}
-
-impl CardCollection {
-    pub fn empty() -> Self {
-        Self { cards: Vec::new() }
-    }
-    /// Convert a series of shorthand identifiers into a `CardCollection`.
-    /// Panics if the input is incorrect. This exists only for test-writing.
-    ///
-    /// ```
-    /// # use solver_core::prelude::{Suit, Rank, Card, CardCollection};
-    /// let cards = CardCollection::from_idents("KH TD JS 2C");
-    /// assert_eq!(cards.nth(2), Some(Card {
-    ///     rank: Rank::Jack,
-    ///     suit: Suit::Spades,
-    /// }));
-    /// ```
-    pub fn from_idents(idents: &str) -> Self {
-        let idents = idents.split_ascii_whitespace();
-        Self::from(idents.map(Card::from_ident).collect::<Vec<_>>().as_slice())
-    }
-    pub fn nth(&self, n: usize) -> Option<Card> {
-        if n < self.cards.len() {
-            Some(self.cards[n])
-        } else {
-            None
-        }
-    }
-}
-
-#[macro_export]
-macro_rules! cards {
-    ($ident:literal) => {
-        $crate::solver::cards::CardCollection::from_idents($ident)
-    };
-}
-
-impl From<&[Card]> for CardCollection {
-    fn from(value: &[Card]) -> Self {
-        Self {
-            cards: Vec::from(value),
-        }
-    }
-}
-
-impl CardView for CardCollection {
-    fn view(&self) -> &[Card] {
-        &self.cards
-    }
-}
-
-#[derive(Copy, Clone, Debug, Hash, PartialOrd, Ord, PartialEq, Eq, EnumIter)]
-#[repr(u8)]
-pub enum HandKind {
-    HighCard = 0,
-    Pair = 1,
-    TwoPair = 2,
-    ThreeOfAKind = 3,
-    Straight = 4,
-    Flush = 5,
-    FullHouse = 6,
-    FourOfAKind = 7,
-    StraightFlush = 8,
-    FiveOfAKind = 9,
-    FlushHouse = 10,
-    FlushFive = 11,
-}
-
-#[cfg(test)]
-mod tests {
-    use std::collections::HashSet;
-
-    use super::*;
-
-    #[test]
-    fn base_deck_test() {
-        let mut seen = HashSet::new();
-        let deck = Deck::base_deck();
-        for card in deck.cards {
-            seen.insert(card);
-        }
-        assert_eq!(seen.len(), 52);
-    }
-
-    #[test]
-    fn base_shuffle_test() {
-        let mut seen = HashSet::new();
-        let mut rng = SmallRng::from_entropy();
-        let deck = Deck::shuffled(&mut rng);
-        for card in deck.cards {
-            seen.insert(card);
-        }
-        assert_eq!(seen.len(), 52);
-    }
-
-    #[test]
-    fn deck_draw_test() {
-        let mut seen = HashSet::new();
-        let mut rng = SmallRng::from_entropy();
-        let mut deck = Deck::shuffled(&mut rng);
-        for _ in 0..52 {
-            seen.insert(deck.draw());
-        }
-        assert_eq!(seen.len(), 52);
-        assert_eq!(deck.count(), 0);
-        assert_eq!(deck.peek_top_card(), None);
-        assert_eq!(deck.draw(), None);
-    }
-}
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use heapless;
+use itertools::Itertools;
+use rand::{
+    prelude::{Rng, SeedableRng, SliceRandom},
+    rngs::SmallRng,
+};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::solver::{
+    cardset::CardSet,
+    error::{Error, Result},
+};
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, EnumIter, EnumString)]
+#[repr(u8)]
+pub enum Suit {
+    Spades = 0,
+    Clubs = 1,
+    Hearts = 2,
+    Diamonds = 3,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, EnumIter, EnumString)]
+#[repr(u8)]
+pub enum Rank {
+    Deuce = 0,
+    Three = 1,
+    Four = 2,
+    Five = 3,
+    Six = 4,
+    Seven = 5,
+    Eight = 6,
+    Nine = 7,
+    Ten = 8,
+    Jack = 9,
+    Queen = 10,
+    King = 11,
+    Ace = 12,
+}
+
+impl Rank {
+    /// The next rank up, with Aces wrapping around to Deuces.
+    #[must_use]
+    pub fn next(self) -> Self {
+        Self::iter().nth(self as usize + 1).unwrap_or(Self::Deuce)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
+#[repr(u8)]
+pub enum Enhancement {
+    Bonus = 0,
+    Mult = 1,
+    Wild = 2,
+    Glass = 3,
+    Steel = 4,
+    Stone = 5,
+    Gold = 6,
+    Lucky = 7,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
+#[repr(u8)]
+pub enum Edition {
+    Foil = 0,
+    Holographic = 1,
+    Polychrome = 2,
+    Negative = 3,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
+#[repr(u8)]
+pub enum Seal {
+    Gold = 0,
+    Red = 1,
+    Blue = 2,
+    Purple = 3,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+    pub enhancement: Option<Enhancement>,
+    pub edition: Option<Edition>,
+    pub seal: Option<Seal>,
+}
+
+impl Card {
+    pub const fn new(rank: Rank, suit: Suit) -> Self {
+        Self {
+            rank,
+            suit,
+            enhancement: None,
+            edition: None,
+            seal: None,
+        }
+    }
+
+    /// Whether this is a Jack, Queen or King. Stone cards have no rank, so
+    /// they're never face cards on their own.
+    pub fn is_face(self) -> bool {
+        !self.is_stone() && matches!(self.rank, Rank::Jack | Rank::Queen | Rank::King)
+    }
+
+    /// Whether this is a Stone card, which has no rank or suit, and always
+    /// scores when played.
+    pub fn is_stone(self) -> bool {
+        self.enhancement == Some(Enhancement::Stone)
+    }
+
+    /// Whether this counts as `suit` when making a hand. Wild cards count as
+    /// every suit, and Stone cards as none.
+    pub fn is_suit(self, suit: Suit) -> bool {
+        match self.enhancement {
+            Some(Enhancement::Wild) => true,
+            Some(Enhancement::Stone) => false,
+            _ => self.suit == suit,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_enhancement(self, enhancement: Enhancement) -> Self {
+        Self {
+            enhancement: Some(enhancement),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn with_edition(self, edition: Edition) -> Self {
+        Self {
+            edition: Some(edition),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn with_seal(self, seal: Seal) -> Self {
+        Self {
+            seal: Some(seal),
+            ..self
+        }
+    }
+
+    /// Convert a shorthand identifier into a card. Panics if the identifier
+    /// is incorrect. This exists only for test-writing.
+    ///
+    /// ```
+    /// # use solver_core::prelude::{Card, Suit, Rank};
+    /// let a = Card::from_ident("KH");
+    /// let b = Card::new(Rank::King, Suit::Hearts);
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn from_ident(ident: &str) -> Self {
+        ident.parse().unwrap()
+    }
+
+    /// The shorthand identifier along with any modifiers, in the form
+    /// `from_str` parses, like `KH+glass+foil+red-seal`.
+    ///
+    /// ```
+    /// # use solver_core::prelude::{Card, Enhancement};
+    /// let card = Card::from_ident("KH").with_enhancement(Enhancement::Glass);
+    /// assert_eq!(card.ident(), "KH+glass");
+    /// ```
+    pub fn ident(self) -> String {
+        let mut ident = self.to_string();
+        if let Some(enhancement) = self.enhancement {
+            ident = format!("{ident}+{enhancement:?}");
+        }
+        if let Some(edition) = self.edition {
+            ident = format!("{ident}+{edition:?}");
+        }
+        if let Some(seal) = self.seal {
+            ident = format!("{ident}+{seal:?}-seal");
+        }
+        // The rank and suit are upper case, and the modifiers lower
+        format!("{}{}", &ident[..2], ident[2..].to_ascii_lowercase())
+    }
+}
+
+impl FromStr for Card {
+    type Err = Error;
+
+    /// Parse a shorthand identifier like `KH` or `tc`, with the rank first,
+    /// and then any modifiers, each after a `+`: an enhancement like `glass`,
+    /// an edition like `foil`, or a seal like `red-seal`, as in
+    /// `KH+glass+red-seal`.
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || Error::InvalidCard(text.to_owned());
+        let mut parts = text.split('+');
+        let ident = parts.next().unwrap_or_default();
+        let mut chars = ident.chars();
+        let (Some(rank), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(invalid());
+        };
+
+        let rank = match rank.to_ascii_uppercase() {
+            '2' => Rank::Deuce,
+            '3' => Rank::Three,
+            '4' => Rank::Four,
+            '5' => Rank::Five,
+            '6' => Rank::Six,
+            '7' => Rank::Seven,
+            '8' => Rank::Eight,
+            '9' => Rank::Nine,
+            'T' => Rank::Ten,
+            'J' => Rank::Jack,
+            'Q' => Rank::Queen,
+            'K' => Rank::King,
+            'A' => Rank::Ace,
+            _ => return Err(invalid()),
+        };
+        let suit = match suit.to_ascii_uppercase() {
+            'S' => Suit::Spades,
+            'C' => Suit::Clubs,
+            'H' => Suit::Hearts,
+            'D' => Suit::Diamonds,
+            _ => return Err(invalid()),
+        };
+
+        let mut card = Self::new(rank, suit);
+        for modifier in parts {
+            if let Some(seal) = modifier.strip_suffix("-seal") {
+                let seal = seal.parse().map_err(|_| invalid())?;
+                card.seal.replace(seal).map_or(Ok(()), |_| Err(invalid()))?;
+            } else if let Ok(enhancement) = modifier.parse() {
+                card.enhancement
+                    .replace(enhancement)
+                    .map_or(Ok(()), |_| Err(invalid()))?;
+            } else {
+                let edition = modifier.parse().map_err(|_| invalid())?;
+                card.edition
+                    .replace(edition)
+                    .map_or(Ok(()), |_| Err(invalid()))?;
+            }
+        }
+        Ok(card)
+    }
+}
+
+impl fmt::Display for Card {
+    /// Formats a card as its shorthand identifier, like `KH` or `TS`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const RANKS: [char; 13] = [
+            '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+        ];
+        const SUITS: [char; 4] = ['S', 'C', 'H', 'D'];
+        write!(
+            f,
+            "{}{}",
+            RANKS[self.rank as usize], SUITS[self.suit as usize]
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.ident())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[macro_export]
+macro_rules! card {
+    ($ident:literal) => {
+        $crate::solver::cards::Card::from_ident($ident)
+    };
+}
+
+pub trait CardView {
+    fn view(&self) -> &[Card];
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+static BASE_DECK_CARDS: LazyLock<Vec<Card>> = LazyLock::new(|| {
+    let mut cards = Vec::with_capacity(52);
+    for suit in Suit::iter() {
+        for rank in Rank::iter() {
+            cards.push(Card::new(rank, suit));
+        }
+    }
+    cards
+});
+
+impl Deck {
+    pub fn base_deck() -> Self {
+        Self {
+            cards: BASE_DECK_CARDS.clone(),
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn from_cards(cards: Vec<Card>) -> Self {
+        Self { cards }
+    }
+
+    /// Put a card on top of the deck.
+    pub fn add(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    /// Replace one copy of `old` with `new`, wherever it is in the deck.
+    /// Returns whether `old` was found.
+    pub fn replace(&mut self, old: Card, new: Card) -> bool {
+        if let Some(card) = self.cards.iter_mut().find(|card| **card == old) {
+            *card = new;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove one copy of `card` from the deck, returning whether it was found.
+    pub fn remove(&mut self, card: Card) -> bool {
+        if let Some(index) = self.cards.iter().position(|owned| *owned == card) {
+            self.cards.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn shuffle(&mut self, rng: &mut (impl Rng + ?Sized)) {
+        self.cards.shuffle(rng);
+    }
+
+    pub fn shuffled(rng: &mut impl Rng) -> Self {
+        let mut deck = Self::base_deck();
+        deck.shuffle(rng);
+        deck
+    }
+
+    pub fn peek_top_card(&self) -> Option<Card> {
+        self.cards.last().copied()
+    }
+
+    pub fn draw(&mut self) -> Option<Card> {
+        if self.count() == 0 {
+            None
+        } else {
+            Some(self.cards.remove(self.count() - 1))
+        }
+    }
+
+    pub fn draw_hand(&mut self) -> Option<Hand> {
+        if self.count() < 5 {
+            None
+        } else {
+            let mut hand = Hand::empty();
+            for _ in 0..5 {
+                hand.cards.push(self.draw().unwrap()).unwrap();
+            }
+            Some(hand)
+        }
+    }
+
+    pub fn draw_n(&mut self, n: usize) -> Option<CardCollection> {
+        if self.count() < n {
+            None
+        } else {
+            let mut cards = Vec::with_capacity(n);
+            for _ in 0..n {
+                cards.push(self.draw().unwrap());
+            }
+            Some(CardCollection { cards })
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.cards.len()
+    }
+}
+
+impl CardView for Deck {
+    fn view(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Hand {
+    pub(crate) cards: heapless::Vec<Card, 5>,
+}
+
+impl Hand {
+    pub fn empty() -> Self {
+        Self {
+            cards: heapless::Vec::new(),
+        }
+    }
+    pub fn from_slice(cards: &[Card]) -> Result<Self> {
+        Ok(Self {
+            cards: heapless::Vec::from_slice(cards).map_err(|()| Error::OverfullHand)?,
+        })
+    }
+    /// Convert a series of shorthand identifiers into a `Hand`.
+    /// Panics if the input is incorrect. This exists only for test-writing.
+    pub fn from_idents(idents: &str) -> Self {
+        Self::from_slice(CardCollection::from_idents(idents).view()).unwrap()
+    }
+}
+
+#[macro_export]
+macro_rules! hand {
+    ($ident:literal) => {
+        $crate::solver::cards::Hand::from_idents($ident)
+    };
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cards == other.cards
+        // let mut a = self.cards.clone();
+        // a.sort();
+        // let mut b = other.cards.clone();
+        // b.sort();
+        // a == b
+    }
+}
+
+impl From<heapless::Vec<Card, 5>> for Hand {
+    fn from(value: heapless::Vec<Card, 5>) -> Self {
+        Self { cards: value }
+    }
+}
+
+impl std::iter::FromIterator<Card> for Hand {
+    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
+        Self {
+            cards: iter.into_iter().take(5).collect::<heapless::Vec<_, 5>>(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Hand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.cards.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Hand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Self::from_slice(&Vec::<Card>::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+impl CardView for Hand {
+    fn view(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+pub struct CardCollection {
+    cards: Vec<Card>,
+}
+
+impl CardCollection {
+    pub fn empty() -> Self {
+        Self { cards: Vec::new() }
+    }
+    /// Convert a series of shorthand identifiers into a `CardCollection`.
+    /// Panics if the input is incorrect. This exists only for test-writing.
+    ///
+    /// ```
+    /// # use solver_core::prelude::{Suit, Rank, Card, CardCollection};
+    /// let cards = CardCollection::from_idents("KH TD JS 2C");
+    /// assert_eq!(cards.nth(2), Some(Card::new(Rank::Jack, Suit::Spades)));
+    /// ```
+    pub fn from_idents(idents: &str) -> Self {
+        let idents = idents.split_ascii_whitespace();
+        Self::from(idents.map(Card::from_ident).collect::<Vec<_>>().as_slice())
+    }
+    pub fn nth(&self, n: usize) -> Option<Card> {
+        if n < self.cards.len() {
+            Some(self.cards[n])
+        } else {
+            None
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! cards {
+    ($ident:literal) => {
+        $crate::solver::cards::CardCollection::from_idents($ident)
+    };
+}
+
+impl From<&[Card]> for CardCollection {
+    fn from(value: &[Card]) -> Self {
+        Self {
+            cards: Vec::from(value),
+        }
+    }
+}
+
+impl CardView for CardCollection {
+    fn view(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialOrd, Ord, PartialEq, Eq, EnumIter, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+pub enum HandKind {
+    HighCard = 0,
+    Pair = 1,
+    TwoPair = 2,
+    ThreeOfAKind = 3,
+    Straight = 4,
+    Flush = 5,
+    FullHouse = 6,
+    FourOfAKind = 7,
+    StraightFlush = 8,
+    FiveOfAKind = 9,
+    FlushHouse = 10,
+    FlushFive = 11,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn base_deck_test() {
+        let mut seen = HashSet::new();
+        let deck = Deck::base_deck();
+        for card in deck.cards {
+            seen.insert(card);
+        }
+        assert_eq!(seen.len(), 52);
+    }
+
+    #[test]
+    fn base_shuffle_test() {
+        let mut seen = HashSet::new();
+        let mut rng = SmallRng::from_entropy();
+        let deck = Deck::shuffled(&mut rng);
+        for card in deck.cards {
+            seen.insert(card);
+        }
+        assert_eq!(seen.len(), 52);
+    }
+
+    #[test]
+    fn deck_draw_test() {
+        let mut seen = HashSet::new();
+        let mut rng = SmallRng::from_entropy();
+        let mut deck = Deck::shuffled(&mut rng);
+        for _ in 0..52 {
+            seen.insert(deck.draw());
+        }
+        assert_eq!(seen.len(), 52);
+        assert_eq!(deck.count(), 0);
+        assert_eq!(deck.peek_top_card(), None);
+        assert_eq!(deck.draw(), None);
+    }
+
+    #[test]
+    fn face_test() {
+        assert!(card!("JH").is_face());
+        assert!(card!("KS").is_face());
+        assert!(!card!("AS").is_face());
+        assert!(!card!("TD").is_face());
+        assert!(!card!("QC").with_enhancement(Enhancement::Stone).is_face());
+    }
+
+    #[test]
+    fn suit_test() {
+        assert!(card!("QC").is_suit(Suit::Clubs));
+        assert!(!card!("QC").is_suit(Suit::Hearts));
+        let wild = card!("QC").with_enhancement(Enhancement::Wild);
+        assert!(Suit::iter().all(|suit| wild.is_suit(suit)));
+        let stone = card!("QC").with_enhancement(Enhancement::Stone);
+        assert!(stone.is_stone());
+        assert!(!Suit::iter().any(|suit| stone.is_suit(suit)));
+    }
+
+    #[test]
+    fn rank_next_test() {
+        assert_eq!(Rank::Ten.next(), Rank::Jack);
+        assert_eq!(Rank::Ace.next(), Rank::Deuce);
+    }
+
+    #[test]
+    fn display_test() {
+        for ident in ["2S", "9C", "TH", "AD", "KS"] {
+            assert_eq!(Card::from_ident(ident).to_string(), ident);
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!("qd".parse::<Card>().unwrap(), Card::from_ident("QD"));
+        for ident in ["", "Q", "QDD", "1S", "QX"] {
+            assert!(matches!(ident.parse::<Card>(), Err(Error::InvalidCard(_))));
+        }
+    }
+
+    #[test]
+    fn parse_modifiers_test() {
+        assert_eq!(
+            "KH+Glass+foil+red-seal".parse::<Card>().unwrap(),
+            card!("KH")
+                .with_enhancement(Enhancement::Glass)
+                .with_edition(Edition::Foil)
+                .with_seal(Seal::Red)
+        );
+        assert_eq!(
+            "2c+gold-seal+gold".parse::<Card>().unwrap(),
+            card!("2C")
+                .with_enhancement(Enhancement::Gold)
+                .with_seal(Seal::Gold)
+        );
+        for ident in [
+            "KH+",
+            "KH+shiny",
+            "KH+glass+steel",
+            "KH+foil+negative",
+            "KH+green-seal",
+        ] {
+            assert!(matches!(ident.parse::<Card>(), Err(Error::InvalidCard(_))));
+        }
+    }
+
+    #[test]
+    fn ident_test() {
+        let card = card!("TD")
+            .with_enhancement(Enhancement::Lucky)
+            .with_edition(Edition::Polychrome)
+            .with_seal(Seal::Purple);
+        assert_eq!(card.ident(), "TD+lucky+polychrome+purple-seal");
+        assert_eq!(card.ident().parse::<Card>().unwrap(), card);
+        assert_eq!(card!("2S").ident(), "2S");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let card = card!("KH").with_enhancement(Enhancement::Glass);
+        let json = serde_json::to_string(&card).unwrap();
+        assert_eq!(json, r#""KH+glass""#);
+        assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), card);
+
+        let hand = hand!("AS KS QS JS TS");
+        let json = serde_json::to_string(&hand).unwrap();
+        assert_eq!(json, r#"["AS","KS","QS","JS","TS"]"#);
+        assert_eq!(serde_json::from_str::<Hand>(&json).unwrap(), hand);
+        assert!(serde_json::from_str::<Hand>(r#"["AS","KS","QS","JS","TS","9S"]"#).is_err());
+        assert!(serde_json::from_str::<Card>(r#""ZZ""#).is_err());
+
+        let deck = Deck::base_deck();
+        let json = serde_json::to_string(&deck).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Deck>(&json).unwrap().view(),
+            deck.view()
+        );
+
+        let json = serde_json::to_string(&HandKind::FullHouse).unwrap();
+        assert_eq!(
+            serde_json::from_str::<HandKind>(&json).unwrap(),
+            HandKind::FullHouse
+        );
+    }
+}
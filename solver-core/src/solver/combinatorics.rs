@@ -0,0 +1,229 @@
+//! Hand-kind probabilities worked out from a deck's ranks and suits, rather
+//! than by sampling draws from it.
+
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::solver::cards::{Card, CardCollection, CardView, HandKind, Rank, Suit};
+use crate::solver::hand_evaluator::{HandEvaluator, Options};
+
+/// Draws of 5 cards that all make the same kind of hand from cards of the
+/// same ranks, as one of them and how many there are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DrawClass {
+    pub cards: Vec<Card>,
+    pub ways: u64,
+}
+
+/// How many ways there are to choose `k` of `n` things.
+fn choose(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k.min(n - k)).fold(1, |ways, i| ways * (n - i) / (i + 1))
+}
+
+/// Call `found` with every way of taking `left` cards by rank, from rank
+/// `rank` on, when there are `available` of each.
+fn rank_counts(
+    available: &[u64; 13],
+    rank: usize,
+    left: u64,
+    counts: &mut [u64; 13],
+    found: &mut impl FnMut(&[u64; 13]),
+) {
+    if rank == available.len() {
+        if left == 0 {
+            found(counts);
+        }
+        return;
+    }
+    for count in 0..=left.min(available[rank]) {
+        counts[rank] = count;
+        rank_counts(available, rank + 1, left - count, counts, found);
+    }
+    counts[rank] = 0;
+}
+
+/// Every draw of 5 cards from `deck`, or all of it if it's smaller, split up
+/// into classes that make the same kind of hand.
+///
+/// The draws of each set of ranks are split by suit only as far as a flush
+/// depends on it: those all of one suit, those with all but one card of one
+/// suit when `options` allows four-card flushes, and the rest, which can't
+/// make a flush or a straight flush whatever their suits. Only ranks and
+/// suits are considered, not enhancements.
+#[allow(clippy::needless_pass_by_value)]
+pub fn draw_classes(deck: impl CardView, options: Options) -> Vec<DrawClass> {
+    let ranks = Rank::iter().collect_vec();
+    let suits = Suit::iter().collect_vec();
+    let mut in_deck = [[0_u64; 4]; 13];
+    for card in deck.view() {
+        let rank = ranks.iter().position(|rank| *rank == card.rank).unwrap();
+        let suit = suits.iter().position(|suit| *suit == card.suit).unwrap();
+        in_deck[rank][suit] += 1;
+    }
+    let available = in_deck.map(|suits| suits.iter().sum::<u64>());
+    let size = deck.view().len().min(5);
+    let flush_size = if options.contains(Options::FourCardStraightsAndFlushes) {
+        4
+    } else {
+        5
+    };
+
+    let mut classes = Vec::new();
+    let mut add_class = |counts: &[u64; 13]| {
+        // The ranks of the cards, lowest first, and the suit of each
+        let drawn = (0..ranks.len())
+            .flat_map(|rank| std::iter::repeat_n(rank, usize::try_from(counts[rank]).unwrap()))
+            .collect_vec();
+        let cards = |suit_of: &dyn Fn(usize) -> usize| {
+            drawn
+                .iter()
+                .enumerate()
+                .map(|(index, rank)| Card::new(ranks[*rank], suits[suit_of(index)]))
+                .collect_vec()
+        };
+        let total: u64 = (0..ranks.len())
+            .map(|rank| choose(available[rank], counts[rank]))
+            .product();
+
+        let mut suited = 0;
+        if size >= flush_size {
+            for suit in 0..suits.len() {
+                let ways: u64 = (0..ranks.len())
+                    .map(|rank| choose(in_deck[rank][suit], counts[rank]))
+                    .product();
+                if ways > 0 {
+                    classes.push(DrawClass {
+                        cards: cards(&|_| suit),
+                        ways,
+                    });
+                    suited += ways;
+                }
+                if size == flush_size {
+                    continue;
+                }
+
+                // One card of another suit, for four-card flushes
+                for (index, odd_rank) in drawn.iter().enumerate().dedup_by(|a, b| a.1 == b.1) {
+                    for odd_suit in (0..suits.len()).filter(|odd_suit| *odd_suit != suit) {
+                        let ways: u64 = (0..ranks.len())
+                            .map(|rank| {
+                                if rank == *odd_rank {
+                                    choose(in_deck[rank][suit], counts[rank] - 1)
+                                        * in_deck[rank][odd_suit]
+                                } else {
+                                    choose(in_deck[rank][suit], counts[rank])
+                                }
+                            })
+                            .product();
+                        if ways > 0 {
+                            classes.push(DrawClass {
+                                cards: cards(&|card| if card == index { odd_suit } else { suit }),
+                                ways,
+                            });
+                            suited += ways;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Dealing the suits out in turn leaves too few of any one for a flush
+        if total > suited {
+            classes.push(DrawClass {
+                cards: cards(&|card| card % suits.len()),
+                ways: total - suited,
+            });
+        }
+    };
+    rank_counts(&available, 0, size as u64, &mut [0; 13], &mut add_class);
+    classes
+}
+
+/// The chance of each kind of hand, from High Card up, when 5 cards are
+/// drawn from `deck`, worked out exactly without sampling. See
+/// `draw_classes`.
+#[allow(clippy::cast_precision_loss)]
+pub fn kind_probabilities(deck: impl CardView, options: Options) -> Vec<(HandKind, f64)> {
+    let mut ways = [0_u64; 12];
+    for class in draw_classes(deck, options) {
+        if let Some((kind, _)) = HandEvaluator::evaluate_poker_hand(
+            CardCollection::from(class.cards.as_slice()),
+            options,
+        ) {
+            ways[kind as usize] += class.ways;
+        }
+    }
+    let total = ways.iter().sum::<u64>().max(1) as f64;
+    HandKind::iter()
+        .map(|kind| (kind, ways[kind as usize] as f64 / total))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::cards::Deck;
+
+    #[test]
+    fn choose_test() {
+        assert_eq!(choose(52, 5), 2_598_960);
+        assert_eq!(choose(4, 0), 1);
+        assert_eq!(choose(2, 3), 0);
+    }
+
+    #[test]
+    fn standard_deck_test() {
+        let deck = Deck::base_deck();
+        let classes = draw_classes(deck.clone(), Options::empty());
+        assert_eq!(
+            classes.iter().map(|class| class.ways).sum::<u64>(),
+            2_598_960
+        );
+
+        let probabilities = kind_probabilities(deck, Options::empty());
+        let probability = |kind: HandKind| probabilities[kind as usize].1;
+        assert_relative_eq!(probability(HandKind::FourOfAKind), 624.0 / 2_598_960.0);
+        assert_relative_eq!(probability(HandKind::FullHouse), 3744.0 / 2_598_960.0);
+        assert_relative_eq!(probability(HandKind::FlushFive), 0.0);
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn matches_every_draw_test() {
+        // Few enough cards to try every draw, with copies, gaps and a lopsided
+        // spread of suits
+        let deck = CardCollection::from_idents(
+            "AS AS KS QS JS TS 9S 9H 8H 7H 6H 5H 5D 4D 3C 2C AH KD QC 9S",
+        )
+        .view()
+        .to_vec();
+        for options in [
+            Options::empty(),
+            Options::GappedStraights,
+            Options::FourCardStraightsAndFlushes,
+            Options::GappedStraights | Options::FourCardStraightsAndFlushes,
+        ] {
+            let mut ways = [0_u64; 12];
+            for draw in deck.iter().copied().combinations(5) {
+                let (kind, _) = HandEvaluator::evaluate_poker_hand(
+                    CardCollection::from(draw.as_slice()),
+                    options,
+                )
+                .unwrap();
+                ways[kind as usize] += 1;
+            }
+            let total = ways.iter().sum::<u64>() as f64;
+
+            for (kind, probability) in
+                kind_probabilities(CardCollection::from(deck.as_slice()), options)
+            {
+                assert_relative_eq!(probability, ways[kind as usize] as f64 / total);
+            }
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! Tarot and planet cards, and what using them does to a run.
+
+use std::fmt;
+
+use rand::seq::IteratorRandom;
+use rand::{Rng, RngCore};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
+
+use crate::solver::cards::{Card, Edition, Enhancement, HandKind, Suit};
+use crate::solver::error::{Error, Result};
+use crate::solver::game_state::GameState;
+use crate::solver::joker::JokerSlot;
+use crate::solver::shop;
+
+/// A card held in one of the consumable slots.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Consumable {
+    /// The planet card that levels up the given hand
+    Planet(HandKind),
+    Tarot(Tarot),
+}
+
+impl fmt::Display for Consumable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Planet(kind) => write!(f, "{kind:?} planet"),
+            Self::Tarot(tarot) => write!(f, "{tarot}"),
+        }
+    }
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Tarot {
+    #[strum(serialize = "The Fool")]
+    TheFool,
+    #[strum(serialize = "The Magician")]
+    TheMagician,
+    #[strum(serialize = "The High Priestess")]
+    TheHighPriestess,
+    #[strum(serialize = "The Empress")]
+    TheEmpress,
+    #[strum(serialize = "The Emperor")]
+    TheEmperor,
+    #[strum(serialize = "The Hierophant")]
+    TheHierophant,
+    #[strum(serialize = "The Lovers")]
+    TheLovers,
+    #[strum(serialize = "The Chariot")]
+    TheChariot,
+    Justice,
+    #[strum(serialize = "The Hermit")]
+    TheHermit,
+    #[strum(serialize = "The Wheel of Fortune")]
+    TheWheelOfFortune,
+    Strength,
+    #[strum(serialize = "The Hanged Man")]
+    TheHangedMan,
+    Death,
+    Temperance,
+    #[strum(serialize = "The Devil")]
+    TheDevil,
+    #[strum(serialize = "The Tower")]
+    TheTower,
+    #[strum(serialize = "The Star")]
+    TheStar,
+    #[strum(serialize = "The Moon")]
+    TheMoon,
+    #[strum(serialize = "The Sun")]
+    TheSun,
+    Judgement,
+    #[strum(serialize = "The World")]
+    TheWorld,
+}
+
+impl Tarot {
+    /// How many cards in hand must be selected to use this tarot, as an
+    /// inclusive range.
+    pub fn selection(self) -> (usize, usize) {
+        match self {
+            Self::TheMagician
+            | Self::TheEmpress
+            | Self::TheHierophant
+            | Self::Strength
+            | Self::TheHangedMan => (1, 2),
+            Self::TheLovers
+            | Self::TheChariot
+            | Self::Justice
+            | Self::TheDevil
+            | Self::TheTower => (1, 1),
+            Self::Death => (2, 2),
+            Self::TheStar | Self::TheMoon | Self::TheSun | Self::TheWorld => (1, 3),
+            Self::TheFool
+            | Self::TheHighPriestess
+            | Self::TheEmperor
+            | Self::TheHermit
+            | Self::TheWheelOfFortune
+            | Self::Temperance
+            | Self::Judgement => (0, 0),
+        }
+    }
+
+    /// The enhancement this tarot gives the selected cards, if that's what
+    /// it does.
+    fn enhancement(self) -> Option<Enhancement> {
+        match self {
+            Self::TheMagician => Some(Enhancement::Lucky),
+            Self::TheEmpress => Some(Enhancement::Mult),
+            Self::TheHierophant => Some(Enhancement::Bonus),
+            Self::TheLovers => Some(Enhancement::Wild),
+            Self::TheChariot => Some(Enhancement::Steel),
+            Self::Justice => Some(Enhancement::Glass),
+            Self::TheDevil => Some(Enhancement::Gold),
+            Self::TheTower => Some(Enhancement::Stone),
+            _ => None,
+        }
+    }
+
+    /// The suit this tarot converts the selected cards to, if that's what it
+    /// does.
+    pub(crate) fn suit(self) -> Option<Suit> {
+        match self {
+            Self::TheStar => Some(Suit::Diamonds),
+            Self::TheMoon => Some(Suit::Clubs),
+            Self::TheSun => Some(Suit::Hearts),
+            Self::TheWorld => Some(Suit::Spades),
+            _ => None,
+        }
+    }
+
+    /// Check that this tarot can be used on `state` with `selected` cards,
+    /// without changing anything.
+    pub(crate) fn validate(self, state: &GameState, selected: &[usize]) -> Result<()> {
+        let (min, max) = self.selection();
+        if !(min..=max).contains(&selected.len()) {
+            return Err(Error::SelectionSize {
+                consumable: Consumable::Tarot(self),
+                min,
+                max,
+                selected: selected.len(),
+            });
+        }
+        let usable = match self {
+            Self::TheFool => state.last_used.is_some(),
+            Self::TheWheelOfFortune => state.jokers.iter().any(|slot| slot.edition.is_none()),
+            Self::Judgement => state.free_joker_slots() > 0,
+            _ => true,
+        };
+        if usable {
+            Ok(())
+        } else {
+            Err(Error::CannotUse(Consumable::Tarot(self)))
+        }
+    }
+
+    /// Apply this tarot's effect to `state`. The selection must already have
+    /// been validated, and the tarot taken out of its slot.
+    pub(crate) fn apply(self, state: &mut GameState, selected: &[usize], rng: &mut dyn RngCore) {
+        if let Some(enhancement) = self.enhancement() {
+            for &index in selected {
+                let card = state.hand[index];
+                state.replace_card(index, card.with_enhancement(enhancement));
+            }
+            return;
+        }
+        if let Some(suit) = self.suit() {
+            for &index in selected {
+                let card = state.hand[index];
+                state.replace_card(index, Card { suit, ..card });
+            }
+            return;
+        }
+
+        match self {
+            Self::TheFool => {
+                let last = state.last_used.expect("validated");
+                state.create_consumable(last);
+            }
+            Self::TheHighPriestess => {
+                for _ in 0..2 {
+                    let kind = shop::random_planet(state, rng);
+                    state.create_consumable(Consumable::Planet(kind));
+                }
+            }
+            Self::TheEmperor => {
+                for _ in 0..2 {
+                    let tarot = Self::iter().choose(rng).unwrap();
+                    state.create_consumable(Consumable::Tarot(tarot));
+                }
+            }
+            Self::TheHermit => state.money += state.money.clamp(0, 20),
+            Self::TheWheelOfFortune => {
+                if rng.gen_ratio(1, 4) {
+                    let roll = rng.gen_range(0..100);
+                    let edition = match roll {
+                        0..50 => Edition::Foil,
+                        50..85 => Edition::Holographic,
+                        _ => Edition::Polychrome,
+                    };
+                    let slot = state
+                        .jokers
+                        .iter_mut()
+                        .filter(|slot| slot.edition.is_none())
+                        .choose(rng)
+                        .expect("validated");
+                    slot.edition = Some(edition);
+                }
+            }
+            Self::Strength => {
+                for &index in selected {
+                    let card = state.hand[index];
+                    state.replace_card(
+                        index,
+                        Card {
+                            rank: card.rank.next(),
+                            ..card
+                        },
+                    );
+                }
+            }
+            Self::TheHangedMan => state.destroy_cards(selected),
+            Self::Death => {
+                let (left, right) = (selected[0].min(selected[1]), selected[0].max(selected[1]));
+                let copy = state.hand[right];
+                state.replace_card(left, copy);
+            }
+            Self::Temperance => {
+                let value: u32 = state.jokers.iter().filter_map(JokerSlot::sell_value).sum();
+                state.money += i64::from(value.min(50));
+            }
+            Self::Judgement => state.add_joker(shop::random_joker(rng).joker()),
+            _ => unreachable!("{self} changes cards in hand"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::card;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardCollection, CardView, Deck};
+    use crate::solver::joker::builtin;
+
+    fn state(hand: &str) -> GameState {
+        let hand = CardCollection::from_idents(hand).view().to_vec();
+        GameState::from_parts(
+            Deck::from_cards(hand.clone()),
+            hand,
+            4,
+            3,
+            10,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    fn use_tarot(state: &mut GameState, tarot: Tarot, selected: &[usize]) -> Result<()> {
+        state.consumables.push(Consumable::Tarot(tarot));
+        let index = state.consumables.len() - 1;
+        state.use_consumable(index, selected, &mut SmallRng::seed_from_u64(0))
+    }
+
+    #[test]
+    fn enhance_test() {
+        let mut state = state("AS KS 2C");
+        use_tarot(&mut state, Tarot::TheMagician, &[0, 2]).unwrap();
+        let lucky = card!("AS").with_enhancement(Enhancement::Lucky);
+        assert_eq!(state.hand[0], lucky);
+        assert_eq!(state.hand[1], card!("KS"));
+        // The deck's copy changes too
+        assert!(state.deck.view().contains(&lucky));
+        assert!(!state.deck.view().contains(&card!("AS")));
+        assert!(state.consumables.is_empty());
+    }
+
+    #[test]
+    fn change_cards_test() {
+        let mut state = state("AS KS 2C 7D");
+        use_tarot(&mut state, Tarot::TheSun, &[0, 1]).unwrap();
+        assert_eq!(state.hand[..2], [card!("AH"), card!("KH")]);
+
+        use_tarot(&mut state, Tarot::Strength, &[0, 1]).unwrap();
+        assert_eq!(state.hand[..2], [card!("2H"), card!("AH")]);
+
+        // The left card becomes a copy of the right one
+        use_tarot(&mut state, Tarot::Death, &[3, 2]).unwrap();
+        assert_eq!(state.hand[2..], [card!("7D"), card!("7D")]);
+
+        use_tarot(&mut state, Tarot::TheHangedMan, &[0, 3]).unwrap();
+        assert_eq!(state.hand, [card!("AH"), card!("7D")]);
+        assert_eq!(state.deck.count(), 2);
+    }
+
+    #[test]
+    fn money_test() {
+        let mut state = state("AS");
+        use_tarot(&mut state, Tarot::TheHermit, &[]).unwrap();
+        assert_eq!(state.money, 20);
+        use_tarot(&mut state, Tarot::TheHermit, &[]).unwrap();
+        assert_eq!(state.money, 40);
+
+        state.add_joker(builtin::by_name("Joker").unwrap());
+        state.add_joker(builtin::by_name("Baron").unwrap());
+        use_tarot(&mut state, Tarot::Temperance, &[]).unwrap();
+        assert_eq!(state.money, 45);
+    }
+
+    #[test]
+    fn create_test() {
+        let mut state = state("AS");
+        assert!(matches!(
+            use_tarot(&mut state, Tarot::TheFool, &[]),
+            Err(Error::CannotUse(_))
+        ));
+        state.consumables.clear();
+
+        use_tarot(&mut state, Tarot::TheHermit, &[]).unwrap();
+        use_tarot(&mut state, Tarot::TheFool, &[]).unwrap();
+        assert_eq!(state.consumables, [Consumable::Tarot(Tarot::TheHermit)]);
+        // Extra creations are lost when the slots are full
+        use_tarot(&mut state, Tarot::TheHighPriestess, &[]).unwrap();
+        assert_eq!(state.consumables.len(), 2);
+        assert!(matches!(
+            state.add_consumable(Consumable::Tarot(Tarot::Death)),
+            Err(Error::NoConsumableSlots)
+        ));
+
+        use_tarot(&mut state, Tarot::Judgement, &[]).unwrap();
+        assert_eq!(state.jokers.len(), 1);
+    }
+
+    #[test]
+    fn selection_test() {
+        let mut state = state("AS KS");
+        assert!(matches!(
+            use_tarot(&mut state, Tarot::Death, &[0]),
+            Err(Error::SelectionSize {
+                min: 2,
+                max: 2,
+                selected: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            state.use_consumable(0, &[0, 2], &mut SmallRng::seed_from_u64(0)),
+            Err(Error::CardNotInHand { index: 2, len: 2 })
+        ));
+        assert!(matches!(
+            state.use_consumable(1, &[], &mut SmallRng::seed_from_u64(0)),
+            Err(Error::ConsumableNotHeld { index: 1, len: 1 })
+        ));
+        // Failed uses leave the tarot where it was
+        assert_eq!(state.consumables, [Consumable::Tarot(Tarot::Death)]);
+    }
+}
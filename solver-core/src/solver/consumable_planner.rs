@@ -0,0 +1,159 @@
+//! Deciding which consumables to use before a hand, and on which cards.
+
+use itertools::Itertools;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::solver::advisor::Advisor;
+use crate::solver::consumable::{Consumable, Tarot};
+use crate::solver::game_state::GameState;
+
+/// A consumable to use, by its index in `GameState::consumables`, and the
+/// cards in hand to use it on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsumableUse {
+    pub index: usize,
+    pub selected: Vec<usize>,
+    /// How much it raises the score of the best play in hand
+    pub gain: f64,
+}
+
+/// Weighs up the consumables whose worth shows in the hand being played:
+/// planets, and tarots that enhance, convert, raise or copy cards in hand.
+/// Each is judged by how much it raises the best play's score, so a planet
+/// for a hand that isn't being played is saved for later, and a tarot goes
+/// on the fewest cards that get the most out of it.
+///
+/// Tarots that don't touch the hand, and The Hanged Man, whose worth is in a
+/// thinner deck, are left to the player.
+#[derive(Clone, Debug, Default)]
+pub struct ConsumablePlanner {
+    /// Finds and scores the plays
+    pub advisor: Advisor,
+}
+
+impl ConsumablePlanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_advisor(mut self, advisor: Advisor) -> Self {
+        self.advisor = advisor;
+        self
+    }
+
+    /// Every use of a held consumable that would raise the best play's
+    /// score, best first.
+    pub fn uses(&self, state: &GameState) -> Vec<ConsumableUse> {
+        let (_, before) = self.advisor.best_play(&state.hand, state);
+        let mut uses = Vec::new();
+        for (index, consumable) in state.consumables.iter().enumerate() {
+            for selected in selections(*consumable, state.hand.len()) {
+                let mut after = state.clone();
+                // None of the consumables weighed up here draw on chance
+                let mut rng = SmallRng::seed_from_u64(0);
+                if after.use_consumable(index, &selected, &mut rng).is_err() {
+                    continue;
+                }
+                let (_, score) = self.advisor.best_play(&after.hand, &after);
+                let gain = score.value() - before.value();
+                if gain > 0.0 {
+                    uses.push(ConsumableUse {
+                        index,
+                        selected,
+                        gain,
+                    });
+                }
+            }
+        }
+        // Stable, so the fewest cards go first among equals
+        uses.sort_by(|a, b| b.gain.total_cmp(&a.gain));
+        uses
+    }
+
+    /// The use that raises the best play's score most, if any does.
+    pub fn best(&self, state: &GameState) -> Option<ConsumableUse> {
+        self.uses(state).into_iter().next()
+    }
+}
+
+/// The selections worth trying for `consumable` with `len` cards in hand,
+/// fewest cards first.
+fn selections(consumable: Consumable, len: usize) -> Vec<Vec<usize>> {
+    match consumable {
+        Consumable::Planet(_) => vec![Vec::new()],
+        Consumable::Tarot(tarot) if touches_hand(tarot) => {
+            let (min, max) = tarot.selection();
+            (min..=max.min(len))
+                .flat_map(|size| (0..len).combinations(size))
+                .collect()
+        }
+        Consumable::Tarot(_) => Vec::new(),
+    }
+}
+
+/// Whether `tarot` changes the cards in hand into better ones.
+fn touches_hand(tarot: Tarot) -> bool {
+    let (min, _) = tarot.selection();
+    min > 0 && tarot != Tarot::TheHangedMan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardCollection, CardView, Deck, HandKind};
+
+    fn holding(hand: &str, consumables: Vec<Consumable>) -> GameState {
+        let mut state = GameState::from_parts(
+            Deck::base_deck(),
+            CardCollection::from_idents(hand).view().to_vec(),
+            4,
+            3,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        );
+        state.consumables = consumables;
+        state
+    }
+
+    #[test]
+    fn tarot_test() {
+        // Turning the heart into a spade makes a flush, and nothing else
+        // needs converting
+        let state = holding(
+            "AS KS QS JS 9H 2C 3D 4C",
+            vec![Consumable::Tarot(Tarot::TheWorld)],
+        );
+        let best = ConsumablePlanner::new().best(&state).unwrap();
+        assert_eq!(best.index, 0);
+        assert_eq!(best.selected, vec![4]);
+
+        // The Hermit doesn't change the hand
+        let state = holding(
+            "AS KS QS JS 9H 2C 3D 4C",
+            vec![Consumable::Tarot(Tarot::TheHermit)],
+        );
+        assert!(ConsumablePlanner::new().uses(&state).is_empty());
+    }
+
+    #[test]
+    fn planet_test() {
+        // A pair is worth levelling up for a pair of aces, but a flush isn't
+        let state = holding(
+            "AS AD 2C 5H 9S",
+            vec![
+                Consumable::Planet(HandKind::Flush),
+                Consumable::Planet(HandKind::Pair),
+            ],
+        );
+        let uses = ConsumablePlanner::new().uses(&state);
+        assert_eq!(uses.len(), 1);
+        assert_eq!(uses[0].index, 1);
+        assert!(uses[0].selected.is_empty());
+    }
+}
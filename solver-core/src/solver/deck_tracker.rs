@@ -0,0 +1,90 @@
+//! The cards a player hasn't seen yet this round, which is everything the
+//! advisor can know about what's left to draw.
+
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+use crate::solver::cards::{Card, CardView};
+use crate::solver::game_state::GameState;
+
+/// The unseen cards, in no particular order. Unlike `GameState::draw_pile`,
+/// this doesn't know which card is on top.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeckTracker {
+    unseen: Vec<Card>,
+}
+
+impl DeckTracker {
+    pub fn new(mut unseen: Vec<Card>) -> Self {
+        unseen.sort_unstable();
+        Self { unseen }
+    }
+
+    /// Everything left in the state's draw pile.
+    pub fn from_state(state: &GameState) -> Self {
+        Self::new(state.draw_pile.view().to_vec())
+    }
+
+    /// Mark one copy of `card` as seen, returning whether it was unseen.
+    pub fn see(&mut self, card: Card) -> bool {
+        if let Some(index) = self.unseen.iter().position(|unseen| *unseen == card) {
+            self.unseen.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.unseen.len()
+    }
+
+    /// `n` different unseen cards, picked at random; or all of them, if there
+    /// aren't that many left.
+    pub fn sample(&self, n: usize, rng: &mut dyn RngCore) -> Vec<Card> {
+        self.unseen.choose_multiple(rng, n).copied().collect()
+    }
+}
+
+impl CardView for DeckTracker {
+    fn view(&self) -> &[Card] {
+        &self.unseen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::Deck;
+
+    #[test]
+    fn tracker_test() {
+        let hand = vec![Card::from_ident("AS"), Card::from_ident("KD")];
+        let state = GameState::from_parts(
+            Deck::base_deck(),
+            hand,
+            4,
+            3,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        );
+        let mut tracker = DeckTracker::from_state(&state);
+        assert_eq!(tracker.count(), 50);
+        assert!(!tracker.see(Card::from_ident("AS")));
+        assert!(tracker.see(Card::from_ident("2C")));
+        assert_eq!(tracker.count(), 49);
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let sample = tracker.sample(5, &mut rng);
+        assert_eq!(sample.len(), 5);
+        assert!(sample.iter().all(|card| tracker.view().contains(card)));
+        assert_eq!(tracker.sample(100, &mut rng).len(), 49);
+    }
+}
@@ -0,0 +1,226 @@
+//! Advising with some of the hand face down, as under The House or The
+//! Wheel, by imagining what the hidden cards could be.
+
+use itertools::Itertools;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::solver::advisor::{selections, Advisor};
+use crate::solver::cards::Card;
+use crate::solver::deck_tracker::DeckTracker;
+use crate::solver::game_state::GameState;
+use crate::solver::round::Action;
+
+/// Recommends actions for a hand with face-down cards. Each of a number of
+/// worlds fills the face-down cards in with unseen cards, and every play and
+/// discard is valued in each world as the `Advisor` would value it. The
+/// action with the best value on average across the worlds wins.
+///
+/// Worlds are sampled from a fixed seed, so the same situation always gets
+/// the same advice.
+#[derive(Clone, Debug)]
+pub struct Determinizer {
+    /// Values each action within a world
+    pub advisor: Advisor,
+    /// How many worlds to sample, when any cards are face down
+    pub worlds: usize,
+}
+
+impl Determinizer {
+    pub const DEFAULT_WORLDS: usize = 16;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_advisor(self, advisor: Advisor) -> Self {
+        Self { advisor, ..self }
+    }
+
+    #[must_use]
+    pub fn with_worlds(self, worlds: usize) -> Self {
+        Self { worlds, ..self }
+    }
+
+    /// The best thing to do with `hand`, where `None` is a face-down card.
+    /// The face-down cards must still be unseen by `tracker`. As with
+    /// `Advisor::advise`, discarding is only advised when it's expected to
+    /// beat the best play.
+    pub fn advise(
+        &self,
+        hand: &[Option<Card>],
+        tracker: &DeckTracker,
+        state: &GameState,
+    ) -> Action {
+        self.values(hand, tracker, state)
+            .into_iter()
+            .next()
+            .map_or(Action::Play(Vec::new()), |(action, _)| action)
+    }
+
+    /// Every play, and every discard if there are any left, with its value
+    /// averaged over the worlds, best first. Plays are valued by their score
+    /// and discards by their expected score, like `Advisor::advise` does. Of
+    /// actions with the same value, plays come before discards, and fewer
+    /// cards before more.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn values(
+        &self,
+        hand: &[Option<Card>],
+        tracker: &DeckTracker,
+        state: &GameState,
+    ) -> Vec<(Action, f64)> {
+        let worlds = self.worlds(hand, tracker);
+        if worlds.is_empty() {
+            return Vec::new();
+        }
+
+        let mut values = selections(hand.len())
+            .map(|indices| (Action::Play(indices), 0.0))
+            .collect_vec();
+        let hidden = hand.iter().filter(|card| card.is_none()).count();
+        if state.discards > 0 && tracker.count() > hidden {
+            values.extend(selections(hand.len()).map(|indices| (Action::Discard(indices), 0.0)));
+        }
+
+        for (cards, tracker) in &worlds {
+            for (action, total) in &mut values {
+                *total += match action {
+                    Action::Play(indices) => self.advisor.score_play(cards, indices, state).value(),
+                    Action::Discard(indices) => {
+                        self.advisor.discard_ev(cards, indices, tracker, state)
+                    }
+                };
+            }
+        }
+
+        let worlds = worlds.len() as f64;
+        for (_, total) in &mut values {
+            *total /= worlds;
+        }
+        // The sort is stable, so ties keep the order they were listed in
+        values.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        values
+    }
+
+    /// The worlds consistent with `hand` and `tracker`: the hand with its
+    /// face-down cards filled in by different unseen cards, and the tracker
+    /// with those cards seen. A hand with nothing face down has just the one
+    /// world, and one with more face-down cards than unseen cards has none.
+    pub fn worlds(
+        &self,
+        hand: &[Option<Card>],
+        tracker: &DeckTracker,
+    ) -> Vec<(Vec<Card>, DeckTracker)> {
+        let hidden = hand.iter().filter(|card| card.is_none()).count();
+        if hidden > tracker.count() {
+            return Vec::new();
+        }
+        let worlds = if hidden == 0 { 1 } else { self.worlds.max(1) };
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..worlds)
+            .map(|_| {
+                let mut tracker = tracker.clone();
+                let mut filled = tracker.sample(hidden, &mut rng).into_iter();
+                let cards = hand
+                    .iter()
+                    .map(|card| card.unwrap_or_else(|| filled.next().unwrap()))
+                    .collect_vec();
+                for (card, face_down) in cards.iter().zip(hand) {
+                    if face_down.is_none() {
+                        tracker.see(*card);
+                    }
+                }
+                (cards, tracker)
+            })
+            .collect()
+    }
+}
+
+impl Default for Determinizer {
+    fn default() -> Self {
+        Self {
+            advisor: Advisor::default(),
+            worlds: Self::DEFAULT_WORLDS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardCollection, CardView, Deck};
+
+    fn dealt(hand: &str, discards: u32) -> GameState {
+        GameState::from_parts(
+            Deck::base_deck(),
+            CardCollection::from_idents(hand).view().to_vec(),
+            4,
+            discards,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn face_up_test() {
+        // With every card known, this is just the advisor
+        let state = dealt("2S 7D 9C JH 4S", 3);
+        let tracker = DeckTracker::from_state(&state);
+        let hand = state.hand.iter().copied().map(Some).collect_vec();
+        let determinizer = Determinizer::new();
+        assert_eq!(determinizer.worlds(&hand, &tracker).len(), 1);
+        assert_eq!(
+            determinizer.advise(&hand, &tracker, &state),
+            Advisor::new().advise(&state.hand, &tracker, &state)
+        );
+    }
+
+    #[test]
+    fn face_down_test() {
+        let state = dealt("AS AD", 3);
+        let hand = [
+            Some(Card::from_ident("AS")),
+            Some(Card::from_ident("AD")),
+            None,
+        ];
+        // The face-down card is the ace of hearts or the three of clubs
+        let tracker = DeckTracker::new(vec![Card::from_ident("AH"), Card::from_ident("3C")]);
+        let determinizer = Determinizer::new().with_worlds(8);
+
+        let worlds = determinizer.worlds(&hand, &tracker);
+        assert_eq!(worlds.len(), 8);
+        for (cards, tracker) in &worlds {
+            assert_eq!(cards[..2], state.hand[..]);
+            assert_eq!(tracker.count(), 1);
+            assert!(!tracker.view().contains(&cards[2]));
+        }
+
+        // The pair is worth the same in every world, (10 + 22) * 2
+        let values = determinizer.values(&hand, &tracker, &state);
+        let pair = values
+            .iter()
+            .find(|(action, _)| *action == Action::Play(vec![0, 1]))
+            .unwrap();
+        assert_relative_eq!(pair.1, 64.0);
+
+        // Whereas playing the face-down card too might make three aces
+        assert_eq!(
+            determinizer.advise(&hand, &tracker, &state),
+            Action::Play(vec![0, 1, 2])
+        );
+
+        // Too many face-down cards for the unseen ones
+        assert!(determinizer
+            .worlds(&[None, None, None], &tracker)
+            .is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+//! Money earned at the end of each round, and the rules that decide it.
+
+/// How the end of round payout works. Like `Ruleset`, this defaults to
+/// vanilla, but some decks change it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EconomyRules {
+    /// Paid for each hand left unplayed
+    pub money_per_hand: u32,
+    /// Paid for each discard left unused
+    pub money_per_discard: u32,
+    /// Whether interest is paid at all
+    pub interest: bool,
+}
+
+impl EconomyRules {
+    pub const VANILLA: Self = Self {
+        money_per_hand: 1,
+        money_per_discard: 0,
+        interest: true,
+    };
+
+    /// Green Deck: more for leftover hands and discards, but no interest.
+    pub const GREEN_DECK: Self = Self {
+        money_per_hand: 2,
+        money_per_discard: 1,
+        interest: false,
+    };
+}
+
+impl Default for EconomyRules {
+    fn default() -> Self {
+        Self::VANILLA
+    }
+}
+
+/// Everything paid out when a blind is beaten.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CashOut {
+    /// The blind's own reward
+    pub blind: u32,
+    /// For the hands left unplayed
+    pub hands: u32,
+    /// For the discards left unused
+    pub discards: u32,
+    pub interest: u32,
+    /// From jokers and Investment Tags
+    pub jokers: u32,
+}
+
+impl CashOut {
+    pub fn total(&self) -> u32 {
+        self.blind + self.hands + self.discards + self.interest + self.jokers
+    }
+}
@@ -1,9 +1,82 @@
-use thiserror::Error;
-
-#[derive(Debug, Error)]
-pub enum Error {
-    #[error("a hand can have a maximum of 5 cards")]
-    OverfullHand,
-}
-
-pub type Result<T> = std::result::Result<T, Error>;
+use thiserror::Error;
+
+use crate::solver::cards::Card;
+use crate::solver::consumable::Consumable;
+use crate::solver::voucher::Voucher;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("a hand can have a maximum of 5 cards")]
+    OverfullHand,
+    #[error("invalid card `{0}`")]
+    InvalidCard(String),
+    #[error("unknown joker `{0}`")]
+    UnknownJoker(String),
+    #[error("a hand is one to five cards, but {0} were given")]
+    PlayedHandSize(usize),
+    #[error("not a poker hand")]
+    NotAHand,
+    #[error("there are no cards in hand")]
+    EmptyHand,
+    #[error("{0} is in hand more than once")]
+    DuplicateCard(Card),
+    #[error("{0} was seen, but isn't left in the deck")]
+    SeenCardNotInDeck(Card),
+    #[error("invalid game state: {0}")]
+    GameState(String),
+    #[error("game states of version {0} can't be read")]
+    GameStateVersion(u64),
+    #[error("invalid save file: {0}")]
+    SaveFile(String),
+    #[error("invalid joker definition: {0}")]
+    JokerDefinition(String),
+    #[error("invalid heuristic weights: {0}")]
+    Weights(String),
+    #[error("invalid joker script: {0}")]
+    Script(String),
+    #[error("the round is already over")]
+    RoundOver,
+    #[error("no hands left to play")]
+    NoHandsLeft,
+    #[error("no discards left")]
+    NoDiscardsLeft,
+    #[error("at least one card must be selected")]
+    EmptySelection,
+    #[error("card {index} was selected, but only {len} are in hand")]
+    CardNotInHand { index: usize, len: usize },
+    #[error("card {0} was selected more than once")]
+    DuplicateSelection(usize),
+    #[error("{consumable} needs between {min} and {max} cards selected, got {selected}")]
+    SelectionSize {
+        consumable: Consumable,
+        min: usize,
+        max: usize,
+        selected: usize,
+    },
+    #[error("{0} can't be used right now")]
+    CannotUse(Consumable),
+    #[error("consumable {index} was used, but only {len} are held")]
+    ConsumableNotHeld { index: usize, len: usize },
+    #[error("no consumable slots left")]
+    NoConsumableSlots,
+    #[error("no joker slots left")]
+    NoJokerSlots,
+    #[error("costs ${cost}, but only ${money} is held")]
+    NotEnoughMoney { cost: u32, money: i64 },
+    #[error("item {index} was picked, but only {len} are available")]
+    ItemNotAvailable { index: usize, len: usize },
+    #[error("no picks left in this pack")]
+    NoPicksLeft,
+    #[error("joker {index} was picked, but only {len} are held")]
+    JokerNotHeld { index: usize, len: usize },
+    #[error("{0} is Eternal, and can't be sold")]
+    Eternal(String),
+    #[error("the Boss Blind can't be skipped")]
+    CannotSkipBoss,
+    #[error("{0} has already been redeemed")]
+    VoucherRedeemed(Voucher),
+    #[error("{voucher} needs {requires} to be redeemed first")]
+    VoucherLocked { voucher: Voucher, requires: Voucher },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
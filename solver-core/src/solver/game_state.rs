@@ -0,0 +1,858 @@
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod save;
+
+use std::sync::Arc;
+
+use rand::seq::IteratorRandom;
+use rand::{Rng, RngCore};
+use strum::IntoEnumIterator;
+
+use crate::solver::blind::Blind;
+use crate::solver::cards::{Card, CardView, Deck, Edition};
+use crate::solver::consumable::{Consumable, Tarot};
+use crate::solver::economy::{CashOut, EconomyRules};
+use crate::solver::error::{Error, Result};
+use crate::solver::hand_levels::HandLevels;
+use crate::solver::joker::{Joker, JokerSlot, RoundEnd, Stickers};
+use crate::solver::score::Score;
+use crate::solver::scorer::{Randomness, ScoreBreakdown, Scorer, ScoringContext, ScoringOptions};
+use crate::solver::shop::OpenPack;
+use crate::solver::stake::Stake;
+use crate::solver::starting_deck::StartingDeck;
+use crate::solver::tag::Tag;
+use crate::solver::voucher::Voucher;
+
+/// Everything that persists between hands over the course of a run.
+#[derive(Clone, Debug)]
+pub struct GameState {
+    /// Every playing card owned, whether it's in the draw pile or not
+    pub deck: Deck,
+    /// The cards left to draw this round; the next card drawn is on top
+    pub draw_pile: Deck,
+    /// The cards currently in hand
+    pub hand: Vec<Card>,
+    /// How many hands are left to play this round
+    pub hands: u32,
+    /// How many discards are left this round
+    pub discards: u32,
+    /// Money can go negative, with Credit Card
+    pub money: i64,
+    /// How money is paid out at the end of each round
+    pub economy: EconomyRules,
+    pub ante: u32,
+    pub stake: Stake,
+    /// The blind currently being played, or up next
+    pub blind: Blind,
+    /// The jokers in play, in slot order, along with their accumulated state
+    pub jokers: Vec<JokerSlot>,
+    pub consumables: Vec<Consumable>,
+    /// Each hand's level, and how often it's been played this run
+    pub hand_levels: HandLevels,
+    /// Every voucher redeemed this run, in order
+    pub vouchers: Vec<Voucher>,
+    /// The voucher the shop offers this ante, until it's bought
+    pub voucher_offer: Option<Voucher>,
+    /// Tags gained but not used up yet
+    pub tags: Vec<Tag>,
+    pub blinds_skipped: u32,
+    /// Discards left over at the end of every round so far
+    pub unused_discards: u32,
+    /// Extra hand size for this round only, from Juggle Tags
+    pub hand_size_bonus: usize,
+    /// The last tarot or planet used, other than The Fool
+    pub last_used: Option<Consumable>,
+    /// How many discards have been used so far this round
+    pub discards_used: u32,
+    /// How many cards are drawn into hand
+    pub hand_size: usize,
+    pub hands_per_round: u32,
+    pub discards_per_round: u32,
+    /// How many jokers can be held, not counting Negative ones
+    pub joker_slots: usize,
+    pub consumable_slots: usize,
+    /// How many jokers and consumables the shop offers at once
+    pub shop_slots: usize,
+}
+
+impl GameState {
+    /// An empty state, with no cards, money, or jokers, but the vanilla hand
+    /// size and slots. Mostly useful for looking at jokers in isolation; see
+    /// `fresh_run` for a real run.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a state from the versioned JSON format described in `json`.
+    #[cfg(feature = "serde")]
+    pub fn from_json(source: &str) -> Result<Self> {
+        json::from_json(source)
+    }
+
+    /// The state in the newest version of the JSON format described in
+    /// `json`.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        json::to_json(self)
+    }
+
+    /// Read the run in progress from one of Balatro's save files, as
+    /// described in `save`.
+    pub fn from_save(bytes: &[u8]) -> Result<Self> {
+        save::from_save(bytes)
+    }
+
+    /// The start of a vanilla run: a standard 52-card deck, $4, and the Small
+    /// Blind of ante 1 up next. No cards are drawn until the round starts.
+    pub fn fresh_run(rng: &mut impl Rng) -> Self {
+        Self::fresh_run_with(StartingDeck::Plain, rng)
+    }
+
+    /// The start of a run with `deck`'s cards and bonuses.
+    pub fn fresh_run_with(deck: StartingDeck, rng: &mut impl Rng) -> Self {
+        let mut state = Self::from_parts(
+            deck.cards(rng),
+            Vec::new(),
+            4,
+            3,
+            4,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        );
+        deck.apply(&mut state);
+        state.draw_pile.shuffle(rng);
+        state.restock_voucher(rng);
+        state
+    }
+
+    /// A state in the middle of a run, with every card not in hand left in the
+    /// draw pile (in the order given). The hand size and per-round hands and
+    /// discards are the vanilla ones.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_parts(
+        deck: Deck,
+        hand: Vec<Card>,
+        hands: u32,
+        discards: u32,
+        money: i64,
+        ante: u32,
+        blind: Blind,
+        jokers: Vec<JokerSlot>,
+        consumables: Vec<Consumable>,
+    ) -> Self {
+        let mut in_hand = hand.clone();
+        let draw_pile = deck
+            .view()
+            .iter()
+            .copied()
+            .filter(|card| {
+                if let Some(index) = in_hand.iter().position(|held| held == card) {
+                    in_hand.swap_remove(index);
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect();
+        Self {
+            deck,
+            draw_pile: Deck::from_cards(draw_pile),
+            hand,
+            hands,
+            discards,
+            money,
+            economy: EconomyRules::VANILLA,
+            ante,
+            stake: Stake::White,
+            blind,
+            jokers,
+            consumables,
+            hand_levels: HandLevels::default(),
+            vouchers: Vec::new(),
+            voucher_offer: None,
+            tags: Vec::new(),
+            blinds_skipped: 0,
+            unused_discards: 0,
+            hand_size_bonus: 0,
+            last_used: None,
+            discards_used: 0,
+            hand_size: 8,
+            hands_per_round: 4,
+            discards_per_round: 3,
+            joker_slots: 5,
+            consumable_slots: 2,
+            shop_slots: 2,
+        }
+    }
+
+    /// Play at a different stake, taking away Blue Stake's discard if it
+    /// applies.
+    #[must_use]
+    pub fn with_stake(self, stake: Stake) -> Self {
+        let adjust = |discards: u32| {
+            (discards + self.stake.discard_penalty()).saturating_sub(stake.discard_penalty())
+        };
+        Self {
+            stake,
+            discards_per_round: adjust(self.discards_per_round),
+            discards: adjust(self.discards),
+            ..self
+        }
+    }
+
+    /// The score needed to beat the current blind.
+    pub fn current_target(&self) -> Score {
+        self.blind.target_at(self.ante, self.stake)
+    }
+
+    /// The money the current blind pays out when it's beaten.
+    pub fn current_reward(&self) -> u32 {
+        self.stake.reward(self.blind)
+    }
+
+    /// Skip the current Small or Big Blind for `tag`, and move on to the next
+    /// blind. A pending Double Tag gains the tag a second time. Returns the
+    /// packs opened by pack tags, for picking from.
+    pub fn skip_blind(&mut self, tag: Tag, rng: &mut dyn RngCore) -> Result<Vec<OpenPack>> {
+        if self.blind == Blind::Boss {
+            return Err(Error::CannotSkipBoss);
+        }
+        self.blinds_skipped += 1;
+        self.advance_blind();
+
+        let mut times = 1;
+        if tag != Tag::Double {
+            self.tags.retain(|pending| {
+                if *pending == Tag::Double {
+                    times += 1;
+                }
+                *pending != Tag::Double
+            });
+        }
+        Ok((0..times).filter_map(|_| tag.gain(self, rng)).collect())
+    }
+
+    /// Move on to the next blind, and the next ante after a Boss Blind.
+    pub fn advance_blind(&mut self) {
+        let (blind, new_ante) = self.blind.next();
+        self.blind = blind;
+        if new_ante {
+            self.ante += 1;
+        }
+    }
+
+    pub fn add_joker(&mut self, joker: Arc<dyn Joker>) {
+        self.jokers.push(JokerSlot::new(joker));
+    }
+
+    pub fn has_voucher(&self, voucher: Voucher) -> bool {
+        self.vouchers.contains(&voucher)
+    }
+
+    /// Check that `voucher` could be redeemed: it hasn't been already, and
+    /// its base voucher has.
+    pub fn check_voucher(&self, voucher: Voucher) -> Result<()> {
+        if self.has_voucher(voucher) {
+            return Err(Error::VoucherRedeemed(voucher));
+        }
+        match voucher.requires() {
+            Some(requires) if !self.has_voucher(requires) => {
+                Err(Error::VoucherLocked { voucher, requires })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Redeem a voucher for the rest of the run, without paying for it.
+    pub fn redeem_voucher(&mut self, voucher: Voucher) -> Result<()> {
+        self.check_voucher(voucher)?;
+        self.vouchers.push(voucher);
+        voucher.redeem(self);
+        Ok(())
+    }
+
+    /// Pick the voucher the shop offers for the ante, out of the ones that can
+    /// still be redeemed.
+    pub fn restock_voucher(&mut self, rng: &mut dyn RngCore) {
+        self.voucher_offer = Voucher::iter()
+            .filter(|voucher| self.check_voucher(*voucher).is_ok())
+            .choose(rng);
+    }
+
+    /// What something with the given base price costs in the shop, after
+    /// Clearance Sale or Liquidation.
+    pub fn price(&self, base: u32) -> u32 {
+        let discount = if self.has_voucher(Voucher::Liquidation) {
+            50
+        } else if self.has_voucher(Voucher::ClearanceSale) {
+            25
+        } else {
+            0
+        };
+        // The game adds half a dollar before discounting, then rounds down
+        ((2 * base + 1) * (100 - discount) / 200).max(1)
+    }
+
+    /// The most interest that can be earned in a round.
+    pub fn interest_cap(&self) -> u32 {
+        if self.has_voucher(Voucher::MoneyTree) {
+            20
+        } else if self.has_voucher(Voucher::SeedMoney) {
+            10
+        } else {
+            5
+        }
+    }
+
+    /// Sell the joker in slot `index`, returning what it paid. Eternal jokers
+    /// can't be sold.
+    pub fn sell_joker(&mut self, index: usize) -> Result<u32> {
+        let len = self.jokers.len();
+        let slot = self
+            .jokers
+            .get(index)
+            .ok_or(Error::JokerNotHeld { index, len })?;
+        if slot.stickers.contains(Stickers::Eternal) {
+            return Err(Error::Eternal(slot.name().to_owned()));
+        }
+        let value = slot.sell_value().unwrap_or(1);
+        self.jokers.remove(index);
+        self.money += i64::from(value);
+        Ok(value)
+    }
+
+    /// The interest the money held right now would earn: $1 for every $5,
+    /// up to the interest cap.
+    pub fn interest(&self) -> u32 {
+        if !self.economy.interest {
+            return 0;
+        }
+        u32::try_from(self.money / 5)
+            .unwrap_or(0)
+            .min(self.interest_cap())
+    }
+
+    /// Cash out a beaten blind: its reward, money for leftover hands and
+    /// discards, interest on the money held, and whatever the jokers pay at
+    /// the end of the round.
+    pub fn cash_out(&mut self) -> CashOut {
+        let interest = self.interest();
+        let mut cash_out = CashOut {
+            blind: self.current_reward(),
+            hands: self.hands * self.economy.money_per_hand,
+            discards: self.discards * self.economy.money_per_discard,
+            interest,
+            jokers: 0,
+        };
+        cash_out.jokers = self.end_round();
+        self.money += i64::from(cash_out.total() - cash_out.jokers);
+        cash_out
+    }
+
+    /// How many more jokers fit. Negative jokers don't take up a slot.
+    pub fn free_joker_slots(&self) -> usize {
+        let taken = self
+            .jokers
+            .iter()
+            .filter(|slot| slot.edition != Some(Edition::Negative))
+            .count();
+        self.joker_slots.saturating_sub(taken)
+    }
+
+    /// Put a consumable in a free slot.
+    pub fn add_consumable(&mut self, consumable: Consumable) -> Result<()> {
+        if self.consumables.len() >= self.consumable_slots {
+            return Err(Error::NoConsumableSlots);
+        }
+        self.consumables.push(consumable);
+        Ok(())
+    }
+
+    /// Add a consumable created by another one, if there's room for it.
+    pub(crate) fn create_consumable(&mut self, consumable: Consumable) {
+        // Like in game, anything created without room is simply lost
+        let _ = self.add_consumable(consumable);
+    }
+
+    /// Use the consumable in slot `index` on the `selected` cards in hand. If
+    /// it can't be used, nothing changes and it stays in its slot.
+    ///
+    pub fn use_consumable(
+        &mut self,
+        index: usize,
+        selected: &[usize],
+        rng: &mut dyn RngCore,
+    ) -> Result<()> {
+        let len = self.consumables.len();
+        let consumable = *self
+            .consumables
+            .get(index)
+            .ok_or(Error::ConsumableNotHeld { index, len })?;
+        self.validate_selection(selected)?;
+        match consumable {
+            Consumable::Planet(_) => {
+                if !selected.is_empty() {
+                    return Err(Error::SelectionSize {
+                        consumable,
+                        min: 0,
+                        max: 0,
+                        selected: selected.len(),
+                    });
+                }
+            }
+            Consumable::Tarot(tarot) => tarot.validate(self, selected)?,
+        }
+
+        self.consumables.remove(index);
+        match consumable {
+            Consumable::Planet(kind) => self.hand_levels.level_up(kind),
+            Consumable::Tarot(tarot) => tarot.apply(self, selected, rng),
+        }
+        if consumable != Consumable::Tarot(Tarot::TheFool) {
+            self.last_used = Some(consumable);
+        }
+        Ok(())
+    }
+
+    /// Check that `indices` pick distinct cards that are all in hand.
+    pub fn validate_selection(&self, indices: &[usize]) -> Result<()> {
+        let len = self.hand.len();
+        for (position, &index) in indices.iter().enumerate() {
+            if index >= len {
+                return Err(Error::CardNotInHand { index, len });
+            }
+            if indices[..position].contains(&index) {
+                return Err(Error::DuplicateSelection(index));
+            }
+        }
+        Ok(())
+    }
+
+    /// Change a card in hand, and the deck's copy of it along with it.
+    pub fn replace_card(&mut self, index: usize, card: Card) {
+        self.deck.replace(self.hand[index], card);
+        self.hand[index] = card;
+    }
+
+    /// Destroy the selected cards in hand, removing them from the deck for
+    /// good.
+    pub fn destroy_cards(&mut self, indices: &[usize]) {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        for index in indices.into_iter().rev() {
+            let card = self.hand.remove(index);
+            self.deck.remove(card);
+        }
+    }
+
+    /// Draw from the draw pile until the hand is full or the pile runs out.
+    pub fn draw_to_hand_size(&mut self) {
+        while self.hand.len() < self.hand_size + self.hand_size_bonus {
+            let Some(card) = self.draw_pile.draw() else {
+                break;
+            };
+            self.hand.push(card);
+        }
+    }
+
+    /// Play a hand: count it towards its kind, let the jokers react to it,
+    /// then score it with them at its current level. Any jokers or hand levels
+    /// already on `context` are replaced with this state's.
+    pub fn play_hand(&mut self, context: ScoringContext, randomness: Randomness) -> Score {
+        self.play_hand_with(context, |context| {
+            Scorer::score_hand_with(context, randomness)
+        })
+    }
+
+    /// Like `play_hand`, but with a full account of the score.
+    pub fn play_hand_breakdown(
+        &mut self,
+        context: ScoringContext,
+        randomness: Randomness,
+    ) -> ScoreBreakdown {
+        self.play_hand_with(context, |context| {
+            Scorer::score_breakdown_with(context, randomness)
+        })
+    }
+
+    fn play_hand_with<T>(
+        &mut self,
+        context: ScoringContext,
+        score: impl FnOnce(&ScoringContext) -> T,
+    ) -> T {
+        self.hands = self.hands.saturating_sub(1);
+        self.hand_levels.record_played(context.kind);
+        for slot in &mut self.jokers {
+            slot.joker.on_hand_played(&mut slot.state, &context);
+        }
+        score(&self.scoring_context(context))
+    }
+
+    /// What a hand would score if it were played now, without playing it. The
+    /// jokers don't get to react to it first, so any that grow when a hand is
+    /// played score as they stand.
+    pub fn preview_hand(&self, context: ScoringContext, randomness: Randomness) -> Score {
+        Scorer::score_hand_with(&self.scoring_context(context), randomness)
+    }
+
+    /// Every score a hand could get if it were played now, with its
+    /// probability, lowest first. Like `preview_hand`, the jokers don't get to
+    /// react to it first. If anything random has more than `limit`
+    /// combinations of outcomes, the expected score is taken as certain.
+    pub fn preview_outcomes(&self, context: ScoringContext, limit: usize) -> Vec<(Score, f64)> {
+        let context = self.scoring_context(context);
+        Scorer::score_outcomes(&context, limit)
+            .unwrap_or_else(|| vec![(Scorer::score_hand(&context), 1.0)])
+    }
+
+    /// `context`, with this state's jokers, money, hand levels and so on.
+    fn scoring_context<'a>(&'a self, context: ScoringContext<'a>) -> ScoringContext<'a> {
+        let mut options = context.options;
+        if self.has_voucher(Voucher::Observatory) {
+            options |= ScoringOptions::Observatory;
+        }
+        context
+            .with_jokers(&self.jokers)
+            .with_money(self.money)
+            .with_hand_levels(&self.hand_levels)
+            .with_consumables(&self.consumables)
+            .with_options(options)
+    }
+
+    /// Discard some cards, collecting whatever the jokers pay out for it.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn discard(&mut self, cards: &[Card]) {
+        self.discards = self.discards.saturating_sub(1);
+        self.discards_used += 1;
+        let mut money = 0.0;
+        for slot in &mut self.jokers {
+            money += slot.joker.discard_money(&slot.state, cards);
+            slot.joker.on_discard(&mut slot.state, cards);
+        }
+        // Money held is whole dollars; the payouts are only fractional when
+        // a joker hasn't made its pick, which a round in play always has
+        self.money += money.round() as i64;
+    }
+
+    /// Start a new round: reset the hands and discards, shuffle the whole deck
+    /// into the draw pile, let the jokers make their picks, and draw a hand.
+    pub fn start_round(&mut self, rng: &mut dyn RngCore) {
+        self.hands = self.hands_per_round;
+        self.discards = self.discards_per_round;
+        self.discards_used = 0;
+        self.hand.clear();
+        self.tags.retain(|tag| {
+            if *tag == Tag::Juggle {
+                self.hand_size_bonus += 3;
+            }
+            *tag != Tag::Juggle
+        });
+        self.draw_pile = self.deck.clone();
+        self.draw_pile.shuffle(rng);
+        for slot in &mut self.jokers {
+            slot.joker
+                .on_round_start(&mut slot.state, self.deck.view(), rng);
+        }
+        self.draw_to_hand_size();
+    }
+
+    /// Collect the jokers' end of round income, plus any Investment Tags
+    /// after a Boss Blind, returning how much they paid. Rental jokers are
+    /// charged for separately, and every joker ages a round.
+    pub fn end_round(&mut self) -> u32 {
+        let round = RoundEnd {
+            money: self.money,
+            discards_used: self.discards_used,
+            discards_left: self.discards,
+            boss_defeated: self.blind == Blind::Boss,
+        };
+        let mut income: u32 = self
+            .jokers
+            .iter_mut()
+            .map(|slot| slot.joker.on_round_end(&mut slot.state, &round))
+            .sum();
+        if round.boss_defeated {
+            let investments = self.tags.iter().filter(|tag| **tag == Tag::Investment);
+            income += 25 * u32::try_from(investments.count()).unwrap();
+            self.tags.retain(|tag| *tag != Tag::Investment);
+        }
+        for slot in &mut self.jokers {
+            slot.rounds_held += 1;
+            if slot.stickers.contains(Stickers::Rental) {
+                self.money -= 3;
+            }
+        }
+        self.unused_discards += self.discards;
+        self.hand_size_bonus = 0;
+        self.money += i64::from(income);
+        income
+    }
+
+    /// Add a playing card to the deck, letting the jokers know about it.
+    pub fn add_card(&mut self, card: Card) {
+        self.deck.add(card);
+        for slot in &mut self.jokers {
+            slot.joker.on_card_added(&mut slot.state, card);
+        }
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::from_parts(
+            Deck::empty(),
+            Vec::new(),
+            0,
+            0,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::cards::HandKind;
+    use crate::solver::joker::builtin;
+    use crate::{cards, hand};
+
+    #[test]
+    fn play_hand_test() {
+        let mut state = GameState::new();
+        state.add_joker(builtin::by_name("Joker").unwrap());
+        state.add_joker(builtin::by_name("Green Joker").unwrap());
+
+        let hand = hand!("KH KS");
+        // 30 * (2 + 4 + 1)
+        let score = state.play_hand(
+            ScoringContext::new(HandKind::Pair, &hand),
+            Randomness::Expected,
+        );
+        assert_relative_eq!(score.value(), 210.0);
+
+        // Cloning a state carries the joker progress along with it
+        let snapshot = state.clone();
+        state.play_hand(
+            ScoringContext::new(HandKind::Pair, &hand),
+            Randomness::Expected,
+        );
+        assert_relative_eq!(snapshot.jokers[1].state.value, 1.0);
+        assert_relative_eq!(state.jokers[1].state.value, 2.0);
+    }
+
+    #[test]
+    fn planet_test() {
+        let mut state = GameState::new();
+        state.consumable_slots = 1;
+        state
+            .add_consumable(Consumable::Planet(HandKind::Pair))
+            .unwrap();
+        state
+            .use_consumable(0, &[], &mut SmallRng::seed_from_u64(0))
+            .unwrap();
+        assert_eq!(state.hand_levels.level(HandKind::Pair), 2);
+
+        let hand = hand!("KH KS");
+        // (10 + 15 + 20) * (2 + 1)
+        let score = state.play_hand(
+            ScoringContext::new(HandKind::Pair, &hand),
+            Randomness::Expected,
+        );
+        assert_relative_eq!(score.value(), 135.0);
+        assert_eq!(state.hand_levels.times_played(HandKind::Pair), 1);
+    }
+
+    #[test]
+    fn voucher_test() {
+        let mut state = GameState::fresh_run(&mut SmallRng::seed_from_u64(0));
+        assert!(state.voucher_offer.is_some());
+        assert!(matches!(
+            state.redeem_voucher(Voucher::NachoTong),
+            Err(Error::VoucherLocked {
+                requires: Voucher::Grabber,
+                ..
+            })
+        ));
+        state.redeem_voucher(Voucher::Grabber).unwrap();
+        state.redeem_voucher(Voucher::NachoTong).unwrap();
+        state.redeem_voucher(Voucher::PaintBrush).unwrap();
+        assert!(matches!(
+            state.redeem_voucher(Voucher::Grabber),
+            Err(Error::VoucherRedeemed(Voucher::Grabber))
+        ));
+        assert_eq!((state.hands_per_round, state.hand_size), (6, 9));
+
+        state.redeem_voucher(Voucher::Hieroglyph).unwrap();
+        assert_eq!((state.ante, state.hands_per_round), (0, 5));
+    }
+
+    #[test]
+    fn skip_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = GameState::fresh_run(&mut rng);
+        state.skip_blind(Tag::Double, &mut rng).unwrap();
+        assert_eq!(state.blind, Blind::Big);
+        // Two Speed Tags, each paying $5 for each of the two skips
+        state.skip_blind(Tag::Speed, &mut rng).unwrap();
+        assert_eq!(state.money, 4 + 20);
+        assert!(state.tags.is_empty());
+        assert!(matches!(
+            state.skip_blind(Tag::Speed, &mut rng),
+            Err(Error::CannotSkipBoss)
+        ));
+
+        state.tags = vec![Tag::Investment, Tag::Juggle];
+        state.start_round(&mut rng);
+        assert_eq!(state.hand.len(), 11);
+        assert_eq!(state.end_round(), 25);
+        assert!(state.tags.is_empty());
+        assert_eq!(state.unused_discards, 3);
+    }
+
+    #[test]
+    fn stake_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = GameState::fresh_run(&mut rng).with_stake(Stake::Blue);
+        assert_eq!((state.discards, state.discards_per_round), (2, 2));
+        assert_relative_eq!(state.current_target().value(), 300.0);
+        assert_eq!(state.current_reward(), 0);
+        state.ante = 2;
+        assert_relative_eq!(state.current_target().value(), 900.0);
+    }
+
+    #[test]
+    fn stickers_test() {
+        let mut state = GameState::new();
+        state.add_joker(builtin::by_name("Joker").unwrap());
+        state.jokers[0].stickers = Stickers::Eternal | Stickers::Rental;
+        assert!(matches!(state.sell_joker(0), Err(Error::Eternal(_))));
+        state.end_round();
+        assert_eq!(state.money, -3);
+
+        state.jokers[0].stickers = Stickers::Perishable;
+        assert!(!state.jokers[0].is_debuffed());
+        for _ in 0..4 {
+            state.end_round();
+        }
+        // Debuffed after five rounds, so only the pair itself scores
+        let hand = hand!("KH KS");
+        let score = state.play_hand(
+            ScoringContext::new(HandKind::Pair, &hand),
+            Randomness::Expected,
+        );
+        assert_relative_eq!(score.value(), 60.0);
+        assert_eq!(state.sell_joker(0).unwrap(), 1);
+    }
+
+    #[test]
+    fn cash_out_test() {
+        let mut state = GameState::new();
+        state.money = 23;
+        state.hands = 2;
+        state.discards = 1;
+        state.blind = Blind::Big;
+        state.add_joker(builtin::by_name("Golden Joker").unwrap());
+        let cash_out = state.cash_out();
+        assert_eq!(
+            cash_out,
+            CashOut {
+                blind: 4,
+                hands: 2,
+                discards: 0,
+                interest: 4,
+                jokers: 4,
+            }
+        );
+        assert_eq!(state.money, 23 + 14);
+
+        // Interest is capped, unless a voucher raises the cap
+        state.money = 100;
+        assert_eq!(state.interest(), 5);
+        state.redeem_voucher(Voucher::SeedMoney).unwrap();
+        assert_eq!(state.interest(), 10);
+
+        state.economy = EconomyRules::GREEN_DECK;
+        state.hands = 2;
+        state.discards = 1;
+        assert_eq!(state.interest(), 0);
+        assert_eq!(state.cash_out().total(), 4 + 4 + 1 + 4);
+    }
+
+    #[test]
+    fn observatory_test() {
+        let mut state = GameState::new();
+        state.redeem_voucher(Voucher::Telescope).unwrap();
+        state.redeem_voucher(Voucher::Observatory).unwrap();
+        state.consumables = vec![
+            Consumable::Planet(HandKind::Pair),
+            Consumable::Planet(HandKind::Flush),
+        ];
+
+        let hand = hand!("KH KS");
+        // 30 * 2 * 1.5
+        let score = state.play_hand(
+            ScoringContext::new(HandKind::Pair, &hand),
+            Randomness::Expected,
+        );
+        assert_relative_eq!(score.value(), 90.0);
+    }
+
+    #[test]
+    fn fresh_run_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = GameState::fresh_run(&mut rng);
+        assert_eq!((state.ante, state.blind, state.money), (1, Blind::Small, 4));
+        assert_eq!(state.deck.count(), 52);
+        assert!(state.hand.is_empty());
+
+        state.start_round(&mut rng);
+        assert_eq!((state.hands, state.discards), (4, 3));
+        assert_eq!(state.hand.len(), 8);
+        assert_eq!(state.draw_pile.count(), 44);
+    }
+
+    #[test]
+    fn blind_test() {
+        let mut state = GameState::fresh_run(&mut SmallRng::seed_from_u64(0));
+        assert_relative_eq!(state.current_target().value(), 300.0);
+        assert_eq!(state.current_reward(), 3);
+
+        state.advance_blind();
+        state.advance_blind();
+        assert_eq!((state.ante, state.blind), (1, Blind::Boss));
+        assert_relative_eq!(state.current_target().value(), 600.0);
+        state.advance_blind();
+        assert_eq!((state.ante, state.blind), (2, Blind::Small));
+        assert_relative_eq!(state.current_target().value(), 800.0);
+    }
+
+    #[test]
+    fn from_parts_test() {
+        let hand = cards!("AS KS").view().to_vec();
+        let state = GameState::from_parts(
+            Deck::base_deck(),
+            hand.clone(),
+            2,
+            0,
+            10,
+            3,
+            Blind::Boss,
+            Vec::new(),
+            vec![Consumable::Planet(HandKind::Flush)],
+        );
+        assert_eq!(state.hand, hand);
+        assert_eq!(state.draw_pile.count(), 50);
+        assert!(!state.draw_pile.view().contains(&hand[0]));
+    }
+}
@@ -0,0 +1,439 @@
+//! A stable JSON format for `GameState`, so mods and trackers can hand the
+//! solver a run in progress and read one back. `SCHEMA` is the format as a
+//! JSON Schema, for producers that can't read this.
+//!
+//! Every document carries the `version` of the format it's written in, and
+//! versions are only ever added: a document written for version 1 will
+//! always be read the same way. Version 1 looks like this, where only
+//! `version` and `deck` are required:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "deck": ["AS", "KH+glass", "7C+foil+red-seal"],
+//!   "draw_pile": ["7C+foil+red-seal"],
+//!   "hand": ["AS", "KH+glass"],
+//!   "jokers": [
+//!     {
+//!       "name": "Green Joker",
+//!       "edition": "foil",
+//!       "stickers": ["eternal"],
+//!       "rounds_held": 2,
+//!       "state": { "value": 3.0, "counter": 0, "card": null }
+//!     }
+//!   ],
+//!   "consumables": [{ "tarot": "The Fool" }, { "planet": "Flush" }],
+//!   "blind": "Small",
+//!   "ante": 1,
+//!   "stake": "White",
+//!   "money": 4,
+//!   "hands": 4,
+//!   "discards": 3,
+//!   "hand_size": 8,
+//!   "joker_slots": 5,
+//!   "consumable_slots": 2,
+//!   "hand_levels": { "Flush": 3 },
+//!   "hands_played": { "Flush": 2, "Pair": 5 },
+//!   "vouchers": ["Grabber"]
+//! }
+//! ```
+//!
+//! - Cards are their idents, as `Card::ident` writes them.
+//! - The draw pile is drawn from its end. Without one, every card of the deck
+//!   that isn't in hand is left to draw, as `GameState::from_parts` does.
+//! - Jokers, tarots, blinds, stakes and vouchers go by their in-game names,
+//!   in any case. A joker without a `state` starts from its initial one.
+//! - Hands go by their `HandKind`. Hands missing from `hand_levels` are at
+//!   level 1, and ones missing from `hands_played` haven't been played.
+//! - Anything else missing, and everything the format doesn't cover, like
+//!   tags or the economy, is as `GameState::new` has it.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::solver::blind::Blind;
+use crate::solver::cards::{Card, CardView, Deck, Edition, HandKind};
+use crate::solver::consumable::{Consumable, Tarot};
+use crate::solver::error::{Error, Result};
+use crate::solver::game_state::GameState;
+use crate::solver::joker::registry::JokerId;
+use crate::solver::joker::{JokerSlot, JokerState, Stickers};
+use crate::solver::stake::Stake;
+use crate::solver::voucher::Voucher;
+
+/// The version of the format written, and the newest one read.
+pub const VERSION: u32 = 1;
+
+/// The format as a JSON Schema.
+pub const SCHEMA: &str = include_str!("../../../data/game_state.schema.json");
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StateJson {
+    version: u32,
+    deck: Vec<Card>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    draw_pile: Option<Vec<Card>>,
+    hand: Vec<Card>,
+    jokers: Vec<JokerJson>,
+    consumables: Vec<ConsumableJson>,
+    blind: String,
+    ante: u32,
+    stake: String,
+    money: i64,
+    hands: u32,
+    discards: u32,
+    hand_size: usize,
+    joker_slots: usize,
+    consumable_slots: usize,
+    hand_levels: BTreeMap<HandKind, u32>,
+    hands_played: BTreeMap<HandKind, u32>,
+    vouchers: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JokerJson {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    edition: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    stickers: Vec<String>,
+    #[serde(default)]
+    rounds_held: u32,
+    #[serde(default)]
+    state: Option<JokerStateJson>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JokerStateJson {
+    value: f64,
+    counter: i64,
+    card: Option<Card>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", deny_unknown_fields)]
+enum ConsumableJson {
+    Planet(HandKind),
+    Tarot(String),
+}
+
+/// A name as it's written, like `foil` for `Edition::Foil`.
+fn lowercase(value: impl Debug) -> String {
+    format!("{value:?}").to_ascii_lowercase()
+}
+
+/// Parse one of the names in a document, saying what it was meant to be if
+/// it isn't one.
+fn parse<T: FromStr>(what: &str, name: &str) -> Result<T> {
+    name.parse()
+        .map_err(|_| Error::GameState(format!("unknown {what} `{name}`")))
+}
+
+impl Default for StateJson {
+    fn default() -> Self {
+        let mut empty = Self::from(&GameState::new());
+        // Without a draw pile, it's worked out from the deck and hand
+        empty.draw_pile = None;
+        empty
+    }
+}
+
+impl From<&GameState> for StateJson {
+    fn from(state: &GameState) -> Self {
+        let jokers = state
+            .jokers
+            .iter()
+            .map(|slot| JokerJson {
+                name: slot.name().to_owned(),
+                edition: slot.edition.map(lowercase),
+                stickers: slot
+                    .stickers
+                    .iter_names()
+                    .map(|(name, _)| name.to_ascii_lowercase())
+                    .collect(),
+                rounds_held: slot.rounds_held,
+                state: Some(JokerStateJson {
+                    value: slot.state.value,
+                    counter: slot.state.counter,
+                    card: slot.state.card,
+                }),
+            })
+            .collect();
+        let consumables = state
+            .consumables
+            .iter()
+            .map(|consumable| match consumable {
+                Consumable::Planet(kind) => ConsumableJson::Planet(*kind),
+                Consumable::Tarot(tarot) => ConsumableJson::Tarot(tarot.to_string()),
+            })
+            .collect();
+        Self {
+            version: VERSION,
+            deck: state.deck.view().to_vec(),
+            draw_pile: Some(state.draw_pile.view().to_vec()),
+            hand: state.hand.clone(),
+            jokers,
+            consumables,
+            blind: state.blind.to_string(),
+            ante: state.ante,
+            stake: state.stake.to_string(),
+            money: state.money,
+            hands: state.hands,
+            discards: state.discards,
+            hand_size: state.hand_size,
+            joker_slots: state.joker_slots,
+            consumable_slots: state.consumable_slots,
+            hand_levels: HandKind::iter()
+                .map(|kind| (kind, state.hand_levels.level(kind)))
+                .filter(|(_, level)| *level != 1)
+                .collect(),
+            hands_played: HandKind::iter()
+                .map(|kind| (kind, state.hand_levels.times_played(kind)))
+                .filter(|(_, played)| *played != 0)
+                .collect(),
+            vouchers: state.vouchers.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl JokerJson {
+    fn into_slot(self) -> Result<JokerSlot> {
+        let id: JokerId = parse("joker", &self.name)?;
+        let mut slot = JokerSlot::new(id.joker());
+        if let Some(edition) = &self.edition {
+            slot = slot.with_edition(parse::<Edition>("edition", edition)?);
+        }
+        for sticker in &self.stickers {
+            let (_, flag) = Stickers::all()
+                .iter_names()
+                .find(|(name, _)| name.eq_ignore_ascii_case(sticker))
+                .ok_or_else(|| Error::GameState(format!("unknown sticker `{sticker}`")))?;
+            slot.stickers |= flag;
+        }
+        slot.rounds_held = self.rounds_held;
+        if let Some(state) = self.state {
+            slot.state = JokerState {
+                value: state.value,
+                counter: state.counter,
+                card: state.card,
+            };
+        }
+        Ok(slot)
+    }
+}
+
+impl StateJson {
+    fn into_state(self) -> Result<GameState> {
+        let jokers = self
+            .jokers
+            .into_iter()
+            .map(JokerJson::into_slot)
+            .collect::<Result<_>>()?;
+        let consumables = self
+            .consumables
+            .into_iter()
+            .map(|consumable| match consumable {
+                ConsumableJson::Planet(kind) => Ok(Consumable::Planet(kind)),
+                ConsumableJson::Tarot(name) => {
+                    Ok(Consumable::Tarot(parse::<Tarot>("tarot", &name)?))
+                }
+            })
+            .collect::<Result<_>>()?;
+
+        let mut state = GameState::from_parts(
+            Deck::from_cards(self.deck),
+            self.hand,
+            self.hands,
+            self.discards,
+            self.money,
+            self.ante,
+            parse::<Blind>("blind", &self.blind)?,
+            jokers,
+            consumables,
+        );
+        if let Some(draw_pile) = self.draw_pile {
+            state.draw_pile = Deck::from_cards(draw_pile);
+        }
+        state.stake = parse::<Stake>("stake", &self.stake)?;
+        state.hand_size = self.hand_size;
+        state.joker_slots = self.joker_slots;
+        state.consumable_slots = self.consumable_slots;
+        for (kind, level) in self.hand_levels {
+            state.hand_levels.set_level(kind, level);
+        }
+        for (kind, played) in self.hands_played {
+            state.hand_levels.set_times_played(kind, played);
+        }
+        state.vouchers = self
+            .vouchers
+            .iter()
+            .map(|name| parse::<Voucher>("voucher", name))
+            .collect::<Result<_>>()?;
+        Ok(state)
+    }
+}
+
+pub(crate) fn to_json(state: &GameState) -> String {
+    serde_json::to_string_pretty(&StateJson::from(state))
+        .expect("game states are always valid JSON")
+}
+
+pub(crate) fn from_json(json: &str) -> Result<GameState> {
+    let invalid = |err: serde_json::Error| Error::GameState(err.to_string());
+    // The version decides how the rest is read, so it's checked first
+    let document: serde_json::Value = serde_json::from_str(json).map_err(invalid)?;
+    let version = document
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| Error::GameState("missing `version`".to_owned()))?;
+    if version != u64::from(VERSION) {
+        return Err(Error::GameStateVersion(version));
+    }
+    if document.get("deck").is_none() {
+        return Err(Error::GameState("missing `deck`".to_owned()));
+    }
+    serde_json::from_value::<StateJson>(document)
+        .map_err(invalid)?
+        .into_state()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::cards::Enhancement;
+    use crate::solver::joker::builtin;
+    use crate::solver::starting_deck::StartingDeck;
+    use crate::{card, cards};
+
+    /// A run with something in every part of the format.
+    fn sample_state() -> GameState {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = GameState::fresh_run_with(StartingDeck::Plain, &mut rng);
+        state.hand = vec![
+            card!("AS"),
+            card!("KH").with_enhancement(Enhancement::Glass),
+        ];
+        let mut green = JokerSlot::new(builtin::by_name("Green Joker").unwrap())
+            .with_edition(Edition::Foil)
+            .with_stickers(Stickers::Eternal | Stickers::Rental);
+        green.state.value = 3.0;
+        green.rounds_held = 2;
+        state.jokers.push(green);
+        state.consumables = vec![
+            Consumable::Tarot(Tarot::TheFool),
+            Consumable::Planet(HandKind::Flush),
+        ];
+        state.hand_levels.set_level(HandKind::Flush, 3);
+        state.hand_levels.record_played(HandKind::Pair);
+        state.vouchers.push(Voucher::Grabber);
+        state.money = 12;
+        state.stake = Stake::Red;
+        state
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let state = sample_state();
+        let json = state.to_json();
+        let read = GameState::from_json(&json).unwrap();
+        assert_eq!(read.to_json(), json);
+        assert_eq!(read.deck.view(), state.deck.view());
+        assert_eq!(read.draw_pile.view(), state.draw_pile.view());
+        assert_eq!(read.hand, state.hand);
+        assert_eq!(read.jokers[0].name(), "Green Joker");
+        assert_eq!(
+            read.jokers[0].stickers,
+            Stickers::Eternal | Stickers::Rental
+        );
+        assert_eq!(read.jokers[0].state, state.jokers[0].state);
+        assert_eq!(read.consumables, state.consumables);
+        assert_eq!(read.hand_levels, state.hand_levels);
+        assert_eq!(read.vouchers, state.vouchers);
+        assert_eq!(read.stake, Stake::Red);
+    }
+
+    #[test]
+    fn defaults_test() {
+        let state = GameState::from_json(
+            r#"{"version": 1, "deck": ["AS", "KH", "2C"], "hand": ["KH"], "blind": "boss",
+                "jokers": [{"name": "joker"}], "consumables": [{"tarot": "the fool"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(state.draw_pile.view(), cards!("AS 2C").view());
+        assert_eq!(state.blind, Blind::Boss);
+        assert_eq!(state.jokers[0].name(), "Joker");
+        assert_eq!(state.hand_size, GameState::new().hand_size);
+    }
+
+    #[test]
+    fn invalid_test() {
+        for (json, expected) in [
+            (r#"{"deck": []}"#, "missing `version`"),
+            (r#"{"version": 1}"#, "missing `deck`"),
+            (
+                r#"{"version": 1, "deck": [], "blind": "Huge"}"#,
+                "unknown blind `Huge`",
+            ),
+            (
+                r#"{"version": 1, "deck": [], "jokers": [{"name": "Nope"}]}"#,
+                "unknown joker `Nope`",
+            ),
+        ] {
+            let err = GameState::from_json(json).unwrap_err();
+            assert!(matches!(err, Error::GameState(message) if message == expected));
+        }
+        assert!(matches!(
+            GameState::from_json(r#"{"version": 2, "deck": []}"#),
+            Err(Error::GameStateVersion(2))
+        ));
+        assert!(GameState::from_json(r#"{"version": 1, "deck": [], "tags": []}"#).is_err());
+    }
+
+    #[test]
+    fn schema_test() {
+        let schema: serde_json::Value = serde_json::from_str(SCHEMA).unwrap();
+        assert_eq!(
+            schema["properties"]["version"]["const"],
+            serde_json::json!(VERSION)
+        );
+
+        // Everything written is in the schema, and everything in the schema
+        // is written when the run has something for it
+        let names = |object: &serde_json::Value| {
+            object
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let mut written: serde_json::Value =
+            serde_json::from_str(&sample_state().to_json()).unwrap();
+        let joker = written["jokers"][0].take();
+        let joker_schema = &schema["$defs"]["joker"];
+        for (object, properties) in [
+            (&written, &schema["properties"]),
+            (&joker, &joker_schema["properties"]),
+            (
+                &joker["state"],
+                &joker_schema["properties"]["state"]["properties"],
+            ),
+        ] {
+            let mut object = names(object);
+            let mut properties = names(properties);
+            object.sort();
+            properties.sort();
+            assert_eq!(object, properties);
+        }
+    }
+}
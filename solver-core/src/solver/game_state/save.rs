@@ -0,0 +1,712 @@
+//! Balatro's own save files, so the solver can pick up a run where the game
+//! left it rather than having it typed in.
+//!
+//! The game keeps the run in progress in `save.jkr`, in its profile's folder
+//! (`%AppData%/Balatro/1/save.jkr` on Windows). That's a raw DEFLATE stream
+//! of a Lua chunk, `return {...}`, holding one big table: the cards in each
+//! area under `cardAreas`, and everything else about the run under `GAME`.
+//!
+//! What's read:
+//!
+//! - The cards in the deck, hand, discard pile and play area, with their
+//!   enhancements, editions and seals. Only the deck's are left to draw.
+//! - The jokers, with their editions and stickers, and the state of the ones
+//!   that build it up, like Green Joker or The Idol. A joker the solver
+//!   doesn't know fails the read, since leaving it out would change scores.
+//! - Planets and tarots held. Spectral cards are left out, since the solver
+//!   doesn't model them.
+//! - The blind, ante, stake, money, hands and discards, hand size, slots,
+//!   hand levels and vouchers.
+//!
+//! Anything else, like tags or the shop, is as `GameState::new` has it.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::str::FromStr;
+
+use flate2::read::DeflateDecoder;
+use strum::IntoEnumIterator;
+
+use crate::solver::blind::Blind;
+use crate::solver::cards::{Card, Deck, Edition, Enhancement, HandKind, Rank, Seal, Suit};
+use crate::solver::consumable::{Consumable, Tarot};
+use crate::solver::error::{Error, Result};
+use crate::solver::game_state::GameState;
+use crate::solver::joker::registry::JokerId;
+use crate::solver::joker::{JokerSlot, Stickers};
+use crate::solver::stake::Stake;
+use crate::solver::voucher::Voucher;
+
+/// How deeply tables can nest. The game's own saves nest a handful deep, so
+/// anything past this is a corrupt or hostile file that would otherwise
+/// recurse until the stack overflows.
+const MAX_DEPTH: usize = 64;
+
+/// The most a save can decompress to. Real saves are well under a megabyte.
+const MAX_SIZE: u64 = 16 * 1024 * 1024;
+
+fn invalid(message: String) -> Error {
+    Error::SaveFile(message)
+}
+
+/// Parse one of the names in a save, saying what it was meant to be if it
+/// isn't one.
+fn parse<T: FromStr>(what: &str, name: &str) -> Result<T> {
+    name.parse()
+        .map_err(|_| invalid(format!("unknown {what} `{name}`")))
+}
+
+/// A table key. Integer keys sort before names, so a table's array part
+/// comes first, in order.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Key {
+    Index(i64),
+    Name(String),
+}
+
+/// A Lua value, as far as save files use them.
+#[derive(Debug, PartialEq)]
+enum Value {
+    Nil,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Table(BTreeMap<Key, Self>),
+}
+
+impl Value {
+    fn get(&self, name: &str) -> Option<&Self> {
+        match self {
+            Self::Table(entries) => entries.get(&Key::Name(name.to_owned())),
+            _ => None,
+        }
+    }
+
+    /// The value at a path of names separated by dots, like
+    /// `current_round.hands_left`.
+    fn path(&self, path: &str) -> Option<&Self> {
+        path.split('.').try_fold(self, Self::get)
+    }
+
+    fn field(&self, path: &str) -> Result<&Self> {
+        self.path(path)
+            .ok_or_else(|| invalid(format!("missing `{path}`")))
+    }
+
+    fn string(&self, path: &str) -> Result<&str> {
+        match self.field(path)? {
+            Self::String(string) => Ok(string),
+            _ => Err(invalid(format!("`{path}` isn't a string"))),
+        }
+    }
+
+    fn number(&self, path: &str) -> Result<f64> {
+        match self.field(path)? {
+            Self::Number(number) => Ok(*number),
+            _ => Err(invalid(format!("`{path}` isn't a number"))),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn count(&self, path: &str) -> Result<u32> {
+        let number = self.number(path)?;
+        if !(0.0..=f64::from(u32::MAX)).contains(&number) {
+            return Err(invalid(format!("`{path}` isn't a count")));
+        }
+        Ok(number.round() as u32)
+    }
+
+    /// A count that the game only saves some of the time.
+    fn count_or(&self, path: &str, default: u32) -> Result<u32> {
+        match self.path(path) {
+            Some(_) => self.count(path),
+            None => Ok(default),
+        }
+    }
+
+    fn flag(&self, path: &str) -> bool {
+        matches!(self.path(path), Some(Self::Bool(true)))
+    }
+
+    /// The array part of a table, in order.
+    fn items(&self) -> impl Iterator<Item = &Self> {
+        let entries = match self {
+            Self::Table(entries) => Some(entries),
+            _ => None,
+        };
+        entries
+            .into_iter()
+            .flatten()
+            .filter(|(key, _)| matches!(key, Key::Index(_)))
+            .map(|(_, value)| value)
+    }
+
+    /// The names of a table's entries, other than its array part.
+    fn names(&self) -> impl Iterator<Item = (&str, &Self)> {
+        let entries = match self {
+            Self::Table(entries) => Some(entries),
+            _ => None,
+        };
+        entries
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, value)| match key {
+                Key::Name(name) => Some((name.as_str(), value)),
+                Key::Index(_) => None,
+            })
+    }
+}
+
+/// Reads the table literal the game writes: strings, numbers, booleans and
+/// nested tables, with keys in brackets, bare names, or left out.
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    /// How many tables the parser is inside
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a [u8]) -> Self {
+        Self {
+            text,
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    fn error(&self, expected: &str) -> Error {
+        invalid(format!("expected {expected} at byte {}", self.pos))
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        while self.text.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+        self.text.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("`{}`", char::from(byte))))
+        }
+    }
+
+    fn word(&mut self) -> &str {
+        self.peek();
+        let start = self.pos;
+        while self
+            .text
+            .get(self.pos)
+            .is_some_and(|byte| byte.is_ascii_alphanumeric() || *byte == b'_')
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.text[start..self.pos]).expect("words are ASCII")
+    }
+
+    fn chunk(&mut self) -> Result<Value> {
+        if self.word() != "return" {
+            return Err(self.error("`return`"));
+        }
+        let value = self.value()?;
+        match self.peek() {
+            None => Ok(value),
+            Some(_) => Err(self.error("the end")),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.peek() {
+            Some(b'{') => self.table(),
+            Some(quote @ (b'"' | b'\'')) => self.string(quote).map(Value::String),
+            Some(b'-' | b'.' | b'0'..=b'9') => self.number().map(Value::Number),
+            Some(byte) if byte.is_ascii_alphabetic() => match self.word() {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                "nil" => Ok(Value::Nil),
+                "inf" => Ok(Value::Number(f64::INFINITY)),
+                "nan" => Ok(Value::Number(f64::NAN)),
+                _ => Err(self.error("a value")),
+            },
+            _ => Err(self.error("a value")),
+        }
+    }
+
+    fn table(&mut self) -> Result<Value> {
+        if self.depth == MAX_DEPTH {
+            return Err(invalid(format!(
+                "tables nested more than {MAX_DEPTH} deep at byte {}",
+                self.pos
+            )));
+        }
+        self.depth += 1;
+        let table = self.entries();
+        self.depth -= 1;
+        table
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn entries(&mut self) -> Result<Value> {
+        self.expect(b'{')?;
+        let mut entries = BTreeMap::new();
+        let mut next_index = 1;
+        while !self.eat(b'}') {
+            let key = if self.eat(b'[') {
+                let key = match self.value()? {
+                    Value::String(name) => Key::Name(name),
+                    Value::Number(number) if number.fract() == 0.0 => Key::Index(number as i64),
+                    _ => return Err(self.error("a name or an integer key")),
+                };
+                self.expect(b']')?;
+                self.expect(b'=')?;
+                key
+            } else {
+                // A bare name is a key if it's followed by `=`, and otherwise
+                // the start of a value in the array part
+                let start = self.pos;
+                let name = self.word().to_owned();
+                if !name.is_empty() && self.eat(b'=') {
+                    Key::Name(name)
+                } else {
+                    self.pos = start;
+                    next_index += 1;
+                    Key::Index(next_index - 1)
+                }
+            };
+            entries.insert(key, self.value()?);
+            if !self.eat(b',') && !self.eat(b';') {
+                self.expect(b'}')?;
+                break;
+            }
+        }
+        Ok(Value::Table(entries))
+    }
+
+    fn string(&mut self, quote: u8) -> Result<String> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&byte) = self.text.get(self.pos) else {
+                return Err(self.error("the end of the string"));
+            };
+            self.pos += 1;
+            match byte {
+                _ if byte == quote => break,
+                b'\\' => {
+                    let Some(&escaped) = self.text.get(self.pos) else {
+                        return Err(self.error("an escape"));
+                    };
+                    self.pos += 1;
+                    bytes.push(match escaped {
+                        b'n' => b'\n',
+                        b't' => b'\t',
+                        b'r' => b'\r',
+                        other => other,
+                    });
+                }
+                _ => bytes.push(byte),
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn number(&mut self) -> Result<f64> {
+        let start = self.pos;
+        if self.text.get(self.pos) == Some(&b'-') {
+            self.pos += 1;
+            match self.word() {
+                "inf" => return Ok(f64::NEG_INFINITY),
+                "nan" => return Ok(f64::NAN),
+                _ => self.pos = start + 1,
+            }
+        }
+        while self.text.get(self.pos).is_some_and(|byte| {
+            byte.is_ascii_digit() || matches!(byte, b'.' | b'e' | b'E' | b'+' | b'-')
+        }) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.text[start..self.pos])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| {
+                self.pos = start;
+                self.error("a number")
+            })
+    }
+}
+
+/// A save file's Lua source, decompressing it if it hasn't been already.
+pub fn decompress(bytes: &[u8]) -> Result<String> {
+    if bytes.starts_with(b"return") {
+        return Ok(String::from_utf8_lossy(bytes).into_owned());
+    }
+    let mut text = Vec::new();
+    DeflateDecoder::new(bytes)
+        .take(MAX_SIZE + 1)
+        .read_to_end(&mut text)
+        .map_err(|err| invalid(format!("couldn't decompress it: {err}")))?;
+    if text.len() as u64 > MAX_SIZE {
+        return Err(invalid(format!(
+            "it decompresses to more than {MAX_SIZE} bytes"
+        )));
+    }
+    Ok(String::from_utf8_lossy(&text).into_owned())
+}
+
+/// A rank as the game names it, like `10` or `Queen`.
+fn rank(name: &str) -> Result<Rank> {
+    let rank = match name {
+        "2" => Rank::Deuce,
+        "3" => Rank::Three,
+        "4" => Rank::Four,
+        "5" => Rank::Five,
+        "6" => Rank::Six,
+        "7" => Rank::Seven,
+        "8" => Rank::Eight,
+        "9" => Rank::Nine,
+        "10" => Rank::Ten,
+        "Jack" => Rank::Jack,
+        "Queen" => Rank::Queen,
+        "King" => Rank::King,
+        "Ace" => Rank::Ace,
+        _ => return Err(invalid(format!("unknown rank `{name}`"))),
+    };
+    Ok(rank)
+}
+
+/// A hand as the game names it, like `Three of a Kind`.
+fn hand_kind(name: &str) -> Result<HandKind> {
+    let squashed = name.replace(' ', "");
+    HandKind::iter()
+        .find(|kind| format!("{kind:?}").eq_ignore_ascii_case(&squashed))
+        .ok_or_else(|| invalid(format!("unknown hand `{name}`")))
+}
+
+fn read_edition(card: &Value) -> Result<Option<Edition>> {
+    let Some(edition) = card.get("edition") else {
+        return Ok(None);
+    };
+    [
+        ("foil", Edition::Foil),
+        ("holo", Edition::Holographic),
+        ("polychrome", Edition::Polychrome),
+        ("negative", Edition::Negative),
+    ]
+    .into_iter()
+    .find(|(flag, _)| edition.flag(flag))
+    .map(|(_, edition)| Some(edition))
+    .ok_or_else(|| invalid("unknown edition".to_owned()))
+}
+
+fn read_card(card: &Value) -> Result<Card> {
+    // Like `H_T`, for the Ten of Hearts
+    let key = card.string("save_fields.card")?;
+    let (suit, rank) = key
+        .split_once('_')
+        .ok_or_else(|| invalid(format!("unknown card `{key}`")))?;
+    let mut read: Card = format!("{rank}{suit}")
+        .parse()
+        .map_err(|_| invalid(format!("unknown card `{key}`")))?;
+    if let Some(enhancement) = card.string("save_fields.center")?.strip_prefix("m_") {
+        read.enhancement = Some(parse::<Enhancement>("enhancement", enhancement)?);
+    }
+    read.edition = read_edition(card)?;
+    if card.get("seal").is_some() {
+        read.seal = Some(parse::<Seal>("seal", card.string("seal")?)?);
+    }
+    Ok(read)
+}
+
+fn read_joker(joker: &Value, game: &Value) -> Result<JokerSlot> {
+    let name = joker.string("ability.name")?;
+    let id: JokerId = name
+        .parse()
+        .map_err(|_| Error::UnknownJoker(name.to_owned()))?;
+    let mut slot = JokerSlot::new(id.joker());
+    if let Some(edition) = read_edition(joker)? {
+        slot = slot.with_edition(edition);
+    }
+    for (flag, sticker) in [
+        ("ability.eternal", Stickers::Eternal),
+        ("ability.perishable", Stickers::Perishable),
+        ("ability.rental", Stickers::Rental),
+    ] {
+        if joker.flag(flag) {
+            slot.stickers |= sticker;
+        }
+    }
+    if slot.stickers.contains(Stickers::Perishable) {
+        // The game counts down the rounds left instead
+        let left = joker.count("ability.perish_tally")?;
+        slot.rounds_held = JokerSlot::PERISHABLE_ROUNDS.saturating_sub(left);
+    }
+
+    // The cards picked each round are kept with the round, not the jokers
+    let picked = |path: &str| -> Result<Option<Card>> {
+        let Some(picked) = game.path(path) else {
+            return Ok(None);
+        };
+        let rank = match picked.get("rank") {
+            Some(_) => rank(picked.string("rank")?)?,
+            None => Rank::Ace,
+        };
+        let suit = match picked.get("suit") {
+            Some(_) => parse::<Suit>("suit", picked.string("suit")?)?,
+            None => Suit::Spades,
+        };
+        Ok(Some(Card::new(rank, suit)))
+    };
+    match id {
+        JokerId::GreenJoker | JokerId::RideTheBus => {
+            slot.state.value = joker.number("ability.mult")?;
+        }
+        JokerId::Hologram | JokerId::LuckyCat => {
+            slot.state.value = joker.number("ability.x_mult")?;
+        }
+        JokerId::Rocket => slot.state.value = joker.number("ability.extra.dollars")?,
+        JokerId::Seltzer => slot.state.counter = joker.count("ability.extra")?.into(),
+        JokerId::TheIdol => slot.state.card = picked("current_round.idol_card")?,
+        JokerId::AncientJoker => slot.state.card = picked("current_round.ancient_card")?,
+        JokerId::MailInRebate => slot.state.card = picked("current_round.mail_card")?,
+        _ => {}
+    }
+    Ok(slot)
+}
+
+/// A planet or tarot, or `None` for a spectral card.
+fn read_consumable(card: &Value) -> Result<Option<Consumable>> {
+    let consumable = match card.string("ability.set")? {
+        "Planet" => Consumable::Planet(hand_kind(card.string("ability.consumeable.hand_type")?)?),
+        "Tarot" => Consumable::Tarot(parse::<Tarot>("tarot", card.string("ability.name")?)?),
+        _ => return Ok(None),
+    };
+    Ok(Some(consumable))
+}
+
+/// A voucher from its key, like `v_seed_money`.
+fn read_voucher(key: &str) -> Result<Voucher> {
+    let name = match key.trim_start_matches("v_") {
+        "overstock_norm" => "overstock".to_owned(),
+        "directors_cut" => "director's cut".to_owned(),
+        name => name.replace('_', " "),
+    };
+    parse("voucher", &name)
+}
+
+fn read_state(save: &Value) -> Result<GameState> {
+    let areas = save.field("cardAreas")?;
+    let game = save.field("GAME")?;
+    let cards = |area: &str| -> Result<Vec<&Value>> {
+        Ok(areas.field(area)?.field("cards")?.items().collect())
+    };
+    let read_cards = |area: &str| -> Result<Vec<Card>> {
+        // The discard pile and play area are only saved when they're in use
+        if areas.get(area).is_none() {
+            return Ok(Vec::new());
+        }
+        cards(area)?.into_iter().map(read_card).collect()
+    };
+
+    let draw_pile = read_cards("deck")?;
+    let hand = read_cards("hand")?;
+    let mut deck = draw_pile.clone();
+    deck.extend_from_slice(&hand);
+    deck.extend(read_cards("discard")?);
+    deck.extend(read_cards("play")?);
+
+    let jokers: Vec<JokerSlot> = cards("jokers")?
+        .into_iter()
+        .map(|joker| read_joker(joker, game))
+        .collect::<Result<_>>()?;
+    let consumables = cards("consumeables")?
+        .into_iter()
+        .filter_map(|card| read_consumable(card).transpose())
+        .collect::<Result<Vec<_>>>()?;
+    let blind = match game.get("blind_on_deck") {
+        Some(_) => parse::<Blind>("blind", game.string("blind_on_deck")?)?,
+        None => Blind::Small,
+    };
+    #[allow(clippy::cast_possible_truncation)]
+    let money = game.number("dollars")?.round() as i64;
+
+    let mut state = GameState::from_parts(
+        Deck::from_cards(deck),
+        hand,
+        game.count("current_round.hands_left")?,
+        game.count("current_round.discards_left")?,
+        money,
+        game.count("round_resets.ante")?,
+        blind,
+        jokers,
+        consumables,
+    );
+    state.draw_pile = Deck::from_cards(draw_pile);
+    // Stakes are numbered from 1, for White
+    state.stake = Stake::iter()
+        .nth(game.count("stake")?.saturating_sub(1) as usize)
+        .ok_or_else(|| invalid("unknown stake".to_owned()))?;
+    state.hands_per_round = game.count("round_resets.hands")?;
+    state.discards_per_round = game.count("round_resets.discards")?;
+    state.discards_used = game.count_or("current_round.discards_used", 0)?;
+    state.unused_discards = game.count_or("unused_discards", 0)?;
+    state.blinds_skipped = game.count_or("skips", 0)?;
+
+    // The game counts Negative cards in the slots; the solver doesn't
+    let negatives = |held: &[Option<Edition>]| {
+        held.iter()
+            .filter(|edition| **edition == Some(Edition::Negative))
+            .count()
+    };
+    let slots = |area: &str| -> Result<usize> {
+        Ok(areas.count(&format!("{area}.config.card_limit"))? as usize)
+    };
+    state.hand_size = slots("hand")?;
+    let editions = state
+        .jokers
+        .iter()
+        .map(|slot| slot.edition)
+        .collect::<Vec<_>>();
+    state.joker_slots = slots("jokers")?.saturating_sub(negatives(&editions));
+    let editions = cards("consumeables")?
+        .into_iter()
+        .map(read_edition)
+        .collect::<Result<Vec<_>>>()?;
+    state.consumable_slots = slots("consumeables")?.saturating_sub(negatives(&editions));
+
+    if let Some(hands) = game.get("hands") {
+        for (name, hand) in hands.names() {
+            let kind = hand_kind(name)?;
+            state.hand_levels.set_level(kind, hand.count("level")?);
+            state
+                .hand_levels
+                .set_times_played(kind, hand.count("played")?);
+        }
+    }
+    if let Some(vouchers) = game.get("used_vouchers") {
+        state.vouchers = vouchers
+            .names()
+            .filter(|(_, used)| **used == Value::Bool(true))
+            .map(|(key, _)| read_voucher(key))
+            .collect::<Result<_>>()?;
+    }
+    Ok(state)
+}
+
+pub(crate) fn from_save(bytes: &[u8]) -> Result<GameState> {
+    let text = decompress(bytes)?;
+    let save = Parser::new(text.as_bytes()).chunk()?;
+    read_state(&save)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use approx::assert_relative_eq;
+    use flate2::write::DeflateEncoder;
+    use flate2::Compression;
+
+    use super::*;
+    use crate::solver::cards::CardView;
+    use crate::{card, cards};
+
+    /// A save from the Big Blind of ante 2, trimmed down to what's read.
+    const SAVE: &str = r#"return {["cardAreas"]={["deck"]={["cards"]={[1]={["save_fields"]={["center"]="c_base",["card"]="S_2",},},[2]={["save_fields"]={["center"]="m_glass",["card"]="D_T",},},},["config"]={["card_limit"]=52,},},["hand"]={["cards"]={[1]={["save_fields"]={["center"]="c_base",["card"]="H_K",},["edition"]={["holo"]=true,["mult"]=10,["type"]="holo",},["seal"]="Red",},[2]={["save_fields"]={["center"]="c_base",["card"]="C_A",},},},["config"]={["card_limit"]=8,},},["jokers"]={["cards"]={[1]={["save_fields"]={["center"]="j_green_joker",},["ability"]={["name"]="Green Joker",["mult"]=3,["eternal"]=true,},["edition"]={["negative"]=true,},},[2]={["save_fields"]={["center"]="j_idol",},["ability"]={["name"]="The Idol",["perishable"]=true,["perish_tally"]=3,},},},["config"]={["card_limit"]=6,},},["consumeables"]={["cards"]={[1]={["save_fields"]={["center"]="c_jupiter",},["ability"]={["name"]="Jupiter",["set"]="Planet",["consumeable"]={["hand_type"]="Flush",},},},[2]={["save_fields"]={["center"]="c_familiar",},["ability"]={["name"]="Familiar",["set"]="Spectral",},},},["config"]={["card_limit"]=2,},},},["GAME"]={["dollars"]=12,["stake"]=2,["blind_on_deck"]="Big",["round_resets"]={["ante"]=2,["hands"]=4,["discards"]=3,},["current_round"]={["hands_left"]=3,["discards_left"]=2,["discards_used"]=1,["idol_card"]={["suit"]="Hearts",["rank"]="King",["id"]=13,},},["hands"]={["Flush"]={["level"]=3,["played"]=2,},["Three of a Kind"]={["level"]=1,["played"]=0,},},["used_vouchers"]={["v_overstock_norm"]=true,["v_grabber"]=true,},},}"#;
+
+    fn compress(text: &str) -> Vec<u8> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn save_test() {
+        let state = GameState::from_save(&compress(SAVE)).unwrap();
+        assert_eq!(state.draw_pile.view(), cards!("2S TD+glass").view());
+        assert_eq!(state.hand, cards!("KH+holographic+red-seal AC").view());
+        assert_eq!(state.deck.view().len(), 4);
+        assert_eq!(
+            (state.hands, state.discards, state.discards_used),
+            (3, 2, 1)
+        );
+        assert_eq!((state.money, state.ante, state.blind), (12, 2, Blind::Big));
+        assert_eq!(state.stake, Stake::Red);
+
+        assert_eq!(state.jokers[0].name(), "Green Joker");
+        assert_relative_eq!(state.jokers[0].state.value, 3.0);
+        assert_eq!(state.jokers[0].stickers, Stickers::Eternal);
+        assert_eq!(state.jokers[1].state.card, Some(card!("KH")));
+        assert_eq!(state.jokers[1].rounds_held, 2);
+        // The Negative Green Joker takes up the sixth slot
+        assert_eq!(state.joker_slots, 5);
+        assert_eq!(state.consumables, vec![Consumable::Planet(HandKind::Flush)]);
+
+        assert_eq!(state.hand_levels.level(HandKind::Flush), 3);
+        assert_eq!(state.hand_levels.times_played(HandKind::Flush), 2);
+        assert_eq!(state.vouchers, vec![Voucher::Grabber, Voucher::Overstock]);
+
+        // Saves that have already been decompressed are read as they are
+        let plain = GameState::from_save(SAVE.as_bytes()).unwrap();
+        assert_eq!(plain.hand, state.hand);
+    }
+
+    #[test]
+    fn parser_test() {
+        let mut parser = Parser::new(
+            br#"return { "a", 'b\'c', name = -1.5e2, [3] = { nested = true }, [5]=nil; }"#,
+        );
+        let value = parser.chunk().unwrap();
+        let items: Vec<_> = value.items().collect();
+        assert_eq!(items[0], &Value::String("a".to_owned()));
+        assert_eq!(items[1], &Value::String("b'c".to_owned()));
+        assert!(value
+            .path("name")
+            .is_some_and(|name| *name == Value::Number(-150.0)));
+        assert!(items[2].flag("nested"));
+        assert_eq!(items[3], &Value::Nil);
+
+        for text in ["{}", "return {", "return {} {}", "return {[true]=1}"] {
+            let mut parser = Parser::new(text.as_bytes());
+            assert!(parser.chunk().is_err(), "{text}");
+        }
+
+        let nested = format!("return {}{}", "{".repeat(100_000), "}".repeat(100_000));
+        assert!(matches!(
+            Parser::new(nested.as_bytes()).chunk(),
+            Err(Error::SaveFile(message)) if message.starts_with("tables nested more than 64 deep")
+        ));
+        let shallow = format!("return {}{}", "{".repeat(64), "}".repeat(64));
+        assert!(Parser::new(shallow.as_bytes()).chunk().is_ok());
+    }
+
+    #[test]
+    fn invalid_test() {
+        let unknown = SAVE.replace("The Idol", "Not A Joker");
+        assert!(matches!(
+            GameState::from_save(&compress(&unknown)),
+            Err(Error::UnknownJoker(name)) if name == "Not A Joker"
+        ));
+        let missing = SAVE.replace("[\"dollars\"]=12,", "");
+        assert!(matches!(
+            GameState::from_save(&compress(&missing)),
+            Err(Error::SaveFile(message)) if message == "missing `dollars`"
+        ));
+        assert!(GameState::from_save(b"not a save").is_err());
+        let huge = compress(&" ".repeat(usize::try_from(MAX_SIZE).unwrap() + 1));
+        assert!(matches!(
+            decompress(&huge),
+            Err(Error::SaveFile(message)) if message.starts_with("it decompresses to more than")
+        ));
+    }
+}
@@ -1,570 +1,994 @@
-use std::cmp::Ordering;
-use std::thread::current;
-
-use bitflags::bitflags;
-use heapless;
-use itertools::Itertools;
-use slab::Slab;
-use strum::IntoEnumIterator;
-
-use crate::solver::cards::{Card, CardView, Hand, HandKind, Rank, Suit};
-use crate::solver::cardset::CardSet;
-use crate::solver::error::{Error, Result};
-use crate::{card, cards, hand};
-
-bitflags! {
-    #[derive(Copy, Clone, Debug)]
-    pub struct Options: u32 {
-        const GappedStraights             = 0b0001;
-        const FourCardStraightsAndFlushes = 0b0010;
-    }
-}
-
-#[derive(Debug)]
-pub struct HandEvaluator {
-    len: usize,
-    cards: Hand,
-    sorted: Hand,
-    cardset: CardSet,
-    options: Options,
-}
-
-impl HandEvaluator {
-    fn new(card_view: impl CardView, options: Options) -> Self {
-        let card_slice = card_view.view();
-        let len = card_slice.len();
-
-        // For now, we're restricting ourselves to scoring 5-card hands, because that eliminates
-        // any "tiebreaking" that we'd have to do when scoring more than 5 cards at once.
-        // This doesn't directly affect Balatro, because you can only play 5 cards anyways, but it's
-        // probably still worth extending to this functionality at some point.
-        assert!(len <= 5);
-
-        let cards = Hand::from_slice(card_slice).unwrap();
-
-        let mut sorted = cards.clone();
-        sorted.cards.sort_by(|a, b| b.cmp(a));
-
-        let cardset = CardSet::from(card_view);
-
-        Self {
-            len,
-            cards,
-            sorted,
-            cardset,
-            options,
-        }
-    }
-
-    fn evaluate_high_card(&self) -> Option<Hand> {
-        Some(Hand::from_slice(&[*self.sorted.cards.first()?]).unwrap())
-    }
-
-    fn evaluate_suit_matches(&self, four_card: bool) -> Option<Hand> {
-        let length = if four_card { 4 } else { 5 };
-
-        if self.len < length {
-            return None;
-        }
-
-        if self.cardset.count() < self.len {
-            let mut seen: [usize; 4] = [0, 0, 0, 0];
-
-            for card in self.cards.view() {
-                seen[card.suit as usize] += 1;
-            }
-
-            for count in seen {
-                if count >= length {
-                    return Some(self.cards.clone());
-                }
-            }
-
-            None
-        } else {
-            Suit::iter()
-                .find(|suit| self.cardset.count_in_suit(*suit) == 5)
-                .map(|_| self.cards.clone())
-        }
-    }
-
-    fn evaluate_run(&self) -> Option<Hand> {
-        #[inline]
-        fn is_consecutive(left: Rank, right: Rank) -> bool {
-            if left == Rank::Deuce && right == Rank::Ace {
-                true
-            } else {
-                (left as i32 - 1) == (right as i32)
-            }
-        }
-
-        #[inline]
-        fn has_gap(left: Rank, right: Rank) -> bool {
-            if left == Rank::Three && right == Rank::Ace {
-                true
-            } else {
-                (left as i32 - 2) == (right as i32)
-            }
-        }
-
-        let four_card = self.options.contains(Options::FourCardStraightsAndFlushes);
-
-        let min_length = if four_card { 4 } else { 5 };
-        if self.len < min_length {
-            return None;
-        }
-
-        let mut can_gap = self.options.contains(Options::GappedStraights);
-        let mut can_fail = self.options.contains(Options::FourCardStraightsAndFlushes);
-        let mut straight_length = 1;
-
-        for (i, (left, right)) in self.sorted.view().iter().tuple_windows().enumerate() {
-            let consecutive = is_consecutive(left.rank, right.rank);
-            let gapped = has_gap(left.rank, right.rank);
-
-            if consecutive {
-                straight_length += 1;
-            } else if gapped && can_gap {
-                straight_length += 1;
-                can_gap = false;
-            } else if four_card && i == 3 {
-                break;
-            } else if can_fail {
-                straight_length = 1;
-                can_fail = false;
-            } else {
-                break;
-            }
-        }
-
-        if straight_length == 5 || (self.len == 4 && four_card && straight_length == 4) {
-            Some(self.cards.clone())
-        } else if four_card && straight_length == 4 {
-            let except_card = if can_fail { 4 } else { 0 };
-
-            let mut ditched_card = false;
-            let mut vec = heapless::Vec::<_, 5>::new();
-
-            for card in self.cards.view() {
-                if !ditched_card && card == &self.sorted.view()[except_card] {
-                    ditched_card = true;
-                    continue;
-                }
-                vec.push(*card).unwrap();
-            }
-
-            Some(Hand::from(vec))
-        } else {
-            None
-        }
-    }
-
-    fn evaluate_rank_matches(&self, match_size: usize, match_count: usize) -> Option<Hand> {
-        let min_length = match_size * match_count;
-        if self.len < min_length {
-            return None;
-        }
-
-        let mut ranks: [u8; 13] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-
-        for card in self.sorted.view() {
-            ranks[card.rank as usize] += 1;
-        }
-
-        let matched_ranks: Vec<_> = ranks
-            .iter()
-            .enumerate()
-            .filter(|(_, count)| (**count as usize) == match_size)
-            .map(|(index, _)| index)
-            .collect();
-
-        (matched_ranks.len() == match_count).then(|| {
-            self.cards
-                .view()
-                .iter()
-                .copied()
-                .filter(|card| matched_ranks.contains(&(card.rank as usize)))
-                .collect()
-        })
-    }
-
-    fn evaluate_full_house(&self) -> Option<Hand> {
-        if self.len < 5 {
-            return None;
-        }
-
-        let sorted_cards = self.sorted.view();
-
-        let first_rank = sorted_cards[0].rank;
-
-        if sorted_cards[1].rank != first_rank {
-            return None;
-        }
-
-        if sorted_cards[2].rank == first_rank {
-            let second_rank = sorted_cards[3].rank;
-
-            if second_rank == first_rank {
-                return None;
-            }
-
-            if sorted_cards[4].rank != second_rank {
-                return None;
-            }
-        } else {
-            let second_rank = sorted_cards[2].rank;
-
-            if sorted_cards[3].rank != second_rank {
-                return None;
-            }
-
-            if sorted_cards[4].rank != second_rank {
-                return None;
-            }
-        }
-
-        Some(self.cards.clone())
-    }
-
-    fn evaluate(&self) -> Option<(HandKind, Hand)> {
-        if self.cards.view().is_empty() {
-            return None;
-        }
-
-        let five_card_flush = self.evaluate_suit_matches(false).is_some();
-
-        // 1. FLUSH FIVE
-        if five_card_flush {
-            if let Some(hand) = self.evaluate_rank_matches(5, 1) {
-                return Some((HandKind::FlushFive, hand));
-            }
-        }
-
-        let full_house = self.evaluate_full_house();
-
-        // 2. FLUSH HOUSE
-        if five_card_flush {
-            if let Some(hand) = full_house {
-                return Some((HandKind::FlushHouse, hand));
-            }
-        }
-
-        // 3. FIVE OF A KIND
-        if let Some(hand) = self.evaluate_rank_matches(5, 1) {
-            return Some((HandKind::FiveOfAKind, hand));
-        }
-
-        let straight = self.evaluate_run();
-
-        // 4. STRAIGHT FLUSH
-        if let Some(straight) = straight.clone() {
-            if straight
-                .view()
-                .iter()
-                .all(|card| card.suit == straight.view()[0].suit)
-            {
-                return Some((HandKind::StraightFlush, straight));
-            }
-        }
-
-        // 5. FOUR OF A KIND
-        if let Some(hand) = self.evaluate_rank_matches(4, 1) {
-            return Some((HandKind::FourOfAKind, hand));
-        }
-
-        // 6. FULL HOUSE
-        if let Some(hand) = full_house {
-            return Some((HandKind::FullHouse, hand));
-        }
-
-        // 7. FLUSH
-        if let Some(hand) =
-            self.evaluate_suit_matches(self.options.contains(Options::FourCardStraightsAndFlushes))
-        {
-            return Some((HandKind::Flush, hand));
-        }
-
-        // 8. STRAIGHT
-        if let Some(hand) = straight {
-            return Some((HandKind::Straight, hand));
-        }
-
-        // 9. THREE OF A KIND
-        if let Some(hand) = self.evaluate_rank_matches(3, 1) {
-            return Some((HandKind::ThreeOfAKind, hand));
-        }
-
-        // 10. TWO PAIR
-        if let Some(hand) = self.evaluate_rank_matches(2, 2) {
-            return Some((HandKind::TwoPair, hand));
-        }
-
-        // 11. PAIR
-        if let Some(hand) = self.evaluate_rank_matches(2, 1) {
-            return Some((HandKind::Pair, hand));
-        }
-
-        // 12. HIGH CARD
-        if let Some(hand) = self.evaluate_high_card() {
-            return Some((HandKind::HighCard, hand));
-        }
-
-        unreachable!()
-    }
-
-    pub fn evaluate_poker_hand(
-        card_view: impl CardView,
-        options: Options,
-    ) -> Option<(HandKind, Hand)> {
-        let evaluator = Self::new(card_view, options);
-        evaluator.evaluate()
-    }
-
-    fn find_best(&self) -> Option<(HandKind, Hand)> {
-        todo!()
-    }
-
-    pub fn find_best_poker_hand(
-        card_view: impl CardView,
-        options: Options,
-    ) -> Option<(HandKind, Hand)> {
-        let evaluator = Self::new(card_view, options);
-        evaluator.find_best()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::solver::cards::CardCollection;
-
-    use super::*;
-
-    fn expect(
-        cards: impl CardView,
-        expected_kind: HandKind,
-        expected_hand: Hand,
-        options: Options,
-    ) {
-        let hand = HandEvaluator::evaluate_poker_hand(cards, options);
-        assert_eq!(hand, Some((expected_kind, expected_hand)));
-    }
-
-    #[test]
-    fn empty_hand_test() {
-        let cards = CardCollection::empty();
-        assert_eq!(
-            HandEvaluator::evaluate_poker_hand(cards, Options::empty()),
-            None
-        );
-    }
-
-    #[test]
-    fn high_card_test() {
-        expect(
-            cards!("AS 9C 6C KH TS"),
-            HandKind::HighCard,
-            hand!("AS"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn pair_test() {
-        expect(
-            cards!("9S 2S 3C 9D AS"),
-            HandKind::Pair,
-            hand!("9S 9D"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn two_pair_test() {
-        expect(
-            cards!("9S 2S 3C 9D 2S"),
-            HandKind::TwoPair,
-            hand!("9S 2S 9D 2S"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn three_of_a_kind_test() {
-        expect(
-            cards!("9S 2S 3C 9D 9S"),
-            HandKind::ThreeOfAKind,
-            hand!("9S 9D 9S"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn straight_test() {
-        expect(
-            cards!("5S 8D 7S 6C 9S"),
-            HandKind::Straight,
-            hand!("5S 8D 7S 6C 9S"),
-            Options::empty(),
-        );
-        // Gapped straights
-        expect(
-            cards!("5S 8D 7S 6C TS"),
-            HandKind::HighCard,
-            hand!("TS"),
-            Options::empty(),
-        );
-        expect(
-            cards!("5S 8D 7S 6C TS"),
-            HandKind::Straight,
-            hand!("5S 8D 7S 6C TS"),
-            Options::GappedStraights,
-        );
-        expect(
-            cards!("8D 2S 6D 4S 7D"),
-            HandKind::HighCard,
-            hand!("8D"),
-            Options::GappedStraights,
-        );
-        // Four-card straights
-        expect(
-            cards!("5S AS 8D 7S 6C"),
-            HandKind::HighCard,
-            hand!("AS"),
-            Options::empty(),
-        );
-        expect(
-            cards!("5S 8D 7S 6C"),
-            HandKind::Straight,
-            hand!("5S 8D 7S 6C"),
-            Options::FourCardStraightsAndFlushes,
-        );
-        expect(
-            cards!("5S AS 8D 7S 6C"),
-            HandKind::Straight,
-            hand!("5S 8D 7S 6C"),
-            Options::FourCardStraightsAndFlushes,
-        );
-        expect(
-            cards!("5S 2S 8D 7S 6C"),
-            HandKind::Straight,
-            hand!("5S 8D 7S 6C"),
-            Options::FourCardStraightsAndFlushes,
-        );
-        // Gapped four-card straights
-        expect(
-            cards!("8D 2S 6D 4S 7D"),
-            HandKind::Straight,
-            hand!("8D 6D 4S 7D"),
-            Options::FourCardStraightsAndFlushes | Options::GappedStraights,
-        );
-    }
-
-    #[test]
-    fn flush_test() {
-        expect(
-            cards!("AS TS 9S 2S 5S"),
-            HandKind::Flush,
-            hand!("AS TS 9S 2S 5S"),
-            Options::empty(),
-        );
-        expect(
-            cards!("AS AS AS TS 2S"),
-            HandKind::Flush,
-            hand!("AS AS AS TS 2S"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn full_house_test() {
-        expect(
-            cards!("9S 2D 2S 9D 9C"),
-            HandKind::FullHouse,
-            hand!("9S 2D 2S 9D 9C"),
-            Options::empty(),
-        );
-        expect(
-            cards!("2S 2D 2S 9D 9C"),
-            HandKind::FullHouse,
-            hand!("2S 2D 2S 9D 9C"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn four_of_a_kind_test() {
-        expect(
-            cards!("9S 9D 2S 9D 9C"),
-            HandKind::FourOfAKind,
-            hand!("9S 9D 9D 9C"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn straight_flush() {
-        expect(
-            cards!("5S 8S 7S 6S 9S"),
-            HandKind::StraightFlush,
-            hand!("5S 8S 7S 6S 9S"),
-            Options::empty(),
-        );
-        // Gapped straights
-        expect(
-            cards!("5D 8D 7D 6D TD"),
-            HandKind::StraightFlush,
-            hand!("5D 8D 7D 6D TD"),
-            Options::GappedStraights,
-        );
-        // Four-card straights
-        expect(
-            cards!("AD 5S 8S 7S 6S"),
-            HandKind::StraightFlush,
-            hand!("5S 8S 7S 6S"),
-            Options::FourCardStraightsAndFlushes,
-        );
-        expect(
-            cards!("AS 5D 8S 7S 6S"),
-            HandKind::Straight,
-            hand!("5D 8S 7S 6S"),
-            Options::FourCardStraightsAndFlushes,
-        );
-        // Gapped four-card straights
-        expect(
-            cards!("8H 2S 6H 4H 7H"),
-            HandKind::StraightFlush,
-            hand!("8H 6H 4H 7H"),
-            Options::FourCardStraightsAndFlushes | Options::GappedStraights,
-        );
-    }
-
-    #[test]
-    fn five_of_a_kind_test() {
-        expect(
-            cards!("9S 9D 9S 9D 9C"),
-            HandKind::FiveOfAKind,
-            hand!("9S 9D 9S 9D 9C"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn flush_house_test() {
-        expect(
-            cards!("9S AS 9S AS 9S"),
-            HandKind::FlushHouse,
-            hand!("9S AS 9S AS 9S"),
-            Options::empty(),
-        );
-    }
-
-    #[test]
-    fn flush_five_test() {
-        expect(
-            cards!("9S 9S 9S 9S 9S"),
-            HandKind::FlushFive,
-            hand!("9S 9S 9S 9S 9S"),
-            Options::empty(),
-        );
-    }
-}
+use std::cmp::Ordering;
+use std::thread::current;
+
+use bitflags::bitflags;
+use heapless;
+use itertools::Itertools;
+use slab::Slab;
+use strum::IntoEnumIterator;
+
+use crate::solver::cards::{
+    Card, CardCollection, CardView, Enhancement, Hand, HandKind, Rank, Suit,
+};
+use crate::solver::cardset::CardSet;
+use crate::solver::error::{Error, Result};
+use crate::{card, cards, hand};
+
+pub mod reference;
+
+bitflags! {
+    #[derive(Copy, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Options: u32 {
+        const GappedStraights             = 0b0001;
+        const FourCardStraightsAndFlushes = 0b0010;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HandEvaluator {
+    /// The cards as they were given, Stone cards and all
+    played: Hand,
+    /// How many cards there are with a rank and suit
+    len: usize,
+    /// The cards with a rank and suit: all of them but the Stone cards, which
+    /// score whatever hand the rest make
+    cards: Hand,
+    sorted: Hand,
+    cardset: CardSet,
+    /// How many of the cards there are of each rank
+    ranks: [u8; 13],
+    /// How many of the cards count as each suit, with Wild cards counting as
+    /// every one
+    suits: [u8; 4],
+    options: Options,
+}
+
+impl HandEvaluator {
+    /// Get ready to evaluate up to five cards. Panics with any more.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn new(card_view: impl CardView, options: Options) -> Self {
+        let card_slice = card_view.view();
+
+        // For now, we're restricting ourselves to scoring 5-card hands, because that eliminates
+        // any "tiebreaking" that we'd have to do when scoring more than 5 cards at once.
+        // This doesn't directly affect Balatro, because you can only play 5 cards anyways, but it's
+        // probably still worth extending to this functionality at some point.
+        assert!(card_slice.len() <= 5);
+
+        let played = Hand::from_slice(card_slice).unwrap();
+        let cards: Hand = card_slice
+            .iter()
+            .copied()
+            .filter(|card| !card.is_stone())
+            .collect();
+        let len = cards.view().len();
+
+        let mut sorted = cards.clone();
+        sorted.cards.sort_by(|a, b| b.cmp(a));
+
+        let cardset = CardSet::from(cards.clone());
+
+        let mut ranks = [0; 13];
+        let mut suits = [0; 4];
+        for card in cards.view() {
+            ranks[card.rank as usize] += 1;
+            for suit in Suit::iter().filter(|suit| card.is_suit(*suit)) {
+                suits[suit as usize] += 1;
+            }
+        }
+
+        Self {
+            played,
+            len,
+            cards,
+            sorted,
+            cardset,
+            ranks,
+            suits,
+            options,
+        }
+    }
+
+    /// The same cards, but with the first `old` replaced by `new` in its
+    /// place. Only what changes is updated, which is cheaper than starting
+    /// over when trying many hands that differ by a card. `None` if `old`
+    /// isn't one of the cards.
+    #[must_use]
+    pub fn with_swap(&self, old: Card, new: Card) -> Option<Self> {
+        if old.is_stone() || new.is_stone() {
+            let index = self.played.view().iter().position(|card| *card == old)?;
+            let mut played = self.played.clone();
+            played.cards[index] = new;
+            return Some(Self::new(played, self.options));
+        }
+
+        let index = self.cards.view().iter().position(|card| *card == old)?;
+        let mut swapped = self.clone();
+        swapped.cards.cards[index] = new;
+        let index = swapped.played.view().iter().position(|card| *card == old)?;
+        swapped.played.cards[index] = new;
+
+        let sorted = &mut swapped.sorted.cards;
+        let index = sorted.iter().position(|card| *card == old)?;
+        sorted.remove(index);
+        let index = sorted
+            .iter()
+            .position(|card| *card < new)
+            .unwrap_or(sorted.len());
+        sorted.insert(index, new).unwrap();
+
+        swapped.cardset.remove(old);
+        if swapped.cards.view().contains(&old) {
+            swapped.cardset.insert(old);
+        }
+        swapped.cardset.insert(new);
+
+        swapped.ranks[old.rank as usize] -= 1;
+        swapped.ranks[new.rank as usize] += 1;
+        for suit in Suit::iter() {
+            swapped.suits[suit as usize] -= u8::from(old.is_suit(suit));
+            swapped.suits[suit as usize] += u8::from(new.is_suit(suit));
+        }
+        Some(swapped)
+    }
+
+    fn evaluate_high_card(&self) -> Option<Hand> {
+        Some(Hand::from_slice(&[*self.sorted.cards.first()?]).unwrap())
+    }
+
+    fn evaluate_suit_matches(&self, four_card: bool) -> Option<Hand> {
+        let length = if four_card { 4 } else { 5 };
+
+        if self.len < length {
+            return None;
+        }
+
+        // The card set only knows each card's own suit
+        let wild = self
+            .cards
+            .view()
+            .iter()
+            .any(|card| card.enhancement == Some(Enhancement::Wild));
+        if wild || self.cardset.count() < self.len {
+            self.suits
+                .iter()
+                .any(|count| usize::from(*count) >= length)
+                .then(|| self.cards.clone())
+        } else {
+            Suit::iter()
+                .find(|suit| self.cardset.count_in_suit(*suit) == 5)
+                .map(|_| self.cards.clone())
+        }
+    }
+
+    fn evaluate_run(&self) -> Option<Hand> {
+        #[inline]
+        fn is_consecutive(left: Rank, right: Rank) -> bool {
+            if left == Rank::Deuce && right == Rank::Ace {
+                true
+            } else {
+                (left as i32 - 1) == (right as i32)
+            }
+        }
+
+        #[inline]
+        fn has_gap(left: Rank, right: Rank) -> bool {
+            if left == Rank::Three && right == Rank::Ace {
+                true
+            } else {
+                (left as i32 - 2) == (right as i32)
+            }
+        }
+
+        let four_card = self.options.contains(Options::FourCardStraightsAndFlushes);
+
+        let min_length = if four_card { 4 } else { 5 };
+        if self.len < min_length {
+            return None;
+        }
+
+        let mut can_gap = self.options.contains(Options::GappedStraights);
+        let mut can_fail = self.options.contains(Options::FourCardStraightsAndFlushes);
+        let mut straight_length = 1;
+
+        for (i, (left, right)) in self.sorted.view().iter().tuple_windows().enumerate() {
+            let consecutive = is_consecutive(left.rank, right.rank);
+            let gapped = has_gap(left.rank, right.rank);
+
+            if consecutive {
+                straight_length += 1;
+            } else if gapped && can_gap {
+                straight_length += 1;
+                can_gap = false;
+            } else if four_card && i == 3 {
+                break;
+            } else if can_fail {
+                straight_length = 1;
+                can_fail = false;
+            } else {
+                break;
+            }
+        }
+
+        if straight_length == 5 || (self.len == 4 && four_card && straight_length == 4) {
+            Some(self.cards.clone())
+        } else if four_card && straight_length == 4 {
+            let except_card = if can_fail { 4 } else { 0 };
+
+            let mut ditched_card = false;
+            let mut vec = heapless::Vec::<_, 5>::new();
+
+            for card in self.cards.view() {
+                if !ditched_card && card == &self.sorted.view()[except_card] {
+                    ditched_card = true;
+                    continue;
+                }
+                vec.push(*card).unwrap();
+            }
+
+            Some(Hand::from(vec))
+        } else {
+            None
+        }
+    }
+
+    fn evaluate_rank_matches(&self, match_size: usize, match_count: usize) -> Option<Hand> {
+        let min_length = match_size * match_count;
+        if self.len < min_length {
+            return None;
+        }
+
+        let matched_ranks: Vec<_> = self
+            .ranks
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| (**count as usize) == match_size)
+            .map(|(index, _)| index)
+            .collect();
+
+        (matched_ranks.len() == match_count).then(|| {
+            self.cards
+                .view()
+                .iter()
+                .copied()
+                .filter(|card| matched_ranks.contains(&(card.rank as usize)))
+                .collect()
+        })
+    }
+
+    fn evaluate_full_house(&self) -> Option<Hand> {
+        if self.len < 5 {
+            return None;
+        }
+
+        let sorted_cards = self.sorted.view();
+
+        let first_rank = sorted_cards[0].rank;
+
+        if sorted_cards[1].rank != first_rank {
+            return None;
+        }
+
+        if sorted_cards[2].rank == first_rank {
+            let second_rank = sorted_cards[3].rank;
+
+            if second_rank == first_rank {
+                return None;
+            }
+
+            if sorted_cards[4].rank != second_rank {
+                return None;
+            }
+        } else {
+            let second_rank = sorted_cards[2].rank;
+
+            if sorted_cards[3].rank != second_rank {
+                return None;
+            }
+
+            if sorted_cards[4].rank != second_rank {
+                return None;
+            }
+        }
+
+        Some(self.cards.clone())
+    }
+
+    /// Determine which poker hand the cards make, along with the cards that
+    /// score it, like `evaluate_poker_hand`. Stone cards always score, on
+    /// top of whatever hand the others make, or as a High Card on their own.
+    pub fn evaluate(&self) -> Option<(HandKind, Hand)> {
+        if self.played.view().is_empty() {
+            return None;
+        }
+        let (kind, scoring) = self
+            .evaluate_ranked()
+            .unwrap_or((HandKind::HighCard, Hand::from_slice(&[]).unwrap()));
+        let mut remaining = scoring.cards;
+        let scoring = self
+            .played
+            .view()
+            .iter()
+            .copied()
+            .filter(|card| {
+                if let Some(index) = remaining.iter().position(|scored| scored == card) {
+                    remaining.swap_remove(index);
+                    true
+                } else {
+                    card.is_stone()
+                }
+            })
+            .collect();
+        Some((kind, scoring))
+    }
+
+    /// The hand the cards with a rank and suit make, and those of them that
+    /// score it.
+    fn evaluate_ranked(&self) -> Option<(HandKind, Hand)> {
+        if self.cards.view().is_empty() {
+            return None;
+        }
+
+        let five_card_flush = self.evaluate_suit_matches(false).is_some();
+
+        // 1. FLUSH FIVE
+        if five_card_flush {
+            if let Some(hand) = self.evaluate_rank_matches(5, 1) {
+                return Some((HandKind::FlushFive, hand));
+            }
+        }
+
+        let full_house = self.evaluate_full_house();
+
+        // 2. FLUSH HOUSE
+        if five_card_flush {
+            if let Some(hand) = full_house {
+                return Some((HandKind::FlushHouse, hand));
+            }
+        }
+
+        // 3. FIVE OF A KIND
+        if let Some(hand) = self.evaluate_rank_matches(5, 1) {
+            return Some((HandKind::FiveOfAKind, hand));
+        }
+
+        let straight = self.evaluate_run();
+
+        // 4. STRAIGHT FLUSH
+        if let Some(straight) = straight.clone() {
+            if Suit::iter().any(|suit| straight.view().iter().all(|card| card.is_suit(suit))) {
+                return Some((HandKind::StraightFlush, straight));
+            }
+        }
+
+        // 5. FOUR OF A KIND
+        if let Some(hand) = self.evaluate_rank_matches(4, 1) {
+            return Some((HandKind::FourOfAKind, hand));
+        }
+
+        // 6. FULL HOUSE
+        if let Some(hand) = full_house {
+            return Some((HandKind::FullHouse, hand));
+        }
+
+        // 7. FLUSH
+        if let Some(hand) =
+            self.evaluate_suit_matches(self.options.contains(Options::FourCardStraightsAndFlushes))
+        {
+            return Some((HandKind::Flush, hand));
+        }
+
+        // 8. STRAIGHT
+        if let Some(hand) = straight {
+            return Some((HandKind::Straight, hand));
+        }
+
+        // 9. THREE OF A KIND
+        if let Some(hand) = self.evaluate_rank_matches(3, 1) {
+            return Some((HandKind::ThreeOfAKind, hand));
+        }
+
+        // 10. TWO PAIR
+        if let Some(hand) = self.evaluate_rank_matches(2, 2) {
+            return Some((HandKind::TwoPair, hand));
+        }
+
+        // 11. PAIR
+        if let Some(hand) = self.evaluate_rank_matches(2, 1) {
+            return Some((HandKind::Pair, hand));
+        }
+
+        // 12. HIGH CARD
+        if let Some(hand) = self.evaluate_high_card() {
+            return Some((HandKind::HighCard, hand));
+        }
+
+        unreachable!()
+    }
+
+    /// Determine which poker hand the given cards make, along with the cards
+    /// that score it. The scoring cards are returned in the order they were
+    /// given, since that is the order Balatro scores them in.
+    pub fn evaluate_poker_hand(
+        card_view: impl CardView,
+        options: Options,
+    ) -> Option<(HandKind, Hand)> {
+        let evaluator = Self::new(card_view, options);
+        evaluator.evaluate()
+    }
+
+    /// The given cards that aren't part of `scoring`, in the order they were
+    /// given.
+    fn unscored(&self, scoring: &Hand) -> Hand {
+        let mut remaining = scoring.cards.clone();
+        self.played
+            .view()
+            .iter()
+            .copied()
+            .filter(|card| {
+                if let Some(index) = remaining.iter().position(|scored| scored == card) {
+                    remaining.swap_remove(index);
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
+
+    /// Like `evaluate_poker_hand`, but also returns the cards that were played
+    /// without scoring, in the order they were given. The scorer only counts
+    /// these under `ScoringOptions::AllPlayedCardsScore`.
+    pub fn evaluate_played_hand(
+        card_view: impl CardView,
+        options: Options,
+    ) -> Option<(HandKind, Hand, Hand)> {
+        let evaluator = Self::new(card_view, options);
+        let (kind, scoring) = evaluator.evaluate()?;
+        let unscored = evaluator.unscored(&scoring);
+        Some((kind, scoring, unscored))
+    }
+
+    /// The best poker hand that can be made from any five of the given cards,
+    /// along with the cards that score it. Hands of the same kind are ranked
+    /// by their highest scoring cards.
+    ///
+    /// Combinations that can't make a hand as good as the best found so far,
+    /// going by `kind_bound`, aren't evaluated.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn find_best_poker_hand(
+        card_view: impl CardView,
+        options: Options,
+    ) -> Option<(HandKind, Hand)> {
+        let key = |kind: HandKind, scoring: &Hand| {
+            let mut ranks = scoring.cards.iter().map(|card| card.rank).collect_vec();
+            ranks.sort_by(|a, b| b.cmp(a));
+            (kind, ranks)
+        };
+
+        let cards = card_view.view();
+        let mut best = None;
+        let mut best_key: Option<(HandKind, Vec<Rank>)> = None;
+        for combination in cards.iter().copied().combinations(cards.len().min(5)) {
+            if best_key
+                .as_ref()
+                .is_some_and(|(kind, _)| Self::kind_bound(&combination, options) < *kind)
+            {
+                continue;
+            }
+            let Some((kind, scoring)) =
+                Self::evaluate_poker_hand(CardCollection::from(&combination[..]), options)
+            else {
+                continue;
+            };
+            let found = key(kind, &scoring);
+            // Of equally good hands, the last one found wins
+            if best_key.as_ref().is_none_or(|best_key| found >= *best_key) {
+                best_key = Some(found);
+                best = Some((kind, scoring));
+            }
+        }
+        best
+    }
+
+    /// The best kind of hand `cards` could possibly make, going only by how
+    /// many there are of each rank and suit. Any straight is assumed possible
+    /// when there are enough different ranks, so this can overestimate, but it
+    /// never underestimates.
+    fn kind_bound(cards: &[Card], options: Options) -> HandKind {
+        let four_card = options.contains(Options::FourCardStraightsAndFlushes);
+        let length = if four_card { 4 } else { 5 };
+
+        let mut ranks = [0_u8; 13];
+        let mut suits = [0_usize; 4];
+        for card in cards.iter().filter(|card| !card.is_stone()) {
+            ranks[card.rank as usize] += 1;
+            for suit in Suit::iter().filter(|suit| card.is_suit(*suit)) {
+                suits[suit as usize] += 1;
+            }
+        }
+        let largest_match = ranks.iter().copied().max().unwrap_or(0);
+        let matched_ranks = ranks.iter().filter(|count| **count >= 2).count();
+        let distinct_ranks = ranks.iter().filter(|count| **count > 0).count();
+        let flush = suits.iter().any(|count| *count >= length);
+        let straight = distinct_ranks >= length;
+        let full_house = largest_match >= 3 && matched_ranks >= 2;
+
+        if flush && largest_match >= 5 {
+            HandKind::FlushFive
+        } else if flush && full_house {
+            HandKind::FlushHouse
+        } else if largest_match >= 5 {
+            HandKind::FiveOfAKind
+        } else if flush && straight {
+            HandKind::StraightFlush
+        } else if largest_match >= 4 {
+            HandKind::FourOfAKind
+        } else if full_house {
+            HandKind::FullHouse
+        } else if flush {
+            HandKind::Flush
+        } else if straight {
+            HandKind::Straight
+        } else if largest_match >= 3 {
+            HandKind::ThreeOfAKind
+        } else if matched_ranks >= 2 {
+            HandKind::TwoPair
+        } else if largest_match >= 2 {
+            HandKind::Pair
+        } else {
+            HandKind::HighCard
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use crate::solver::cards::{CardCollection, Deck};
+
+    use super::*;
+
+    fn expect(
+        cards: impl CardView,
+        expected_kind: HandKind,
+        expected_hand: Hand,
+        options: Options,
+    ) {
+        let hand = HandEvaluator::evaluate_poker_hand(cards, options);
+        assert_eq!(hand, Some((expected_kind, expected_hand)));
+    }
+
+    #[test]
+    fn empty_hand_test() {
+        let cards = CardCollection::empty();
+        assert_eq!(
+            HandEvaluator::evaluate_poker_hand(cards, Options::empty()),
+            None
+        );
+    }
+
+    #[test]
+    fn high_card_test() {
+        expect(
+            cards!("AS 9C 6C KH TS"),
+            HandKind::HighCard,
+            hand!("AS"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn pair_test() {
+        expect(
+            cards!("9S 2S 3C 9D AS"),
+            HandKind::Pair,
+            hand!("9S 9D"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn two_pair_test() {
+        expect(
+            cards!("9S 2S 3C 9D 2S"),
+            HandKind::TwoPair,
+            hand!("9S 2S 9D 2S"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn three_of_a_kind_test() {
+        expect(
+            cards!("9S 2S 3C 9D 9S"),
+            HandKind::ThreeOfAKind,
+            hand!("9S 9D 9S"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn straight_test() {
+        expect(
+            cards!("5S 8D 7S 6C 9S"),
+            HandKind::Straight,
+            hand!("5S 8D 7S 6C 9S"),
+            Options::empty(),
+        );
+        // Gapped straights
+        expect(
+            cards!("5S 8D 7S 6C TS"),
+            HandKind::HighCard,
+            hand!("TS"),
+            Options::empty(),
+        );
+        expect(
+            cards!("5S 8D 7S 6C TS"),
+            HandKind::Straight,
+            hand!("5S 8D 7S 6C TS"),
+            Options::GappedStraights,
+        );
+        expect(
+            cards!("8D 2S 6D 4S 7D"),
+            HandKind::HighCard,
+            hand!("8D"),
+            Options::GappedStraights,
+        );
+        // Four-card straights
+        expect(
+            cards!("5S AS 8D 7S 6C"),
+            HandKind::HighCard,
+            hand!("AS"),
+            Options::empty(),
+        );
+        expect(
+            cards!("5S 8D 7S 6C"),
+            HandKind::Straight,
+            hand!("5S 8D 7S 6C"),
+            Options::FourCardStraightsAndFlushes,
+        );
+        expect(
+            cards!("5S AS 8D 7S 6C"),
+            HandKind::Straight,
+            hand!("5S 8D 7S 6C"),
+            Options::FourCardStraightsAndFlushes,
+        );
+        expect(
+            cards!("5S 2S 8D 7S 6C"),
+            HandKind::Straight,
+            hand!("5S 8D 7S 6C"),
+            Options::FourCardStraightsAndFlushes,
+        );
+        // Gapped four-card straights
+        expect(
+            cards!("8D 2S 6D 4S 7D"),
+            HandKind::Straight,
+            hand!("8D 6D 4S 7D"),
+            Options::FourCardStraightsAndFlushes | Options::GappedStraights,
+        );
+    }
+
+    #[test]
+    fn flush_test() {
+        expect(
+            cards!("AS TS 9S 2S 5S"),
+            HandKind::Flush,
+            hand!("AS TS 9S 2S 5S"),
+            Options::empty(),
+        );
+        expect(
+            cards!("AS AS AS TS 2S"),
+            HandKind::Flush,
+            hand!("AS AS AS TS 2S"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn full_house_test() {
+        expect(
+            cards!("9S 2D 2S 9D 9C"),
+            HandKind::FullHouse,
+            hand!("9S 2D 2S 9D 9C"),
+            Options::empty(),
+        );
+        expect(
+            cards!("2S 2D 2S 9D 9C"),
+            HandKind::FullHouse,
+            hand!("2S 2D 2S 9D 9C"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn four_of_a_kind_test() {
+        expect(
+            cards!("9S 9D 2S 9D 9C"),
+            HandKind::FourOfAKind,
+            hand!("9S 9D 9D 9C"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn straight_flush() {
+        expect(
+            cards!("5S 8S 7S 6S 9S"),
+            HandKind::StraightFlush,
+            hand!("5S 8S 7S 6S 9S"),
+            Options::empty(),
+        );
+        // Gapped straights
+        expect(
+            cards!("5D 8D 7D 6D TD"),
+            HandKind::StraightFlush,
+            hand!("5D 8D 7D 6D TD"),
+            Options::GappedStraights,
+        );
+        // Four-card straights
+        expect(
+            cards!("AD 5S 8S 7S 6S"),
+            HandKind::StraightFlush,
+            hand!("5S 8S 7S 6S"),
+            Options::FourCardStraightsAndFlushes,
+        );
+        expect(
+            cards!("AS 5D 8S 7S 6S"),
+            HandKind::Straight,
+            hand!("5D 8S 7S 6S"),
+            Options::FourCardStraightsAndFlushes,
+        );
+        // Gapped four-card straights
+        expect(
+            cards!("8H 2S 6H 4H 7H"),
+            HandKind::StraightFlush,
+            hand!("8H 6H 4H 7H"),
+            Options::FourCardStraightsAndFlushes | Options::GappedStraights,
+        );
+    }
+
+    #[test]
+    fn five_of_a_kind_test() {
+        expect(
+            cards!("9S 9D 9S 9D 9C"),
+            HandKind::FiveOfAKind,
+            hand!("9S 9D 9S 9D 9C"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn flush_house_test() {
+        expect(
+            cards!("9S AS 9S AS 9S"),
+            HandKind::FlushHouse,
+            hand!("9S AS 9S AS 9S"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn flush_five_test() {
+        expect(
+            cards!("9S 9S 9S 9S 9S"),
+            HandKind::FlushFive,
+            hand!("9S 9S 9S 9S 9S"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn stone_test() {
+        // Stone cards have no rank, so they don't make the pair a three of a
+        // kind, but they always score alongside it
+        expect(
+            cards!("AS AH AD+stone"),
+            HandKind::Pair,
+            hand!("AS AH AD+stone"),
+            Options::empty(),
+        );
+        expect(
+            cards!("AS KD+stone AH 2C"),
+            HandKind::Pair,
+            hand!("AS KD+stone AH"),
+            Options::empty(),
+        );
+        // ...or suit, so they don't make up a flush either
+        expect(
+            cards!("AS KS QS JS 9S+stone"),
+            HandKind::HighCard,
+            hand!("AS 9S+stone"),
+            Options::empty(),
+        );
+        // On their own, they score as a High Card
+        expect(
+            cards!("AS+stone KD+stone"),
+            HandKind::HighCard,
+            hand!("AS+stone KD+stone"),
+            Options::empty(),
+        );
+        assert_eq!(
+            HandEvaluator::evaluate_played_hand(cards!("7C 9S+stone 2D"), Options::empty()),
+            Some((HandKind::HighCard, hand!("7C 9S+stone"), hand!("2D")))
+        );
+    }
+
+    #[test]
+    fn wild_test() {
+        // Wild cards count as every suit
+        expect(
+            cards!("AS KS QS JS TH+wild"),
+            HandKind::StraightFlush,
+            hand!("AS KS QS JS TH+wild"),
+            Options::empty(),
+        );
+        expect(
+            cards!("2D 9H+wild JD 6C+wild KD"),
+            HandKind::Flush,
+            hand!("2D 9H+wild JD 6C+wild KD"),
+            Options::empty(),
+        );
+        expect(
+            cards!("2D 9H+wild JD 6C KD"),
+            HandKind::HighCard,
+            hand!("KD"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn scoring_order_test() {
+        // Scoring cards keep their played order, regardless of rank
+        expect(
+            cards!("2S KD 2C 5H KS"),
+            HandKind::TwoPair,
+            hand!("2S KD 2C KS"),
+            Options::empty(),
+        );
+        expect(
+            cards!("9S 5S 7S 6C 8S"),
+            HandKind::Straight,
+            hand!("9S 5S 7S 6C 8S"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn unscored_test() {
+        assert_eq!(
+            HandEvaluator::evaluate_played_hand(cards!("2S KD 2C 5H KS"), Options::empty()),
+            Some((HandKind::TwoPair, hand!("2S KD 2C KS"), hand!("5H")))
+        );
+        // Duplicates only count as unscored once their copies have scored
+        assert_eq!(
+            HandEvaluator::evaluate_played_hand(cards!("9S 9S 9S 4C 4C"), Options::empty()),
+            Some((HandKind::FullHouse, hand!("9S 9S 9S 4C 4C"), Hand::empty()))
+        );
+        assert_eq!(
+            HandEvaluator::evaluate_played_hand(cards!("AS 9C AS 6C 9C"), Options::empty()),
+            Some((HandKind::TwoPair, hand!("AS 9C AS 9C"), hand!("6C")))
+        );
+    }
+
+    #[test]
+    fn find_best_test() {
+        // The flush beats the three of a kind, and the higher pair wins a tie
+        assert_eq!(
+            HandEvaluator::find_best_poker_hand(cards!("2H 7H 7S 7C 9H JH QH"), Options::empty()),
+            Some((HandKind::Flush, hand!("2H 7H 9H JH QH")))
+        );
+        assert_eq!(
+            HandEvaluator::find_best_poker_hand(cards!("3S 3C 8D KS KH 5C"), Options::empty()),
+            Some((HandKind::TwoPair, hand!("3S 3C KS KH")))
+        );
+        assert_eq!(
+            HandEvaluator::find_best_poker_hand(cards!("4D 9C"), Options::empty()),
+            Some((HandKind::HighCard, hand!("9C")))
+        );
+    }
+
+    #[test]
+    fn swap_test() {
+        let evaluator = HandEvaluator::new(cards!("KS KD 7C 7H 2S"), Options::empty());
+        assert_eq!(
+            evaluator.evaluate().map(|(kind, _)| kind),
+            Some(HandKind::TwoPair)
+        );
+
+        // The new card goes where the old one was
+        let swapped = evaluator.with_swap(card!("2S"), card!("KH")).unwrap();
+        assert_eq!(
+            swapped.evaluate(),
+            Some((HandKind::FullHouse, hand!("KS KD 7C 7H KH")))
+        );
+        let swapped = swapped.with_swap(card!("7C"), card!("QD")).unwrap();
+        assert_eq!(
+            swapped.evaluate(),
+            Some((HandKind::ThreeOfAKind, hand!("KS KD KH")))
+        );
+        assert!(evaluator.with_swap(card!("AS"), card!("AD")).is_none());
+
+        // Swapping gets the same answer as starting over, every time
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..500 {
+            let mut deck = Deck::base_deck();
+            deck.shuffle(&mut rng);
+            let dealt = deck.draw_n(5).unwrap();
+            let new = deck.draw().unwrap();
+            let old = dealt.view()[2];
+            let mut cards = dealt.view().to_vec();
+            cards[2] = new;
+            for options in [Options::empty(), Options::all()] {
+                let swapped = HandEvaluator::new(CardCollection::from(dealt.view()), options)
+                    .with_swap(old, new)
+                    .unwrap();
+                assert_eq!(
+                    swapped.evaluate(),
+                    HandEvaluator::evaluate_poker_hand(CardCollection::from(&cards[..]), options)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pruning_test() {
+        // The bound is never below the real thing...
+        let options = [Options::empty(), Options::all()];
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let mut deck = Deck::base_deck();
+            deck.shuffle(&mut rng);
+            let dealt = deck.draw_n(8).unwrap();
+            for options in options {
+                for combination in dealt.view().iter().copied().combinations(5) {
+                    let (kind, _) = HandEvaluator::evaluate_poker_hand(
+                        CardCollection::from(&combination[..]),
+                        options,
+                    )
+                    .unwrap();
+                    assert!(HandEvaluator::kind_bound(&combination, options) >= kind);
+                }
+
+                // ...so the pruned search finds the same best hand as trying
+                // everything
+                let exhaustive = dealt
+                    .view()
+                    .iter()
+                    .copied()
+                    .combinations(5)
+                    .filter_map(|combination| {
+                        HandEvaluator::evaluate_poker_hand(
+                            CardCollection::from(&combination[..]),
+                            options,
+                        )
+                    })
+                    .max_by_key(|(kind, scoring)| {
+                        let mut ranks = scoring.cards.iter().map(|card| card.rank).collect_vec();
+                        ranks.sort_by(|a, b| b.cmp(a));
+                        (*kind, ranks)
+                    });
+                assert_eq!(
+                    HandEvaluator::find_best_poker_hand(
+                        CardCollection::from(dealt.view()),
+                        options
+                    ),
+                    exhaustive
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn options_serde_test() {
+        let options = Options::GappedStraights | Options::FourCardStraightsAndFlushes;
+        let json = serde_json::to_string(&options).unwrap();
+        assert_eq!(json, r#""GappedStraights | FourCardStraightsAndFlushes""#);
+        let parsed: Options = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.bits(), options.bits());
+    }
+}
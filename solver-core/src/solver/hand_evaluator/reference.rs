@@ -0,0 +1,343 @@
+//! A slow but plain statement of which poker hand some cards make, for
+//! checking `HandEvaluator` against as it's optimized.
+//!
+//! Every group of the cards is tried against a definition of each kind of
+//! hand, with nothing worked out ahead of time. The hand is the best kind any
+//! group makes, and the cards that score it are those in the largest groups
+//! that make it, so a card that could take another's place in a four-card
+//! straight scores as well. Stone cards are left out of every group, and
+//! score whatever the hand is.
+
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::solver::cards::{Card, CardView, Hand, HandKind, Rank, Suit};
+use crate::solver::hand_evaluator::Options;
+
+/// Whether `cards` are all of the same rank.
+fn same_rank(cards: &[Card]) -> bool {
+    cards.iter().map(|card| card.rank).all_equal()
+}
+
+/// Whether `cards` all count as the same suit, which Wild cards count as
+/// any of.
+fn same_suit(cards: &[Card]) -> bool {
+    Suit::iter().any(|suit| cards.iter().all(|card| card.is_suit(suit)))
+}
+
+/// How many cards a straight or flush takes.
+fn run_length(options: Options) -> usize {
+    if options.contains(Options::FourCardStraightsAndFlushes) {
+        4
+    } else {
+        5
+    }
+}
+
+/// Whether `cards` are `sizes.len()` groups of a rank each, of the given
+/// sizes, in some order.
+fn rank_groups(cards: &[Card], sizes: &[usize]) -> bool {
+    let mut found = cards
+        .iter()
+        .counts_by(|card| card.rank)
+        .into_values()
+        .collect_vec();
+    let mut sizes = sizes.to_vec();
+    found.sort_unstable();
+    sizes.sort_unstable();
+    found == sizes
+}
+
+/// Whether `cards` are a straight: enough cards of different ranks, each one
+/// rank up from the last, or two ranks up once under
+/// `Options::GappedStraights`. Aces count high or low.
+fn is_straight(cards: &[Card], options: Options) -> bool {
+    if cards.len() < run_length(options) || !cards.iter().map(|card| card.rank).all_unique() {
+        return false;
+    }
+    let gaps_allowed = usize::from(options.contains(Options::GappedStraights));
+    [false, true].into_iter().any(|aces_low| {
+        let steps = cards
+            .iter()
+            .map(|card| match card.rank {
+                Rank::Ace if aces_low => -1,
+                rank => rank as i32,
+            })
+            .sorted_unstable()
+            .tuple_windows()
+            .map(|(low, high)| high - low)
+            .collect_vec();
+        steps.iter().all(|step| *step == 1 || *step == 2)
+            && steps.iter().filter(|step| **step == 2).count() <= gaps_allowed
+    })
+}
+
+/// Whether `cards`, every one of them, make a hand of `kind`.
+fn makes(kind: HandKind, cards: &[Card], options: Options) -> bool {
+    let flush = cards.len() >= run_length(options) && same_suit(cards);
+    match kind {
+        HandKind::FlushFive => cards.len() == 5 && same_rank(cards) && same_suit(cards),
+        HandKind::FlushHouse => rank_groups(cards, &[3, 2]) && same_suit(cards),
+        HandKind::FiveOfAKind => cards.len() == 5 && same_rank(cards),
+        HandKind::StraightFlush => is_straight(cards, options) && flush,
+        HandKind::FourOfAKind => rank_groups(cards, &[4]),
+        HandKind::FullHouse => rank_groups(cards, &[3, 2]),
+        HandKind::Flush => flush,
+        HandKind::Straight => is_straight(cards, options),
+        HandKind::ThreeOfAKind => rank_groups(cards, &[3]),
+        HandKind::TwoPair => rank_groups(cards, &[2, 2]),
+        HandKind::Pair => rank_groups(cards, &[2]),
+        HandKind::HighCard => cards.len() == 1,
+    }
+}
+
+/// Determine which poker hand the given cards make, along with the cards
+/// that score it, in the order they were given, like
+/// `HandEvaluator::evaluate_poker_hand`. Panics with more than five cards.
+#[allow(clippy::needless_pass_by_value)]
+pub fn evaluate_poker_hand(card_view: impl CardView, options: Options) -> Option<(HandKind, Hand)> {
+    let cards = card_view.view();
+    assert!(cards.len() <= 5);
+
+    if cards.is_empty() {
+        return None;
+    }
+
+    // Every group of the cards with a rank and suit, by their places among
+    // all of them
+    let ranked = (0..cards.len())
+        .filter(|index| !cards[*index].is_stone())
+        .collect_vec();
+    let stones = (0..cards.len())
+        .filter(|index| cards[*index].is_stone())
+        .collect_vec();
+    let groups = (1..=ranked.len())
+        .flat_map(|size| ranked.iter().copied().combinations(size))
+        .collect_vec();
+    let group_cards = |group: &[usize]| group.iter().map(|index| cards[*index]).collect_vec();
+
+    // The best kind first, or a High Card of only Stone cards
+    let (kind, scoring) = HandKind::iter()
+        .rev()
+        .find_map(|kind| {
+            let making = groups
+                .iter()
+                .filter(|group| makes(kind, &group_cards(group), options))
+                .collect_vec();
+            let scoring = if kind == HandKind::HighCard {
+                // Only the highest card scores, and the first of equals
+                let highest = ranked.iter().map(|index| cards[*index].rank).max()?;
+                let index = ranked
+                    .iter()
+                    .copied()
+                    .find(|index| cards[*index].rank == highest)?;
+                vec![index]
+            } else {
+                let largest = making.iter().map(|group| group.len()).max()?;
+                making
+                    .iter()
+                    .filter(|group| group.len() == largest)
+                    .flat_map(|group| group.iter().copied())
+                    .collect_vec()
+            };
+            Some((kind, scoring))
+        })
+        .unwrap_or((HandKind::HighCard, Vec::new()));
+
+    let scoring = scoring
+        .into_iter()
+        .chain(stones)
+        .sorted_unstable()
+        .dedup()
+        .collect_vec();
+    Some((kind, Hand::from_slice(&group_cards(&scoring)).unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::cards::CardCollection;
+    use crate::{cards, hand};
+
+    use super::*;
+
+    fn expect(cards: CardCollection, kind: HandKind, scoring: Hand, options: Options) {
+        assert_eq!(evaluate_poker_hand(cards, options), Some((kind, scoring)));
+    }
+
+    #[test]
+    fn kinds_test() {
+        assert_eq!(
+            evaluate_poker_hand(CardCollection::empty(), Options::empty()),
+            None
+        );
+        let none = Options::empty();
+        expect(
+            cards!("AS 9C 6C KH TS"),
+            HandKind::HighCard,
+            hand!("AS"),
+            none,
+        );
+        expect(
+            cards!("9S 2S 3C 9D AS"),
+            HandKind::Pair,
+            hand!("9S 9D"),
+            none,
+        );
+        expect(
+            cards!("2S KD 2C 5H KS"),
+            HandKind::TwoPair,
+            hand!("2S KD 2C KS"),
+            none,
+        );
+        expect(
+            cards!("9S 2S 3C 9D 9S"),
+            HandKind::ThreeOfAKind,
+            hand!("9S 9D 9S"),
+            none,
+        );
+        expect(
+            cards!("9S 5S 7S 6C 8S"),
+            HandKind::Straight,
+            hand!("9S 5S 7S 6C 8S"),
+            none,
+        );
+        expect(
+            cards!("AS AS AS TS 2S"),
+            HandKind::Flush,
+            hand!("AS AS AS TS 2S"),
+            none,
+        );
+        expect(
+            cards!("2S 2D 2S 9D 9C"),
+            HandKind::FullHouse,
+            hand!("2S 2D 2S 9D 9C"),
+            none,
+        );
+        expect(
+            cards!("9S 9D 2S 9D 9C"),
+            HandKind::FourOfAKind,
+            hand!("9S 9D 9D 9C"),
+            none,
+        );
+        expect(
+            cards!("5S 8S 7S 6S 9S"),
+            HandKind::StraightFlush,
+            hand!("5S 8S 7S 6S 9S"),
+            none,
+        );
+        expect(
+            cards!("9S 9D 9S 9D 9C"),
+            HandKind::FiveOfAKind,
+            hand!("9S 9D 9S 9D 9C"),
+            none,
+        );
+        expect(
+            cards!("9S AS 9S AS 9S"),
+            HandKind::FlushHouse,
+            hand!("9S AS 9S AS 9S"),
+            none,
+        );
+        expect(
+            cards!("9S 9S 9S 9S 9S"),
+            HandKind::FlushFive,
+            hand!("9S 9S 9S 9S 9S"),
+            none,
+        );
+    }
+
+    #[test]
+    fn straights_test() {
+        // Aces count low as well as high, but don't wrap around
+        expect(
+            cards!("3D AS 2C 5H 4S"),
+            HandKind::Straight,
+            hand!("3D AS 2C 5H 4S"),
+            Options::empty(),
+        );
+        expect(
+            cards!("QD KS AC 2H 3S"),
+            HandKind::HighCard,
+            hand!("AC"),
+            Options::empty(),
+        );
+        // One gap with Shortcut, but no more
+        expect(
+            cards!("5S 8D 7S 6C TS"),
+            HandKind::Straight,
+            hand!("5S 8D 7S 6C TS"),
+            Options::GappedStraights,
+        );
+        expect(
+            cards!("8D 2S 6D 4S 7D"),
+            HandKind::HighCard,
+            hand!("8D"),
+            Options::GappedStraights,
+        );
+        expect(
+            cards!("8D 2S 6D 4S 7D"),
+            HandKind::Straight,
+            hand!("8D 6D 4S 7D"),
+            Options::all(),
+        );
+        // Four cards with Four Fingers, and both of a pair in one score
+        expect(
+            cards!("5S AS 8D 7S 6C"),
+            HandKind::Straight,
+            hand!("5S 8D 7S 6C"),
+            Options::FourCardStraightsAndFlushes,
+        );
+        expect(
+            cards!("5S 5D 8D 7S 6C"),
+            HandKind::Straight,
+            hand!("5S 5D 8D 7S 6C"),
+            Options::FourCardStraightsAndFlushes,
+        );
+        expect(
+            cards!("AD 5S 8S 7S 6S"),
+            HandKind::StraightFlush,
+            hand!("5S 8S 7S 6S"),
+            Options::FourCardStraightsAndFlushes,
+        );
+    }
+
+    #[test]
+    fn enhancements_test() {
+        // Stone cards make nothing but always score, and Wild cards are
+        // every suit
+        expect(
+            cards!("AS AH AD+stone"),
+            HandKind::Pair,
+            hand!("AS AH AD+stone"),
+            Options::empty(),
+        );
+        expect(
+            cards!("AS+stone KD+stone"),
+            HandKind::HighCard,
+            hand!("AS+stone KD+stone"),
+            Options::empty(),
+        );
+        expect(
+            cards!("AS KS QS JS TH+wild"),
+            HandKind::StraightFlush,
+            hand!("AS KS QS JS TH+wild"),
+            Options::empty(),
+        );
+    }
+
+    #[test]
+    fn four_card_flush_test() {
+        // Only the cards of the flush's suit score
+        expect(
+            cards!("2S 9S JS 6S KD"),
+            HandKind::Flush,
+            hand!("2S 9S JS 6S"),
+            Options::FourCardStraightsAndFlushes,
+        );
+        expect(
+            cards!("2S 9S JS 6S KD"),
+            HandKind::HighCard,
+            hand!("KD"),
+            Options::empty(),
+        );
+    }
+}
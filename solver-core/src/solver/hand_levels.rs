@@ -0,0 +1,78 @@
+//! How far each poker hand has been levelled up by planets over a run, and how
+//! often each has been played.
+
+use strum::IntoEnumIterator;
+
+use crate::solver::cards::HandKind;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandLevels {
+    /// Indexed by `HandKind`; every hand starts at level 1
+    levels: [u32; 12],
+    /// How many times each hand has been played, indexed by `HandKind`
+    played: [u32; 12],
+}
+
+impl HandLevels {
+    /// Every hand at level 1, and none played yet.
+    pub const NEW: Self = Self {
+        levels: [1; 12],
+        played: [0; 12],
+    };
+
+    pub fn level(&self, kind: HandKind) -> u32 {
+        self.levels[kind as usize]
+    }
+
+    pub fn set_level(&mut self, kind: HandKind, level: u32) {
+        self.levels[kind as usize] = level;
+    }
+
+    /// Level a hand up once, like using its planet does.
+    pub fn level_up(&mut self, kind: HandKind) {
+        self.levels[kind as usize] += 1;
+    }
+
+    pub fn times_played(&self, kind: HandKind) -> u32 {
+        self.played[kind as usize]
+    }
+
+    pub fn record_played(&mut self, kind: HandKind) {
+        self.played[kind as usize] += 1;
+    }
+
+    pub fn set_times_played(&mut self, kind: HandKind, times: u32) {
+        self.played[kind as usize] = times;
+    }
+
+    /// The hand played the most this run, as Telescope looks for. Ties go to
+    /// the better hand, and nothing counts until something's been played.
+    pub fn most_played(&self) -> Option<HandKind> {
+        HandKind::iter()
+            .filter(|kind| self.times_played(*kind) > 0)
+            .max_by_key(|kind| (self.times_played(*kind), *kind))
+    }
+}
+
+impl Default for HandLevels {
+    fn default() -> Self {
+        Self::NEW
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_played_test() {
+        let mut levels = HandLevels::default();
+        assert_eq!(levels.most_played(), None);
+        levels.record_played(HandKind::Pair);
+        levels.record_played(HandKind::Pair);
+        levels.record_played(HandKind::Flush);
+        assert_eq!(levels.most_played(), Some(HandKind::Pair));
+        levels.record_played(HandKind::Flush);
+        assert_eq!(levels.most_played(), Some(HandKind::Flush));
+    }
+}
@@ -0,0 +1,174 @@
+//! Judging how well a run is going from its state alone, for searches that
+//! can't play every line out to the end.
+//!
+//! The default heuristic weighs up a handful of features of the state, and
+//! its weights can be loaded from a file to tune it without recompiling:
+//!
+//! ```toml
+//! money = 1.0
+//! jokers = 12.0
+//! rarity = [1.0, 1.5, 2.5, 4.0]
+//! hand_levels = 6.0
+//! ```
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::solver::cards::{CardView, HandKind};
+use crate::solver::error::{Error, Result};
+use crate::solver::game_state::GameState;
+use crate::solver::joker::registry::JokerId;
+use crate::solver::joker::Rarity;
+use crate::solver::ruleset::Ruleset;
+
+/// How good a state is, higher being better. Only comparisons between values
+/// mean anything.
+///
+/// Any `Fn(&GameState) -> f64` is a heuristic too.
+pub trait Heuristic {
+    fn evaluate(&self, state: &GameState) -> f64;
+}
+
+impl<F: Fn(&GameState) -> f64> Heuristic for F {
+    fn evaluate(&self, state: &GameState) -> f64 {
+        self(state)
+    }
+}
+
+/// A weighted sum of features of the state. Weights left out of a file keep
+/// their default.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeightedHeuristic {
+    /// Per dollar
+    pub money: f64,
+    /// Per joker, times its rarity's factor
+    pub jokers: f64,
+    /// The factor for each rarity, from Common to Legendary
+    pub rarity: [f64; 4],
+    /// Per chip the average card in the deck scores
+    pub deck: f64,
+    /// Per card with an enhancement, edition or seal
+    pub improved_cards: f64,
+    /// Per hand level gained, over every hand
+    pub hand_levels: f64,
+    /// Per ante reached
+    pub ante: f64,
+}
+
+impl WeightedHeuristic {
+    pub const DEFAULT: Self = Self {
+        money: 1.0,
+        jokers: 10.0,
+        rarity: [1.0, 1.5, 2.5, 4.0],
+        deck: 1.0,
+        improved_cards: 2.0,
+        hand_levels: 5.0,
+        ante: 50.0,
+    };
+
+    /// Load weights from a JSON object.
+    pub fn from_json(source: &str) -> Result<Self> {
+        serde_json::from_str(source).map_err(|err| Error::Weights(err.to_string()))
+    }
+
+    /// Load weights from a TOML document.
+    pub fn from_toml(source: &str) -> Result<Self> {
+        toml::from_str(source).map_err(|err| Error::Weights(err.to_string()))
+    }
+
+    fn rarity_factor(&self, rarity: Rarity) -> f64 {
+        match rarity {
+            Rarity::Common => self.rarity[0],
+            Rarity::Uncommon => self.rarity[1],
+            Rarity::Rare => self.rarity[2],
+            Rarity::Legendary => self.rarity[3],
+        }
+    }
+}
+
+impl Default for WeightedHeuristic {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Heuristic for WeightedHeuristic {
+    #[allow(clippy::cast_precision_loss)]
+    fn evaluate(&self, state: &GameState) -> f64 {
+        // Jokers without a registry entry (scripted ones, say) count as
+        // Common
+        let jokers: f64 = state
+            .jokers
+            .iter()
+            .map(|slot| self.rarity_factor(slot.id().map_or(Rarity::Common, JokerId::rarity)))
+            .sum();
+
+        let cards = state.deck.view();
+        let chips: u64 = cards
+            .iter()
+            .map(|card| Ruleset::VANILLA.chips_for_rank(card.rank))
+            .sum();
+        let deck = if cards.is_empty() {
+            0.0
+        } else {
+            chips as f64 / cards.len() as f64
+        };
+        let improved = cards
+            .iter()
+            .filter(|card| {
+                card.enhancement.is_some() || card.edition.is_some() || card.seal.is_some()
+            })
+            .count();
+
+        let levels: u32 = HandKind::iter()
+            .map(|kind| state.hand_levels.level(kind).saturating_sub(1))
+            .sum();
+
+        self.money * state.money as f64
+            + self.jokers * jokers
+            + self.deck * deck
+            + self.improved_cards * improved as f64
+            + self.hand_levels * f64::from(levels)
+            + self.ante * f64::from(state.ante)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::cards::{Card, Enhancement};
+    use crate::solver::joker::builtin;
+
+    #[test]
+    fn evaluate_test() {
+        let mut state = GameState::new();
+        state.money = 10;
+        let heuristic = WeightedHeuristic::default();
+        // $10, and ante 1
+        assert_relative_eq!(heuristic.evaluate(&state), 60.0);
+
+        state.add_joker(builtin::by_name("Joker").unwrap());
+        state.hand_levels.level_up(HandKind::Flush);
+        state.add_card(Card::from_ident("AS").with_enhancement(Enhancement::Glass));
+        // A common joker, a level, and a deck of one improved ace
+        assert_relative_eq!(heuristic.evaluate(&state), 60.0 + 10.0 + 5.0 + 11.0 + 2.0);
+    }
+
+    #[test]
+    fn load_test() {
+        let heuristic = WeightedHeuristic::from_toml("money = 2.0\nrarity = [1, 2, 3, 4]").unwrap();
+        assert_relative_eq!(heuristic.money, 2.0);
+        assert_relative_eq!(heuristic.rarity[3], 4.0);
+        assert_relative_eq!(heuristic.ante, WeightedHeuristic::DEFAULT.ante);
+
+        let json = serde_json::to_string(&heuristic).unwrap();
+        assert_eq!(WeightedHeuristic::from_json(&json).unwrap(), heuristic);
+        assert!(matches!(
+            WeightedHeuristic::from_json("{\"money\": \"lots\"}"),
+            Err(Error::Weights(_))
+        ));
+    }
+}
@@ -0,0 +1,257 @@
+pub mod builtin;
+pub mod copy;
+pub mod economy;
+pub mod face;
+pub mod loader;
+pub mod random;
+pub mod registry;
+pub mod retrigger;
+pub mod round;
+pub mod scaling;
+#[cfg(feature = "scripting")]
+pub mod script;
+
+use std::fmt;
+use std::sync::Arc;
+
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+use bitflags::bitflags;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::solver::cards::{Card, Edition};
+use crate::solver::joker::registry::JokerId;
+use crate::solver::scorer::{Effect, ScoringContext};
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, EnumIter, EnumString)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Legendary,
+}
+
+/// Which joker a copying joker like Blueprint copies.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CopyTarget {
+    /// The joker in the next slot to the right
+    Right,
+    /// The joker in the leftmost slot
+    Leftmost,
+}
+
+/// What the jokers get to see when a round ends.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RoundEnd {
+    /// The money held when the round ends, before anything is paid out
+    pub money: i64,
+    pub discards_used: u32,
+    pub discards_left: u32,
+    /// Whether the round was a Boss Blind, now defeated
+    pub boss_defeated: bool,
+}
+
+/// The state a joker builds up over the course of a run, like Green Joker's
+/// mult or Hologram's xMult. What the fields mean is up to each joker.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JokerState {
+    /// A scaling value, such as an accumulated mult or xMult
+    pub value: f64,
+    /// A count of something, such as remaining uses
+    pub counter: i64,
+    /// The card picked for this round by jokers like The Idol, or `None` when
+    /// it isn't known
+    pub card: Option<Card>,
+}
+
+/// A joker's behaviour, expressed as hooks that the scorer calls at each point
+/// in Balatro's scoring sequence. Every hook defaults to doing nothing, so a
+/// joker only needs to implement the ones it cares about.
+///
+/// Scoring hooks report what they do by pushing onto `effects`, which the
+/// scorer then applies in order. They can read the joker's state, but only the
+/// event hooks (`on_hand_played`, `on_discard`, ...) get to change it.
+pub trait Joker: fmt::Debug + Send + Sync {
+    fn name(&self) -> &str;
+
+    /// For jokers like Blueprint, which joker this one copies. A copying joker
+    /// doesn't do anything itself; the scorer runs the copied joker's scoring
+    /// hooks in its place, with the copied joker's state.
+    fn copies(&self) -> Option<CopyTarget> {
+        None
+    }
+
+    /// Whether Blueprint and Brainstorm can copy this joker.
+    fn copyable(&self) -> bool {
+        true
+    }
+
+    /// The state this joker starts with when it's first acquired.
+    fn initial_state(&self) -> JokerState {
+        JokerState::default()
+    }
+
+    /// Called once when a hand is played, before any cards are scored.
+    fn on_hand_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+    }
+
+    /// Called each time a played card is scored, including retriggers.
+    fn on_card_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+    }
+
+    /// Called each time a card held in hand is scored, including retriggers.
+    fn on_held_card(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+    }
+
+    /// How many extra times to score the played card at `index` (counting
+    /// only the cards that score). These add up with red seals and with each
+    /// other.
+    fn retriggers(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        index: usize,
+        card: Card,
+    ) -> usize {
+        0
+    }
+
+    /// Called once after every card has been scored, in joker order.
+    fn on_independent(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+    }
+
+    /// Called when a hand is played, before it's scored.
+    fn on_hand_played(&self, state: &mut JokerState, context: &ScoringContext) {}
+
+    /// Called when cards are discarded.
+    fn on_discard(&self, state: &mut JokerState, discarded: &[Card]) {}
+
+    /// Called when a playing card is added to the deck.
+    fn on_card_added(&self, state: &mut JokerState, card: Card) {}
+
+    /// The money this joker pays out when the given cards are discarded.
+    /// Fractional when it's an expected value, like Mail-In Rebate's before
+    /// its rank is picked.
+    fn discard_money(&self, state: &JokerState, discarded: &[Card]) -> f64 {
+        0.0
+    }
+
+    /// Called when a round is won, returning the money this joker pays out.
+    fn on_round_end(&self, state: &mut JokerState, round: &RoundEnd) -> u32 {
+        0
+    }
+
+    /// The cards this joker could pick as `JokerState::card` at the start of a
+    /// round, given the deck. A card that appears more than once is that much
+    /// more likely. Empty for jokers that don't pick anything.
+    fn round_choices(&self, state: &JokerState, deck: &[Card]) -> Vec<Card> {
+        Vec::new()
+    }
+
+    /// Called at the start of every round. By default, picks one of the
+    /// `round_choices` at random.
+    fn on_round_start(&self, state: &mut JokerState, deck: &[Card], rng: &mut dyn RngCore) {
+        if let Some(card) = self.round_choices(state, deck).choose(rng) {
+            state.card = Some(*card);
+        }
+    }
+}
+
+bitflags! {
+    /// The stickers higher stakes put on jokers in the shop.
+    #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+    pub struct Stickers: u32 {
+        /// Can't be sold or destroyed
+        const Eternal    = 0b001;
+        /// Debuffed after `JokerSlot::PERISHABLE_ROUNDS` rounds
+        const Perishable = 0b010;
+        /// Costs $1 to buy, and $3 at the end of every round
+        const Rental     = 0b100;
+    }
+}
+
+/// A joker sitting in one of the joker slots.
+#[derive(Clone, Debug)]
+pub struct JokerSlot {
+    pub joker: Arc<dyn Joker>,
+    pub edition: Option<Edition>,
+    pub stickers: Stickers,
+    /// How many rounds this joker has been held through
+    pub rounds_held: u32,
+    pub state: JokerState,
+}
+
+impl JokerSlot {
+    /// How many rounds a Perishable joker lasts before it's debuffed.
+    pub const PERISHABLE_ROUNDS: u32 = 5;
+
+    pub fn new(joker: Arc<dyn Joker>) -> Self {
+        Self {
+            state: joker.initial_state(),
+            joker,
+            edition: None,
+            stickers: Stickers::empty(),
+            rounds_held: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn with_stickers(self, stickers: Stickers) -> Self {
+        Self { stickers, ..self }
+    }
+
+    /// Whether this joker has stopped working. Debuffed jokers don't score,
+    /// and can't be copied.
+    pub fn is_debuffed(&self) -> bool {
+        self.stickers.contains(Stickers::Perishable) && self.rounds_held >= Self::PERISHABLE_ROUNDS
+    }
+
+    #[must_use]
+    pub fn with_edition(self, edition: Edition) -> Self {
+        Self {
+            edition: Some(edition),
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.joker.name()
+    }
+
+    /// The registry entry for this joker, if it's one of the builtin jokers.
+    pub fn id(&self) -> Option<JokerId> {
+        self.name().parse().ok()
+    }
+
+    /// What selling this joker would pay, if it's one of the builtin jokers.
+    /// Rental jokers were bought for $1, so they sell for $1.
+    pub fn sell_value(&self) -> Option<u32> {
+        if self.stickers.contains(Stickers::Rental) {
+            return Some(1);
+        }
+        self.id().map(|id| id.sell_value(self.edition))
+    }
+}
@@ -0,0 +1,154 @@
+//! The jokers that ship with the crate. Most of their definitions live in
+//! `data/jokers.json` (see the `loader` module for the format), and the rest
+//! are written out in Rust.
+
+use std::sync::{Arc, LazyLock};
+
+use crate::solver::joker::{copy, economy, face, loader, random, retrigger, round, scaling, Joker};
+
+static BUILTIN_JOKERS: LazyLock<Vec<Arc<dyn Joker>>> = LazyLock::new(|| {
+    let mut jokers = loader::embedded();
+    jokers.extend(scaling::all());
+    jokers.extend(random::all());
+    jokers.extend(face::all());
+    jokers.extend(round::all());
+    jokers.extend(retrigger::all());
+    jokers.extend(copy::all());
+    jokers.extend(economy::all());
+    jokers
+});
+
+/// Every built-in joker.
+pub fn all() -> Vec<Arc<dyn Joker>> {
+    BUILTIN_JOKERS.iter().map(Arc::clone).collect()
+}
+
+/// Look up a built-in joker by its in-game name, ignoring case.
+pub fn by_name(name: &str) -> Option<Arc<dyn Joker>> {
+    BUILTIN_JOKERS
+        .iter()
+        .find(|joker| joker.name().eq_ignore_ascii_case(name))
+        .map(Arc::clone)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::cards::{Edition, Hand, HandKind};
+    use crate::solver::joker::JokerSlot;
+    use crate::solver::scorer::{Scorer, ScoringContext};
+    use crate::{card, hand};
+
+    fn slots(names: &[&str]) -> Vec<JokerSlot> {
+        names
+            .iter()
+            .map(|name| JokerSlot::new(by_name(name).unwrap()))
+            .collect()
+    }
+
+    fn expect_score(hand: &Hand, kind: HandKind, jokers: &[JokerSlot], expected_score: f64) {
+        let context = ScoringContext::new(kind, hand).with_jokers(jokers);
+        assert_relative_eq!(Scorer::score_hand(&context).value(), expected_score);
+    }
+
+    #[test]
+    fn lookup_test() {
+        assert_eq!(by_name("joker").unwrap().name(), "Joker");
+        assert_eq!(by_name("the duo").unwrap().name(), "The Duo");
+        assert!(by_name("Not A Joker").is_none());
+    }
+
+    #[test]
+    fn flat_joker_test() {
+        // 30 * (2 + 4)
+        expect_score(&hand!("KH KS"), HandKind::Pair, &slots(&["Joker"]), 180.0);
+    }
+
+    #[test]
+    fn suit_joker_test() {
+        // 30 * (2 + 3)
+        expect_score(
+            &hand!("KD KS"),
+            HandKind::Pair,
+            &slots(&["Greedy Joker"]),
+            150.0,
+        );
+    }
+
+    #[test]
+    fn hand_kind_joker_test() {
+        // A full house contains a pair: 66 * (4 + 8)
+        expect_score(
+            &hand!("KD KS 2C 2D 2H"),
+            HandKind::FullHouse,
+            &slots(&["Jolly Joker"]),
+            792.0,
+        );
+        // But a straight doesn't: 65 * 4
+        expect_score(
+            &hand!("9D 8S 7C 6D 5H"),
+            HandKind::Straight,
+            &slots(&["Jolly Joker"]),
+            260.0,
+        );
+    }
+
+    #[test]
+    fn joker_order_test() {
+        // (2 + 4) * 2
+        expect_score(
+            &hand!("KH KS"),
+            HandKind::Pair,
+            &slots(&["Joker", "The Duo"]),
+            360.0,
+        );
+        // (2 * 2) + 4
+        expect_score(
+            &hand!("KH KS"),
+            HandKind::Pair,
+            &slots(&["The Duo", "Joker"]),
+            240.0,
+        );
+    }
+
+    #[test]
+    fn rank_joker_test() {
+        // (10 + 11 + 20 + 11 + 20) * (2 + 4 + 4)
+        expect_score(&hand!("AH AS"), HandKind::Pair, &slots(&["Scholar"]), 720.0);
+    }
+
+    #[test]
+    fn held_rank_joker_test() {
+        let played = hand!("2H 2S");
+        let held = [card!("KH"), card!("KS"), card!("QD")];
+        let jokers = slots(&["Baron", "Shoot the Moon"]);
+        let context = ScoringContext::new(HandKind::Pair, &played)
+            .with_held(&held)
+            .with_jokers(&jokers);
+        // 14 * (2 * 1.5 * 1.5 + 13)
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 245.0);
+    }
+
+    #[test]
+    fn scaling_joker_test() {
+        // Each one gives +3 mult per joker: 30 * (2 + 6 + 6)
+        expect_score(
+            &hand!("KH KS"),
+            HandKind::Pair,
+            &slots(&["Abstract Joker", "Abstract Joker"]),
+            420.0,
+        );
+    }
+
+    #[test]
+    fn joker_edition_test() {
+        let jokers = vec![
+            JokerSlot::new(by_name("Joker").unwrap()).with_edition(Edition::Polychrome),
+            JokerSlot::new(by_name("Joker").unwrap()).with_edition(Edition::Foil),
+        ];
+        // (30 + 50) * ((2 + 4) * 1.5 + 4)
+        expect_score(&hand!("KH KS"), HandKind::Pair, &jokers, 1040.0);
+    }
+}
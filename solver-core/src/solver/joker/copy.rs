@@ -0,0 +1,157 @@
+//! Jokers that copy other jokers, and working out what they end up copying.
+
+use std::sync::Arc;
+
+use crate::solver::joker::{CopyTarget, Joker, JokerSlot};
+
+/// Copies the joker to its right.
+#[derive(Debug)]
+pub struct Blueprint;
+
+impl Joker for Blueprint {
+    fn name(&self) -> &'static str {
+        "Blueprint"
+    }
+
+    fn copies(&self) -> Option<CopyTarget> {
+        Some(CopyTarget::Right)
+    }
+}
+
+/// Copies the leftmost joker.
+#[derive(Debug)]
+pub struct Brainstorm;
+
+impl Joker for Brainstorm {
+    fn name(&self) -> &'static str {
+        "Brainstorm"
+    }
+
+    fn copies(&self) -> Option<CopyTarget> {
+        Some(CopyTarget::Leftmost)
+    }
+}
+
+/// Every copying joker.
+pub fn all() -> Vec<Arc<dyn Joker>> {
+    vec![Arc::new(Blueprint), Arc::new(Brainstorm)]
+}
+
+/// For each slot, the slot whose scoring hooks it runs. Ordinary jokers run
+/// their own. Copying jokers follow their targets, through any chain of other
+/// copying jokers, to the joker that actually does something. They run nothing
+/// (`None`) when the chain runs off the end, loops back on itself, or ends at
+/// a joker that can't be copied. Debuffed jokers run nothing, and can't be
+/// copied either.
+pub fn resolve_copies(jokers: &[JokerSlot]) -> Vec<Option<usize>> {
+    (0..jokers.len())
+        .map(|slot| {
+            let mut current = slot;
+            let mut visited = vec![false; jokers.len()];
+            loop {
+                visited[current] = true;
+                if jokers[current].is_debuffed() {
+                    return None;
+                }
+                let next = match jokers[current].joker.copies() {
+                    None if current == slot => return Some(slot),
+                    None => return jokers[current].joker.copyable().then_some(current),
+                    Some(CopyTarget::Right) => current + 1,
+                    Some(CopyTarget::Leftmost) => 0,
+                };
+                if next >= jokers.len() || visited[next] {
+                    return None;
+                }
+                current = next;
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::cards::{Edition, Hand, HandKind};
+    use crate::solver::joker::builtin;
+    use crate::solver::scorer::{Scorer, ScoringContext};
+
+    #[derive(Debug)]
+    struct Uncopyable;
+
+    impl Joker for Uncopyable {
+        fn name(&self) -> &'static str {
+            "Uncopyable"
+        }
+
+        fn copyable(&self) -> bool {
+            false
+        }
+    }
+
+    fn slots(names: &[&str]) -> Vec<JokerSlot> {
+        names
+            .iter()
+            .map(|name| match *name {
+                "Uncopyable" => JokerSlot::new(Arc::new(Uncopyable)),
+                name => JokerSlot::new(builtin::by_name(name).unwrap()),
+            })
+            .collect()
+    }
+
+    fn resolve(names: &[&str]) -> Vec<Option<usize>> {
+        resolve_copies(&slots(names))
+    }
+
+    #[test]
+    fn resolve_test() {
+        assert_eq!(resolve(&["Blueprint", "Joker"]), [Some(1), Some(1)]);
+        // Nothing to the right
+        assert_eq!(resolve(&["Joker", "Blueprint"]), [Some(0), None]);
+        // Chains resolve to the end of the chain
+        assert_eq!(
+            resolve(&["Blueprint", "Blueprint", "Joker"]),
+            [Some(2), Some(2), Some(2)]
+        );
+        assert_eq!(
+            resolve(&["Joker", "Brainstorm", "Blueprint", "Brainstorm"]),
+            [Some(0), Some(0), Some(0), Some(0)]
+        );
+        // Brainstorm in the leftmost slot would copy itself
+        assert_eq!(resolve(&["Brainstorm", "Joker"]), [None, Some(1)]);
+        // Brainstorm copying a Blueprint copies whatever the Blueprint copies
+        assert_eq!(
+            resolve(&["Blueprint", "Joker", "Brainstorm"]),
+            [Some(1), Some(1), Some(1)]
+        );
+        // A loop copies nothing
+        assert_eq!(resolve(&["Blueprint", "Brainstorm"]), [None, None]);
+        assert_eq!(
+            resolve(&["Blueprint", "Uncopyable", "Brainstorm"]),
+            [None, Some(1), None]
+        );
+    }
+
+    #[test]
+    fn scoring_test() {
+        let hand = Hand::from_idents("KH KS");
+        let score = |jokers: &[JokerSlot]| {
+            let context = ScoringContext::new(HandKind::Pair, &hand).with_jokers(jokers);
+            Scorer::score_hand(&context).value()
+        };
+
+        // 30 * (2 + 4 + 4)
+        assert_relative_eq!(score(&slots(&["Blueprint", "Joker"])), 300.0);
+        // A copy keeps its own edition
+        let mut jokers = slots(&["Joker", "Brainstorm"]);
+        jokers[1].edition = Some(Edition::Polychrome);
+        assert_relative_eq!(score(&jokers), 30.0 * (2.0 + 4.0 + 4.0) * 1.5);
+        // Copies use the copied joker's state
+        let mut jokers = slots(&["Blueprint", "Green Joker"]);
+        jokers[1].state.value = 3.0;
+        assert_relative_eq!(score(&jokers), 30.0 * (2.0 + 3.0 + 3.0));
+        // And its retriggers: (10 + 10 * 3 + 10 * 3) * 2
+        assert_relative_eq!(score(&slots(&["Blueprint", "Sock and Buskin"])), 140.0);
+    }
+}
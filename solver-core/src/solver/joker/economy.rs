@@ -0,0 +1,165 @@
+//! Jokers that pay out at the end of each round. Bull, which turns money into
+//! chips, is defined in `data/jokers.json`.
+
+use std::sync::Arc;
+
+use crate::solver::joker::{Joker, JokerState, RoundEnd};
+
+/// Earn $4 at the end of every round.
+#[derive(Debug)]
+pub struct GoldenJoker;
+
+impl Joker for GoldenJoker {
+    fn name(&self) -> &'static str {
+        "Golden Joker"
+    }
+
+    fn copyable(&self) -> bool {
+        false
+    }
+
+    fn on_round_end(&self, state: &mut JokerState, round: &RoundEnd) -> u32 {
+        4
+    }
+}
+
+/// Earn an extra $1 of interest for every $5 held at the end of the round,
+/// up to the usual interest cap of five $5s.
+#[derive(Debug)]
+pub struct ToTheMoon;
+
+impl Joker for ToTheMoon {
+    fn name(&self) -> &'static str {
+        "To the Moon"
+    }
+
+    fn copyable(&self) -> bool {
+        false
+    }
+
+    fn on_round_end(&self, state: &mut JokerState, round: &RoundEnd) -> u32 {
+        u32::try_from(round.money / 5).unwrap_or(0).min(5)
+    }
+}
+
+/// Earn $1 at the end of every round, and $2 more for each Boss Blind
+/// defeated. The payout is kept in `value`.
+#[derive(Debug)]
+pub struct Rocket;
+
+impl Joker for Rocket {
+    fn name(&self) -> &'static str {
+        "Rocket"
+    }
+
+    fn copyable(&self) -> bool {
+        false
+    }
+
+    fn initial_state(&self) -> JokerState {
+        JokerState {
+            value: 1.0,
+            ..JokerState::default()
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn on_round_end(&self, state: &mut JokerState, round: &RoundEnd) -> u32 {
+        if round.boss_defeated {
+            state.value += 2.0;
+        }
+        state.value as u32
+    }
+}
+
+/// Earn $2 per discard left at the end of the round, if no discards were used.
+#[derive(Debug)]
+pub struct DelayedGratification;
+
+impl Joker for DelayedGratification {
+    fn name(&self) -> &'static str {
+        "Delayed Gratification"
+    }
+
+    fn copyable(&self) -> bool {
+        false
+    }
+
+    fn on_round_end(&self, state: &mut JokerState, round: &RoundEnd) -> u32 {
+        if round.discards_used == 0 {
+            2 * round.discards_left
+        } else {
+            0
+        }
+    }
+}
+
+/// Every joker that pays out at the end of the round.
+pub fn all() -> Vec<Arc<dyn Joker>> {
+    vec![
+        Arc::new(GoldenJoker),
+        Arc::new(ToTheMoon),
+        Arc::new(Rocket),
+        Arc::new(DelayedGratification),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::cards;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardView, Hand, HandKind};
+    use crate::solver::game_state::GameState;
+    use crate::solver::joker::builtin;
+    use crate::solver::scorer::{Randomness, ScoringContext};
+
+    fn state_with(names: &[&str]) -> GameState {
+        let mut state = GameState::new();
+        for name in names {
+            state.add_joker(builtin::by_name(name).unwrap());
+        }
+        state
+    }
+
+    #[test]
+    fn income_test() {
+        let mut state = state_with(&["Golden Joker", "To the Moon", "Rocket"]);
+        state.money = 23;
+        // 4 + 4 + 1
+        assert_eq!(state.end_round(), 9);
+        assert_eq!(state.money, 32);
+        // 4 + 5 (capped) + 3
+        state.money = 100;
+        state.blind = Blind::Boss;
+        assert_eq!(state.end_round(), 12);
+        // Rocket keeps its raised payout
+        state.blind = Blind::Small;
+        assert_eq!(state.end_round(), 4 + 5 + 3);
+    }
+
+    #[test]
+    fn delayed_gratification_test() {
+        let mut state = state_with(&["Delayed Gratification"]);
+        state.discards = 3;
+        assert_eq!(state.end_round(), 6);
+
+        state.discard(cards!("2C").view());
+        assert_eq!(state.end_round(), 0);
+    }
+
+    #[test]
+    fn bull_test() {
+        let mut state = state_with(&["Bull"]);
+        state.money = 10;
+        let hand = Hand::from_idents("KH KS");
+        // (30 + 2 * 10) * 2
+        let score = state.play_hand(
+            ScoringContext::new(HandKind::Pair, &hand),
+            Randomness::Expected,
+        );
+        assert_relative_eq!(score.value(), 100.0);
+    }
+}
@@ -0,0 +1,109 @@
+//! Face card jokers that need more than a per-card condition. The simple ones
+//! (Scary Face, Smiley Face) are defined in `data/jokers.json`.
+
+use std::sync::Arc;
+
+use crate::solver::cards::Card;
+use crate::solver::joker::{Joker, JokerState};
+use crate::solver::scorer::{Effect, ScoringContext};
+
+/// The first scored face card gives x2 mult, every time it's triggered.
+///
+/// Cards are only known by value when they're scored, so an identical copy of
+/// the first face card scored in the same hand triggers it too.
+#[derive(Debug)]
+pub struct Photograph;
+
+impl Joker for Photograph {
+    fn name(&self) -> &'static str {
+        "Photograph"
+    }
+
+    fn on_card_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        let first_face = context.scored_cards().find(|card| context.is_face(*card));
+        if first_face == Some(card) {
+            effects.push(Effect::XMult(2.0));
+        }
+    }
+}
+
+/// Every face card joker written out in Rust.
+pub fn all() -> Vec<Arc<dyn Joker>> {
+    vec![Arc::new(Photograph)]
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::cards::{Hand, HandKind};
+    use crate::solver::joker::{builtin, JokerSlot};
+    use crate::solver::scorer::{Scorer, ScoringOptions};
+
+    fn score(hand: &str, kind: HandKind, joker: &str, options: ScoringOptions) -> f64 {
+        let hand = Hand::from_idents(hand);
+        let jokers = [JokerSlot::new(builtin::by_name(joker).unwrap())];
+        let context = ScoringContext::new(kind, &hand)
+            .with_jokers(&jokers)
+            .with_options(options);
+        Scorer::score_hand(&context).value()
+    }
+
+    #[test]
+    fn photograph_test() {
+        // (10 + 10 + 10) * 2 * 2, only the first King counts
+        assert_relative_eq!(
+            score(
+                "KH KS",
+                HandKind::Pair,
+                "Photograph",
+                ScoringOptions::empty()
+            ),
+            120.0
+        );
+        assert_relative_eq!(
+            score(
+                "2H 2S",
+                HandKind::Pair,
+                "Photograph",
+                ScoringOptions::empty()
+            ),
+            28.0
+        );
+    }
+
+    #[test]
+    fn pareidolia_test() {
+        let pareidolia = ScoringOptions::AllCardsAreFace;
+        // (10 + 2 + 2 + 30 + 30) * 2
+        assert_relative_eq!(
+            score("2H 2S", HandKind::Pair, "Scary Face", pareidolia),
+            148.0
+        );
+        assert_relative_eq!(
+            score(
+                "2H 2S",
+                HandKind::Pair,
+                "Scary Face",
+                ScoringOptions::empty()
+            ),
+            28.0
+        );
+        // 14 * (2 + 5 + 5)
+        assert_relative_eq!(
+            score("2H 2S", HandKind::Pair, "Smiley Face", pareidolia),
+            168.0
+        );
+        assert_relative_eq!(
+            score("2H 2S", HandKind::Pair, "Photograph", pareidolia),
+            56.0
+        );
+    }
+}
@@ -0,0 +1,392 @@
+//! Loads joker definitions from data files, so that new jokers (or rebalanced
+//! ones) don't require recompiling the crate.
+//!
+//! A definition describes when a joker triggers, what has to be true for it to
+//! apply, and what it does:
+//!
+//! ```toml
+//! [[jokers]]
+//! name = "Scholar"
+//! rarity = "Common"
+//! cost = 4
+//! trigger = "card_scored"
+//! condition = { ranks = ["Ace"] }
+//! effects = [{ chips = 20 }, { mult = 4 }]
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::solver::cards::{Card, CardView, HandKind, Rank, Suit};
+use crate::solver::error::{Error, Result};
+use crate::solver::joker::{Joker, JokerState, Rarity};
+use crate::solver::scorer::{Effect, ScoringContext};
+
+/// The definitions for every built-in joker.
+const EMBEDDED_JOKERS: &str = include_str!("../../../data/jokers.json");
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    HandScored,
+    CardScored,
+    HeldCard,
+    Independent,
+}
+
+/// Every listed requirement has to hold for the joker to apply. Suits, ranks
+/// and `face` are checked against the card being scored, so they only make
+/// sense for card triggers.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConditionDefinition {
+    pub suits: Vec<String>,
+    pub ranks: Vec<String>,
+    /// Only face cards, which with Pareidolia means every card
+    pub face: bool,
+    pub contains: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Quantity {
+    Joker,
+    PlayedCard,
+    HeldCard,
+    /// Each dollar held, if any
+    Dollar,
+}
+
+impl Quantity {
+    fn count(self, context: &ScoringContext) -> usize {
+        match self {
+            Self::Joker => context.jokers.len(),
+            Self::PlayedCard => context.played.view().len(),
+            Self::HeldCard => context.held.len(),
+            Self::Dollar => usize::try_from(context.money).unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EffectDefinition {
+    pub chips: Option<u64>,
+    pub mult: Option<f64>,
+    pub xmult: Option<f64>,
+    /// Scale the effect by how many of something there are
+    pub per: Option<Quantity>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JokerDefinition {
+    pub name: String,
+    pub rarity: String,
+    pub cost: u32,
+    pub trigger: Trigger,
+    #[serde(default)]
+    pub condition: ConditionDefinition,
+    pub effects: Vec<EffectDefinition>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JokerDefinitions {
+    jokers: Vec<JokerDefinition>,
+}
+
+type HandPredicate = Box<dyn Fn(&ScoringContext) -> bool + Send + Sync>;
+type CardPredicate = Box<dyn Fn(&ScoringContext, Card) -> bool + Send + Sync>;
+
+#[derive(Copy, Clone, Debug)]
+struct ScaledEffect {
+    effect: Effect,
+    per: Option<Quantity>,
+}
+
+impl ScaledEffect {
+    #[allow(clippy::cast_precision_loss)]
+    fn resolve(self, context: &ScoringContext) -> Effect {
+        let Some(per) = self.per else {
+            return self.effect;
+        };
+        let count = per.count(context);
+        match self.effect {
+            Effect::Chips(chips) => Effect::Chips(chips * count as u64),
+            Effect::Mult(mult) => Effect::Mult(mult * count as f64),
+            effect => effect,
+        }
+    }
+}
+
+/// A joker built from a `JokerDefinition`.
+pub struct DataJoker {
+    name: String,
+    rarity: Rarity,
+    cost: u32,
+    trigger: Trigger,
+    hand_condition: HandPredicate,
+    card_condition: CardPredicate,
+    effects: Vec<ScaledEffect>,
+}
+
+impl DataJoker {
+    pub fn rarity(&self) -> Rarity {
+        self.rarity
+    }
+
+    pub fn cost(&self) -> u32 {
+        self.cost
+    }
+
+    fn apply(&self, trigger: Trigger, context: &ScoringContext, effects: &mut Vec<Effect>) {
+        if self.trigger == trigger && (self.hand_condition)(context) {
+            effects.extend(self.effects.iter().map(|effect| effect.resolve(context)));
+        }
+    }
+
+    fn apply_to_card(
+        &self,
+        trigger: Trigger,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        if (self.card_condition)(context, card) {
+            self.apply(trigger, context, effects);
+        }
+    }
+}
+
+impl fmt::Debug for DataJoker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataJoker")
+            .field("name", &self.name)
+            .field("rarity", &self.rarity)
+            .field("cost", &self.cost)
+            .field("trigger", &self.trigger)
+            .field("effects", &self.effects)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Joker for DataJoker {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_hand_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.apply(Trigger::HandScored, context, effects);
+    }
+
+    fn on_card_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.apply_to_card(Trigger::CardScored, context, card, effects);
+    }
+
+    fn on_held_card(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.apply_to_card(Trigger::HeldCard, context, card, effects);
+    }
+
+    fn on_independent(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.apply(Trigger::Independent, context, effects);
+    }
+}
+
+fn parse<T: FromStr>(joker: &str, what: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| Error::JokerDefinition(format!("{joker}: unknown {what} `{value}`")))
+}
+
+impl TryFrom<JokerDefinition> for DataJoker {
+    type Error = Error;
+
+    fn try_from(definition: JokerDefinition) -> Result<Self> {
+        let name = definition.name;
+        let condition = definition.condition;
+
+        let suits = condition
+            .suits
+            .iter()
+            .map(|suit| parse::<Suit>(&name, "suit", suit))
+            .collect::<Result<Vec<_>>>()?;
+        let ranks = condition
+            .ranks
+            .iter()
+            .map(|rank| parse::<Rank>(&name, "rank", rank))
+            .collect::<Result<Vec<_>>>()?;
+        let contains = condition
+            .contains
+            .as_deref()
+            .map(|kind| parse::<HandKind>(&name, "hand kind", kind))
+            .transpose()?;
+
+        let card_trigger = matches!(definition.trigger, Trigger::CardScored | Trigger::HeldCard);
+        let face = condition.face;
+        let card_condition = !suits.is_empty() || !ranks.is_empty() || face;
+        if card_condition && !card_trigger {
+            return Err(Error::JokerDefinition(format!(
+                "{name}: suit, rank and face conditions need a card trigger"
+            )));
+        }
+
+        let effects = definition
+            .effects
+            .iter()
+            .map(|effect| {
+                let resolved = match (effect.chips, effect.mult, effect.xmult) {
+                    (Some(chips), None, None) => Effect::Chips(chips),
+                    (None, Some(mult), None) => Effect::Mult(mult),
+                    (None, None, Some(xmult)) => Effect::XMult(xmult),
+                    _ => {
+                        return Err(Error::JokerDefinition(format!(
+                            "{name}: each effect needs exactly one of chips, mult, or xmult"
+                        )))
+                    }
+                };
+                if effect.per.is_some() && matches!(resolved, Effect::XMult(_)) {
+                    return Err(Error::JokerDefinition(format!(
+                        "{name}: xmult effects can't be scaled"
+                    )));
+                }
+                Ok(ScaledEffect {
+                    effect: resolved,
+                    per: effect.per,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            rarity: parse(&name, "rarity", &definition.rarity)?,
+            cost: definition.cost,
+            trigger: definition.trigger,
+            hand_condition: Box::new(move |context| {
+                contains.is_none_or(|kind| context.contains(kind))
+            }),
+            card_condition: Box::new(move |context, card| {
+                (suits.is_empty() || suits.contains(&card.suit))
+                    && (ranks.is_empty() || ranks.contains(&card.rank))
+                    && (!face || context.is_face(card))
+            }),
+            effects,
+            name,
+        })
+    }
+}
+
+fn build(definitions: JokerDefinitions) -> Result<Vec<Arc<dyn Joker>>> {
+    definitions
+        .jokers
+        .into_iter()
+        .map(|definition| Ok(Arc::new(DataJoker::try_from(definition)?) as Arc<dyn Joker>))
+        .collect()
+}
+
+/// Load jokers from a JSON document of the form `{ "jokers": [...] }`.
+pub fn from_json(source: &str) -> Result<Vec<Arc<dyn Joker>>> {
+    let definitions =
+        serde_json::from_str(source).map_err(|err| Error::JokerDefinition(err.to_string()))?;
+    build(definitions)
+}
+
+/// Load jokers from a TOML document made of `[[jokers]]` tables.
+pub fn from_toml(source: &str) -> Result<Vec<Arc<dyn Joker>>> {
+    let definitions =
+        toml::from_str(source).map_err(|err| Error::JokerDefinition(err.to_string()))?;
+    build(definitions)
+}
+
+/// Load the jokers that ship with the crate.
+pub fn embedded() -> Vec<Arc<dyn Joker>> {
+    from_json(EMBEDDED_JOKERS).expect("embedded joker definitions are valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::hand;
+    use crate::solver::joker::JokerSlot;
+    use crate::solver::scorer::Scorer;
+
+    #[test]
+    fn embedded_test() {
+        let jokers = embedded();
+        assert!(jokers.len() >= 30);
+        assert!(jokers.iter().any(|joker| joker.name() == "Scholar"));
+    }
+
+    #[test]
+    fn toml_test() {
+        let jokers = from_toml(
+            r#"
+            [[jokers]]
+            name = "Pair Lover"
+            rarity = "Uncommon"
+            cost = 6
+            trigger = "independent"
+            condition = { contains = "Pair" }
+            effects = [{ chips = 5, per = "played_card" }, { xmult = 1.5 }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(jokers.len(), 1);
+
+        let slots = [JokerSlot::new(Arc::clone(&jokers[0]))];
+        let hand = hand!("KH KS");
+        let context = ScoringContext::new(HandKind::Pair, &hand).with_jokers(&slots);
+        // (30 + 5 * 2) * (2 * 1.5)
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 120.0);
+    }
+
+    #[test]
+    fn invalid_definition_test() {
+        let definition = |body: &str| {
+            format!(
+                r#"{{ "jokers": [{{ "name": "Bad", "rarity": "Common", "cost": 1, {body} }}] }}"#
+            )
+        };
+
+        assert!(from_json(&definition(r#""trigger": "independent", "effects": []"#)).is_ok());
+        assert!(from_json(&definition(
+            r#""trigger": "independent", "condition": { "suits": ["Hearts"] }, "effects": []"#
+        ))
+        .is_err());
+        assert!(from_json(&definition(
+            r#""trigger": "card_scored", "condition": { "ranks": ["Eleven"] }, "effects": []"#
+        ))
+        .is_err());
+        assert!(from_json(&definition(
+            r#""trigger": "independent", "effects": [{ "chips": 1, "mult": 1 }]"#
+        ))
+        .is_err());
+        assert!(from_json(&definition(r#""trigger": "sometimes", "effects": []"#)).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! Jokers whose effects are left to chance. They produce `Effect::Chance`,
+//! which the scorer resolves according to its `Randomness`.
+
+use std::sync::Arc;
+
+use crate::solver::cards::{Card, Suit};
+use crate::solver::joker::{Joker, JokerState};
+use crate::solver::scorer::{Chance, Effect, ScoringContext};
+
+/// 1 in 2 chance for scored Hearts to give x1.5 mult.
+#[derive(Debug)]
+pub struct Bloodstone;
+
+impl Joker for Bloodstone {
+    fn name(&self) -> &'static str {
+        "Bloodstone"
+    }
+
+    fn on_card_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        if card.suit == Suit::Hearts {
+            effects.push(Effect::Chance(Chance::XMult {
+                xmult: 1.5,
+                odds: 2,
+            }));
+        }
+    }
+}
+
+/// 1 in 2 chance for scored face cards to give $2.
+#[derive(Debug)]
+pub struct BusinessCard;
+
+impl Joker for BusinessCard {
+    fn name(&self) -> &'static str {
+        "Business Card"
+    }
+
+    fn on_card_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        if context.is_face(card) {
+            effects.push(Effect::Chance(Chance::Money { money: 2, odds: 2 }));
+        }
+    }
+}
+
+/// +0 to +23 mult.
+#[derive(Debug)]
+pub struct Misprint;
+
+impl Joker for Misprint {
+    fn name(&self) -> &'static str {
+        "Misprint"
+    }
+
+    fn on_independent(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        effects.push(Effect::Chance(Chance::MultRange { min: 0, max: 23 }));
+    }
+}
+
+/// Every random joker.
+pub fn all() -> Vec<Arc<dyn Joker>> {
+    vec![
+        Arc::new(Bloodstone),
+        Arc::new(BusinessCard),
+        Arc::new(Misprint),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::hand;
+    use crate::solver::cards::HandKind;
+    use crate::solver::joker::JokerSlot;
+    use crate::solver::scorer::{Randomness, Scorer};
+
+    #[test]
+    fn expected_test() {
+        let hand = hand!("KH KS");
+        let jokers = [
+            JokerSlot::new(Arc::new(Bloodstone)),
+            JokerSlot::new(Arc::new(BusinessCard)),
+            JokerSlot::new(Arc::new(Misprint)),
+        ];
+        let context = ScoringContext::new(HandKind::Pair, &hand).with_jokers(&jokers);
+        let breakdown = Scorer::score_breakdown(&context);
+        // 30 * (2 * 1.25 + 11.5)
+        assert_relative_eq!(breakdown.total.value(), 420.0);
+        assert_relative_eq!(breakdown.money, 2.0);
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn sampled_test() {
+        let hand = hand!("KH KS");
+        let jokers = [JokerSlot::new(Arc::new(Misprint))];
+        let context = ScoringContext::new(HandKind::Pair, &hand).with_jokers(&jokers);
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut seen = [false; 24];
+        for _ in 0..1000 {
+            let score = Scorer::score_hand_with(&context, Randomness::Sampled(&mut rng));
+            // 30 * (2 + 0..=23)
+            let mult = score.value() / 30.0 - 2.0;
+            assert_relative_eq!(mult, mult.round());
+            seen[mult as usize] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+    }
+}
@@ -0,0 +1,261 @@
+//! Every joker the crate knows about, along with what it costs. Scoring
+//! behaviour lives with the jokers themselves; this is for reasoning about
+//! buying and selling them.
+
+use std::sync::Arc;
+
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
+
+use crate::solver::cards::Edition;
+use crate::solver::joker::{builtin, Joker, Rarity};
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum JokerId {
+    Joker,
+    #[strum(serialize = "Greedy Joker")]
+    GreedyJoker,
+    #[strum(serialize = "Lusty Joker")]
+    LustyJoker,
+    #[strum(serialize = "Wrathful Joker")]
+    WrathfulJoker,
+    #[strum(serialize = "Gluttonous Joker")]
+    GluttonousJoker,
+    #[strum(serialize = "Jolly Joker")]
+    JollyJoker,
+    #[strum(serialize = "Zany Joker")]
+    ZanyJoker,
+    #[strum(serialize = "Mad Joker")]
+    MadJoker,
+    #[strum(serialize = "Crazy Joker")]
+    CrazyJoker,
+    #[strum(serialize = "Droll Joker")]
+    DrollJoker,
+    #[strum(serialize = "Sly Joker")]
+    SlyJoker,
+    #[strum(serialize = "Wily Joker")]
+    WilyJoker,
+    #[strum(serialize = "Clever Joker")]
+    CleverJoker,
+    #[strum(serialize = "Devious Joker")]
+    DeviousJoker,
+    #[strum(serialize = "Crafty Joker")]
+    CraftyJoker,
+    #[strum(serialize = "Abstract Joker")]
+    AbstractJoker,
+    Scholar,
+    Fibonacci,
+    #[strum(serialize = "Even Steven")]
+    EvenSteven,
+    #[strum(serialize = "Odd Todd")]
+    OddTodd,
+    #[strum(serialize = "Scary Face")]
+    ScaryFace,
+    #[strum(serialize = "Smiley Face")]
+    SmileyFace,
+    #[strum(serialize = "Walkie Talkie")]
+    WalkieTalkie,
+    #[strum(serialize = "Shoot the Moon")]
+    ShootTheMoon,
+    Cavendish,
+    Baron,
+    #[strum(serialize = "The Duo")]
+    TheDuo,
+    #[strum(serialize = "The Trio")]
+    TheTrio,
+    #[strum(serialize = "The Family")]
+    TheFamily,
+    #[strum(serialize = "The Order")]
+    TheOrder,
+    #[strum(serialize = "The Tribe")]
+    TheTribe,
+    Triboulet,
+    #[strum(serialize = "Ride the Bus")]
+    RideTheBus,
+    #[strum(serialize = "Green Joker")]
+    GreenJoker,
+    Hologram,
+    #[strum(serialize = "Lucky Cat")]
+    LuckyCat,
+    Bloodstone,
+    #[strum(serialize = "Business Card")]
+    BusinessCard,
+    Misprint,
+    Photograph,
+    #[strum(serialize = "The Idol")]
+    TheIdol,
+    #[strum(serialize = "Mail-In Rebate")]
+    MailInRebate,
+    #[strum(serialize = "Ancient Joker")]
+    AncientJoker,
+    Hack,
+    Dusk,
+    #[strum(serialize = "Sock and Buskin")]
+    SockAndBuskin,
+    Seltzer,
+    #[strum(serialize = "Hanging Chad")]
+    HangingChad,
+    Blueprint,
+    Brainstorm,
+    #[strum(serialize = "Golden Joker")]
+    GoldenJoker,
+    #[strum(serialize = "To the Moon")]
+    ToTheMoon,
+    Bull,
+    Rocket,
+    #[strum(serialize = "Delayed Gratification")]
+    DelayedGratification,
+}
+
+impl JokerId {
+    /// The joker's name, as it appears in game.
+    pub fn name(self) -> &'static str {
+        self.into()
+    }
+
+    pub fn rarity(self) -> Rarity {
+        self.info().0
+    }
+
+    /// The base price in the shop, before any edition.
+    pub fn cost(self) -> u32 {
+        self.info().1
+    }
+
+    /// The price in the shop of a copy with the given edition.
+    pub fn buy_cost(self, edition: Option<Edition>) -> u32 {
+        let edition_cost = match edition {
+            Some(Edition::Foil) => 2,
+            Some(Edition::Holographic) => 3,
+            Some(Edition::Polychrome | Edition::Negative) => 5,
+            None => 0,
+        };
+        self.cost() + edition_cost
+    }
+
+    /// What selling a copy with the given edition pays: half its buy cost,
+    /// rounded down, but always at least $1.
+    pub fn sell_value(self, edition: Option<Edition>) -> u32 {
+        (self.buy_cost(edition) / 2).max(1)
+    }
+
+    /// The joker's scoring behaviour.
+    pub fn joker(self) -> Arc<dyn Joker> {
+        builtin::by_name(self.name()).expect("every joker id has a builtin joker")
+    }
+
+    fn info(self) -> (Rarity, u32) {
+        use Rarity::{Common, Legendary, Rare, Uncommon};
+
+        match self {
+            Self::Joker => (Common, 2),
+            Self::JollyJoker | Self::SlyJoker => (Common, 3),
+            Self::GreedyJoker
+            | Self::LustyJoker
+            | Self::WrathfulJoker
+            | Self::GluttonousJoker
+            | Self::ShootTheMoon
+            | Self::Photograph => (Common, 5),
+            Self::ZanyJoker
+            | Self::MadJoker
+            | Self::CrazyJoker
+            | Self::DrollJoker
+            | Self::WilyJoker
+            | Self::CleverJoker
+            | Self::DeviousJoker
+            | Self::CraftyJoker
+            | Self::AbstractJoker
+            | Self::Scholar
+            | Self::EvenSteven
+            | Self::OddTodd
+            | Self::ScaryFace
+            | Self::SmileyFace
+            | Self::WalkieTalkie
+            | Self::Cavendish
+            | Self::GreenJoker
+            | Self::BusinessCard
+            | Self::Misprint
+            | Self::MailInRebate
+            | Self::HangingChad
+            | Self::DelayedGratification => (Common, 4),
+            Self::RideTheBus | Self::GoldenJoker => (Common, 6),
+            Self::Dusk | Self::ToTheMoon => (Uncommon, 5),
+            Self::LuckyCat
+            | Self::TheIdol
+            | Self::Hack
+            | Self::SockAndBuskin
+            | Self::Seltzer
+            | Self::Bull
+            | Self::Rocket => (Uncommon, 6),
+            Self::Hologram | Self::Bloodstone => (Uncommon, 7),
+            Self::Fibonacci => (Uncommon, 8),
+            Self::Baron
+            | Self::TheDuo
+            | Self::TheTrio
+            | Self::TheFamily
+            | Self::TheOrder
+            | Self::TheTribe
+            | Self::AncientJoker => (Rare, 8),
+            Self::Blueprint | Self::Brainstorm => (Rare, 10),
+            Self::Triboulet => (Legendary, 20),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn builtin_test() {
+        // Ids and builtin jokers line up one to one
+        for id in JokerId::iter() {
+            assert_eq!(id.joker().name(), id.name());
+        }
+        for joker in builtin::all() {
+            assert!(joker.name().parse::<JokerId>().is_ok(), "{}", joker.name());
+        }
+    }
+
+    #[test]
+    fn data_test() {
+        let data: serde_json::Value =
+            serde_json::from_str(include_str!("../../../data/jokers.json")).unwrap();
+        for definition in data["jokers"].as_array().unwrap() {
+            let id: JokerId = definition["name"].as_str().unwrap().parse().unwrap();
+            assert_eq!(
+                id.rarity(),
+                definition["rarity"].as_str().unwrap().parse().unwrap()
+            );
+            assert_eq!(
+                u64::from(id.cost()),
+                definition["cost"].as_u64().unwrap(),
+                "{id}"
+            );
+        }
+    }
+
+    #[test]
+    fn cost_test() {
+        assert_eq!("ride the bus".parse(), Ok(JokerId::RideTheBus));
+        assert_eq!(JokerId::Joker.sell_value(None), 1);
+        assert_eq!(JokerId::Baron.sell_value(None), 4);
+        assert_eq!(JokerId::Baron.buy_cost(Some(Edition::Polychrome)), 13);
+        assert_eq!(JokerId::Baron.sell_value(Some(Edition::Polychrome)), 6);
+    }
+}
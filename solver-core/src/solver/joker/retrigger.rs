@@ -0,0 +1,235 @@
+//! Jokers that retrigger played cards. They only say how many extra times a
+//! card is scored; the scorer adds that to the card's own red seal.
+
+use std::sync::Arc;
+
+use crate::solver::cards::{Card, Rank};
+use crate::solver::joker::{Joker, JokerState};
+use crate::solver::scorer::{ScoringContext, ScoringOptions};
+
+/// Retriggers each played 2, 3, 4, or 5.
+#[derive(Debug)]
+pub struct Hack;
+
+impl Joker for Hack {
+    fn name(&self) -> &'static str {
+        "Hack"
+    }
+
+    fn retriggers(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        index: usize,
+        card: Card,
+    ) -> usize {
+        usize::from(matches!(
+            card.rank,
+            Rank::Deuce | Rank::Three | Rank::Four | Rank::Five
+        ))
+    }
+}
+
+/// Retriggers every played card in the final hand of the round.
+#[derive(Debug)]
+pub struct Dusk;
+
+impl Joker for Dusk {
+    fn name(&self) -> &'static str {
+        "Dusk"
+    }
+
+    fn retriggers(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        index: usize,
+        card: Card,
+    ) -> usize {
+        usize::from(context.options.contains(ScoringOptions::FinalHand))
+    }
+}
+
+/// Retriggers every played face card.
+#[derive(Debug)]
+pub struct SockAndBuskin;
+
+impl Joker for SockAndBuskin {
+    fn name(&self) -> &'static str {
+        "Sock and Buskin"
+    }
+
+    fn retriggers(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        index: usize,
+        card: Card,
+    ) -> usize {
+        usize::from(context.is_face(card))
+    }
+}
+
+/// Retriggers every played card for the next 10 hands. Its `counter` is the
+/// number of hands it has left after the one being played, so it's used up
+/// once that drops below zero.
+#[derive(Debug)]
+pub struct Seltzer;
+
+impl Joker for Seltzer {
+    fn name(&self) -> &'static str {
+        "Seltzer"
+    }
+
+    fn initial_state(&self) -> JokerState {
+        JokerState {
+            counter: 10,
+            ..JokerState::default()
+        }
+    }
+
+    fn retriggers(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        index: usize,
+        card: Card,
+    ) -> usize {
+        usize::from(state.counter >= 0)
+    }
+
+    fn on_hand_played(&self, state: &mut JokerState, context: &ScoringContext) {
+        state.counter = (state.counter - 1).max(-1);
+    }
+}
+
+/// Retriggers the first scored card two more times.
+#[derive(Debug)]
+pub struct HangingChad;
+
+impl Joker for HangingChad {
+    fn name(&self) -> &'static str {
+        "Hanging Chad"
+    }
+
+    fn retriggers(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        index: usize,
+        card: Card,
+    ) -> usize {
+        if index == 0 {
+            2
+        } else {
+            0
+        }
+    }
+}
+
+/// Every retrigger joker.
+pub fn all() -> Vec<Arc<dyn Joker>> {
+    vec![
+        Arc::new(Hack),
+        Arc::new(Dusk),
+        Arc::new(SockAndBuskin),
+        Arc::new(Seltzer),
+        Arc::new(HangingChad),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::card;
+    use crate::solver::cards::{Hand, HandKind, Seal};
+    use crate::solver::game_state::GameState;
+    use crate::solver::joker::{builtin, JokerSlot};
+    use crate::solver::scorer::{Randomness, Scorer};
+
+    fn score(hand: &Hand, kind: HandKind, jokers: &[&str], options: ScoringOptions) -> f64 {
+        let jokers: Vec<JokerSlot> = jokers
+            .iter()
+            .map(|name| JokerSlot::new(builtin::by_name(name).unwrap()))
+            .collect();
+        let context = ScoringContext::new(kind, hand)
+            .with_jokers(&jokers)
+            .with_options(options);
+        Scorer::score_hand(&context).value()
+    }
+
+    #[test]
+    fn retrigger_test() {
+        let none = ScoringOptions::empty();
+        let pair = Hand::from_idents("2H 2S");
+        // (10 + 2 * 2 + 2 * 2) * 2
+        assert_relative_eq!(score(&pair, HandKind::Pair, &["Hack"], none), 36.0);
+        assert_relative_eq!(score(&pair, HandKind::Pair, &["Dusk"], none), 28.0);
+        assert_relative_eq!(
+            score(&pair, HandKind::Pair, &["Dusk"], ScoringOptions::FinalHand),
+            36.0
+        );
+        // (10 + 2 * 3 + 2) * 2
+        assert_relative_eq!(score(&pair, HandKind::Pair, &["Hanging Chad"], none), 36.0);
+
+        let kings = Hand::from_idents("KH KS");
+        // (10 + 10 * 2 + 10 * 2) * 2
+        assert_relative_eq!(
+            score(&kings, HandKind::Pair, &["Sock and Buskin"], none),
+            100.0
+        );
+    }
+
+    #[test]
+    fn compounding_test() {
+        // A red seal, Hack, and Hanging Chad all stack on the first card:
+        // (10 + 2 * (1 + 1 + 1 + 2) + 2 * 2) * 2
+        let hand: Hand = [card!("2H").with_seal(Seal::Red), card!("2S")]
+            .into_iter()
+            .collect();
+        assert_relative_eq!(
+            score(
+                &hand,
+                HandKind::Pair,
+                &["Hack", "Hanging Chad"],
+                ScoringOptions::empty()
+            ),
+            48.0
+        );
+
+        // Every retrigger also retriggers the jokers that act on the card
+        // (10 + 10 * 2 + 10 * 2) * (2 + 5 * 4)
+        let kings = Hand::from_idents("KH KS");
+        assert_relative_eq!(
+            score(
+                &kings,
+                HandKind::Pair,
+                &["Sock and Buskin", "Smiley Face"],
+                ScoringOptions::empty()
+            ),
+            1100.0
+        );
+    }
+
+    #[test]
+    fn seltzer_test() {
+        let mut state = GameState::new();
+        state.add_joker(Arc::new(Seltzer));
+
+        let pair = Hand::from_idents("2H 2S");
+        for _ in 0..10 {
+            let score = state.play_hand(
+                ScoringContext::new(HandKind::Pair, &pair),
+                Randomness::Expected,
+            );
+            assert_relative_eq!(score.value(), 36.0);
+        }
+        let score = state.play_hand(
+            ScoringContext::new(HandKind::Pair, &pair),
+            Randomness::Expected,
+        );
+        assert_relative_eq!(score.value(), 28.0);
+    }
+}
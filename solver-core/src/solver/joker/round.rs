@@ -0,0 +1,243 @@
+//! Jokers that pick a random card, rank, or suit at the start of every round,
+//! keeping it in `JokerState::card`.
+//!
+//! When the pick is known, these jokers score with it. When it isn't, they
+//! score each card as an independent `Chance`, which is only an approximation
+//! since every card in a round shares the same pick; use
+//! `average_over_round_choices` to average over the picks exactly.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::solver::cards::{Card, Rank, Suit};
+use crate::solver::joker::{Joker, JokerSlot, JokerState};
+use crate::solver::scorer::{Chance, Effect, ScoringContext};
+
+/// Each scored card of the round's rank and suit gives x2 mult. The card is
+/// picked from the deck.
+#[derive(Debug)]
+pub struct TheIdol;
+
+impl Joker for TheIdol {
+    fn name(&self) -> &'static str {
+        "The Idol"
+    }
+
+    fn on_card_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        match state.card {
+            Some(picked) if picked.rank == card.rank && picked.suit == card.suit => {
+                effects.push(Effect::XMult(2.0));
+            }
+            Some(_) => {}
+            None => effects.push(Effect::Chance(Chance::XMult {
+                xmult: 2.0,
+                odds: 52,
+            })),
+        }
+    }
+
+    fn round_choices(&self, state: &JokerState, deck: &[Card]) -> Vec<Card> {
+        deck.to_vec()
+    }
+}
+
+/// Earn $5 for each discarded card of the round's rank. The rank is picked
+/// from the deck; until it has been, each discarded card pays out the
+/// expected $5 in 13.
+#[derive(Debug)]
+pub struct MailInRebate;
+
+impl Joker for MailInRebate {
+    fn name(&self) -> &'static str {
+        "Mail-In Rebate"
+    }
+
+    fn discard_money(&self, state: &JokerState, discarded: &[Card]) -> f64 {
+        let matching = match state.card {
+            Some(picked) => discarded
+                .iter()
+                .filter(|card| card.rank == picked.rank)
+                .count(),
+            None => return 5.0 * f64::from(u32::try_from(discarded.len()).unwrap()) / 13.0,
+        };
+        5.0 * f64::from(u32::try_from(matching).unwrap())
+    }
+
+    fn round_choices(&self, state: &JokerState, deck: &[Card]) -> Vec<Card> {
+        deck.to_vec()
+    }
+}
+
+/// Each scored card of the round's suit gives x1.5 mult. The suit changes
+/// every round, and only the picked card's suit matters.
+#[derive(Debug)]
+pub struct AncientJoker;
+
+impl Joker for AncientJoker {
+    fn name(&self) -> &'static str {
+        "Ancient Joker"
+    }
+
+    fn on_card_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        match state.card {
+            Some(picked) if picked.suit == card.suit => effects.push(Effect::XMult(1.5)),
+            Some(_) => {}
+            None => effects.push(Effect::Chance(Chance::XMult {
+                xmult: 1.5,
+                odds: 4,
+            })),
+        }
+    }
+
+    fn round_choices(&self, state: &JokerState, deck: &[Card]) -> Vec<Card> {
+        let current = state.card.map(|card| card.suit);
+        Suit::iter()
+            .filter(|suit| Some(*suit) != current)
+            .map(|suit| Card::new(Rank::Ace, suit))
+            .collect()
+    }
+}
+
+/// Every joker that picks something each round.
+pub fn all() -> Vec<Arc<dyn Joker>> {
+    vec![
+        Arc::new(TheIdol),
+        Arc::new(MailInRebate),
+        Arc::new(AncientJoker),
+    ]
+}
+
+/// Average `score` over everything the jokers might pick this round. Jokers
+/// that already know their pick keep it, so the average is conditioned on
+/// them; the others are tried with each of their `round_choices`, weighted by
+/// how likely each one is.
+pub fn average_over_round_choices<F>(jokers: &[JokerSlot], deck: &[Card], mut score: F) -> f64
+where
+    F: FnMut(&[JokerSlot]) -> f64,
+{
+    fn average<F>(jokers: &mut [JokerSlot], from: usize, deck: &[Card], score: &mut F) -> f64
+    where
+        F: FnMut(&[JokerSlot]) -> f64,
+    {
+        for slot in from..jokers.len() {
+            if jokers[slot].state.card.is_some() {
+                continue;
+            }
+            let choices = jokers[slot].joker.round_choices(&jokers[slot].state, deck);
+            if choices.is_empty() {
+                continue;
+            }
+
+            let mut total = 0.0;
+            for (count, choice) in choices.iter().sorted().dedup_with_count() {
+                jokers[slot].state.card = Some(*choice);
+                total += f64::from(u32::try_from(count).unwrap())
+                    * average(jokers, slot + 1, deck, score);
+            }
+            jokers[slot].state.card = None;
+            return total / f64::from(u32::try_from(choices.len()).unwrap());
+        }
+        score(jokers)
+    }
+
+    let mut jokers = jokers.to_vec();
+    average(&mut jokers, 0, deck, &mut score)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::cards::{CardView, Deck, Hand, HandKind};
+    use crate::solver::game_state::GameState;
+    use crate::solver::scorer::Scorer;
+    use crate::{card, cards};
+
+    fn score(hand: &Hand, kind: HandKind, jokers: &[JokerSlot]) -> f64 {
+        Scorer::score_hand(&ScoringContext::new(kind, hand).with_jokers(jokers)).value()
+    }
+
+    #[test]
+    fn known_pick_test() {
+        let hand = Hand::from_idents("KH KS");
+        let mut idol = JokerSlot::new(Arc::new(TheIdol));
+        idol.state.card = Some(card!("KS"));
+        // 30 * 2 * 2
+        assert_relative_eq!(score(&hand, HandKind::Pair, &[idol]), 120.0);
+
+        let mut ancient = JokerSlot::new(Arc::new(AncientJoker));
+        ancient.state.card = Some(card!("AH"));
+        assert_relative_eq!(score(&hand, HandKind::Pair, &[ancient]), 90.0);
+    }
+
+    #[test]
+    fn average_test() {
+        let hand = Hand::from_idents("KH KS");
+        let jokers = [JokerSlot::new(Arc::new(AncientJoker))];
+        let deck = Deck::base_deck();
+
+        // Only one of the four suits matches each King
+        let average = average_over_round_choices(&jokers, deck.view(), |jokers| {
+            score(&hand, HandKind::Pair, jokers)
+        });
+        assert_relative_eq!(average, (90.0 + 90.0 + 60.0 + 60.0) / 4.0);
+
+        // Conditioning on a known pick
+        let mut known = jokers.clone();
+        known[0].state.card = Some(card!("AD"));
+        let conditioned = average_over_round_choices(&known, deck.view(), |jokers| {
+            score(&hand, HandKind::Pair, jokers)
+        });
+        assert_relative_eq!(conditioned, 60.0);
+    }
+
+    #[test]
+    fn round_start_test() {
+        let mut state = GameState::new();
+        state.add_joker(Arc::new(AncientJoker));
+        state.add_joker(Arc::new(MailInRebate));
+
+        state.deck = Deck::from_cards(vec![card!("7C")]);
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let previous = state.jokers[0].state.card;
+            state.start_round(&mut rng);
+            // Ancient Joker never picks the same suit twice in a row
+            assert_ne!(state.jokers[0].state.card, previous);
+        }
+
+        assert_eq!(state.jokers[1].state.card, Some(card!("7C")));
+        state.discard(cards!("7C 7D 8C").view());
+        assert_eq!(state.money, 10);
+    }
+
+    #[test]
+    fn mail_in_rebate_test() {
+        let discarded = cards!("7C 7D 8C");
+        let mut state = JokerState::default();
+        // Each card has a 1 in 13 chance of being the rank picked
+        assert_relative_eq!(
+            MailInRebate.discard_money(&state, discarded.view()),
+            15.0 / 13.0
+        );
+        state.card = Some(card!("8S"));
+        assert_relative_eq!(MailInRebate.discard_money(&state, discarded.view()), 5.0);
+    }
+}
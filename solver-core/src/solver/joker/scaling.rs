@@ -0,0 +1,231 @@
+//! Jokers that grow (or shrink) over the course of a run, keeping track of
+//! their progress in their `JokerState`.
+
+use std::sync::Arc;
+
+use crate::solver::cards::{Card, Enhancement};
+use crate::solver::joker::{copy, Joker, JokerState};
+use crate::solver::scorer::{Effect, Scorer, ScoringContext};
+
+/// +1 mult for every consecutive hand played without a scoring face card.
+#[derive(Debug)]
+pub struct RideTheBus;
+
+impl Joker for RideTheBus {
+    fn name(&self) -> &'static str {
+        "Ride the Bus"
+    }
+
+    fn on_independent(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        if state.value > 0.0 {
+            effects.push(Effect::Mult(state.value));
+        }
+    }
+
+    fn on_hand_played(&self, state: &mut JokerState, context: &ScoringContext) {
+        let face_scored = context.scored_cards().any(|card| context.is_face(card));
+        if face_scored {
+            state.value = 0.0;
+        } else {
+            state.value += 1.0;
+        }
+    }
+}
+
+/// +1 mult for every hand played, -1 mult for every discard.
+#[derive(Debug)]
+pub struct GreenJoker;
+
+impl Joker for GreenJoker {
+    fn name(&self) -> &'static str {
+        "Green Joker"
+    }
+
+    fn on_independent(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        if state.value > 0.0 {
+            effects.push(Effect::Mult(state.value));
+        }
+    }
+
+    fn on_hand_played(&self, state: &mut JokerState, context: &ScoringContext) {
+        state.value += 1.0;
+    }
+
+    fn on_discard(&self, state: &mut JokerState, discarded: &[Card]) {
+        state.value = (state.value - 1.0).max(0.0);
+    }
+}
+
+/// Gains x0.25 mult every time a playing card is added to the deck.
+#[derive(Debug)]
+pub struct Hologram;
+
+impl Joker for Hologram {
+    fn name(&self) -> &'static str {
+        "Hologram"
+    }
+
+    fn initial_state(&self) -> JokerState {
+        JokerState {
+            value: 1.0,
+            ..JokerState::default()
+        }
+    }
+
+    fn on_independent(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        if state.value > 1.0 {
+            effects.push(Effect::XMult(state.value));
+        }
+    }
+
+    fn on_card_added(&self, state: &mut JokerState, card: Card) {
+        state.value += 0.25;
+    }
+}
+
+/// Gains x0.25 mult every time a Lucky card triggers.
+///
+/// Lucky Cat grows by the expected number of Lucky triggers in each played
+/// hand, even when the hand itself is scored with sampled randomness.
+#[derive(Debug)]
+pub struct LuckyCat;
+
+impl Joker for LuckyCat {
+    fn name(&self) -> &'static str {
+        "Lucky Cat"
+    }
+
+    fn initial_state(&self) -> JokerState {
+        JokerState {
+            value: 1.0,
+            ..JokerState::default()
+        }
+    }
+
+    fn on_independent(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        if state.value > 1.0 {
+            effects.push(Effect::XMult(state.value));
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn on_hand_played(&self, state: &mut JokerState, context: &ScoringContext) {
+        let copies = copy::resolve_copies(context.jokers);
+        let lucky_triggers: usize = context
+            .scored_cards()
+            .enumerate()
+            .filter(|(_, card)| card.enhancement == Some(Enhancement::Lucky))
+            .map(|(index, card)| 1 + Scorer::played_retrigger_count(context, &copies, index, card))
+            .sum();
+        state.value += 0.25 * lucky_triggers as f64 * context.ruleset.lucky_trigger_chance();
+    }
+}
+
+/// Every scaling joker.
+pub fn all() -> Vec<Arc<dyn Joker>> {
+    vec![
+        Arc::new(RideTheBus),
+        Arc::new(GreenJoker),
+        Arc::new(Hologram),
+        Arc::new(LuckyCat),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::cards::{CardView, Hand, HandKind};
+    use crate::solver::game_state::GameState;
+    use crate::solver::ruleset::Ruleset;
+    use crate::solver::scorer::Randomness;
+    use crate::{card, cards, hand};
+
+    fn play(state: &mut GameState, kind: HandKind, hand: &Hand) -> f64 {
+        state
+            .play_hand(ScoringContext::new(kind, hand), Randomness::Expected)
+            .value()
+    }
+
+    #[test]
+    fn ride_the_bus_test() {
+        let mut state = GameState::new();
+        state.add_joker(Arc::new(RideTheBus));
+
+        // 14 * (2 + 1), then 14 * (2 + 2)
+        assert_relative_eq!(play(&mut state, HandKind::Pair, &hand!("2H 2S")), 42.0);
+        assert_relative_eq!(play(&mut state, HandKind::Pair, &hand!("2H 2S")), 56.0);
+        // A scoring face card resets it
+        assert_relative_eq!(play(&mut state, HandKind::Pair, &hand!("KH KS")), 60.0);
+        assert_relative_eq!(state.jokers[0].state.value, 0.0);
+    }
+
+    #[test]
+    fn green_joker_test() {
+        let mut state = GameState::new();
+        state.add_joker(Arc::new(GreenJoker));
+
+        play(&mut state, HandKind::Pair, &hand!("2H 2S"));
+        play(&mut state, HandKind::Pair, &hand!("2H 2S"));
+        state.discard(cards!("3C 4C").view());
+        // 14 * (2 + 1 + 1 - 1 + 1)
+        assert_relative_eq!(play(&mut state, HandKind::Pair, &hand!("2H 2S")), 56.0);
+
+        // Never drops below zero
+        for _ in 0..5 {
+            state.discard(cards!("3C").view());
+        }
+        assert_relative_eq!(state.jokers[0].state.value, 0.0);
+    }
+
+    #[test]
+    fn hologram_test() {
+        let mut state = GameState::new();
+        state.add_joker(Arc::new(Hologram));
+
+        assert_relative_eq!(play(&mut state, HandKind::Pair, &hand!("KH KS")), 60.0);
+        state.add_card(card!("2C"));
+        state.add_card(card!("3C"));
+        // 30 * (2 * 1.5)
+        assert_relative_eq!(play(&mut state, HandKind::Pair, &hand!("KH KS")), 90.0);
+    }
+
+    #[test]
+    fn lucky_cat_test() {
+        let mut state = GameState::new();
+        state.add_joker(Arc::new(LuckyCat));
+
+        let hand: Hand = [
+            card!("KH").with_enhancement(Enhancement::Lucky),
+            card!("KS"),
+        ]
+        .into_iter()
+        .collect();
+        play(&mut state, HandKind::Pair, &hand);
+        assert_relative_eq!(
+            state.jokers[0].state.value,
+            1.0 + 0.25 * Ruleset::VANILLA.lucky_trigger_chance()
+        );
+    }
+}
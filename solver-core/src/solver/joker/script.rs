@@ -0,0 +1,290 @@
+//! Jokers whose behaviour is written as a small [Rhai](https://rhai.rs) script,
+//! for effects too involved to express as a data definition.
+//!
+//! A script defines any of the hook functions it needs. Each one receives the
+//! scoring context (and the card, for card hooks) as object maps, and returns
+//! either nothing, a single effect, or an array of effects:
+//!
+//! ```rhai
+//! fn on_card_scored(context, card) {
+//!     if card.rank == "King" && context.held.len() == 0 {
+//!         #{ xmult: 2.0 }
+//!     }
+//! }
+//!
+//! fn on_independent(context) {
+//!     [#{ chips: 10 * context.jokers }, #{ mult: 4.0 }]
+//! }
+//! ```
+//!
+//! The context map has `kind`, `played`, `held`, `jokers` (the number of
+//! jokers in play), and the joker's own `value` and `counter` state; cards are
+//! maps with `rank`, `suit`, `enhancement`, `edition`, and `seal`, using the
+//! same names as the Rust enums, plus `face` for whether the card counts as a
+//! face card.
+
+use std::fmt;
+
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+
+use crate::solver::cards::{Card, CardView};
+use crate::solver::error::{Error, Result};
+use crate::solver::joker::{Joker, JokerState};
+use crate::solver::scorer::{Effect, ScoringContext};
+
+const HOOKS: [&str; 4] = [
+    "on_hand_scored",
+    "on_card_scored",
+    "on_held_card",
+    "on_independent",
+];
+
+pub struct ScriptJoker {
+    name: String,
+    engine: Engine,
+    ast: AST,
+    /// Which of `HOOKS` the script actually defines
+    defined: [bool; 4],
+}
+
+impl ScriptJoker {
+    pub fn new(name: impl Into<String>, source: &str) -> Result<Self> {
+        let name = name.into();
+        let engine = Engine::new();
+        let ast = engine
+            .compile(source)
+            .map_err(|err| Error::Script(format!("{name}: {err}")))?;
+        let defined = HOOKS.map(|hook| ast.iter_functions().any(|function| function.name == hook));
+        Ok(Self {
+            name,
+            engine,
+            ast,
+            defined,
+        })
+    }
+
+    fn call(&self, hook: usize, args: impl rhai::FuncArgs, effects: &mut Vec<Effect>) {
+        if !self.defined[hook] {
+            return;
+        }
+
+        let result = self
+            .engine
+            .call_fn::<Dynamic>(&mut Scope::new(), &self.ast, HOOKS[hook], args)
+            .map_err(|err| err.to_string())
+            .and_then(|value| collect_effects(value, effects));
+        if let Err(err) = result {
+            tracing::warn!(
+                joker = self.name,
+                hook = HOOKS[hook],
+                "script failed: {err}"
+            );
+        }
+    }
+}
+
+impl fmt::Debug for ScriptJoker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptJoker")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+fn card_map(context: &ScoringContext, card: Card) -> Dynamic {
+    fn name(value: Option<impl fmt::Debug>) -> Dynamic {
+        value.map_or(Dynamic::UNIT, |value| format!("{value:?}").into())
+    }
+
+    let mut map = Map::new();
+    map.insert("rank".into(), format!("{:?}", card.rank).into());
+    map.insert("suit".into(), format!("{:?}", card.suit).into());
+    map.insert("enhancement".into(), name(card.enhancement));
+    map.insert("edition".into(), name(card.edition));
+    map.insert("seal".into(), name(card.seal));
+    map.insert("face".into(), context.is_face(card).into());
+    map.into()
+}
+
+fn context_map(state: &JokerState, context: &ScoringContext) -> Dynamic {
+    let cards = |cards: &[Card]| {
+        cards
+            .iter()
+            .map(|card| card_map(context, *card))
+            .collect::<Array>()
+    };
+
+    let mut map = Map::new();
+    map.insert("kind".into(), format!("{:?}", context.kind).into());
+    map.insert("played".into(), cards(context.played.view()).into());
+    map.insert("held".into(), cards(context.held).into());
+    map.insert(
+        "jokers".into(),
+        i64::try_from(context.jokers.len()).unwrap().into(),
+    );
+    map.insert("value".into(), state.value.into());
+    map.insert("counter".into(), state.counter.into());
+    map.into()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn effect_from_map(map: &Map) -> std::result::Result<Effect, String> {
+    let float = |value: &Dynamic| {
+        value
+            .as_float()
+            .or_else(|_| value.as_int().map(|int| int as f64))
+            .map_err(|_| "expected a number".to_owned())
+    };
+
+    match map.iter().next() {
+        Some((key, value)) if map.len() == 1 => match key.as_str() {
+            "chips" => value
+                .as_int()
+                .ok()
+                .and_then(|chips| u64::try_from(chips).ok())
+                .map(Effect::Chips)
+                .ok_or_else(|| "chips must be a non-negative integer".to_owned()),
+            "mult" => float(value).map(Effect::Mult),
+            "xmult" => float(value).map(Effect::XMult),
+            other => Err(format!("unknown effect `{other}`")),
+        },
+        _ => Err("each effect needs exactly one of chips, mult, or xmult".to_owned()),
+    }
+}
+
+fn collect_effects(value: Dynamic, effects: &mut Vec<Effect>) -> std::result::Result<(), String> {
+    if value.is_unit() {
+        Ok(())
+    } else if value.is_map() {
+        effects.push(effect_from_map(&value.cast::<Map>())?);
+        Ok(())
+    } else if value.is_array() {
+        for value in value.cast::<Array>() {
+            collect_effects(value, effects)?;
+        }
+        Ok(())
+    } else {
+        Err(format!("expected effects, got {}", value.type_name()))
+    }
+}
+
+impl Joker for ScriptJoker {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_hand_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.call(0, (context_map(state, context),), effects);
+    }
+
+    fn on_card_scored(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.call(
+            1,
+            (context_map(state, context), card_map(context, card)),
+            effects,
+        );
+    }
+
+    fn on_held_card(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        card: Card,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.call(
+            2,
+            (context_map(state, context), card_map(context, card)),
+            effects,
+        );
+    }
+
+    fn on_independent(
+        &self,
+        state: &JokerState,
+        context: &ScoringContext,
+        effects: &mut Vec<Effect>,
+    ) {
+        self.call(3, (context_map(state, context),), effects);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::cards::HandKind;
+    use crate::solver::joker::JokerSlot;
+    use crate::solver::scorer::Scorer;
+    use crate::{card, hand};
+
+    fn score(joker: ScriptJoker, kind: HandKind, played: &str, held: &[Card]) -> f64 {
+        let slots = [JokerSlot::new(Arc::new(joker))];
+        let played = crate::solver::cards::Hand::from_idents(played);
+        let context = ScoringContext::new(kind, &played)
+            .with_held(held)
+            .with_jokers(&slots);
+        Scorer::score_hand(&context).value()
+    }
+
+    #[test]
+    fn hooks_test() {
+        let joker = ScriptJoker::new(
+            "Test",
+            r#"
+            fn on_card_scored(context, card) {
+                if card.rank == "King" && context.held.len() == 0 {
+                    #{ xmult: 2.0 }
+                }
+            }
+
+            fn on_independent(context) {
+                [#{ chips: 10 * context.jokers }, #{ mult: 4 }]
+            }
+            "#,
+        )
+        .unwrap();
+        // (30 + 10) * (2 * 2 * 2 + 4)
+        assert_relative_eq!(score(joker, HandKind::Pair, "KH KS", &[]), 480.0);
+    }
+
+    #[test]
+    fn held_card_test() {
+        let joker = ScriptJoker::new(
+            "Test",
+            r#"
+            fn on_held_card(context, card) {
+                if card.enhancement == "Steel" { #{ mult: 1 } }
+            }
+            "#,
+        )
+        .unwrap();
+        let held = [card!("2C").with_enhancement(crate::solver::cards::Enhancement::Steel)];
+        // 30 * (2 * 1.5 + 1)
+        assert_relative_eq!(score(joker, HandKind::Pair, "KH KS", &held), 120.0);
+    }
+
+    #[test]
+    fn errors_test() {
+        assert!(ScriptJoker::new("Broken", "fn on_independent(context {").is_err());
+
+        // Runtime failures are logged and otherwise ignored
+        let joker =
+            ScriptJoker::new("Broken", r"fn on_independent(context) { #{ oops: 1 } }").unwrap();
+        assert_relative_eq!(score(joker, HandKind::Pair, "KH KS", &[]), 60.0);
+    }
+}
@@ -0,0 +1,455 @@
+//! Monte Carlo Tree Search over the rest of a round, for when there's too much
+//! left to play for `RoundPlanner` to look all the way ahead.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::RngCore;
+use serde::Serialize;
+
+use crate::solver::advisor::Advisor;
+use crate::solver::cards::Card;
+use crate::solver::planner::discards;
+use crate::solver::round::{Action, Round, RoundOutcome};
+use crate::solver::scorer::Randomness;
+use crate::solver::strategy::Strategy;
+
+/// The action the search settled on, and what it found out about it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MctsResult {
+    pub action: Action,
+    /// How often the round was won after taking the action
+    pub win_chance: f64,
+    /// How many playouts took the action
+    pub visits: u32,
+}
+
+/// UCT search over a round's plays and discards. Each playout shuffles the
+/// draw pile, since its order isn't known, then walks down the tree: picking
+/// actions by their upper confidence bound, and following whichever hand was
+/// drawn afterwards down a chance node. Past the edge of the tree, the round
+/// is played out greedily.
+///
+/// Every action is played with sampled randomness, so Lucky cards and the
+/// like come out differently between playouts too.
+#[derive(Clone, Debug)]
+pub struct Mcts {
+    /// Finds and scores the plays considered, and plays out the rest of the
+    /// round from the edge of the tree
+    pub advisor: Advisor,
+    /// How many playouts to run
+    pub playouts: usize,
+    /// How much to favour trying less visited actions over better ones
+    pub exploration: f64,
+    /// How many plays, and how many discards, to consider at each decision
+    pub candidates: usize,
+}
+
+impl Mcts {
+    pub const DEFAULT_PLAYOUTS: usize = 1000;
+    pub const DEFAULT_CANDIDATES: usize = 4;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_advisor(self, advisor: Advisor) -> Self {
+        Self { advisor, ..self }
+    }
+
+    #[must_use]
+    pub fn with_playouts(self, playouts: usize) -> Self {
+        Self { playouts, ..self }
+    }
+
+    #[must_use]
+    pub fn with_exploration(self, exploration: f64) -> Self {
+        Self {
+            exploration,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_candidates(self, candidates: usize) -> Self {
+        Self { candidates, ..self }
+    }
+
+    /// Search from `round`, returning the action taken by the most playouts.
+    /// With nothing in hand, this is an empty play that can't win.
+    pub fn search(&self, round: &Round, rng: &mut dyn RngCore) -> MctsResult {
+        best(&self.explore(round, rng))
+    }
+
+    /// Like `search`, but also returning the tree that was explored.
+    pub fn search_with_tree(
+        &self,
+        round: &Round,
+        rng: &mut dyn RngCore,
+    ) -> (MctsResult, SearchTree) {
+        let root = self.explore(round, rng);
+        (best(&root), SearchTree { root: root.node() })
+    }
+
+    fn explore(&self, round: &Round, rng: &mut dyn RngCore) -> Decision {
+        let mut root = Decision::default();
+        for _ in 0..self.playouts {
+            let mut round = round.clone();
+            round.state.draw_pile.shuffle(rng);
+            self.playout(&mut root, &mut round, rng);
+        }
+        root
+    }
+
+    /// Walk down from `node`, returning 1 if the round was won and 0 if not.
+    fn playout(&self, node: &mut Decision, round: &mut Round, rng: &mut dyn RngCore) -> f64 {
+        if node.actions.is_empty() {
+            node.hand.clone_from(&round.state.hand);
+            node.actions = self.actions(round);
+            node.children = node.actions.iter().map(|_| Chance::default()).collect();
+        }
+        let Some(choice) = self.select(node) else {
+            return 0.0;
+        };
+
+        let result = if round
+            .apply(&node.actions[choice], Randomness::Sampled(rng))
+            .is_err()
+        {
+            0.0
+        } else if let Some(outcome) = round.outcome() {
+            won(outcome)
+        } else {
+            let mut drawn = round.state.hand.clone();
+            drawn.sort_unstable();
+            let chance = &mut node.children[choice];
+            if let Some(child) = chance.outcomes.get_mut(&drawn) {
+                self.playout(child, round, rng)
+            } else {
+                chance.outcomes.insert(drawn, Decision::default());
+                self.rollout(round, rng)
+            }
+        };
+
+        node.visits += 1;
+        let chance = &mut node.children[choice];
+        chance.visits += 1;
+        chance.wins += result;
+        result
+    }
+
+    /// The child with the highest upper confidence bound, trying each once
+    /// first.
+    fn select(&self, node: &Decision) -> Option<usize> {
+        if let Some(unvisited) = node.children.iter().position(|child| child.visits == 0) {
+            return Some(unvisited);
+        }
+        let total = f64::from(node.visits.max(1)).ln();
+        node.children
+            .iter()
+            .map(|child| {
+                let visits = f64::from(child.visits);
+                child.wins / visits + self.exploration * (total / visits).sqrt()
+            })
+            .position_max_by(f64::total_cmp)
+    }
+
+    /// The best few plays, and discards that keep them.
+    fn actions(&self, round: &Round) -> Vec<Action> {
+        let hand = &round.state.hand;
+        let plays = self
+            .advisor
+            .plays(hand, &round.state)
+            .into_iter()
+            .dedup_by(|(_, a), (_, b)| a == b)
+            .take(self.candidates)
+            .map(|(indices, _)| indices)
+            .collect_vec();
+
+        let mut actions = plays.iter().cloned().map(Action::Play).collect_vec();
+        if round.state.discards > 0 {
+            actions.extend(
+                discards(hand, plays.into_iter())
+                    .into_iter()
+                    .take(self.candidates)
+                    .map(Action::Discard),
+            );
+        }
+        actions
+    }
+
+    /// Finish the round by playing the best hand every time.
+    fn rollout(&self, round: &mut Round, rng: &mut dyn RngCore) -> f64 {
+        while !round.is_over() {
+            let (play, _) = self.advisor.best_play(&round.state.hand, &round.state);
+            if round.play(&play, Randomness::Sampled(rng)).is_err() {
+                return 0.0;
+            }
+        }
+        round.outcome().map_or(0.0, won)
+    }
+}
+
+impl Default for Mcts {
+    fn default() -> Self {
+        Self {
+            advisor: Advisor::default(),
+            playouts: Self::DEFAULT_PLAYOUTS,
+            exploration: std::f64::consts::SQRT_2,
+            candidates: Self::DEFAULT_CANDIDATES,
+        }
+    }
+}
+
+impl Strategy for Mcts {
+    fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+        self.search(round, rng).action
+    }
+}
+
+/// The action taken by the most playouts from `root`.
+fn best(root: &Decision) -> MctsResult {
+    root.actions
+        .iter()
+        .zip(&root.children)
+        // On a tie, the first action wins, so that plays are preferred to
+        // discards that do just as well
+        .rev()
+        .max_by_key(|(_, child)| child.visits)
+        .map_or(
+            MctsResult {
+                action: Action::Play(Vec::new()),
+                win_chance: 0.0,
+                visits: 0,
+            },
+            |(action, child)| MctsResult {
+                action: action.clone(),
+                win_chance: child.win_chance(),
+                visits: child.visits,
+            },
+        )
+}
+
+fn won(outcome: RoundOutcome) -> f64 {
+    match outcome {
+        RoundOutcome::Won => 1.0,
+        RoundOutcome::Lost => 0.0,
+    }
+}
+
+/// A point where an action has to be chosen.
+#[derive(Debug, Default)]
+struct Decision {
+    /// The hand the actions pick cards from
+    hand: Vec<Card>,
+    actions: Vec<Action>,
+    /// What followed each action, in the same order
+    children: Vec<Chance>,
+    visits: u32,
+}
+
+/// An action taken, with a decision for each hand it's been seen to leave.
+#[derive(Debug, Default)]
+struct Chance {
+    /// Keyed by the hand, sorted
+    outcomes: HashMap<Vec<Card>, Decision>,
+    visits: u32,
+    wins: f64,
+}
+
+impl Decision {
+    fn node(&self) -> DecisionNode {
+        let actions = self
+            .actions
+            .iter()
+            .zip(&self.children)
+            .map(|(action, chance)| {
+                let (verb, indices) = match action {
+                    Action::Play(indices) => ("Play", indices),
+                    Action::Discard(indices) => ("Discard", indices),
+                };
+                let cards = indices.iter().map(|&index| self.hand[index]).join(" ");
+                ActionNode {
+                    action: format!("{verb} {cards}"),
+                    visits: chance.visits,
+                    win_chance: chance.win_chance(),
+                    outcomes: chance
+                        .outcomes
+                        .values()
+                        .map(Self::node)
+                        .sorted_by(|a, b| b.visits.cmp(&a.visits).then_with(|| a.hand.cmp(&b.hand)))
+                        .collect(),
+                }
+            })
+            .collect();
+        DecisionNode {
+            hand: self.hand.iter().join(" "),
+            visits: self.visits,
+            actions,
+        }
+    }
+}
+
+impl Chance {
+    #[allow(clippy::cast_precision_loss)]
+    fn win_chance(&self) -> f64 {
+        self.wins / f64::from(self.visits.max(1))
+    }
+}
+
+/// The tree explored by `Mcts::search_with_tree`, for seeing why the search
+/// settled on what it did. Can be written out as JSON, or as DOT for Graphviz.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SearchTree {
+    pub root: DecisionNode,
+}
+
+/// A decision in a `SearchTree`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DecisionNode {
+    /// The cards in hand, like "AS KD 7C"
+    pub hand: String,
+    /// How many playouts reached this decision
+    pub visits: u32,
+    pub actions: Vec<ActionNode>,
+}
+
+/// An action considered at a decision in a `SearchTree`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ActionNode {
+    /// What was done, like "Discard 2C 5H"
+    pub action: String,
+    /// How many playouts took the action
+    pub visits: u32,
+    /// How often the round was won after taking the action
+    pub win_chance: f64,
+    /// The decisions that came after, one for each hand that was left, most
+    /// visited first
+    pub outcomes: Vec<DecisionNode>,
+}
+
+impl SearchTree {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("search trees are always valid JSON")
+    }
+
+    /// The tree as a Graphviz digraph: boxes for decisions, and ellipses for
+    /// the actions considered at them.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph search {\n");
+        let mut next_id = 0;
+        write_decision(&mut dot, &self.root, &mut next_id);
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Write out `node` and everything under it, returning its id.
+fn write_decision(dot: &mut String, node: &DecisionNode, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+    writeln!(
+        dot,
+        "    n{id} [shape=box, label=\"{}\\n{} visits\"];",
+        node.hand, node.visits
+    )
+    .unwrap();
+    for action in &node.actions {
+        let action_id = *next_id;
+        *next_id += 1;
+        writeln!(
+            dot,
+            "    n{action_id} [label=\"{}\\n{} visits, {:.1}% won\"];",
+            action.action,
+            action.visits,
+            action.win_chance * 100.0
+        )
+        .unwrap();
+        writeln!(dot, "    n{id} -> n{action_id};").unwrap();
+        for outcome in &action.outcomes {
+            let outcome_id = write_decision(dot, outcome, next_id);
+            writeln!(dot, "    n{action_id} -> n{outcome_id};").unwrap();
+        }
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardCollection, CardView, Deck};
+    use crate::solver::game_state::GameState;
+    use crate::solver::score::Score;
+
+    fn resumed(hand: &str, hands: u32, discards: u32, target: f64) -> Round {
+        let state = GameState::from_parts(
+            Deck::base_deck(),
+            CardCollection::from_idents(hand).view().to_vec(),
+            hands,
+            discards,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        );
+        let mut round = Round::resume(state, Score::ZERO);
+        round.target = Score::from(target);
+        round
+    }
+
+    #[test]
+    fn search_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mcts = Mcts::new().with_playouts(100);
+
+        // A straight flush wins outright
+        let round = resumed("AS KS QS JS TS 2D 3C 4H", 4, 3, 300.0);
+        let result = mcts.search(&round, &mut rng);
+        assert_eq!(result.action, Action::Play(vec![0, 1, 2, 3, 4]));
+        assert!((result.win_chance - 1.0).abs() < f64::EPSILON);
+
+        // Playing the aces with the last hand can't win, but discarding
+        // might
+        let round = resumed("AS AD 2C 5H 9S JD 4C 7H", 1, 3, 100.0);
+        let result = mcts.search(&round, &mut rng);
+        assert!(matches!(result.action, Action::Discard(_)));
+        assert!(result.win_chance > 0.0);
+    }
+
+    #[test]
+    fn tree_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mcts = Mcts::new().with_playouts(50);
+        let round = resumed("AS AD 2C 5H 9S JD 4C 7H", 2, 1, 100.0);
+        let (result, tree) = mcts.search_with_tree(&round, &mut rng);
+
+        let root = &tree.root;
+        assert_eq!(root.hand, "AS AD 2C 5H 9S JD 4C 7H");
+        assert_eq!(root.visits, 50);
+        assert_eq!(
+            root.actions.iter().map(|action| action.visits).sum::<u32>(),
+            50
+        );
+        let best = root
+            .actions
+            .iter()
+            .find(|action| action.visits == result.visits)
+            .unwrap();
+        assert!((best.win_chance - result.win_chance).abs() < f64::EPSILON);
+        assert_eq!(root.actions[0].action, "Play AS AD");
+
+        let json: serde_json::Value = serde_json::from_str(&tree.to_json()).unwrap();
+        assert_eq!(json["root"]["visits"], 50);
+        let dot = tree.to_dot();
+        assert!(dot.starts_with("digraph search {"));
+        assert!(dot.contains("n0 [shape=box, label=\"AS AD 2C 5H 9S JD 4C 7H\\n50 visits\"];"));
+        assert!(dot.contains("n0 -> n1;"));
+    }
+}
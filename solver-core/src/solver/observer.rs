@@ -0,0 +1,119 @@
+//! Hooks for watching a simulation as it runs, to collect statistics or add
+//! effects of your own without touching the simulator.
+
+use std::fmt;
+
+use crate::solver::cards::Card;
+use crate::solver::economy::CashOut;
+use crate::solver::game_state::GameState;
+use crate::solver::round::RoundOutcome;
+use crate::solver::scorer::ScoreBreakdown;
+use crate::solver::shop::Shop;
+
+/// Something told about everything that happens in a `Round` or a
+/// `RunSimulator`. Every hook does nothing by default.
+///
+/// Observers are shared between clones of a round, so they take `&self`;
+/// use atomics or a `Mutex` to keep counts. The state is passed mutably, so
+/// an observer can also change the run, e.g. to model an effect the crate
+/// doesn't have.
+pub trait Observer: fmt::Debug + Send + Sync {
+    /// A card was drawn into hand.
+    fn on_card_drawn(&self, state: &mut GameState, card: Card) {}
+
+    /// A hand was played and scored. This comes after `on_card_scored` has
+    /// been called for each of its cards.
+    fn on_hand_played(&self, state: &mut GameState, breakdown: &ScoreBreakdown) {}
+
+    /// A played card scored `chips`, counting its retriggers.
+    fn on_card_scored(&self, state: &mut GameState, card: Card, chips: u64) {}
+
+    /// A round ended. Beaten blinds come with what they paid out.
+    fn on_round_end(
+        &self,
+        state: &mut GameState,
+        outcome: RoundOutcome,
+        cash_out: Option<&CashOut>,
+    ) {
+    }
+
+    /// The shop between blinds was stocked, and is about to be shopped in.
+    fn on_shop_entered(&self, state: &mut GameState, shop: &mut Shop) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+
+    use super::*;
+    use crate::solver::round::{Action, Round};
+    use crate::solver::run::RunSimulator;
+    use crate::solver::strategy::Strategy;
+
+    #[derive(Debug, Default)]
+    struct Counter {
+        drawn: AtomicU32,
+        hands: AtomicU32,
+        scored: AtomicU32,
+        rounds: AtomicU32,
+        shops: AtomicU32,
+    }
+
+    impl Observer for Counter {
+        fn on_card_drawn(&self, state: &mut GameState, card: Card) {
+            self.drawn.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_hand_played(&self, state: &mut GameState, breakdown: &ScoreBreakdown) {
+            self.hands.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_card_scored(&self, state: &mut GameState, card: Card, chips: u64) {
+            self.scored.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_round_end(
+            &self,
+            state: &mut GameState,
+            outcome: RoundOutcome,
+            cash_out: Option<&CashOut>,
+        ) {
+            self.rounds.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_shop_entered(&self, state: &mut GameState, shop: &mut Shop) {
+            self.shops.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Plays the first five cards in hand, every time.
+    struct FirstFive;
+
+    impl Strategy for FirstFive {
+        fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+            Action::Play((0..round.state.hand.len().min(5)).collect())
+        }
+    }
+
+    #[test]
+    fn run_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let counter = Arc::new(Counter::default());
+        let summary = RunSimulator::new(FirstFive)
+            .with_observer(Arc::clone(&counter) as Arc<dyn Observer>)
+            .run(GameState::fresh_run(&mut rng), &mut rng)
+            .unwrap();
+
+        let rounds = summary.blinds_beaten + 1;
+        assert_eq!(counter.rounds.load(Ordering::Relaxed), rounds);
+        assert_eq!(counter.shops.load(Ordering::Relaxed), summary.blinds_beaten);
+        assert_eq!(counter.hands.load(Ordering::Relaxed), summary.hands_played);
+        // Every round draws a full hand, then a card for each one played
+        assert!(counter.drawn.load(Ordering::Relaxed) >= 8 * rounds);
+        assert!(counter.scored.load(Ordering::Relaxed) >= summary.hands_played);
+    }
+}
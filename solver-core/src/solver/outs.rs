@@ -0,0 +1,240 @@
+//! Outs: the cards still to come that would finish off a hand.
+
+use itertools::Itertools;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use strum::IntoEnumIterator;
+
+use crate::solver::advisor::{binomial, selections};
+use crate::solver::cards::{Card, CardCollection, CardView, Hand, HandKind};
+use crate::solver::deck_tracker::DeckTracker;
+use crate::solver::hand_evaluator::{HandEvaluator, Options};
+use crate::solver::scorer::ScoringContext;
+
+/// The unseen cards that would make a kind of hand, and the chance of
+/// drawing one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outs {
+    pub kind: HandKind,
+    /// Every unseen card that makes the kind by itself, counting each copy
+    pub cards: Vec<Card>,
+    /// The chance that at least one of them is drawn
+    pub probability: f64,
+}
+
+/// The outs for each kind of hand that `hand` can't make yet, best kind
+/// first, when drawing `drawn` of the cards unseen by `tracker`. Kinds with
+/// no outs are left out.
+///
+/// Only cards that make a kind by themselves count, so draws that only get
+/// there with two cards together, like a backdoor flush, don't show up in
+/// the chances.
+pub fn outs(hand: &[Card], tracker: &DeckTracker, drawn: usize, options: Options) -> Vec<Outs> {
+    let made = kinds_made(hand, options);
+    let unseen = tracker.view();
+    let makes = unseen
+        .iter()
+        .map(|card| {
+            let mut cards = hand.to_vec();
+            cards.push(*card);
+            kinds_made(&cards, options)
+        })
+        .collect_vec();
+
+    HandKind::iter()
+        .rev()
+        .filter(|kind| !made.contains(kind))
+        .filter_map(|kind| {
+            let cards = unseen
+                .iter()
+                .zip(&makes)
+                .filter(|(_, makes)| makes.contains(&kind))
+                .map(|(card, _)| *card)
+                .collect_vec();
+            (!cards.is_empty()).then(|| Outs {
+                kind,
+                probability: at_least_one(cards.len(), unseen.len(), drawn),
+                cards,
+            })
+        })
+        .collect()
+}
+
+/// The chance of holding at least each kind of hand, from High Card up, once
+/// `drawn` of the cards unseen by `tracker` join `kept`. A hand's kind is the
+/// best one it makes, so a full house counts towards a pair but a flush
+/// doesn't count towards a full house.
+///
+/// Every draw is tried when there are at most `exact_limit` of them, and
+/// otherwise `samples` draws are taken from a fixed seed.
+#[allow(clippy::cast_precision_loss)]
+pub fn kind_odds(
+    kept: &[Card],
+    tracker: &DeckTracker,
+    drawn: usize,
+    options: Options,
+    exact_limit: usize,
+    samples: usize,
+) -> Vec<(HandKind, f64)> {
+    let drawn = drawn.min(tracker.count());
+    let mut counts = [0_usize; 12];
+    let mut count = |draw: &[Card]| {
+        let mut cards = kept.to_vec();
+        cards.extend_from_slice(draw);
+        if let Some((kind, _)) =
+            HandEvaluator::find_best_poker_hand(CardCollection::from(cards.as_slice()), options)
+        {
+            counts[kind as usize] += 1;
+        }
+    };
+
+    let total = if binomial(tracker.count(), drawn).is_some_and(|ways| ways <= exact_limit) {
+        let mut total = 0;
+        for draw in tracker.view().iter().copied().combinations(drawn) {
+            count(&draw);
+            total += 1;
+        }
+        total
+    } else {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..samples {
+            count(&tracker.sample(drawn, &mut rng));
+        }
+        samples
+    };
+
+    // Running totals from the best kind down give "at least" each kind
+    let mut at_least = 0;
+    let mut odds = HandKind::iter()
+        .rev()
+        .map(|kind| {
+            at_least += counts[kind as usize];
+            (kind, at_least as f64 / total.max(1) as f64)
+        })
+        .collect_vec();
+    odds.reverse();
+    odds
+}
+
+/// Every kind of hand that some selection of `cards` makes, the way
+/// `ScoringContext::contains` sees it.
+fn kinds_made(cards: &[Card], options: Options) -> Vec<HandKind> {
+    let mut made = Vec::new();
+    for indices in selections(cards.len()) {
+        let played = indices.iter().map(|&index| cards[index]).collect_vec();
+        let Some((kind, hand)) = Hand::from_slice(&played).ok().and_then(|hand| {
+            let (kind, _, _) = HandEvaluator::evaluate_played_hand(hand.clone(), options)?;
+            Some((kind, hand))
+        }) else {
+            continue;
+        };
+        let context = ScoringContext::new(kind, &hand);
+        made.extend(HandKind::iter().filter(|kind| context.contains(*kind)));
+    }
+    made.sort_unstable();
+    made.dedup();
+    made
+}
+
+/// The chance that drawing `drawn` of `unseen` cards gets at least one of
+/// `outs` of them.
+#[allow(clippy::cast_precision_loss)]
+fn at_least_one(outs: usize, unseen: usize, drawn: usize) -> f64 {
+    let misses: f64 = (0..drawn.min(unseen))
+        .map(|i| (unseen - outs).saturating_sub(i) as f64 / (unseen - i) as f64)
+        .product();
+    1.0 - misses
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::cards::{CardCollection, Deck};
+
+    fn tracker(hand: &[Card]) -> DeckTracker {
+        let mut tracker = DeckTracker::new(Deck::base_deck().view().to_vec());
+        for card in hand {
+            tracker.see(*card);
+        }
+        tracker
+    }
+
+    #[test]
+    fn flush_draw_test() {
+        let hand = CardCollection::from_idents("AS KS QS 2S 9D 3C");
+        let outs = outs(hand.view(), &tracker(hand.view()), 2, Options::empty());
+        let flush = outs
+            .iter()
+            .find(|outs| outs.kind == HandKind::Flush)
+            .unwrap();
+        assert_eq!(flush.cards.len(), 9);
+        assert!(flush
+            .cards
+            .iter()
+            .all(|card| card.suit == hand.view()[0].suit));
+        // 1 - (37 / 46) * (36 / 45)
+        assert_relative_eq!(flush.probability, 1.0 - 1332.0 / 2070.0);
+
+        // Any of the ranks in hand pairs up, but the high card is made already
+        let pair = outs
+            .iter()
+            .find(|outs| outs.kind == HandKind::Pair)
+            .unwrap();
+        assert_eq!(pair.cards.len(), 18);
+        assert!(outs.iter().all(|outs| outs.kind != HandKind::HighCard));
+        assert!(outs.iter().all(|outs| outs.kind != HandKind::Straight));
+    }
+
+    #[test]
+    fn straight_draw_test() {
+        // Open-ended: any nine or four
+        let hand = CardCollection::from_idents("8S 7D 6C 5H 2D");
+        let outs = outs(hand.view(), &tracker(hand.view()), 1, Options::empty());
+        let straight = outs
+            .iter()
+            .find(|outs| outs.kind == HandKind::Straight)
+            .unwrap();
+        assert_eq!(straight.cards.len(), 8);
+        assert_relative_eq!(straight.probability, 8.0 / 47.0);
+    }
+
+    #[test]
+    fn kind_odds_test() {
+        // Four aces and a king make four of a kind, whatever else is drawn
+        let hand = CardCollection::from_idents("AS AD AH AC KS");
+        let mut last = tracker(hand.view());
+        let unseen = last.view().to_vec();
+        for card in unseen.iter().skip(1) {
+            last.see(*card);
+        }
+        let odds = kind_odds(hand.view(), &last, 1, Options::empty(), 256, 0);
+        assert_eq!(odds.len(), 12);
+        assert_eq!(odds[0].0, HandKind::HighCard);
+        assert_relative_eq!(odds[0].1, 1.0);
+        assert_relative_eq!(odds[HandKind::FourOfAKind as usize].1, 1.0);
+        assert_relative_eq!(odds[HandKind::StraightFlush as usize].1, 0.0);
+
+        // Drawing two from a full deck, sampled and exact agree closely
+        let hand = CardCollection::from_idents("AS AD 7C 2H 9S JD");
+        let tracker = tracker(hand.view());
+        let exact = kind_odds(hand.view(), &tracker, 2, Options::empty(), usize::MAX, 0);
+        let sampled = kind_odds(hand.view(), &tracker, 2, Options::empty(), 0, 5_000);
+        for ((kind, exact), (_, sampled)) in exact.iter().zip(&sampled) {
+            assert!(
+                (exact - sampled).abs() < 0.02,
+                "{kind:?}: {exact} vs {sampled}"
+            );
+        }
+        assert_relative_eq!(exact[HandKind::Pair as usize].1, 1.0);
+    }
+
+    #[test]
+    fn at_least_one_test() {
+        assert_relative_eq!(at_least_one(0, 10, 3), 0.0);
+        assert_relative_eq!(at_least_one(10, 10, 1), 1.0);
+        assert_relative_eq!(at_least_one(2, 4, 2), 1.0 - 1.0 / 6.0);
+        assert_relative_eq!(at_least_one(1, 3, 5), 1.0);
+    }
+}
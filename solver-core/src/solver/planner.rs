@@ -0,0 +1,420 @@
+//! Planning the rest of a round at once: which play or discard gives the best
+//! chance of beating the blind with the hands and discards that are left,
+//! rather than just the best score right now.
+
+use itertools::Itertools;
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+
+use crate::solver::advisor::Advisor;
+use crate::solver::cards::Card;
+use crate::solver::consumable_planner::ConsumablePlanner;
+use crate::solver::deck_tracker::DeckTracker;
+use crate::solver::game_state::GameState;
+use crate::solver::round::{Action, Round};
+use crate::solver::strategy::Strategy;
+
+/// What to do next, and how likely it is to lead to beating the blind.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plan {
+    /// A consumable to use first, by its index in `GameState::consumables`,
+    /// and the cards in hand to use it on
+    pub consumable: Option<(usize, Vec<usize>)>,
+    /// The play or discard, on the hand after any consumable's been used
+    pub action: Action,
+    /// The estimated chance of reaching the target by following the plan
+    pub win_chance: f64,
+}
+
+/// Expectimax over the rest of a round. At each decision, the few most
+/// promising plays and discards are weighed up, each by averaging how things
+/// go over a sample of draws of the replacement cards. Plays with Lucky cards
+/// or jokers that score by chance, like Bloodstone and Misprint, are weighed
+/// over every way they can score, each by its exact probability. Only chances
+/// met while scoring are branched on: Glass cards breaking and jokers like
+/// Cavendish going extinct aren't modelled, and a consumable like The Wheel
+/// of Fortune is tried out on a single roll of its chance.
+///
+/// The search stops after `depth` decisions. From there, a play is assumed to
+/// win if playing as well with every hand left would reach the target.
+///
+/// Before the search, the consumable that would most improve the best play
+/// is tried out, and used if it raises the chance of winning.
+#[derive(Clone, Debug)]
+pub struct RoundPlanner {
+    /// Finds and scores the plays
+    pub advisor: Advisor,
+    /// How many plays, and how many discards, to weigh up at each decision
+    pub candidates: usize,
+    /// How many draws to sample after each play or discard
+    pub samples: usize,
+    /// How many decisions to look ahead
+    pub depth: usize,
+    /// Picks out consumables worth using
+    pub consumables: ConsumablePlanner,
+}
+
+impl RoundPlanner {
+    pub const DEFAULT_CANDIDATES: usize = 3;
+    pub const DEFAULT_SAMPLES: usize = 4;
+    pub const DEFAULT_DEPTH: usize = 2;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_advisor(self, advisor: Advisor) -> Self {
+        Self { advisor, ..self }
+    }
+
+    #[must_use]
+    pub fn with_candidates(self, candidates: usize) -> Self {
+        Self { candidates, ..self }
+    }
+
+    #[must_use]
+    pub fn with_samples(self, samples: usize) -> Self {
+        Self { samples, ..self }
+    }
+
+    #[must_use]
+    pub fn with_depth(self, depth: usize) -> Self {
+        Self { depth, ..self }
+    }
+
+    #[must_use]
+    pub fn with_consumables(self, consumables: ConsumablePlanner) -> Self {
+        Self {
+            consumables,
+            ..self
+        }
+    }
+
+    /// The action most likely to lead to beating the round, given the cards
+    /// that might be drawn, and any consumable to use first. With nothing in
+    /// hand, this is an empty play that can't win.
+    pub fn plan(&self, round: &Round, tracker: &DeckTracker) -> Plan {
+        let needed = round.target.value() - round.score.value();
+        // Draws are sampled from a fixed seed, so plans are repeatable
+        let mut rng = SmallRng::seed_from_u64(0);
+        let plan = |state: &GameState, rng: &mut SmallRng| {
+            self.search(
+                &state.hand,
+                tracker,
+                &mut state.clone(),
+                needed,
+                self.depth,
+                rng,
+            )
+            .unwrap_or((Action::Play(Vec::new()), 0.0))
+        };
+
+        let (action, win_chance) = plan(&round.state, &mut rng);
+        if win_chance < 1.0 {
+            if let Some(best) = self.consumables.best(&round.state) {
+                let mut state = round.state.clone();
+                if state
+                    .use_consumable(best.index, &best.selected, &mut rng)
+                    .is_ok()
+                {
+                    let (then, chance) = plan(&state, &mut rng);
+                    if chance > win_chance {
+                        return Plan {
+                            consumable: Some((best.index, best.selected)),
+                            action: then,
+                            win_chance: chance,
+                        };
+                    }
+                }
+            }
+        }
+        Plan {
+            consumable: None,
+            action,
+            win_chance,
+        }
+    }
+
+    /// The best action for `hand`, and its chance of scoring `needed` more
+    /// with the hands and discards left in `state`.
+    fn search(
+        &self,
+        hand: &[Card],
+        tracker: &DeckTracker,
+        state: &mut GameState,
+        needed: f64,
+        depth: usize,
+        rng: &mut dyn RngCore,
+    ) -> Option<(Action, f64)> {
+        let mut best: Option<(Action, f64)> = None;
+        let mut consider = |action: Action, chance: f64| {
+            if best.as_ref().is_none_or(|(_, best)| chance > *best) {
+                best = Some((action, chance));
+            }
+        };
+
+        // Plays that score the same are as good as each other
+        let plays = self
+            .advisor
+            .plays(hand, state)
+            .into_iter()
+            .dedup_by(|(_, a), (_, b)| a == b)
+            .take(self.candidates)
+            .collect_vec();
+        for (indices, _) in &plays {
+            let chance = self
+                .advisor
+                .play_outcomes(hand, indices, state)
+                .into_iter()
+                .map(|(score, probability)| {
+                    let chance = self.after_play(
+                        hand,
+                        indices,
+                        score.value(),
+                        tracker,
+                        state,
+                        needed,
+                        depth,
+                        rng,
+                    );
+                    probability * chance
+                })
+                .sum::<f64>();
+            if chance >= 1.0 {
+                return Some((Action::Play(indices.clone()), chance));
+            }
+            consider(Action::Play(indices.clone()), chance);
+        }
+
+        if state.discards > 0 && depth > 0 && tracker.count() > 0 {
+            let keeps = plays.into_iter().map(|(indices, _)| indices);
+            for discard in discards(hand, keeps).into_iter().take(self.candidates) {
+                state.discards -= 1;
+                let chance =
+                    self.after_draw(hand, &discard, tracker, state, needed, depth - 1, rng);
+                state.discards += 1;
+                consider(Action::Discard(discard), chance);
+            }
+        }
+        best
+    }
+
+    /// The chance of scoring `needed` more after playing the cards at `played`
+    /// for `score`.
+    #[allow(clippy::too_many_arguments)]
+    fn after_play(
+        &self,
+        hand: &[Card],
+        played: &[usize],
+        score: f64,
+        tracker: &DeckTracker,
+        state: &mut GameState,
+        needed: f64,
+        depth: usize,
+        rng: &mut dyn RngCore,
+    ) -> f64 {
+        if score >= needed {
+            1.0
+        } else if state.hands <= 1 {
+            0.0
+        } else if depth == 0 {
+            if score * f64::from(state.hands) >= needed {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            state.hands -= 1;
+            let chance =
+                self.after_draw(hand, played, tracker, state, needed - score, depth - 1, rng);
+            state.hands += 1;
+            chance
+        }
+    }
+
+    /// The chance of scoring `needed` more once the cards at `removed` have
+    /// been replaced, averaged over a sample of draws.
+    #[allow(clippy::cast_precision_loss, clippy::too_many_arguments)]
+    fn after_draw(
+        &self,
+        hand: &[Card],
+        removed: &[usize],
+        tracker: &DeckTracker,
+        state: &mut GameState,
+        needed: f64,
+        depth: usize,
+        rng: &mut dyn RngCore,
+    ) -> f64 {
+        let kept = hand
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !removed.contains(index))
+            .map(|(_, card)| *card)
+            .collect_vec();
+        let samples = self.samples.max(1);
+        let total: f64 = (0..samples)
+            .map(|_| {
+                let drawn = tracker.sample(removed.len(), rng);
+                let mut unseen = tracker.clone();
+                for card in &drawn {
+                    unseen.see(*card);
+                }
+                let mut hand = kept.clone();
+                hand.extend(drawn);
+                self.search(&hand, &unseen, state, needed, depth, rng)
+                    .map_or(0.0, |(_, chance)| chance)
+            })
+            .sum();
+        total / samples as f64
+    }
+}
+
+impl Default for RoundPlanner {
+    fn default() -> Self {
+        Self {
+            advisor: Advisor::default(),
+            candidates: Self::DEFAULT_CANDIDATES,
+            samples: Self::DEFAULT_SAMPLES,
+            depth: Self::DEFAULT_DEPTH,
+            consumables: ConsumablePlanner::default(),
+        }
+    }
+}
+
+/// Plans each round as it goes, assuming nothing about the order of the draw
+/// pile, and uses the consumables its plans call for.
+impl Strategy for RoundPlanner {
+    fn use_consumable(
+        &mut self,
+        round: &Round,
+        rng: &mut dyn RngCore,
+    ) -> Option<(usize, Vec<usize>)> {
+        if round.state.consumables.is_empty() {
+            return None;
+        }
+        self.plan(round, &DeckTracker::from_state(&round.state))
+            .consumable
+    }
+
+    fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+        self.plan(round, &DeckTracker::from_state(&round.state))
+            .action
+    }
+}
+
+/// Discards that hold on to something worth building on: each of `keeps`, or
+/// the cards of the most common suit. Only five cards can be discarded at
+/// once, so the lowest-ranked of the rest go first.
+pub(crate) fn discards(hand: &[Card], keeps: impl Iterator<Item = Vec<usize>>) -> Vec<Vec<usize>> {
+    let suits = hand.iter().counts_by(|card| card.suit);
+    let flush_draw = suits
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(suit, _)| {
+            (0..hand.len())
+                .filter(|index| hand[*index].suit == suit)
+                .collect_vec()
+        });
+
+    keeps
+        .chain(flush_draw)
+        .filter_map(|keep| {
+            let mut discard = (0..hand.len())
+                .filter(|index| !keep.contains(index))
+                .sorted_by_key(|index| hand[*index].rank)
+                .take(5)
+                .collect_vec();
+            discard.sort_unstable();
+            (!discard.is_empty()).then_some(discard)
+        })
+        .unique()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardCollection, CardView, Deck, Enhancement};
+    use crate::solver::consumable::{Consumable, Tarot};
+    use crate::solver::score::Score;
+
+    fn resumed(hand: &str, hands: u32, discards: u32, target: f64) -> Round {
+        let state = GameState::from_parts(
+            Deck::base_deck(),
+            CardCollection::from_idents(hand).view().to_vec(),
+            hands,
+            discards,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        );
+        let mut round = Round::resume(state, Score::ZERO);
+        round.target = Score::from(target);
+        round
+    }
+
+    #[test]
+    fn plan_test() {
+        let planner = RoundPlanner::new().with_candidates(2).with_samples(2);
+
+        // A straight flush wins outright
+        let round = resumed("AS KS QS JS TS 2D 3C 4H", 4, 3, 300.0);
+        let tracker = DeckTracker::from_state(&round.state);
+        let plan = planner.plan(&round, &tracker);
+        assert_eq!(plan.action, Action::Play(vec![0, 1, 2, 3, 4]));
+        assert!((plan.win_chance - 1.0).abs() < f64::EPSILON);
+
+        // A pair of aces can't win with the last hand, so it's worth fishing
+        // for a third
+        let round = resumed("AS AD 2C 5H 9S JD 4C 7H", 1, 3, 100.0);
+        let tracker = DeckTracker::from_state(&round.state);
+        let plan = planner.plan(&round, &tracker);
+        assert!(matches!(plan.action, Action::Discard(_)));
+
+        // ...but with nothing to discard, there's no hope
+        let round = resumed("AS AD 2C 5H 9S JD 4C 7H", 1, 0, 100.0);
+        let plan = planner.plan(&round, &tracker);
+        assert_eq!(plan.action, Action::Play(vec![0, 1]));
+        assert!(plan.win_chance.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn lucky_test() {
+        // A lone ace scores (5 + 11) * 1, or (5 + 11) * 21 if its Lucky mult
+        // hits. It only wins when it does, however good it is on average.
+        let mut round = resumed("AS", 1, 0, 100.0);
+        round.state.hand[0].enhancement = Some(Enhancement::Lucky);
+        let plan = RoundPlanner::new().plan(&round, &DeckTracker::new(Vec::new()));
+        assert_eq!(plan.action, Action::Play(vec![0]));
+        assert_relative_eq!(plan.win_chance, 0.2);
+    }
+
+    #[test]
+    fn consumable_test() {
+        // Nothing in hand comes close, but turning the heart into a spade
+        // makes a flush that wins outright
+        let mut round = resumed("AS KS QS JS 9H 2C 3D 4C", 1, 0, 300.0);
+        round
+            .state
+            .consumables
+            .push(Consumable::Tarot(Tarot::TheWorld));
+        let plan = RoundPlanner::new().plan(&round, &DeckTracker::from_state(&round.state));
+        assert_eq!(plan.consumable, Some((0, vec![4])));
+        assert_eq!(plan.action, Action::Play(vec![0, 1, 2, 3, 4]));
+        assert!((plan.win_chance - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn discards_test() {
+        let hand = CardCollection::from_idents("AS AD 2H 5H 9H JH 4C 7S");
+        let discards = discards(hand.view(), std::iter::once(vec![0, 1]));
+        // Keep the aces, or draw to the hearts
+        assert_eq!(discards, vec![vec![2, 3, 4, 6, 7], vec![0, 1, 6, 7]]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Estimating how likely a run is to get through what's in front of it, by
+//! playing it out many times.
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::solver::error::Result;
+use crate::solver::game_state::GameState;
+use crate::solver::run::RunSimulator;
+use crate::solver::strategy::greedy::Greedy;
+use crate::solver::strategy::Strategy;
+
+/// How often a state's rollouts got past its current blind, and past the
+/// Boss Blind of its current ante.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct WinProbability {
+    /// The chance of beating or skipping the current blind
+    pub blind: f64,
+    /// The chance of beating the current ante's Boss Blind
+    pub ante: f64,
+}
+
+/// Estimate the chances of getting through `state`'s current blind and ante,
+/// by playing `samples` rollouts from it with `strategy`. The state should be
+/// between blinds, with the current blind up next.
+///
+/// Rollout `n` is seeded with `n`, so estimates are repeatable. Fails if the
+/// strategy picks an action that isn't allowed.
+#[allow(clippy::cast_precision_loss)]
+pub fn win_probability_with<S: Strategy + Clone>(
+    strategy: &S,
+    state: &GameState,
+    samples: usize,
+) -> Result<WinProbability> {
+    let mut blinds = 0;
+    let mut antes = 0;
+    for seed in 0..samples as u64 {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let summary = RunSimulator::new(strategy.clone())
+            .with_winning_ante(state.ante)
+            .run(state.clone(), &mut rng)?;
+        // The run stops at the first blind it loses, so the current one was
+        // only lost if nothing was beaten or skipped
+        if summary.blinds_beaten + summary.blinds_skipped > 0 {
+            blinds += 1;
+        }
+        if summary.won {
+            antes += 1;
+        }
+    }
+
+    let samples = samples.max(1) as f64;
+    Ok(WinProbability {
+        blind: f64::from(blinds) / samples,
+        ante: f64::from(antes) / samples,
+    })
+}
+
+/// Like `win_probability_with`, playing the rollouts with `Greedy`.
+pub fn win_probability(state: &GameState, samples: usize) -> Result<WinProbability> {
+    win_probability_with(&Greedy::default(), state, samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::Deck;
+    use crate::solver::joker::builtin;
+
+    fn state(ante: u32, blind: Blind) -> GameState {
+        GameState::from_parts(
+            Deck::base_deck(),
+            Vec::new(),
+            4,
+            3,
+            4,
+            ante,
+            blind,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn win_probability_test() {
+        // Nobody beats ante 8 with a plain deck and no jokers
+        let odds = win_probability(&state(8, Blind::Boss), 10).unwrap();
+        assert_eq!(odds, WinProbability::default());
+
+        // ...but enough mult makes the first ante a formality
+        let mut easy = state(1, Blind::Small);
+        easy.add_joker(builtin::by_name("Hologram").unwrap());
+        easy.jokers[0].state.value = 1000.0;
+        let odds = win_probability(&easy, 10).unwrap();
+        assert_eq!(
+            odds,
+            WinProbability {
+                blind: 1.0,
+                ante: 1.0
+            }
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! A single blind, played out one action at a time.
+
+use std::sync::Arc;
+
+use rand::RngCore;
+
+use crate::solver::cards::{Card, CardView, Hand};
+use crate::solver::error::{Error, Result};
+use crate::solver::game_state::GameState;
+use crate::solver::hand_evaluator::{HandEvaluator, Options};
+use crate::solver::observer::Observer;
+use crate::solver::score::Score;
+use crate::solver::scorer::{Randomness, ScoringContext, ScoringOptions};
+
+/// Something to do with the cards in hand. Cards are picked by their index in
+/// `GameState::hand`, and are played in the order they're given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Play(Vec<usize>),
+    Discard(Vec<usize>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RoundOutcome {
+    /// The blind's target was reached
+    Won,
+    /// Every hand was played without reaching the target
+    Lost,
+}
+
+/// A round in progress: the state of the run, the score to beat, and how much
+/// has been scored so far.
+#[derive(Clone, Debug)]
+pub struct Round {
+    pub state: GameState,
+    pub target: Score,
+    pub score: Score,
+    pub evaluator_options: Options,
+    pub scoring_options: ScoringOptions,
+    /// Told about every card drawn and hand played
+    pub observers: Vec<Arc<dyn Observer>>,
+    outcome: Option<RoundOutcome>,
+}
+
+impl Round {
+    /// Start a round against the state's current blind, drawing the first
+    /// hand.
+    pub fn start(state: GameState, rng: &mut dyn RngCore) -> Self {
+        Self::start_observed(state, Vec::new(), rng)
+    }
+
+    /// Like `start`, with observers that see the first hand being drawn.
+    pub fn start_observed(
+        mut state: GameState,
+        observers: Vec<Arc<dyn Observer>>,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        state.start_round(rng);
+        let mut round = Self::resume(state, Score::ZERO);
+        round.observers = observers;
+        round.notify_drawn(0);
+        round
+    }
+
+    /// Pick up a round that's already underway, with the state's hand as it
+    /// is and `score` already scored.
+    pub fn resume(state: GameState, score: Score) -> Self {
+        let mut round = Self {
+            target: state.current_target(),
+            state,
+            score,
+            evaluator_options: Options::empty(),
+            scoring_options: ScoringOptions::empty(),
+            observers: Vec::new(),
+            outcome: None,
+        };
+        round.update_outcome();
+        round
+    }
+
+    #[must_use]
+    pub fn with_evaluator_options(self, evaluator_options: Options) -> Self {
+        Self {
+            evaluator_options,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_scoring_options(self, scoring_options: ScoringOptions) -> Self {
+        Self {
+            scoring_options,
+            ..self
+        }
+    }
+
+    /// How the round ended, or `None` while it's still going.
+    pub fn outcome(&self) -> Option<RoundOutcome> {
+        self.outcome
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    pub fn into_state(self) -> GameState {
+        self.state
+    }
+
+    pub fn apply(&mut self, action: &Action, randomness: Randomness) -> Result<()> {
+        match action {
+            Action::Play(indices) => self.play(indices, randomness).map(|_| ()),
+            Action::Discard(indices) => self.discard(indices),
+        }
+    }
+
+    /// Play the selected cards, returning what they scored. The hand is only
+    /// refilled if the round carries on afterwards.
+    pub fn play(&mut self, indices: &[usize], randomness: Randomness) -> Result<Score> {
+        if self.is_over() {
+            return Err(Error::RoundOver);
+        }
+        if self.state.hands == 0 {
+            return Err(Error::NoHandsLeft);
+        }
+        self.validate(indices)?;
+        let played = self.take(indices);
+
+        let (kind, scoring, _) =
+            HandEvaluator::evaluate_played_hand(Hand::from_slice(&played)?, self.evaluator_options)
+                .expect("a non-empty hand always makes some poker hand");
+        let mut options = self.scoring_options;
+        if self.state.hands == 1 {
+            options |= ScoringOptions::FinalHand;
+        }
+        let held = self.state.hand.clone();
+        let context = ScoringContext::new(kind, &scoring)
+            .with_all_played(&played)
+            .with_held(&held)
+            .with_options(options);
+        let score = if self.observers.is_empty() {
+            self.state.play_hand(context, randomness)
+        } else {
+            let breakdown = self.state.play_hand_breakdown(context, randomness);
+            for observer in &self.observers {
+                for (card, chips) in &breakdown.card_chips {
+                    observer.on_card_scored(&mut self.state, *card, *chips);
+                }
+                observer.on_hand_played(&mut self.state, &breakdown);
+            }
+            breakdown.total
+        };
+
+        self.score += score;
+        self.update_outcome();
+        if !self.is_over() {
+            self.draw();
+        }
+        Ok(score)
+    }
+
+    /// Discard the selected cards and draw replacements.
+    pub fn discard(&mut self, indices: &[usize]) -> Result<()> {
+        if self.is_over() {
+            return Err(Error::RoundOver);
+        }
+        if self.state.discards == 0 {
+            return Err(Error::NoDiscardsLeft);
+        }
+        self.validate(indices)?;
+
+        let discarded = self.take(indices);
+        self.state.discard(&discarded);
+        self.draw();
+        Ok(())
+    }
+
+    /// Refill the hand, telling the observers about each card drawn.
+    fn draw(&mut self) {
+        let before = self.state.hand.len();
+        self.state.draw_to_hand_size();
+        self.notify_drawn(before);
+    }
+
+    /// Tell the observers about the cards in hand from `from` onwards.
+    fn notify_drawn(&mut self, from: usize) {
+        for observer in &self.observers {
+            for index in from..self.state.hand.len() {
+                let card = self.state.hand[index];
+                observer.on_card_drawn(&mut self.state, card);
+            }
+        }
+    }
+
+    /// Check a selection before anything is taken out of the hand, so a
+    /// rejected action leaves the round as it was.
+    fn validate(&self, indices: &[usize]) -> Result<()> {
+        if indices.is_empty() {
+            return Err(Error::EmptySelection);
+        }
+        if indices.len() > 5 {
+            return Err(Error::OverfullHand);
+        }
+        self.state.validate_selection(indices)
+    }
+
+    /// Remove the selected cards from the hand, in the order selected. The
+    /// rest of the hand keeps its order.
+    fn take(&mut self, indices: &[usize]) -> Vec<Card> {
+        let taken = indices
+            .iter()
+            .map(|&index| self.state.hand[index])
+            .collect();
+        let mut position = 0;
+        self.state.hand.retain(|_| {
+            position += 1;
+            !indices.contains(&(position - 1))
+        });
+        taken
+    }
+
+    fn update_outcome(&mut self) {
+        self.outcome = if self.score >= self.target {
+            Some(RoundOutcome::Won)
+        } else if self.state.hands == 0 {
+            Some(RoundOutcome::Lost)
+        } else {
+            None
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::cards;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardCollection, CardView, Deck};
+
+    /// A round with a known hand and nothing left to draw but `draw`, with
+    /// the last card of `draw` on top.
+    fn round(hand: &str, draw: &str, hands: u32, discards: u32) -> Round {
+        let hand = CardCollection::from_idents(hand).view().to_vec();
+        let draw = CardCollection::from_idents(draw).view().to_vec();
+        let deck = Deck::from_cards(draw.iter().chain(&hand).copied().collect());
+        let state = GameState::from_parts(
+            deck,
+            hand,
+            hands,
+            discards,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        );
+        Round::resume(state, Score::ZERO)
+    }
+
+    #[test]
+    fn play_test() {
+        let mut round = round("KH KS 2C 7D 9S", "4H 5H", 4, 3);
+        // 30 * 2
+        let score = round.play(&[0, 1], Randomness::Expected).unwrap();
+        assert_relative_eq!(score.value(), 60.0);
+        assert_eq!(round.state.hands, 3);
+        assert_eq!(round.state.hand, cards!("2C 7D 9S 5H 4H").view());
+        assert_eq!(round.outcome(), None);
+    }
+
+    #[test]
+    fn discard_test() {
+        let mut round = round("KH KS 2C 7D 9S", "4H", 4, 1);
+        round.discard(&[4, 2]).unwrap();
+        assert_eq!(round.state.hand, cards!("KH KS 7D 4H").view());
+        assert_eq!(round.state.discards, 0);
+        assert!(matches!(round.discard(&[0]), Err(Error::NoDiscardsLeft)));
+    }
+
+    #[test]
+    fn outcome_test() {
+        let mut round = round_with_target("AH AS AD AC 2C 3C", 500.0);
+        // (60 + 44) * 7
+        round.play(&[0, 1, 2, 3], Randomness::Expected).unwrap();
+        assert_eq!(round.outcome(), Some(RoundOutcome::Won));
+        assert!(matches!(
+            round.play(&[0], Randomness::Expected),
+            Err(Error::RoundOver)
+        ));
+
+        let mut round = round_with_target("2C 3D", 1000.0);
+        round.play(&[0], Randomness::Expected).unwrap();
+        round.play(&[0], Randomness::Expected).unwrap();
+        assert_eq!(round.outcome(), Some(RoundOutcome::Lost));
+    }
+
+    fn round_with_target(hand: &str, target: f64) -> Round {
+        let mut round = round(hand, "", 2, 0);
+        round.target = Score::from(target);
+        round
+    }
+
+    #[test]
+    fn validation_test() {
+        let mut round = round("KH KS 2C", "", 1, 1);
+        assert!(matches!(
+            round.play(&[], Randomness::Expected),
+            Err(Error::EmptySelection)
+        ));
+        assert!(matches!(
+            round.play(&[3], Randomness::Expected),
+            Err(Error::CardNotInHand { index: 3, len: 3 })
+        ));
+        assert!(matches!(
+            round.discard(&[1, 1]),
+            Err(Error::DuplicateSelection(1))
+        ));
+        // Nothing was used up by the failed actions
+        assert_eq!((round.state.hands, round.state.discards), (1, 1));
+    }
+
+    #[test]
+    fn overfull_selection_test() {
+        let mut round = round("KH KS 2C 7D 9S 4H", "", 1, 1);
+        assert!(matches!(
+            round.play(&[0, 1, 2, 3, 4, 5], Randomness::Expected),
+            Err(Error::OverfullHand)
+        ));
+        assert!(matches!(
+            round.discard(&[0, 1, 2, 3, 4, 5]),
+            Err(Error::OverfullHand)
+        ));
+        // The hand is left as it was
+        assert_eq!(round.state.hand, cards!("KH KS 2C 7D 9S 4H").view());
+        assert_eq!((round.state.hands, round.state.discards), (1, 1));
+    }
+}
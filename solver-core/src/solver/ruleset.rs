@@ -0,0 +1,124 @@
+use crate::solver::cards::{HandKind, Rank};
+
+/// The base values the scorer works from. This defaults to vanilla Balatro,
+/// but can be swapped out to model balance mods or to answer what-if questions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ruleset {
+    /// Base chips for each hand kind, indexed by `HandKind`
+    pub hand_base_chips: [u64; 12],
+    /// Base mult for each hand kind, indexed by `HandKind`
+    pub hand_base_mult: [f64; 12],
+    /// Chips gained per level past the first, indexed by `HandKind`
+    pub hand_level_chips: [u64; 12],
+    /// Mult gained per level past the first, indexed by `HandKind`
+    pub hand_level_mult: [f64; 12],
+    /// Chips scored by each rank, indexed by `Rank`
+    pub rank_chips: [u64; 13],
+
+    pub bonus_chips: u64,
+    pub mult_mult: f64,
+    pub glass_xmult: f64,
+    pub stone_chips: u64,
+    pub lucky_mult: f64,
+    /// Lucky cards give their mult with a 1 in this many chance
+    pub lucky_mult_odds: u32,
+    pub lucky_money: u32,
+    /// Lucky cards give their money with a 1 in this many chance
+    pub lucky_money_odds: u32,
+    pub steel_held_xmult: f64,
+    pub gold_held_money: u32,
+
+    pub foil_chips: u64,
+    pub holographic_mult: f64,
+    pub polychrome_xmult: f64,
+}
+
+impl Ruleset {
+    pub const VANILLA: Self = Self {
+        hand_base_chips: [5, 10, 20, 30, 30, 35, 40, 60, 100, 120, 140, 160],
+        hand_base_mult: [
+            1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 4.0, 7.0, 8.0, 12.0, 14.0, 16.0,
+        ],
+        hand_level_chips: [10, 15, 20, 20, 30, 15, 25, 30, 40, 35, 40, 50],
+        hand_level_mult: [1.0, 1.0, 1.0, 2.0, 3.0, 2.0, 2.0, 3.0, 4.0, 3.0, 4.0, 3.0],
+        rank_chips: [2, 3, 4, 5, 6, 7, 8, 9, 10, 10, 10, 10, 11],
+
+        bonus_chips: 30,
+        mult_mult: 4.0,
+        glass_xmult: 2.0,
+        stone_chips: 50,
+        lucky_mult: 20.0,
+        lucky_mult_odds: 5,
+        lucky_money: 20,
+        lucky_money_odds: 15,
+        steel_held_xmult: 1.5,
+        gold_held_money: 3,
+
+        foil_chips: 50,
+        holographic_mult: 10.0,
+        polychrome_xmult: 1.5,
+    };
+
+    pub fn base_chips(&self, kind: HandKind) -> u64 {
+        self.hand_base_chips[kind as usize]
+    }
+
+    pub fn base_mult(&self, kind: HandKind) -> f64 {
+        self.hand_base_mult[kind as usize]
+    }
+
+    /// Base chips for a hand levelled up to `level`.
+    pub fn hand_chips(&self, kind: HandKind, level: u32) -> u64 {
+        self.base_chips(kind)
+            + u64::from(level.saturating_sub(1)) * self.hand_level_chips[kind as usize]
+    }
+
+    /// Base mult for a hand levelled up to `level`.
+    pub fn hand_mult(&self, kind: HandKind, level: u32) -> f64 {
+        self.base_mult(kind)
+            + f64::from(level.saturating_sub(1)) * self.hand_level_mult[kind as usize]
+    }
+
+    pub fn chips_for_rank(&self, rank: Rank) -> u64 {
+        self.rank_chips[rank as usize]
+    }
+
+    /// The mult a Lucky card is worth on average.
+    pub fn lucky_expected_mult(&self) -> f64 {
+        self.lucky_mult / f64::from(self.lucky_mult_odds)
+    }
+
+    /// The chance that a Lucky card triggers at least one of its effects.
+    pub fn lucky_trigger_chance(&self) -> f64 {
+        let mult_misses = 1.0 - 1.0 / f64::from(self.lucky_mult_odds);
+        let money_misses = 1.0 - 1.0 / f64::from(self.lucky_money_odds);
+        1.0 - mult_misses * money_misses
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::VANILLA
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn vanilla_test() {
+        let ruleset = Ruleset::default();
+        assert_eq!(ruleset, Ruleset::VANILLA);
+        assert_eq!(ruleset.base_chips(HandKind::FlushFive), 160);
+        assert_relative_eq!(ruleset.base_mult(HandKind::FourOfAKind), 7.0);
+        assert_eq!(ruleset.chips_for_rank(Rank::Ace), 11);
+        assert_eq!(ruleset.hand_chips(HandKind::Flush, 1), 35);
+        assert_eq!(ruleset.hand_chips(HandKind::Flush, 3), 65);
+        assert_relative_eq!(ruleset.hand_mult(HandKind::Flush, 3), 8.0);
+        assert_relative_eq!(ruleset.lucky_expected_mult(), 4.0);
+        assert_relative_eq!(ruleset.lucky_trigger_chance(), 19.0 / 75.0);
+    }
+}
@@ -0,0 +1,196 @@
+//! Whole runs, played blind after blind until one is lost or the final ante
+//! is beaten.
+
+use std::sync::Arc;
+
+use rand::RngCore;
+
+use crate::solver::blind::Blind;
+use crate::solver::error::Result;
+use crate::solver::game_state::GameState;
+use crate::solver::observer::Observer;
+use crate::solver::round::{Round, RoundOutcome};
+use crate::solver::shop::Shop;
+use crate::solver::stake::Stake;
+use crate::solver::strategy::{take_turn, Strategy};
+use crate::solver::tag::Tag;
+
+/// How a run went.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunSummary {
+    /// Whether the Boss Blind of the winning ante was beaten
+    pub won: bool,
+    pub stake: Stake,
+    /// The ante and blind the run ended on, whether it was beaten or not
+    pub final_ante: u32,
+    pub final_blind: Blind,
+    pub blinds_beaten: u32,
+    pub blinds_skipped: u32,
+    pub hands_played: u32,
+    pub discards_used: u32,
+    /// Money at the start of the run, then after cashing out each blind
+    pub money: Vec<i64>,
+}
+
+/// Plays runs with a `Strategy`, chaining rounds, cash outs, and a shop
+/// between each blind.
+pub struct RunSimulator<S> {
+    pub strategy: S,
+    /// Beating this ante's Boss Blind wins the run
+    pub winning_ante: u32,
+    /// Told about everything that happens in the run
+    pub observers: Vec<Arc<dyn Observer>>,
+}
+
+impl<S: Strategy> RunSimulator<S> {
+    pub fn new(strategy: S) -> Self {
+        Self {
+            strategy,
+            winning_ante: 8,
+            observers: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_winning_ante(self, winning_ante: u32) -> Self {
+        Self {
+            winning_ante,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Play a run from `state` until it's won or lost. Fails if the strategy
+    /// ever picks an action the round won't allow.
+    pub fn run(&mut self, mut state: GameState, rng: &mut dyn RngCore) -> Result<RunSummary> {
+        let mut summary = RunSummary {
+            stake: state.stake,
+            money: vec![state.money],
+            ..RunSummary::default()
+        };
+
+        loop {
+            summary.final_ante = state.ante;
+            summary.final_blind = state.blind;
+
+            if state.blind != Blind::Boss {
+                let tag = Tag::random(state.ante, rng);
+                if self.strategy.skip_blind(&state, tag, rng) {
+                    for mut pack in state.skip_blind(tag, rng)? {
+                        self.strategy.open_pack(&mut pack, &mut state, rng);
+                    }
+                    summary.blinds_skipped += 1;
+                    continue;
+                }
+            }
+
+            let mut round = Round::start_observed(state, self.observers.clone(), rng);
+            while !round.is_over() {
+                take_turn(&mut self.strategy, &mut round, rng)?;
+            }
+            let outcome = round.outcome();
+            state = round.into_state();
+            summary.hands_played += state.hands_per_round - state.hands;
+            summary.discards_used += state.discards_used;
+            if outcome == Some(RoundOutcome::Lost) {
+                for observer in &self.observers {
+                    observer.on_round_end(&mut state, RoundOutcome::Lost, None);
+                }
+                return Ok(summary);
+            }
+
+            summary.blinds_beaten += 1;
+            let cash_out = state.cash_out();
+            for observer in &self.observers {
+                observer.on_round_end(&mut state, RoundOutcome::Won, Some(&cash_out));
+            }
+            summary.money.push(state.money);
+            if state.ante >= self.winning_ante && state.blind == Blind::Boss {
+                summary.won = true;
+                return Ok(summary);
+            }
+
+            let mut shop = Shop::enter(&mut state, rng);
+            for observer in &self.observers {
+                observer.on_shop_entered(&mut state, &mut shop);
+            }
+            self.strategy.visit_shop(&mut shop, &mut state, rng);
+            state.advance_blind();
+            if state.blind == Blind::Small {
+                state.restock_voucher(rng);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::joker::builtin;
+    use crate::solver::round::Action;
+
+    /// Plays the first five cards in hand, every time.
+    struct FirstFive;
+
+    impl Strategy for FirstFive {
+        fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+            Action::Play((0..round.state.hand.len().min(5)).collect())
+        }
+    }
+
+    /// Discards forever, which the round won't allow for long.
+    struct DiscardOnly;
+
+    impl Strategy for DiscardOnly {
+        fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+            Action::Discard(vec![0])
+        }
+    }
+
+    #[test]
+    fn run_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = GameState::fresh_run(&mut rng);
+        // Enough mult that the first ante can't be lost
+        state.add_joker(builtin::by_name("Hologram").unwrap());
+        state.jokers[0].state.value = 1000.0;
+        state.add_joker(builtin::by_name("Golden Joker").unwrap());
+
+        let summary = RunSimulator::new(FirstFive)
+            .with_winning_ante(1)
+            .run(state, &mut rng)
+            .unwrap();
+        assert!(summary.won);
+        assert_eq!((summary.final_ante, summary.final_blind), (1, Blind::Boss));
+        assert_eq!(summary.blinds_beaten, 3);
+        // $4 to start, then each blind's reward, $3 for the three hands left,
+        // interest, and Golden Joker's $4
+        assert_eq!(summary.money, [4, 14, 27, 44]);
+    }
+
+    #[test]
+    fn losing_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let summary = RunSimulator::new(FirstFive)
+            .run(GameState::fresh_run(&mut rng), &mut rng)
+            .unwrap();
+        assert!(!summary.won);
+        assert!(summary.hands_played >= 4);
+        assert_eq!(summary.money.len(), summary.blinds_beaten as usize + 1);
+    }
+
+    #[test]
+    fn invalid_action_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let result = RunSimulator::new(DiscardOnly).run(GameState::fresh_run(&mut rng), &mut rng);
+        assert!(result.is_err());
+    }
+}
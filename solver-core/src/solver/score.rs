@@ -0,0 +1,107 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+use approx::assert_relative_eq;
+
+/// A hand's final score, computed the same way Balatro does: chips are whole
+/// numbers (until Plasma deck balances them), mult is a double, and the
+/// product is floored.
+///
+/// Balatro itself stores scores as doubles, so an `f64` is exactly as precise
+/// as the game is, all the way up to its `naneinf` overflow.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Score(f64);
+
+impl Score {
+    /// Scores at or above this are written in e-notation, like the game does.
+    const SCIENTIFIC_THRESHOLD: f64 = 1e11;
+
+    pub const ZERO: Self = Self(0.0);
+
+    pub fn from_chips_and_mult(chips: f64, mult: f64) -> Self {
+        Self((chips * mult).floor())
+    }
+
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    /// Whether this score has overflowed past what Balatro can represent.
+    pub fn is_naneinf(self) -> bool {
+        !self.0.is_finite()
+    }
+}
+
+impl From<f64> for Score {
+    fn from(value: f64) -> Self {
+        Self(value.floor())
+    }
+}
+
+impl Add for Score {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Score {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sum for Score {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_naneinf() {
+            write!(f, "naneinf")
+        } else if self.0 >= Self::SCIENTIFIC_THRESHOLD {
+            write!(f, "{:.3e}", self.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flooring_test() {
+        assert_relative_eq!(Score::from_chips_and_mult(30.0, 2.0).value(), 60.0);
+        assert_relative_eq!(Score::from_chips_and_mult(35.0, 1.5).value(), 52.0);
+        assert_relative_eq!(Score::from_chips_and_mult(7.0, 3.3).value(), 23.0);
+    }
+
+    #[test]
+    fn large_score_test() {
+        // Well past where an f32 would start losing whole numbers
+        let score = Score::from_chips_and_mult(123_456_789.0, 1_000.0);
+        assert_relative_eq!(score.value(), 123_456_789_000.0);
+        let score = Score::from_chips_and_mult(16_777_217.0, 1.0);
+        assert_relative_eq!(score.value(), 16_777_217.0);
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(Score::from(1208.0).to_string(), "1208");
+        assert_eq!(Score::from(99_999_999_999.0).to_string(), "99999999999");
+        assert_eq!(Score::from(123_456_789_000.0).to_string(), "1.235e11");
+        assert_eq!(Score::from(f64::INFINITY).to_string(), "naneinf");
+    }
+
+    #[test]
+    fn sum_test() {
+        let total: Score = [Score::from(10.0), Score::from(20.5)].into_iter().sum();
+        assert_relative_eq!(total.value(), 30.0);
+    }
+}
@@ -1,57 +1,1309 @@
-use approx::assert_relative_eq;
-
-use crate::hand;
-use crate::solver::cards::{CardView, Hand, HandKind};
-
-pub struct Scorer<'a> {
-    kind: HandKind,
-    hand: &'a Hand,
-}
-
-impl<'a> Scorer<'a> {
-    fn new(kind: HandKind, hand: &'a Hand) -> Self {
-        Self { kind, hand }
-    }
-
-    fn score(&self) -> f32 {
-        const HAND_BASE_CHIPS: [f32; 12] = [
-            5.0, 10.0, 20.0, 30.0, 30.0, 35.0, 40.0, 60.0, 100.0, 120.0, 140.0, 160.0,
-        ];
-        const HAND_BASE_MULT: [f32; 12] = [
-            1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 4.0, 7.0, 8.0, 12.0, 14.0, 16.0,
-        ];
-        const RANK_CHIPS: [f32; 13] = [
-            2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 10.0, 10.0, 10.0, 11.0,
-        ];
-
-        let mut chips = HAND_BASE_CHIPS[self.kind as usize];
-        let mult = HAND_BASE_MULT[self.kind as usize];
-        for card in self.hand.view() {
-            chips += RANK_CHIPS[card.rank as usize];
-        }
-
-        chips * mult
-    }
-
-    pub fn score_hand(kind: HandKind, hand: &'a Hand) -> f32 {
-        let scorer = Self::new(kind, hand);
-        scorer.score()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn expect_score(hand: &Hand, kind: HandKind, expected_score: f32) {
-        let score = Scorer::score_hand(kind, hand);
-        assert_relative_eq!(score, expected_score);
-    }
-
-    #[test]
-    fn scoring_test() {
-        expect_score(&hand!("2H 3H 4H 5H 6C"), HandKind::Straight, 200.0);
-        expect_score(&hand!("3D 3D 2C 2C"), HandKind::TwoPair, 60.0);
-        expect_score(&hand!("AS KS QS JS TS"), HandKind::StraightFlush, 1208.0);
-    }
-}
+use std::fmt;
+
+use approx::assert_relative_eq;
+use bitflags::bitflags;
+use itertools::Itertools;
+use rand::{Rng, RngCore};
+
+use crate::solver::boss::BossBlind;
+use crate::solver::cards::{Card, CardView, Edition, Enhancement, Hand, HandKind, Rank, Seal};
+use crate::solver::consumable::Consumable;
+use crate::solver::hand_levels::HandLevels;
+use crate::solver::joker::{copy, JokerSlot};
+use crate::solver::ruleset::Ruleset;
+use crate::solver::score::Score;
+use crate::{card, hand};
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct ScoringOptions: u32 {
+        /// Plasma deck: chips and mult are balanced to their mean before multiplying
+        const Plasma = 0b0001;
+        /// Splash: cards played without being part of the poker hand still score
+        const AllPlayedCardsScore = 0b0010;
+        /// Pareidolia: every card counts as a face card
+        const AllCardsAreFace = 0b0100;
+        /// This is the last hand of the round (for Dusk)
+        const FinalHand = 0b1000;
+        /// Observatory: planets held give x1.5 mult for their own hand
+        const Observatory = 0b1_0000;
+    }
+}
+
+/// Everything the scorer needs to know about a single hand being played.
+#[derive(Copy, Clone, Debug)]
+pub struct ScoringContext<'a> {
+    pub kind: HandKind,
+    /// The played cards that score, in the order they were played. Cards are
+    /// scored left to right in exactly this order, which matters whenever
+    /// flat and multiplicative mult are mixed.
+    pub played: &'a Hand,
+    /// Every card played, in the order it was played, including the ones that
+    /// aren't part of the poker hand. Those only score with
+    /// `ScoringOptions::AllPlayedCardsScore`, in their place among the rest.
+    pub all_played: &'a [Card],
+    /// The cards left in hand while this hand is played
+    pub held: &'a [Card],
+    pub options: ScoringOptions,
+    pub ruleset: &'a Ruleset,
+    /// The jokers in play, in slot order
+    pub jokers: &'a [JokerSlot],
+    /// The money held while this hand is played
+    pub money: i64,
+    /// The run's hand levels, which set the hand's base chips and mult
+    pub hand_levels: &'a HandLevels,
+    /// The consumables held while this hand is played
+    pub consumables: &'a [Consumable],
+    /// The Boss Blind this hand is played against, if it's one whose rules
+    /// change the score
+    pub boss: Option<BossBlind>,
+}
+
+impl<'a> ScoringContext<'a> {
+    pub fn new(kind: HandKind, played: &'a Hand) -> Self {
+        Self {
+            kind,
+            played,
+            all_played: &[],
+            held: &[],
+            options: ScoringOptions::empty(),
+            ruleset: &Ruleset::VANILLA,
+            jokers: &[],
+            money: 0,
+            hand_levels: &HandLevels::NEW,
+            consumables: &[],
+            boss: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_all_played(self, all_played: &'a [Card]) -> Self {
+        Self { all_played, ..self }
+    }
+
+    #[must_use]
+    pub fn with_held(self, held: &'a [Card]) -> Self {
+        Self { held, ..self }
+    }
+
+    #[must_use]
+    pub fn with_options(self, options: ScoringOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    #[must_use]
+    pub fn with_hand_levels(self, hand_levels: &'a HandLevels) -> Self {
+        Self {
+            hand_levels,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_consumables(self, consumables: &'a [Consumable]) -> Self {
+        Self {
+            consumables,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_ruleset(self, ruleset: &'a Ruleset) -> Self {
+        Self { ruleset, ..self }
+    }
+
+    #[must_use]
+    pub fn with_money(self, money: i64) -> Self {
+        Self { money, ..self }
+    }
+
+    #[must_use]
+    pub fn with_jokers(self, jokers: &'a [JokerSlot]) -> Self {
+        Self { jokers, ..self }
+    }
+
+    #[must_use]
+    pub fn with_boss(self, boss: Option<BossBlind>) -> Self {
+        Self { boss, ..self }
+    }
+
+    /// The hand's chips before anything is scored, at its current level.
+    pub fn base_chips(&self) -> u64 {
+        let chips = self
+            .ruleset
+            .hand_chips(self.kind, self.hand_levels.level(self.kind));
+        if self.boss.is_some_and(BossBlind::halves_base) {
+            chips.div_ceil(2)
+        } else {
+            chips
+        }
+    }
+
+    /// The hand's mult before anything is scored, at its current level.
+    pub fn base_mult(&self) -> f64 {
+        let mult = self
+            .ruleset
+            .hand_mult(self.kind, self.hand_levels.level(self.kind));
+        if self.boss.is_some_and(BossBlind::halves_base) {
+            (mult * 0.5 + 0.5).floor().max(1.0)
+        } else {
+            mult
+        }
+    }
+
+    /// Whether `card` is debuffed by the Boss Blind, so it doesn't score.
+    pub fn is_debuffed(&self, card: Card) -> bool {
+        self.boss
+            .is_some_and(|boss| boss.debuffs(card, self.is_face(card)))
+    }
+
+    /// Whether a card counts as a face card while this hand is played. Every
+    /// joker that cares about face cards should ask this rather than the card.
+    pub fn is_face(&self, card: Card) -> bool {
+        self.options.contains(ScoringOptions::AllCardsAreFace) || card.is_face()
+    }
+
+    /// The played cards that actually score, in the order they're scored.
+    /// With `ScoringOptions::AllPlayedCardsScore` that's every card played,
+    /// in the order they were played, if they've been given.
+    pub fn scored_cards(&self) -> impl Iterator<Item = Card> + '_ {
+        let cards = if self.options.contains(ScoringOptions::AllPlayedCardsScore)
+            && !self.all_played.is_empty()
+        {
+            self.all_played
+        } else {
+            self.played.view()
+        };
+        cards.iter().copied()
+    }
+
+    /// Whether the played hand contains the given kind, the way Balatro
+    /// checks it for jokers like "Jolly Joker": a full house contains a pair,
+    /// a straight flush contains a flush, and so on.
+    pub fn contains(&self, kind: HandKind) -> bool {
+        if kind == self.kind {
+            return true;
+        }
+
+        let mut ranks = [0_u8; 13];
+        for card in self.played.view() {
+            if !card.is_stone() {
+                ranks[card.rank as usize] += 1;
+            }
+        }
+        let largest_match = ranks.iter().copied().max().unwrap_or(0);
+        let matched_ranks = ranks.iter().filter(|count| **count >= 2).count();
+
+        match kind {
+            HandKind::HighCard => true,
+            HandKind::Pair => largest_match >= 2,
+            HandKind::TwoPair => matched_ranks >= 2,
+            HandKind::ThreeOfAKind => largest_match >= 3,
+            HandKind::FourOfAKind => largest_match >= 4,
+            HandKind::FiveOfAKind => largest_match >= 5,
+            HandKind::Straight => self.kind == HandKind::StraightFlush,
+            HandKind::Flush => matches!(
+                self.kind,
+                HandKind::StraightFlush | HandKind::FlushHouse | HandKind::FlushFive
+            ),
+            HandKind::FullHouse => self.kind == HandKind::FlushHouse,
+            HandKind::StraightFlush | HandKind::FlushHouse | HandKind::FlushFive => false,
+        }
+    }
+}
+
+/// How effects that only happen some of the time get resolved.
+#[derive(Default)]
+pub enum Randomness<'r> {
+    /// Replace every chance with its expected value, so scores are
+    /// deterministic. This is what analysis wants.
+    #[default]
+    Expected,
+    /// Roll every chance with the given RNG, like the game does
+    Sampled(&'r mut dyn RngCore),
+    /// Take whichever outcome of each chance the path says to, so that
+    /// scoring over and over can go through every combination of outcomes.
+    /// See `Scorer::score_outcomes`.
+    Enumerated(&'r mut ChancePath),
+}
+
+/// A path through the outcomes of the chances met while scoring a hand, for
+/// enumerating them all with `Randomness::Enumerated`.
+#[derive(Clone, Debug, Default)]
+pub struct ChancePath {
+    /// The outcome taken at each chance, as an index into `Chance::outcomes`
+    choices: Vec<usize>,
+    /// How many outcomes each of those chances has
+    arities: Vec<usize>,
+    /// How many chances have been met while scoring this time
+    depth: usize,
+    /// The probability of the outcomes taken while scoring this time
+    probability: f64,
+}
+
+impl ChancePath {
+    fn restart(&mut self) {
+        self.depth = 0;
+        self.probability = 1.0;
+    }
+
+    /// The outcome to take at the next chance, which has `arity` of them.
+    fn take(&mut self, arity: usize) -> usize {
+        if self.depth == self.choices.len() {
+            self.choices.push(0);
+            self.arities.push(arity);
+        }
+        self.depth += 1;
+        self.choices[self.depth - 1]
+    }
+
+    /// Move on to the next combination of outcomes, returning false once
+    /// they've all been taken. Scoring is deterministic given the outcomes,
+    /// so every path that shares a prefix meets the same chances along it.
+    fn advance(&mut self) -> bool {
+        self.choices.truncate(self.depth);
+        self.arities.truncate(self.depth);
+        while let (Some(choice), Some(arity)) = (self.choices.last_mut(), self.arities.last()) {
+            if *choice + 1 < *arity {
+                *choice += 1;
+                return true;
+            }
+            self.choices.pop();
+            self.arities.pop();
+        }
+        false
+    }
+}
+
+/// An effect that is decided at random when it's applied.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Chance {
+    /// +`mult` mult, with 1 in `odds` chance
+    Mult { mult: f64, odds: u32 },
+    /// x`xmult` mult, with 1 in `odds` chance
+    XMult { xmult: f64, odds: u32 },
+    /// `money` dollars, with 1 in `odds` chance
+    Money { money: u32, odds: u32 },
+    /// Anywhere from +`min` to +`max` mult, inclusive
+    MultRange { min: u32, max: u32 },
+}
+
+impl Chance {
+    /// The effect this chance has on average. Chips and mult are independent
+    /// of one another, so scoring with the expected effects gives the
+    /// expected score (up to flooring).
+    pub fn expected(self) -> Effect {
+        match self {
+            Self::Mult { mult, odds } => Effect::Mult(mult / f64::from(odds)),
+            Self::XMult { xmult, odds } => Effect::XMult(1.0 + (xmult - 1.0) / f64::from(odds)),
+            Self::Money { money, odds } => Effect::Money(f64::from(money) / f64::from(odds)),
+            Self::MultRange { min, max } => {
+                Effect::Mult(f64::midpoint(f64::from(min), f64::from(max)))
+            }
+        }
+    }
+
+    /// Everything this chance can do, with the probability of each. `None`
+    /// is nothing happening.
+    pub fn outcomes(self) -> Vec<(Option<Effect>, f64)> {
+        let hit_or_miss = |effect: Effect, odds: u32| {
+            let chance = 1.0 / f64::from(odds);
+            let mut outcomes = vec![(Some(effect), chance)];
+            if odds > 1 {
+                outcomes.push((None, 1.0 - chance));
+            }
+            outcomes
+        };
+        match self {
+            Self::Mult { mult, odds } => hit_or_miss(Effect::Mult(mult), odds),
+            Self::XMult { xmult, odds } => hit_or_miss(Effect::XMult(xmult), odds),
+            Self::Money { money, odds } => hit_or_miss(Effect::Money(f64::from(money)), odds),
+            Self::MultRange { min, max } => {
+                let chance = 1.0 / f64::from(max - min + 1);
+                (min..=max)
+                    .map(|mult| (Some(Effect::Mult(f64::from(mult))), chance))
+                    .collect()
+            }
+        }
+    }
+
+    /// Roll this chance, returning the effect if it happened.
+    pub fn sample(self, rng: &mut dyn RngCore) -> Option<Effect> {
+        match self {
+            Self::Mult { mult, odds } => rng.gen_ratio(1, odds).then_some(Effect::Mult(mult)),
+            Self::XMult { xmult, odds } => rng.gen_ratio(1, odds).then_some(Effect::XMult(xmult)),
+            Self::Money { money, odds } => rng
+                .gen_ratio(1, odds)
+                .then_some(Effect::Money(f64::from(money))),
+            Self::MultRange { min, max } => Some(Effect::Mult(f64::from(rng.gen_range(min..=max)))),
+        }
+    }
+}
+
+impl fmt::Display for Chance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mult { mult, odds } => write!(f, "1 in {odds}: +{mult} mult"),
+            Self::XMult { xmult, odds } => write!(f, "1 in {odds}: x{xmult} mult"),
+            Self::Money { money, odds } => write!(f, "1 in {odds}: +${money}"),
+            Self::MultRange { min, max } => write!(f, "+{min} to {max} mult"),
+        }
+    }
+}
+
+/// A single change to the running chips or mult.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Effect {
+    Chips(u64),
+    Mult(f64),
+    XMult(f64),
+    /// Set both chips and mult to the mean of the two, rounded down
+    Balance,
+    /// Money earned, which leaves chips and mult alone. Fractional when it's
+    /// the expected value of a `Chance`.
+    Money(f64),
+    /// Something that may or may not happen, resolved by the scorer according
+    /// to its `Randomness`
+    Chance(Chance),
+}
+
+impl Effect {
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn apply(self, chips: &mut f64, mult: &mut f64) {
+        match self {
+            Self::Chips(amount) => *chips += amount as f64,
+            Self::Mult(amount) => *mult += amount,
+            Self::XMult(amount) => *mult *= amount,
+            Self::Balance => {
+                let mean = f64::midpoint(*chips, *mult).floor();
+                *chips = mean;
+                *mult = mean;
+            }
+            Self::Money(_) => {}
+            Self::Chance(chance) => chance.expected().apply(chips, mult),
+        }
+    }
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Chips(amount) => write!(f, "+{amount} chips"),
+            Self::Mult(amount) => write!(f, "+{amount} mult"),
+            Self::XMult(amount) => write!(f, "x{amount} mult"),
+            Self::Balance => write!(f, "balanced"),
+            Self::Money(amount) => write!(f, "+${amount}"),
+            Self::Chance(chance) => write!(f, "{chance}"),
+        }
+    }
+}
+
+/// What caused an effect to be applied.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EffectSource {
+    Played(Card),
+    Held(Card),
+    /// The joker in this slot
+    Joker(usize),
+    /// The consumable in this slot
+    Consumable(usize),
+    Deck,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AppliedEffect {
+    pub source: EffectSource,
+    pub effect: Effect,
+}
+
+/// An applied effect along with the running chips and mult right after it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScoreStep {
+    pub applied: AppliedEffect,
+    /// Only fractional once Plasma deck has balanced it
+    pub chips: f64,
+    pub mult: f64,
+}
+
+/// A full account of how a hand arrived at its score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreBreakdown {
+    pub kind: HandKind,
+    /// The names of the jokers in play, in slot order
+    pub jokers: Vec<String>,
+    pub base_chips: u64,
+    pub base_mult: f64,
+    /// The chips each scored card contributed, including retriggers, in played order
+    pub card_chips: Vec<(Card, u64)>,
+    /// Every effect, in the order it was applied
+    pub effects: Vec<AppliedEffect>,
+    /// Only fractional once Plasma deck has balanced it
+    pub chips: f64,
+    pub mult: f64,
+    pub total: Score,
+    /// The money earned while scoring, e.g. from Lucky cards
+    pub money: f64,
+}
+
+impl ScoreBreakdown {
+    /// Replay the effects in the order they were applied, yielding the running
+    /// chips and mult after each one.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn steps(&self) -> impl Iterator<Item = ScoreStep> + '_ {
+        self.effects.iter().scan(
+            (self.base_chips as f64, self.base_mult),
+            |(chips, mult), applied| {
+                applied.effect.apply(chips, mult);
+                Some(ScoreStep {
+                    applied: *applied,
+                    chips: *chips,
+                    mult: *mult,
+                })
+            },
+        )
+    }
+}
+
+impl fmt::Display for ScoreBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:?}: {} chips x {} mult",
+            self.kind, self.base_chips, self.base_mult
+        )?;
+        for AppliedEffect { source, effect } in &self.effects {
+            match source {
+                EffectSource::Played(card) => writeln!(f, "  {card}: {effect}")?,
+                EffectSource::Held(card) => writeln!(f, "  {card} (held): {effect}")?,
+                EffectSource::Joker(slot) => writeln!(f, "  {}: {effect}", self.jokers[*slot])?,
+                EffectSource::Consumable(slot) => writeln!(f, "  consumable {slot}: {effect}")?,
+                EffectSource::Deck => writeln!(f, "  deck: {effect}")?,
+            }
+        }
+        write!(
+            f,
+            "= {} chips x {} mult = {}",
+            self.chips, self.mult, self.total
+        )
+    }
+}
+
+pub struct Scorer<'a, 'r> {
+    context: &'a ScoringContext<'a>,
+    randomness: Randomness<'r>,
+    chips: f64,
+    mult: f64,
+    money: f64,
+    /// Whether to keep track of the effects for a breakdown. Scoring is
+    /// on the hot path of every simulation, so this is off unless requested.
+    record: bool,
+    card_chips: Vec<(Card, u64)>,
+    effects: Vec<AppliedEffect>,
+    /// Scratch space the jokers push their effects onto
+    joker_effects: Vec<Effect>,
+    /// The slot whose hooks each joker slot runs, see `copy::resolve_copies`
+    copies: Vec<Option<usize>>,
+}
+
+impl<'a, 'r> Scorer<'a, 'r> {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(context: &'a ScoringContext<'a>, randomness: Randomness<'r>, record: bool) -> Self {
+        Self {
+            context,
+            randomness,
+            chips: context.base_chips() as f64,
+            mult: context.base_mult(),
+            money: 0.0,
+            record,
+            card_chips: Vec::new(),
+            effects: Vec::new(),
+            joker_effects: Vec::new(),
+            copies: copy::resolve_copies(context.jokers),
+        }
+    }
+
+    fn apply(&mut self, source: EffectSource, effect: Effect) {
+        let effect = match (effect, &mut self.randomness) {
+            (Effect::Chance(chance), Randomness::Expected) => chance.expected(),
+            (Effect::Chance(chance), Randomness::Sampled(rng)) => match chance.sample(*rng) {
+                Some(effect) => effect,
+                None => return,
+            },
+            (Effect::Chance(chance), Randomness::Enumerated(path)) => {
+                let outcomes = chance.outcomes();
+                let (effect, probability) = outcomes[path.take(outcomes.len())];
+                path.probability *= probability;
+                match effect {
+                    Some(effect) => effect,
+                    None => return,
+                }
+            }
+            (effect, _) => effect,
+        };
+        if let Effect::Money(amount) = effect {
+            self.money += amount;
+        }
+        effect.apply(&mut self.chips, &mut self.mult);
+        if self.record {
+            self.effects.push(AppliedEffect { source, effect });
+        }
+    }
+
+    /// Run a hook on the joker in a single slot, applying whatever effects it
+    /// produces. Copying jokers run the hook of the joker they copy.
+    fn trigger_joker<F>(&mut self, slot: usize, hook: &F)
+    where
+        F: Fn(&JokerSlot, &ScoringContext, &mut Vec<Effect>),
+    {
+        let Some(target) = self.copies[slot] else {
+            return;
+        };
+        let mut effects = std::mem::take(&mut self.joker_effects);
+        hook(&self.context.jokers[target], self.context, &mut effects);
+        for effect in effects.drain(..) {
+            self.apply(EffectSource::Joker(slot), effect);
+        }
+        self.joker_effects = effects;
+    }
+
+    /// Run a hook on every joker, in slot order.
+    fn trigger_jokers<F>(&mut self, hook: F)
+    where
+        F: Fn(&JokerSlot, &ScoringContext, &mut Vec<Effect>),
+    {
+        for slot in 0..self.context.jokers.len() {
+            self.trigger_joker(slot, &hook);
+        }
+    }
+
+    /// Apply a joker's own edition. Foil and Holographic apply before the
+    /// joker's effect, Polychrome after.
+    fn apply_joker_edition(&mut self, slot: usize, after: bool) {
+        if self.context.jokers[slot].is_debuffed() {
+            return;
+        }
+        let ruleset = self.context.ruleset;
+        let source = EffectSource::Joker(slot);
+        match (self.context.jokers[slot].edition, after) {
+            (Some(Edition::Foil), false) => self.apply(source, Effect::Chips(ruleset.foil_chips)),
+            (Some(Edition::Holographic), false) => {
+                self.apply(source, Effect::Mult(ruleset.holographic_mult));
+            }
+            (Some(Edition::Polychrome), true) => {
+                self.apply(source, Effect::XMult(ruleset.polychrome_xmult));
+            }
+            _ => {}
+        }
+    }
+
+    /// Apply a single scored card to the running chips and mult, in the same
+    /// order Balatro does: chips first, then flat mult, then multiplicative mult,
+    /// and finally the card's edition.
+    fn score_card(&mut self, card: Card) {
+        let source = EffectSource::Played(card);
+        let ruleset = self.context.ruleset;
+
+        // Stone cards have no rank, so they don't contribute rank chips
+        if card.is_stone() {
+            self.apply(source, Effect::Chips(ruleset.stone_chips));
+        } else {
+            self.apply(source, Effect::Chips(ruleset.chips_for_rank(card.rank)));
+        }
+
+        match card.enhancement {
+            Some(Enhancement::Bonus) => self.apply(source, Effect::Chips(ruleset.bonus_chips)),
+            Some(Enhancement::Mult) => self.apply(source, Effect::Mult(ruleset.mult_mult)),
+            Some(Enhancement::Glass) => self.apply(source, Effect::XMult(ruleset.glass_xmult)),
+            Some(Enhancement::Lucky) => {
+                self.apply(
+                    source,
+                    Effect::Chance(Chance::Mult {
+                        mult: ruleset.lucky_mult,
+                        odds: ruleset.lucky_mult_odds,
+                    }),
+                );
+                self.apply(
+                    source,
+                    Effect::Chance(Chance::Money {
+                        money: ruleset.lucky_money,
+                        odds: ruleset.lucky_money_odds,
+                    }),
+                );
+            }
+            // Steel and Gold only do anything while held in hand, and Wild
+            // only affects hand evaluation
+            Some(
+                Enhancement::Stone | Enhancement::Wild | Enhancement::Steel | Enhancement::Gold,
+            )
+            | None => {}
+        }
+
+        match card.edition {
+            Some(Edition::Foil) => self.apply(source, Effect::Chips(ruleset.foil_chips)),
+            Some(Edition::Holographic) => {
+                self.apply(source, Effect::Mult(ruleset.holographic_mult));
+            }
+            Some(Edition::Polychrome) => {
+                self.apply(source, Effect::XMult(ruleset.polychrome_xmult));
+            }
+            // Negative only matters for jokers and consumables
+            Some(Edition::Negative) | None => {}
+        }
+    }
+
+    /// How many extra times a card's effects are applied, on top of the first.
+    pub(crate) fn retrigger_count(card: Card) -> usize {
+        usize::from(card.seal == Some(Seal::Red))
+    }
+
+    /// How many extra times the scored card at `index` is applied: once for a
+    /// red seal, plus however many times the jokers retrigger it. `copies` is
+    /// the result of `copy::resolve_copies` on the context's jokers.
+    pub(crate) fn played_retrigger_count(
+        context: &ScoringContext,
+        copies: &[Option<usize>],
+        index: usize,
+        card: Card,
+    ) -> usize {
+        let from_jokers: usize = copies
+            .iter()
+            .flatten()
+            .map(|target| {
+                let slot = &context.jokers[*target];
+                slot.joker.retriggers(&slot.state, context, index, card)
+            })
+            .sum();
+        Self::retrigger_count(card) + from_jokers
+    }
+
+    /// Apply a single card held in hand to the running mult.
+    fn score_held_card(&mut self, card: Card) {
+        if card.enhancement == Some(Enhancement::Steel) {
+            let xmult = self.context.ruleset.steel_held_xmult;
+            self.apply(EffectSource::Held(card), Effect::XMult(xmult));
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn score(&mut self) {
+        self.trigger_jokers(|slot, context, effects| {
+            slot.joker.on_hand_scored(&slot.state, context, effects);
+        });
+        for (index, card) in self.context.scored_cards().enumerate() {
+            if self.context.is_debuffed(card) {
+                continue;
+            }
+            let chips_before = self.chips;
+            let retriggers = Self::played_retrigger_count(self.context, &self.copies, index, card);
+            for _ in 0..=retriggers {
+                self.score_card(card);
+                self.trigger_jokers(|slot, context, effects| {
+                    slot.joker
+                        .on_card_scored(&slot.state, context, card, effects);
+                });
+            }
+            if self.record {
+                // Nothing balances chips until every card has scored
+                self.card_chips
+                    .push((card, (self.chips - chips_before).round() as u64));
+            }
+        }
+        for card in self.context.held {
+            if self.context.is_debuffed(*card) {
+                continue;
+            }
+            for _ in 0..=Self::retrigger_count(*card) {
+                self.score_held_card(*card);
+                self.trigger_jokers(|slot, context, effects| {
+                    slot.joker
+                        .on_held_card(&slot.state, context, *card, effects);
+                });
+            }
+        }
+        for slot in 0..self.context.jokers.len() {
+            self.apply_joker_edition(slot, false);
+            self.trigger_joker(slot, &|slot, context, effects| {
+                slot.joker.on_independent(&slot.state, context, effects);
+            });
+            self.apply_joker_edition(slot, true);
+        }
+        if self.context.options.contains(ScoringOptions::Observatory) {
+            for (slot, consumable) in self.context.consumables.iter().enumerate() {
+                if *consumable == Consumable::Planet(self.context.kind) {
+                    self.apply(EffectSource::Consumable(slot), Effect::XMult(1.5));
+                }
+            }
+        }
+        if self.context.options.contains(ScoringOptions::Plasma) {
+            self.apply(EffectSource::Deck, Effect::Balance);
+        }
+    }
+
+    fn total(&self) -> Score {
+        Score::from_chips_and_mult(self.chips, self.mult)
+    }
+
+    /// Score a hand, using the expected value of anything random.
+    pub fn score_hand(context: &'a ScoringContext<'a>) -> Score {
+        Self::score_hand_with(context, Randomness::Expected)
+    }
+
+    pub fn score_hand_with(context: &'a ScoringContext<'a>, randomness: Randomness<'r>) -> Score {
+        let mut scorer = Self::new(context, randomness, false);
+        scorer.score();
+        scorer.total()
+    }
+
+    /// Every score a hand can get, with its probability, lowest first. The
+    /// scores come from going through every combination of the outcomes of
+    /// anything random, so this is `None` if there are more than `limit`
+    /// combinations. Only chances that change the score are gone through,
+    /// not ones that play out afterwards, like Glass cards breaking.
+    pub fn score_outcomes(
+        context: &'a ScoringContext<'a>,
+        limit: usize,
+    ) -> Option<Vec<(Score, f64)>> {
+        let mut path = ChancePath::default();
+        let mut outcomes = Vec::new();
+        loop {
+            if outcomes.len() == limit {
+                return None;
+            }
+            path.restart();
+            let score = Scorer::score_hand_with(context, Randomness::Enumerated(&mut path));
+            outcomes.push((score, path.probability));
+            if !path.advance() {
+                break;
+            }
+        }
+
+        outcomes.sort_by(|(a, _), (b, _)| a.value().total_cmp(&b.value()));
+        let outcomes = outcomes
+            .into_iter()
+            .coalesce(|(a, p), (b, q)| {
+                if a == b {
+                    Ok((a, p + q))
+                } else {
+                    Err(((a, p), (b, q)))
+                }
+            })
+            .collect();
+        Some(outcomes)
+    }
+
+    /// Score a hand, keeping track of every step along the way. Anything
+    /// random is scored at its expected value.
+    pub fn score_breakdown(context: &'a ScoringContext<'a>) -> ScoreBreakdown {
+        Self::score_breakdown_with(context, Randomness::Expected)
+    }
+
+    pub fn score_breakdown_with(
+        context: &'a ScoringContext<'a>,
+        randomness: Randomness<'r>,
+    ) -> ScoreBreakdown {
+        let mut scorer = Self::new(context, randomness, true);
+        scorer.score();
+        ScoreBreakdown {
+            kind: context.kind,
+            jokers: context
+                .jokers
+                .iter()
+                .map(|slot| slot.name().to_owned())
+                .collect(),
+            base_chips: context.base_chips(),
+            base_mult: context.base_mult(),
+            total: scorer.total(),
+            chips: scorer.chips,
+            mult: scorer.mult,
+            money: scorer.money,
+            card_chips: scorer.card_chips,
+            effects: scorer.effects,
+        }
+    }
+
+    /// The money paid out at the end of the round by the cards held in hand.
+    /// Red seals pay a Gold card's money out again.
+    pub fn end_of_round_money(held: &[Card], ruleset: &Ruleset) -> u32 {
+        let payouts: usize = held
+            .iter()
+            .filter(|card| card.enhancement == Some(Enhancement::Gold))
+            .map(|card| 1 + Self::retrigger_count(*card))
+            .sum();
+        ruleset.gold_held_money * u32::try_from(payouts).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expect_score(hand: &Hand, kind: HandKind, expected_score: f64) {
+        let score = Scorer::score_hand(&ScoringContext::new(kind, hand));
+        assert_relative_eq!(score.value(), expected_score);
+    }
+
+    fn enhanced(hand: &Hand, enhancements: &[Option<Enhancement>]) -> Hand {
+        hand.view()
+            .iter()
+            .zip(enhancements)
+            .map(|(card, enhancement)| Card {
+                enhancement: *enhancement,
+                ..*card
+            })
+            .collect()
+    }
+
+    fn with_editions(hand: &Hand, editions: &[Option<Edition>]) -> Hand {
+        hand.view()
+            .iter()
+            .zip(editions)
+            .map(|(card, edition)| Card {
+                edition: *edition,
+                ..*card
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scoring_test() {
+        expect_score(&hand!("2H 3H 4H 5H 6C"), HandKind::Straight, 200.0);
+        expect_score(&hand!("3D 3D 2C 2C"), HandKind::TwoPair, 60.0);
+        expect_score(&hand!("AS KS QS JS TS"), HandKind::StraightFlush, 1208.0);
+    }
+
+    #[test]
+    fn enhancement_scoring_test() {
+        use Enhancement::{Bonus, Glass, Gold, Lucky, Mult, Steel, Stone};
+
+        let pair = hand!("KH KS");
+        expect_score(
+            &enhanced(&pair, &[Some(Bonus), None]),
+            HandKind::Pair,
+            120.0,
+        );
+        expect_score(&enhanced(&pair, &[Some(Mult), None]), HandKind::Pair, 180.0);
+        expect_score(
+            &enhanced(&pair, &[Some(Glass), None]),
+            HandKind::Pair,
+            120.0,
+        );
+        expect_score(
+            &enhanced(&pair, &[Some(Glass), Some(Glass)]),
+            HandKind::Pair,
+            240.0,
+        );
+        expect_score(
+            &enhanced(&pair, &[Some(Lucky), None]),
+            HandKind::Pair,
+            180.0,
+        );
+        // Steel and gold do nothing when played
+        expect_score(
+            &enhanced(&pair, &[Some(Steel), Some(Gold)]),
+            HandKind::Pair,
+            60.0,
+        );
+        // Stone cards score a flat 50 chips instead of their rank
+        expect_score(
+            &enhanced(&hand!("AS"), &[Some(Stone)]),
+            HandKind::HighCard,
+            55.0,
+        );
+    }
+
+    #[test]
+    fn enhancement_order_test() {
+        use Enhancement::{Glass, Mult};
+
+        let pair = hand!("KH KS");
+        // (2 + 4) * 2
+        expect_score(
+            &enhanced(&pair, &[Some(Mult), Some(Glass)]),
+            HandKind::Pair,
+            360.0,
+        );
+        // (2 * 2) + 4
+        expect_score(
+            &enhanced(&pair, &[Some(Glass), Some(Mult)]),
+            HandKind::Pair,
+            240.0,
+        );
+    }
+
+    #[test]
+    fn edition_scoring_test() {
+        use Edition::{Foil, Holographic, Negative, Polychrome};
+
+        let pair = hand!("KH KS");
+        expect_score(
+            &with_editions(&pair, &[Some(Foil), None]),
+            HandKind::Pair,
+            160.0,
+        );
+        expect_score(
+            &with_editions(&pair, &[Some(Holographic), None]),
+            HandKind::Pair,
+            360.0,
+        );
+        expect_score(
+            &with_editions(&pair, &[Some(Polychrome), None]),
+            HandKind::Pair,
+            90.0,
+        );
+        expect_score(
+            &with_editions(&pair, &[Some(Negative), None]),
+            HandKind::Pair,
+            60.0,
+        );
+    }
+
+    #[test]
+    fn edition_order_test() {
+        let pair = hand!("KH KS");
+
+        // (2 * 1.5) + 4
+        let hand: Hand = [
+            pair.view()[0].with_edition(Edition::Polychrome),
+            pair.view()[1].with_enhancement(Enhancement::Mult),
+        ]
+        .into_iter()
+        .collect();
+        expect_score(&hand, HandKind::Pair, 210.0);
+
+        // (2 + 4) * 1.5
+        let hand: Hand = [
+            pair.view()[0].with_enhancement(Enhancement::Mult),
+            pair.view()[1].with_edition(Edition::Polychrome),
+        ]
+        .into_iter()
+        .collect();
+        expect_score(&hand, HandKind::Pair, 270.0);
+
+        // A card's edition applies after its own enhancement: (2 * 2) + 10
+        let hand: Hand = [
+            pair.view()[0]
+                .with_enhancement(Enhancement::Glass)
+                .with_edition(Edition::Holographic),
+            pair.view()[1],
+        ]
+        .into_iter()
+        .collect();
+        expect_score(&hand, HandKind::Pair, 420.0);
+    }
+
+    #[test]
+    fn held_card_scoring_test() {
+        let pair = hand!("KH KS");
+        let held = [
+            card!("2C").with_enhancement(Enhancement::Steel),
+            card!("3C").with_enhancement(Enhancement::Steel),
+            card!("4C").with_enhancement(Enhancement::Gold),
+            card!("5C"),
+        ];
+        let context = ScoringContext::new(HandKind::Pair, &pair).with_held(&held);
+        // 30 * (2 * 1.5 * 1.5)
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 135.0);
+        assert_eq!(Scorer::end_of_round_money(&held, &Ruleset::VANILLA), 3);
+        assert_eq!(Scorer::end_of_round_money(&[], &Ruleset::VANILLA), 0);
+        let red_seal = [card!("4C")
+            .with_enhancement(Enhancement::Gold)
+            .with_seal(Seal::Red)];
+        assert_eq!(Scorer::end_of_round_money(&red_seal, &Ruleset::VANILLA), 6);
+    }
+
+    #[test]
+    fn retrigger_scoring_test() {
+        // 10 + (10 * 2) + 10
+        let hand: Hand = [card!("KH").with_seal(Seal::Red), card!("KS")]
+            .into_iter()
+            .collect();
+        expect_score(&hand, HandKind::Pair, 80.0);
+
+        // Retriggers repeat the card's enhancement and edition too
+        let hand: Hand = [
+            card!("KH")
+                .with_seal(Seal::Red)
+                .with_enhancement(Enhancement::Glass)
+                .with_edition(Edition::Foil),
+            card!("KS"),
+        ]
+        .into_iter()
+        .collect();
+        // (10 + (10 + 50) * 2 + 10) * (2 * 2 * 2)
+        expect_score(&hand, HandKind::Pair, 1120.0);
+
+        // Held cards get retriggered as well
+        let pair = hand!("KH KS");
+        let held = [card!("2C")
+            .with_enhancement(Enhancement::Steel)
+            .with_seal(Seal::Red)];
+        let context = ScoringContext::new(HandKind::Pair, &pair).with_held(&held);
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 135.0);
+
+        // Other seals don't retrigger
+        let hand: Hand = [card!("KH").with_seal(Seal::Gold), card!("KS")]
+            .into_iter()
+            .collect();
+        expect_score(&hand, HandKind::Pair, 60.0);
+    }
+
+    #[test]
+    fn breakdown_test() {
+        let hand: Hand = [
+            card!("KH").with_enhancement(Enhancement::Bonus),
+            card!("KS").with_seal(Seal::Red),
+        ]
+        .into_iter()
+        .collect();
+        let held = [card!("2C").with_enhancement(Enhancement::Steel)];
+        let context = ScoringContext::new(HandKind::Pair, &hand).with_held(&held);
+        let breakdown = Scorer::score_breakdown(&context);
+
+        assert_eq!(breakdown.base_chips, 10);
+        assert_relative_eq!(breakdown.base_mult, 2.0);
+        assert_eq!(
+            breakdown.card_chips,
+            vec![(hand.view()[0], 40), (hand.view()[1], 20)]
+        );
+        assert_eq!(
+            breakdown.effects,
+            vec![
+                AppliedEffect {
+                    source: EffectSource::Played(hand.view()[0]),
+                    effect: Effect::Chips(10),
+                },
+                AppliedEffect {
+                    source: EffectSource::Played(hand.view()[0]),
+                    effect: Effect::Chips(30),
+                },
+                AppliedEffect {
+                    source: EffectSource::Played(hand.view()[1]),
+                    effect: Effect::Chips(10),
+                },
+                AppliedEffect {
+                    source: EffectSource::Played(hand.view()[1]),
+                    effect: Effect::Chips(10),
+                },
+                AppliedEffect {
+                    source: EffectSource::Held(held[0]),
+                    effect: Effect::XMult(1.5),
+                },
+            ]
+        );
+        assert_relative_eq!(breakdown.chips, 70.0);
+        assert_relative_eq!(breakdown.mult, 3.0);
+        assert_relative_eq!(breakdown.total.value(), 210.0);
+        assert_eq!(breakdown.total, Scorer::score_hand(&context));
+    }
+
+    #[test]
+    fn breakdown_display_test() {
+        let hand = hand!("AS KS QS JS TS");
+        let context = ScoringContext::new(HandKind::StraightFlush, &hand);
+        assert_eq!(
+            Scorer::score_breakdown(&context).to_string(),
+            "StraightFlush: 100 chips x 8 mult\n\
+             \x20 AS: +11 chips\n\
+             \x20 KS: +10 chips\n\
+             \x20 QS: +10 chips\n\
+             \x20 JS: +10 chips\n\
+             \x20 TS: +10 chips\n\
+             = 151 chips x 8 mult = 1208"
+        );
+    }
+
+    #[test]
+    fn plasma_test() {
+        let hand = hand!("AS KS QS JS TS");
+        let context = ScoringContext::new(HandKind::StraightFlush, &hand)
+            .with_options(ScoringOptions::Plasma);
+        // (151 + 8) / 2 = 79.5, rounded down to 79, and 79 x 79 = 6241
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 6241.0);
+
+        let breakdown = Scorer::score_breakdown(&context);
+        assert_relative_eq!(breakdown.chips, 79.0);
+        assert_relative_eq!(breakdown.mult, 79.0);
+        assert_eq!(
+            breakdown.effects.last(),
+            Some(&AppliedEffect {
+                source: EffectSource::Deck,
+                effect: Effect::Balance,
+            })
+        );
+    }
+
+    #[test]
+    fn boss_test() {
+        let hand = hand!("AC AH");
+        let context = ScoringContext::new(HandKind::Pair, &hand);
+        // (10 + 11 + 11) x 2
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 64.0);
+        // The Club's debuffed ace still makes the pair, but doesn't score
+        let club = context.with_boss(Some(BossBlind::Club));
+        assert_relative_eq!(Scorer::score_hand(&club).value(), 42.0);
+        // The Flint halves the pair's 10 chips and 2 mult
+        let flint = context.with_boss(Some(BossBlind::Flint));
+        assert_relative_eq!(Scorer::score_hand(&flint).value(), 27.0);
+        // Pareidolia makes the aces face cards, so The Plant debuffs both
+        let plant = context.with_boss(Some(BossBlind::Plant));
+        assert_relative_eq!(Scorer::score_hand(&plant).value(), 64.0);
+        let pareidolia = plant.with_options(ScoringOptions::AllCardsAreFace);
+        assert_relative_eq!(Scorer::score_hand(&pareidolia).value(), 20.0);
+    }
+
+    #[test]
+    fn played_order_steps_test() {
+        let polychrome = card!("KH").with_edition(Edition::Polychrome);
+        let mult = card!("KS").with_enhancement(Enhancement::Mult);
+
+        let steps_for = |hand: &Hand| {
+            let context = ScoringContext::new(HandKind::Pair, hand);
+            let breakdown = Scorer::score_breakdown(&context);
+            let steps: Vec<_> = breakdown
+                .steps()
+                .map(|step| (step.applied.source, step.chips, step.mult))
+                .collect();
+            // The replayed steps must land on the scorer's own result
+            let last = breakdown.steps().last().unwrap();
+            assert_relative_eq!(last.chips, breakdown.chips);
+            assert_relative_eq!(last.mult, breakdown.mult);
+            steps
+        };
+
+        let hand: Hand = [polychrome, mult].into_iter().collect();
+        assert_eq!(
+            steps_for(&hand),
+            vec![
+                (EffectSource::Played(polychrome), 20.0, 2.0),
+                (EffectSource::Played(polychrome), 20.0, 3.0),
+                (EffectSource::Played(mult), 30.0, 3.0),
+                (EffectSource::Played(mult), 30.0, 7.0),
+            ]
+        );
+
+        let hand: Hand = [mult, polychrome].into_iter().collect();
+        assert_eq!(
+            steps_for(&hand),
+            vec![
+                (EffectSource::Played(mult), 20.0, 2.0),
+                (EffectSource::Played(mult), 20.0, 6.0),
+                (EffectSource::Played(polychrome), 30.0, 6.0),
+                (EffectSource::Played(polychrome), 30.0, 9.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_ruleset_test() {
+        let mut ruleset = Ruleset::default();
+        ruleset.hand_base_mult[HandKind::Pair as usize] = 3.0;
+        ruleset.rank_chips[Rank::King as usize] = 20;
+        ruleset.glass_xmult = 3.0;
+
+        let hand: Hand = [
+            card!("KH").with_enhancement(Enhancement::Glass),
+            card!("KS"),
+        ]
+        .into_iter()
+        .collect();
+        let context = ScoringContext::new(HandKind::Pair, &hand).with_ruleset(&ruleset);
+        // (10 + 20 + 20) * (3 * 3)
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 450.0);
+
+        // Vanilla is unaffected
+        expect_score(&hand, HandKind::Pair, 120.0);
+    }
+
+    #[test]
+    fn contains_test() {
+        let full_house = hand!("KD KS 2C 2D 2H");
+        let context = ScoringContext::new(HandKind::FullHouse, &full_house);
+        assert!(context.contains(HandKind::Pair));
+        assert!(context.contains(HandKind::TwoPair));
+        assert!(context.contains(HandKind::ThreeOfAKind));
+        assert!(!context.contains(HandKind::FourOfAKind));
+        assert!(!context.contains(HandKind::Flush));
+
+        let flush = hand!("AS AS 3S 5S 7S");
+        let context = ScoringContext::new(HandKind::Flush, &flush);
+        assert!(context.contains(HandKind::Pair));
+        assert!(!context.contains(HandKind::Straight));
+
+        let straight_flush = hand!("9S 8S 7S 6S 5S");
+        let context = ScoringContext::new(HandKind::StraightFlush, &straight_flush);
+        assert!(context.contains(HandKind::Straight));
+        assert!(context.contains(HandKind::Flush));
+        assert!(!context.contains(HandKind::Pair));
+    }
+
+    #[test]
+    fn randomness_test() {
+        use rand::rngs::SmallRng;
+        use rand::SeedableRng;
+
+        let hand: Hand = [
+            card!("KH").with_enhancement(Enhancement::Lucky),
+            card!("KS"),
+        ]
+        .into_iter()
+        .collect();
+        let context = ScoringContext::new(HandKind::Pair, &hand);
+
+        let expected = Scorer::score_breakdown(&context);
+        assert_relative_eq!(expected.total.value(), 180.0);
+        assert_relative_eq!(expected.money, 20.0 / 15.0);
+
+        // Every sample either hits the +20 mult or misses it entirely
+        let mut rng = SmallRng::seed_from_u64(0);
+        let samples = 10_000;
+        let mut total = 0.0;
+        for _ in 0..samples {
+            let breakdown = Scorer::score_breakdown_with(&context, Randomness::Sampled(&mut rng));
+            assert!([60.0, 660.0].contains(&breakdown.total.value()));
+            assert!([0.0, 20.0].contains(&breakdown.money));
+            total += breakdown.total.value();
+        }
+        assert_relative_eq!(total / f64::from(samples), 180.0, max_relative = 0.05);
+
+        // The money doesn't change the score, so its outcomes are merged
+        let outcomes = Scorer::score_outcomes(&context, 4).unwrap();
+        assert_eq!(outcomes.len(), 2);
+        assert_relative_eq!(outcomes[0].0.value(), 60.0);
+        assert_relative_eq!(outcomes[0].1, 0.8);
+        assert_relative_eq!(outcomes[1].0.value(), 660.0);
+        assert_relative_eq!(outcomes[1].1, 0.2);
+        // ...but both chances still have to be gone through
+        assert_eq!(Scorer::score_outcomes(&context, 3), None);
+
+        let hand = hand!("KH KS");
+        let context = ScoringContext::new(HandKind::Pair, &hand);
+        assert_eq!(
+            Scorer::score_outcomes(&context, 1),
+            Some(vec![(Score::from(60.0), 1.0)])
+        );
+    }
+
+    #[test]
+    fn all_played_cards_score_test() {
+        let hand = hand!("KH KS");
+        let kicker = card!("5C").with_enhancement(Enhancement::Glass);
+        let played = [card!("KH"), kicker, card!("KS")];
+        let context = ScoringContext::new(HandKind::Pair, &hand).with_all_played(&played);
+        // Kickers don't score by default
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 60.0);
+
+        // With Splash, the 5 adds its chips and its Glass x2 mult, scored
+        // between the kings where it was played
+        let context = context.with_options(ScoringOptions::AllPlayedCardsScore);
+        assert_relative_eq!(Scorer::score_hand(&context).value(), 140.0);
+        let breakdown = Scorer::score_breakdown(&context);
+        assert_eq!(
+            breakdown.card_chips,
+            vec![(card!("KH"), 10), (kicker, 5), (card!("KS"), 10)]
+        );
+    }
+}
@@ -0,0 +1,755 @@
+//! The shop between blinds: what's for sale, what it costs, and what buying
+//! it does to the run.
+
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::{Rng, RngCore};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::solver::cards::{Card, Edition, Enhancement, HandKind, Rank, Seal, Suit};
+use crate::solver::consumable::{Consumable, Tarot};
+use crate::solver::error::{Error, Result};
+use crate::solver::game_state::GameState;
+use crate::solver::joker::registry::JokerId;
+use crate::solver::joker::{JokerSlot, Rarity, Stickers};
+use crate::solver::tag::Tag;
+use crate::solver::voucher::Voucher;
+
+/// Rerolling starts at this much each shop, and goes up by $1 every time.
+const BASE_REROLL_COST: u32 = 5;
+
+/// How often a shop card slot holds each kind of item, relative to the others.
+const JOKER_WEIGHT: f64 = 20.0;
+const CONSUMABLE_WEIGHT: f64 = 4.0;
+const CARD_WEIGHT: f64 = 4.0;
+
+/// Something that can be picked out of a card slot or a booster pack.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShopItem {
+    Joker {
+        id: JokerId,
+        edition: Option<Edition>,
+        stickers: Stickers,
+    },
+    Consumable(Consumable),
+    /// A playing card, only found in Standard packs
+    Card(Card),
+}
+
+impl ShopItem {
+    /// What this costs in a shop card slot, before discounts. Items in packs
+    /// are free.
+    pub fn cost(self) -> u32 {
+        match self {
+            Self::Joker { stickers, .. } if stickers.contains(Stickers::Rental) => 1,
+            Self::Joker { id, edition, .. } => id.buy_cost(edition),
+            Self::Consumable(_) => 3,
+            Self::Card(_) => 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, EnumIter)]
+pub enum PackKind {
+    /// Tarot cards
+    Arcana,
+    /// Planet cards
+    Celestial,
+    /// Jokers
+    Buffoon,
+    /// Playing cards
+    Standard,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, EnumIter)]
+pub enum PackSize {
+    Normal,
+    Jumbo,
+    Mega,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BoosterPack {
+    pub kind: PackKind,
+    pub size: PackSize,
+}
+
+impl BoosterPack {
+    /// The price in the shop, before discounts.
+    pub fn cost(self) -> u32 {
+        match self.size {
+            PackSize::Normal => 4,
+            PackSize::Jumbo => 6,
+            PackSize::Mega => 8,
+        }
+    }
+
+    /// How many items the pack shows when opened.
+    pub fn items(self) -> usize {
+        let normal = if self.kind == PackKind::Buffoon { 2 } else { 3 };
+        match self.size {
+            PackSize::Normal => normal,
+            PackSize::Jumbo | PackSize::Mega => normal + 2,
+        }
+    }
+
+    /// How many of the shown items can be taken.
+    pub fn picks(self) -> usize {
+        if self.size == PackSize::Mega {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// How likely this pack is to show up in a shop, relative to the others.
+    fn weight(self) -> f64 {
+        let size = match self.size {
+            PackSize::Normal => 1.0,
+            PackSize::Jumbo => 0.5,
+            PackSize::Mega => 0.125,
+        };
+        let kind = if self.kind == PackKind::Buffoon {
+            1.2
+        } else {
+            4.0
+        };
+        kind * size
+    }
+
+    fn all() -> impl Iterator<Item = Self> {
+        PackKind::iter().flat_map(|kind| PackSize::iter().map(move |size| Self { kind, size }))
+    }
+
+    pub(crate) fn open(self, state: &GameState, rng: &mut dyn RngCore) -> OpenPack {
+        let mut items: Vec<_> = (0..self.items())
+            .map(|_| match self.kind {
+                PackKind::Arcana => ShopItem::Consumable(Consumable::Tarot(random_tarot(rng))),
+                PackKind::Celestial => {
+                    ShopItem::Consumable(Consumable::Planet(random_planet(state, rng)))
+                }
+                PackKind::Buffoon => random_joker_item(state, rng),
+                PackKind::Standard => ShopItem::Card(random_card(rng)),
+            })
+            .collect();
+        if self.kind == PackKind::Celestial && state.has_voucher(Voucher::Telescope) {
+            if let Some(kind) = state.hand_levels.most_played() {
+                items[0] = ShopItem::Consumable(Consumable::Planet(kind));
+            }
+        }
+        OpenPack {
+            items,
+            picks: self.picks(),
+        }
+    }
+}
+
+/// A booster pack that's been bought and opened.
+///
+/// Planets picked from a pack are used on the spot, like in game. Tarots go
+/// to the consumable slots instead, since there's no hand to use them on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenPack {
+    /// The items that haven't been taken
+    pub items: Vec<ShopItem>,
+    /// How many more items can be taken
+    pub picks: usize,
+}
+
+impl OpenPack {
+    pub fn pick(&mut self, index: usize, state: &mut GameState) -> Result<()> {
+        if self.picks == 0 {
+            return Err(Error::NoPicksLeft);
+        }
+        let item = *self.items.get(index).ok_or(Error::ItemNotAvailable {
+            index,
+            len: self.items.len(),
+        })?;
+        match item {
+            ShopItem::Consumable(Consumable::Planet(kind)) => {
+                state.hand_levels.level_up(kind);
+                state.last_used = Some(Consumable::Planet(kind));
+            }
+            item => receive(item, state)?,
+        }
+        self.items.remove(index);
+        self.picks -= 1;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shop {
+    /// Jokers and consumables for sale; bought items are removed
+    pub cards: Vec<ShopItem>,
+    /// Booster packs for sale; bought packs are removed
+    pub packs: Vec<BoosterPack>,
+    /// The ante's voucher until it's bought, plus any from Voucher Tags
+    pub vouchers: Vec<Voucher>,
+    /// How many times this shop has been rerolled
+    pub rerolls: u32,
+    /// What the first reroll costs
+    pub base_reroll_cost: u32,
+    /// This many of the first card slots are free, thanks to tags
+    pub free_cards: usize,
+    /// Whether the packs are free, thanks to a Coupon Tag
+    pub free_packs: bool,
+}
+
+impl Shop {
+    /// Stock a fresh shop for `state`, with its number of card slots, two
+    /// booster packs, and the ante's voucher if it hasn't been bought.
+    pub fn generate(state: &GameState, rng: &mut dyn RngCore) -> Self {
+        let all_packs: Vec<_> = BoosterPack::all().collect();
+        let packs = (0..2)
+            .map(|_| {
+                *all_packs
+                    .choose_weighted(rng, |pack| pack.weight())
+                    .unwrap()
+            })
+            .collect();
+        let surplus = [Voucher::RerollSurplus, Voucher::RerollGlut]
+            .into_iter()
+            .filter(|voucher| state.has_voucher(*voucher))
+            .count();
+        Self {
+            cards: Self::stock_cards(state, rng),
+            packs,
+            vouchers: state.voucher_offer.into_iter().collect(),
+            rerolls: 0,
+            base_reroll_cost: BASE_REROLL_COST - 2 * u32::try_from(surplus).unwrap(),
+            free_cards: 0,
+            free_packs: false,
+        }
+    }
+
+    /// Stock a fresh shop like `generate`, then cash in every tag that
+    /// changes the shop. Tags that can't apply yet, like an edition tag with
+    /// no plain joker for sale, wait for a later shop.
+    pub fn enter(state: &mut GameState, rng: &mut dyn RngCore) -> Self {
+        let mut shop = Self::generate(state, rng);
+        let mut kept = Vec::new();
+        let mut coupon = false;
+        for tag in std::mem::take(&mut state.tags) {
+            let used = match tag {
+                Tag::Uncommon | Tag::Rare => {
+                    let rarity = if tag == Tag::Uncommon {
+                        Rarity::Uncommon
+                    } else {
+                        Rarity::Rare
+                    };
+                    let id = JokerId::iter()
+                        .filter(|id| id.rarity() == rarity)
+                        .choose(rng)
+                        .unwrap();
+                    let stickers = state.stake.roll_stickers(rng);
+                    shop.cards.insert(
+                        0,
+                        ShopItem::Joker {
+                            id,
+                            edition: None,
+                            stickers,
+                        },
+                    );
+                    shop.free_cards += 1;
+                    true
+                }
+                Tag::Foil | Tag::Holographic | Tag::Polychrome | Tag::Negative => {
+                    shop.apply_edition_tag(tag)
+                }
+                Tag::Voucher => {
+                    let offered = &shop.vouchers;
+                    if let Some(voucher) = Voucher::iter()
+                        .filter(|voucher| {
+                            !offered.contains(voucher) && state.check_voucher(*voucher).is_ok()
+                        })
+                        .choose(rng)
+                    {
+                        shop.vouchers.push(voucher);
+                    }
+                    true
+                }
+                Tag::Coupon => {
+                    coupon = true;
+                    true
+                }
+                Tag::D6 => {
+                    shop.base_reroll_cost = 0;
+                    true
+                }
+                _ => false,
+            };
+            if !used {
+                kept.push(tag);
+            }
+        }
+        if coupon {
+            shop.free_cards = shop.cards.len();
+            shop.free_packs = true;
+        }
+        state.tags = kept;
+        shop
+    }
+
+    /// Give the first plain joker for sale the tag's edition, and make it
+    /// free. Returns whether there was one.
+    fn apply_edition_tag(&mut self, tag: Tag) -> bool {
+        let edition = match tag {
+            Tag::Foil => Edition::Foil,
+            Tag::Holographic => Edition::Holographic,
+            Tag::Polychrome => Edition::Polychrome,
+            _ => Edition::Negative,
+        };
+        let Some(index) = self
+            .cards
+            .iter()
+            .enumerate()
+            .skip(self.free_cards)
+            .position(|(_, item)| matches!(item, ShopItem::Joker { edition: None, .. }))
+        else {
+            return false;
+        };
+        let index = index + self.free_cards;
+        let mut item = self.cards.remove(index);
+        if let ShopItem::Joker {
+            edition: joker_edition,
+            ..
+        } = &mut item
+        {
+            *joker_edition = Some(edition);
+        }
+        self.cards.insert(self.free_cards, item);
+        self.free_cards += 1;
+        true
+    }
+
+    fn stock_cards(state: &GameState, rng: &mut dyn RngCore) -> Vec<ShopItem> {
+        let rate = |merchant, tycoon| {
+            if state.has_voucher(tycoon) {
+                8.0
+            } else if state.has_voucher(merchant) {
+                2.4
+            } else {
+                1.0
+            }
+        };
+        let tarot_weight = CONSUMABLE_WEIGHT * rate(Voucher::TarotMerchant, Voucher::TarotTycoon);
+        let planet_weight =
+            CONSUMABLE_WEIGHT * rate(Voucher::PlanetMerchant, Voucher::PlanetTycoon);
+        let card_weight = if state.has_voucher(Voucher::MagicTrick) {
+            CARD_WEIGHT
+        } else {
+            0.0
+        };
+
+        (0..state.shop_slots)
+            .map(|_| {
+                let mut roll =
+                    rng.gen_range(0.0..JOKER_WEIGHT + tarot_weight + planet_weight + card_weight);
+                if roll < JOKER_WEIGHT {
+                    return random_joker_item(state, rng);
+                }
+                roll -= JOKER_WEIGHT;
+                if roll < tarot_weight {
+                    return ShopItem::Consumable(Consumable::Tarot(random_tarot(rng)));
+                }
+                roll -= tarot_weight;
+                if roll < planet_weight {
+                    return ShopItem::Consumable(Consumable::Planet(random_planet(state, rng)));
+                }
+                if state.has_voucher(Voucher::Illusion) {
+                    ShopItem::Card(random_card(rng))
+                } else {
+                    ShopItem::Card(Card::new(
+                        Rank::iter().choose(rng).unwrap(),
+                        Suit::iter().choose(rng).unwrap(),
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    pub fn reroll_cost(&self) -> u32 {
+        self.base_reroll_cost + self.rerolls
+    }
+
+    /// Pay to replace the card slots with new ones. Packs aren't rerolled.
+    pub fn reroll(&mut self, state: &mut GameState, rng: &mut dyn RngCore) -> Result<()> {
+        pay(state, self.reroll_cost())?;
+        self.rerolls += 1;
+        self.cards = Self::stock_cards(state, rng);
+        self.free_cards = 0;
+        Ok(())
+    }
+
+    /// Buy the item in card slot `index`. Fails without spending anything if
+    /// it can't be afforded or there's nowhere to put it.
+    pub fn buy(&mut self, index: usize, state: &mut GameState) -> Result<()> {
+        let item = *self.cards.get(index).ok_or(Error::ItemNotAvailable {
+            index,
+            len: self.cards.len(),
+        })?;
+        check_room(item, state)?;
+        pay(state, self.price(index, state))?;
+        receive(item, state)?;
+        self.cards.remove(index);
+        if index < self.free_cards {
+            self.free_cards -= 1;
+        }
+        Ok(())
+    }
+
+    /// What the item in card slot `index` costs, after discounts and tags.
+    pub fn price(&self, index: usize, state: &GameState) -> u32 {
+        if index < self.free_cards {
+            0
+        } else {
+            self.cards
+                .get(index)
+                .map_or(0, |item| state.price(item.cost()))
+        }
+    }
+
+    /// What the booster pack in slot `index` costs, after discounts and tags.
+    pub fn pack_price(&self, index: usize, state: &GameState) -> u32 {
+        if self.free_packs {
+            0
+        } else {
+            self.packs
+                .get(index)
+                .map_or(0, |pack| state.price(pack.cost()))
+        }
+    }
+
+    /// Buy and redeem the voucher in voucher slot `index`.
+    pub fn buy_voucher(&mut self, index: usize, state: &mut GameState) -> Result<()> {
+        let voucher = *self.vouchers.get(index).ok_or(Error::ItemNotAvailable {
+            index,
+            len: self.vouchers.len(),
+        })?;
+        state.check_voucher(voucher)?;
+        pay(state, state.price(Voucher::COST))?;
+        state.redeem_voucher(voucher)?;
+        if state.voucher_offer == Some(voucher) {
+            state.voucher_offer = None;
+        }
+        self.vouchers.remove(index);
+        Ok(())
+    }
+
+    /// Buy and open the booster pack in pack slot `index`.
+    pub fn buy_pack(
+        &mut self,
+        index: usize,
+        state: &mut GameState,
+        rng: &mut dyn RngCore,
+    ) -> Result<OpenPack> {
+        let pack = *self.packs.get(index).ok_or(Error::ItemNotAvailable {
+            index,
+            len: self.packs.len(),
+        })?;
+        pay(state, self.pack_price(index, state))?;
+        self.packs.remove(index);
+        Ok(pack.open(state, rng))
+    }
+}
+
+fn pay(state: &mut GameState, cost: u32) -> Result<()> {
+    if state.money < i64::from(cost) {
+        return Err(Error::NotEnoughMoney {
+            cost,
+            money: state.money,
+        });
+    }
+    state.money -= i64::from(cost);
+    Ok(())
+}
+
+fn check_room(item: ShopItem, state: &GameState) -> Result<()> {
+    match item {
+        ShopItem::Joker { edition, .. } => {
+            if edition != Some(Edition::Negative) && state.free_joker_slots() == 0 {
+                return Err(Error::NoJokerSlots);
+            }
+        }
+        ShopItem::Consumable(_) => {
+            if state.consumables.len() >= state.consumable_slots {
+                return Err(Error::NoConsumableSlots);
+            }
+        }
+        ShopItem::Card(_) => {}
+    }
+    Ok(())
+}
+
+/// Put an item where it belongs in `state`.
+fn receive(item: ShopItem, state: &mut GameState) -> Result<()> {
+    check_room(item, state)?;
+    match item {
+        ShopItem::Joker {
+            id,
+            edition,
+            stickers,
+        } => {
+            let mut slot = JokerSlot::new(id.joker()).with_stickers(stickers);
+            slot.edition = edition;
+            state.jokers.push(slot);
+        }
+        ShopItem::Consumable(consumable) => state.add_consumable(consumable)?,
+        ShopItem::Card(card) => state.add_card(card),
+    }
+    Ok(())
+}
+
+/// A random non-legendary joker, weighted by rarity the way the shop is.
+pub(crate) fn random_joker(rng: &mut dyn RngCore) -> JokerId {
+    let rarity = match rng.gen_range(0..100) {
+        0..70 => Rarity::Common,
+        70..95 => Rarity::Uncommon,
+        _ => Rarity::Rare,
+    };
+    JokerId::iter()
+        .filter(|id| id.rarity() == rarity)
+        .choose(rng)
+        .unwrap()
+}
+
+/// A random joker that isn't already held, with the shop's edition odds.
+fn random_joker_item(state: &GameState, rng: &mut dyn RngCore) -> ShopItem {
+    let held: Vec<_> = state.jokers.iter().filter_map(JokerSlot::id).collect();
+    let id = (0..100)
+        .map(|_| random_joker(rng))
+        .find(|id| !held.contains(id))
+        .unwrap_or(JokerId::Joker);
+    let rate = if state.has_voucher(Voucher::GlowUp) {
+        4.0
+    } else if state.has_voucher(Voucher::Hone) {
+        2.0
+    } else {
+        1.0
+    };
+    // Negative jokers aren't made any more common by vouchers
+    let roll = rng.gen_range(0.0..1.0);
+    let edition = if roll < 0.003 {
+        Some(Edition::Negative)
+    } else if roll < 0.003 + 0.003 * rate {
+        Some(Edition::Polychrome)
+    } else if roll < 0.003 + 0.017 * rate {
+        Some(Edition::Holographic)
+    } else if roll < 0.003 + 0.037 * rate {
+        Some(Edition::Foil)
+    } else {
+        None
+    };
+    ShopItem::Joker {
+        id,
+        edition,
+        stickers: state.stake.roll_stickers(rng),
+    }
+}
+
+fn random_tarot(rng: &mut dyn RngCore) -> Tarot {
+    Tarot::iter().choose(rng).unwrap()
+}
+
+/// A random planet. The secret hands' planets only show up once the hand has
+/// been played.
+pub(crate) fn random_planet(state: &GameState, rng: &mut dyn RngCore) -> HandKind {
+    HandKind::iter()
+        .filter(|kind| *kind < HandKind::FiveOfAKind || state.hand_levels.times_played(*kind) > 0)
+        .choose(rng)
+        .unwrap()
+}
+
+/// A random playing card, as found in Standard packs.
+fn random_card(rng: &mut dyn RngCore) -> Card {
+    let mut card = Card::new(
+        Rank::iter().choose(rng).unwrap(),
+        Suit::iter().choose(rng).unwrap(),
+    );
+    if rng.gen_ratio(2, 5) {
+        card = card.with_enhancement(Enhancement::iter().choose(rng).unwrap());
+    }
+    if rng.gen_ratio(1, 5) {
+        card = card.with_seal(Seal::iter().choose(rng).unwrap());
+    }
+    card
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::joker::builtin;
+
+    fn state(money: i64) -> GameState {
+        let mut state = GameState::new();
+        state.money = money;
+        state
+    }
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let state = state(0);
+        for _ in 0..100 {
+            let shop = Shop::generate(&state, &mut rng);
+            assert_eq!(shop.cards.len(), 2);
+            assert_eq!(shop.packs.len(), 2);
+        }
+    }
+
+    #[test]
+    fn reroll_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = state(12);
+        let mut shop = Shop::generate(&state, &mut rng);
+        shop.reroll(&mut state, &mut rng).unwrap();
+        assert_eq!((state.money, shop.reroll_cost()), (7, 6));
+        shop.reroll(&mut state, &mut rng).unwrap();
+        assert_eq!((state.money, shop.reroll_cost()), (1, 7));
+        assert!(matches!(
+            shop.reroll(&mut state, &mut rng),
+            Err(Error::NotEnoughMoney { cost: 7, money: 1 })
+        ));
+    }
+
+    #[test]
+    fn buy_test() {
+        let mut state = state(10);
+        let mut shop = Shop {
+            cards: vec![
+                ShopItem::Joker {
+                    id: JokerId::Baron,
+                    edition: Some(Edition::Foil),
+                    stickers: Stickers::empty(),
+                },
+                ShopItem::Consumable(Consumable::Tarot(Tarot::TheHermit)),
+            ],
+            packs: Vec::new(),
+            vouchers: Vec::new(),
+            rerolls: 0,
+            base_reroll_cost: 5,
+            free_cards: 0,
+            free_packs: false,
+        };
+        shop.buy(0, &mut state).unwrap();
+        assert_eq!(state.money, 0);
+        assert_eq!(state.jokers[0].edition, Some(Edition::Foil));
+        assert_eq!(shop.cards.len(), 1);
+
+        state.money = 3;
+        state.consumable_slots = 0;
+        assert!(matches!(
+            shop.buy(0, &mut state),
+            Err(Error::NoConsumableSlots)
+        ));
+        assert_eq!(state.money, 3);
+    }
+
+    #[test]
+    fn full_joker_slots_test() {
+        let mut state = state(100);
+        for _ in 0..state.joker_slots {
+            state.add_joker(builtin::by_name("Joker").unwrap());
+        }
+        let mut shop = Shop {
+            cards: vec![
+                ShopItem::Joker {
+                    id: JokerId::Baron,
+                    edition: None,
+                    stickers: Stickers::empty(),
+                },
+                ShopItem::Joker {
+                    id: JokerId::Baron,
+                    edition: Some(Edition::Negative),
+                    stickers: Stickers::Rental,
+                },
+            ],
+            packs: Vec::new(),
+            vouchers: Vec::new(),
+            rerolls: 0,
+            base_reroll_cost: 5,
+            free_cards: 0,
+            free_packs: false,
+        };
+        assert!(matches!(shop.buy(0, &mut state), Err(Error::NoJokerSlots)));
+        // Negative jokers don't need a slot, and Rental ones cost $1
+        shop.buy(1, &mut state).unwrap();
+        assert_eq!(state.money, 99);
+        assert_eq!(state.jokers[5].stickers, Stickers::Rental);
+    }
+
+    #[test]
+    fn voucher_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = state(20);
+        state.voucher_offer = Some(Voucher::ClearanceSale);
+        let mut shop = Shop::generate(&state, &mut rng);
+        shop.buy_voucher(0, &mut state).unwrap();
+        assert_eq!(state.money, 10);
+        assert_eq!((shop.vouchers.len(), state.voucher_offer), (0, None));
+
+        // 25% off, rounding half up
+        assert_eq!(state.price(10), 7);
+        assert_eq!(state.price(5), 4);
+        assert_eq!(state.price(1), 1);
+
+        state.redeem_voucher(Voucher::RerollSurplus).unwrap();
+        state.redeem_voucher(Voucher::MagicTrick).unwrap();
+        state.redeem_voucher(Voucher::Overstock).unwrap();
+        let shop = Shop::generate(&state, &mut rng);
+        assert_eq!((shop.cards.len(), shop.reroll_cost()), (3, 3));
+    }
+
+    #[test]
+    fn tag_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = state(0);
+        state.tags = vec![Tag::Rare, Tag::Coupon, Tag::D6, Tag::Investment];
+        let mut shop = Shop::enter(&mut state, &mut rng);
+        assert_eq!(state.tags, [Tag::Investment]);
+        assert_eq!((shop.cards.len(), shop.free_cards), (3, 3));
+        assert_eq!(shop.reroll_cost(), 0);
+        assert_eq!(shop.pack_price(0, &state), 0);
+        let ShopItem::Joker { id, .. } = shop.cards[0] else {
+            panic!("Rare Tags add a joker");
+        };
+        assert_eq!(id.rarity(), Rarity::Rare);
+        shop.buy(0, &mut state).unwrap();
+        assert_eq!(shop.free_cards, 2);
+
+        shop.reroll(&mut state, &mut rng).unwrap();
+        assert_eq!(shop.free_cards, 0);
+    }
+
+    #[test]
+    fn pack_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = state(8);
+        let mut shop = Shop {
+            cards: Vec::new(),
+            packs: vec![BoosterPack {
+                kind: PackKind::Celestial,
+                size: PackSize::Mega,
+            }],
+            vouchers: Vec::new(),
+            rerolls: 0,
+            base_reroll_cost: 5,
+            free_cards: 0,
+            free_packs: false,
+        };
+        let mut pack = shop.buy_pack(0, &mut state, &mut rng).unwrap();
+        assert_eq!((state.money, pack.items.len(), pack.picks), (0, 5, 2));
+
+        let ShopItem::Consumable(Consumable::Planet(kind)) = pack.items[0] else {
+            panic!("Celestial packs only hold planets");
+        };
+        pack.pick(0, &mut state).unwrap();
+        assert!(state.hand_levels.level(kind) >= 2);
+        pack.pick(0, &mut state).unwrap();
+        assert!(matches!(pack.pick(0, &mut state), Err(Error::NoPicksLeft)));
+    }
+}
@@ -0,0 +1,246 @@
+//! Deciding what to buy: every option the shop offers, ranked by how much it
+//! improves the run's outlook over walking out with the money.
+
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+
+use crate::solver::error::Result;
+use crate::solver::game_state::GameState;
+use crate::solver::heuristic::{Heuristic, WeightedHeuristic};
+use crate::solver::rollout::win_probability_with;
+use crate::solver::shop::Shop;
+use crate::solver::strategy::greedy::Greedy;
+use crate::solver::strategy::Strategy;
+
+/// Something to do in the shop.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShopOption {
+    /// Buy the item in this card slot
+    Buy(usize),
+    /// Buy and open the booster pack in this pack slot
+    BuyPack(usize),
+    /// Buy the voucher in this voucher slot
+    BuyVoucher(usize),
+    /// Pay to replace the card slots
+    Reroll,
+    /// Buy nothing more
+    Leave,
+}
+
+/// How the run's outlook is judged after each option.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Valuation {
+    /// Score the state straight after the option
+    Heuristic(WeightedHeuristic),
+    /// The chance of clearing the next ante, over this many rollouts with
+    /// `Greedy`
+    Rollouts(usize),
+}
+
+/// An option, and how much better off it leaves the run than leaving does.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RankedOption {
+    pub option: ShopOption,
+    /// The option's value less the value of leaving, in the valuation's units
+    pub uplift: f64,
+}
+
+/// Ranks what can be done in a shop. Options that depend on chance, like
+/// opening a pack or rerolling, are averaged over a few samples drawn from a
+/// fixed seed, so the same shop always gets the same ranking. Packs are
+/// opened the way `Greedy` would, and a reroll is worth the best single
+/// purchase from the new card slots.
+#[derive(Clone, Debug)]
+pub struct ShopOptimizer {
+    pub valuation: Valuation,
+    /// How many ways to open each pack, or reroll the shop
+    pub samples: usize,
+}
+
+impl ShopOptimizer {
+    pub const DEFAULT_SAMPLES: usize = 8;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_valuation(self, valuation: Valuation) -> Self {
+        Self { valuation, ..self }
+    }
+
+    #[must_use]
+    pub fn with_samples(self, samples: usize) -> Self {
+        Self { samples, ..self }
+    }
+
+    /// Every option that can be afforded and has room, best first. Leaving
+    /// is always among them, with an uplift of zero. `state` is the state in
+    /// the shop, with the blind just beaten still current.
+    ///
+    /// Fails if a rollout does.
+    pub fn rank(&self, shop: &Shop, state: &GameState) -> Result<Vec<RankedOption>> {
+        let baseline = self.value(state)?;
+        let options = (0..shop.cards.len())
+            .map(ShopOption::Buy)
+            .chain((0..shop.packs.len()).map(ShopOption::BuyPack))
+            .chain((0..shop.vouchers.len()).map(ShopOption::BuyVoucher))
+            .chain([ShopOption::Reroll]);
+
+        let mut ranked = vec![RankedOption {
+            option: ShopOption::Leave,
+            uplift: 0.0,
+        }];
+        for option in options {
+            if let Some(value) = self.option_value(option, shop, state)? {
+                ranked.push(RankedOption {
+                    option,
+                    uplift: value - baseline,
+                });
+            }
+        }
+        // Stable, so ties keep leaving first
+        ranked.sort_by(|a, b| b.uplift.total_cmp(&a.uplift));
+        Ok(ranked)
+    }
+
+    /// The best option for the shop; leaving if nothing beats it.
+    pub fn best(&self, shop: &Shop, state: &GameState) -> Result<ShopOption> {
+        Ok(self.rank(shop, state)?[0].option)
+    }
+
+    /// Take the best option, over and over, until leaving is best or
+    /// something fails. Chance is decided by `rng`.
+    pub fn shop(&self, shop: &mut Shop, state: &mut GameState, rng: &mut dyn RngCore) {
+        while let Ok(option) = self.best(shop, state) {
+            if option == ShopOption::Leave || apply(option, shop, state, rng).is_none() {
+                return;
+            }
+        }
+    }
+
+    /// The value of taking `option`, or `None` if it can't be taken.
+    #[allow(clippy::cast_precision_loss)]
+    fn option_value(
+        &self,
+        option: ShopOption,
+        shop: &Shop,
+        state: &GameState,
+    ) -> Result<Option<f64>> {
+        let samples = match option {
+            ShopOption::BuyPack(_) | ShopOption::Reroll => self.samples.max(1),
+            _ => 1,
+        };
+        let mut total = 0.0;
+        for seed in 0..samples as u64 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let mut shop = shop.clone();
+            let mut state = state.clone();
+            if apply(option, &mut shop, &mut state, &mut rng).is_none() {
+                return Ok(None);
+            }
+            total += if option == ShopOption::Reroll {
+                self.best_purchase(&shop, &state)?
+            } else {
+                self.value(&state)?
+            };
+        }
+        Ok(Some(total / samples as f64))
+    }
+
+    /// The value of the best card slot purchase, or of buying nothing.
+    fn best_purchase(&self, shop: &Shop, state: &GameState) -> Result<f64> {
+        let mut best = self.value(state)?;
+        for index in 0..shop.cards.len() {
+            let mut shop = shop.clone();
+            let mut state = state.clone();
+            if shop.buy(index, &mut state).is_ok() {
+                best = best.max(self.value(&state)?);
+            }
+        }
+        Ok(best)
+    }
+
+    fn value(&self, state: &GameState) -> Result<f64> {
+        match &self.valuation {
+            Valuation::Heuristic(heuristic) => Ok(heuristic.evaluate(state)),
+            Valuation::Rollouts(samples) => {
+                // Rollouts start from the blind after the shop
+                let mut state = state.clone();
+                state.advance_blind();
+                Ok(win_probability_with(&Greedy::default(), &state, *samples)?.ante)
+            }
+        }
+    }
+}
+
+impl Default for ShopOptimizer {
+    fn default() -> Self {
+        Self {
+            valuation: Valuation::Heuristic(WeightedHeuristic::default()),
+            samples: Self::DEFAULT_SAMPLES,
+        }
+    }
+}
+
+/// Take `option`, opening any pack like `Greedy`. `None` if it can't be
+/// taken.
+fn apply(
+    option: ShopOption,
+    shop: &mut Shop,
+    state: &mut GameState,
+    rng: &mut dyn RngCore,
+) -> Option<()> {
+    match option {
+        ShopOption::Buy(index) => shop.buy(index, state).ok(),
+        ShopOption::BuyPack(index) => {
+            let mut pack = shop.buy_pack(index, state, rng).ok()?;
+            Greedy::default().open_pack(&mut pack, state, rng);
+            Some(())
+        }
+        ShopOption::BuyVoucher(index) => shop.buy_voucher(index, state).ok(),
+        ShopOption::Reroll => shop.reroll(state, rng).ok(),
+        ShopOption::Leave => Some(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::joker::registry::JokerId;
+    use crate::solver::joker::Stickers;
+    use crate::solver::shop::ShopItem;
+
+    #[test]
+    fn rank_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = GameState::fresh_run(&mut rng);
+        // Enough for one joker, and not for a reroll
+        state.money = i64::from(JokerId::Joker.buy_cost(None)) + 1;
+        let mut shop = Shop::generate(&state, &mut rng);
+        shop.packs.clear();
+        shop.vouchers.clear();
+        let joker = ShopItem::Joker {
+            id: JokerId::Joker,
+            edition: None,
+            stickers: Stickers::empty(),
+        };
+        shop.cards = vec![joker, joker];
+
+        let optimizer = ShopOptimizer::new();
+        let ranked = optimizer.rank(&shop, &state).unwrap();
+        assert!(matches!(ranked[0].option, ShopOption::Buy(_)));
+        assert!(ranked[0].uplift > 0.0);
+        assert!(ranked
+            .iter()
+            .any(|ranked| ranked.option == ShopOption::Leave && ranked.uplift == 0.0));
+
+        optimizer.shop(&mut shop, &mut state, &mut rng);
+        assert_eq!(state.jokers.len(), 1);
+        assert_eq!(shop.cards.len(), 1);
+
+        // With no money, there's nothing to do but leave
+        state.money = 0;
+        assert_eq!(optimizer.best(&shop, &state).unwrap(), ShopOption::Leave);
+    }
+}
@@ -0,0 +1,102 @@
+//! Stakes, the difficulty levels of a run. Each stake includes the rules of
+//! every stake below it.
+
+use rand::{Rng, RngCore};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::solver::blind::{self, Blind};
+use crate::solver::joker::Stickers;
+
+/// Green Stake's ante base chips, which scale faster than White's.
+const GREEN_BASE_CHIPS: [f64; 8] = [
+    300.0, 900.0, 2_600.0, 8_000.0, 20_000.0, 36_000.0, 60_000.0, 100_000.0,
+];
+
+/// Purple Stake's ante base chips, which scale faster again.
+const PURPLE_BASE_CHIPS: [f64; 8] = [
+    300.0, 1_000.0, 3_200.0, 9_000.0, 25_000.0, 60_000.0, 110_000.0, 200_000.0,
+];
+
+#[derive(
+    Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Display, EnumIter, EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Stake {
+    #[default]
+    White,
+    /// Small Blinds give no reward money
+    Red,
+    /// Blind requirements scale faster
+    Green,
+    /// Shop jokers can be Eternal
+    Black,
+    /// -1 discard every round
+    Blue,
+    /// Blind requirements scale faster again
+    Purple,
+    /// Shop jokers can be Perishable
+    Orange,
+    /// Shop jokers can be Rental
+    Gold,
+}
+
+impl Stake {
+    /// The base chip requirement of an ante at this stake.
+    pub fn ante_base_chips(self, ante: u32) -> f64 {
+        if self >= Self::Purple {
+            blind::scaled_base_chips(&PURPLE_BASE_CHIPS, ante)
+        } else if self >= Self::Green {
+            blind::scaled_base_chips(&GREEN_BASE_CHIPS, ante)
+        } else {
+            blind::ante_base_chips(ante)
+        }
+    }
+
+    /// The money paid out for beating `blind` at this stake.
+    pub fn reward(self, blind: Blind) -> u32 {
+        if self >= Self::Red && blind == Blind::Small {
+            0
+        } else {
+            blind.reward()
+        }
+    }
+
+    /// How many fewer discards there are each round.
+    pub fn discard_penalty(self) -> u32 {
+        u32::from(self >= Self::Blue)
+    }
+
+    /// Roll the stickers for a joker in the shop. Eternal and Perishable never
+    /// go together.
+    pub(crate) fn roll_stickers(self, rng: &mut dyn RngCore) -> Stickers {
+        let mut stickers = Stickers::empty();
+        let roll = rng.gen_range(0.0..1.0);
+        if self >= Self::Black && roll < 0.3 {
+            stickers |= Stickers::Eternal;
+        } else if self >= Self::Orange && roll < 0.6 {
+            stickers |= Stickers::Perishable;
+        }
+        if self >= Self::Gold && rng.gen_ratio(3, 10) {
+            stickers |= Stickers::Rental;
+        }
+        stickers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn scaling_test() {
+        assert_relative_eq!(Stake::Red.ante_base_chips(2), 800.0);
+        assert_relative_eq!(Stake::Green.ante_base_chips(2), 900.0);
+        assert_relative_eq!(Stake::Gold.ante_base_chips(8), 200_000.0);
+        assert_relative_eq!(Stake::White.ante_base_chips(0), 100.0);
+        assert_eq!(Stake::White.reward(Blind::Small), 3);
+        assert_eq!(Stake::Red.reward(Blind::Small), 0);
+        assert_eq!(Stake::Red.reward(Blind::Big), 4);
+    }
+}
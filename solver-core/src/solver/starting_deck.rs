@@ -0,0 +1,127 @@
+//! The decks a run can be started with, each with its own cards or bonuses.
+
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::solver::cards::{Card, CardView, Deck, Rank, Suit};
+use crate::solver::game_state::GameState;
+
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, Display, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum StartingDeck {
+    /// The standard 52 cards, with no bonuses
+    #[default]
+    Plain,
+    /// +1 discard every round
+    Red,
+    /// +1 hand every round
+    Blue,
+    /// Start with an extra $10
+    Yellow,
+    /// +1 joker slot, but -1 hand every round
+    Black,
+    /// +2 hand size, but -1 joker slot
+    Painted,
+    /// No Jacks, Queens or Kings
+    Abandoned,
+    /// 26 Spades and 26 Hearts
+    Checkered,
+    /// Every card's rank and suit is random
+    Erratic,
+}
+
+impl StartingDeck {
+    /// The playing cards the deck starts with.
+    pub fn cards(self, rng: &mut impl Rng) -> Deck {
+        let base = Deck::base_deck();
+        let cards = base.view().iter().copied();
+        match self {
+            Self::Abandoned => Deck::from_cards(cards.filter(|card| !card.is_face()).collect()),
+            Self::Checkered => Deck::from_cards(
+                cards
+                    .map(|card| {
+                        let suit = match card.suit {
+                            Suit::Spades | Suit::Clubs => Suit::Spades,
+                            Suit::Hearts | Suit::Diamonds => Suit::Hearts,
+                        };
+                        Card { suit, ..card }
+                    })
+                    .collect(),
+            ),
+            Self::Erratic => Deck::from_cards(
+                cards
+                    .map(|_| {
+                        Card::new(
+                            Rank::iter().choose(rng).unwrap(),
+                            Suit::iter().choose(rng).unwrap(),
+                        )
+                    })
+                    .collect(),
+            ),
+            _ => base,
+        }
+    }
+
+    /// Give `state` the deck's bonuses and penalties, other than its cards.
+    pub fn apply(self, state: &mut GameState) {
+        match self {
+            Self::Red => {
+                state.discards_per_round += 1;
+                state.discards += 1;
+            }
+            Self::Blue => {
+                state.hands_per_round += 1;
+                state.hands += 1;
+            }
+            Self::Yellow => state.money += 10,
+            Self::Black => {
+                state.joker_slots += 1;
+                state.hands_per_round -= 1;
+                state.hands -= 1;
+            }
+            Self::Painted => {
+                state.hand_size += 2;
+                state.joker_slots -= 1;
+            }
+            Self::Plain | Self::Abandoned | Self::Checkered | Self::Erratic => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn starting_deck_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let plain = GameState::fresh_run(&mut rng);
+
+        let red = GameState::fresh_run_with(StartingDeck::Red, &mut rng);
+        assert_eq!(red.discards_per_round, plain.discards_per_round + 1);
+        assert_eq!(red.discards, plain.discards + 1);
+
+        let abandoned = GameState::fresh_run_with(StartingDeck::Abandoned, &mut rng);
+        assert_eq!(abandoned.deck.view().len(), 40);
+        assert_eq!(abandoned.draw_pile.view().len(), 40);
+        assert!(abandoned.deck.view().iter().all(|card| !card.is_face()));
+
+        let checkered = StartingDeck::Checkered.cards(&mut rng);
+        assert_eq!(checkered.view().len(), 52);
+        assert_eq!(
+            checkered
+                .view()
+                .iter()
+                .filter(|card| card.suit == Suit::Spades)
+                .count(),
+            26
+        );
+
+        assert_eq!("painted".parse(), Ok(StartingDeck::Painted));
+    }
+}
@@ -0,0 +1,62 @@
+//! The decisions a run simulation leaves up to its player, and a few players
+//! to make them.
+
+pub mod flush_hunter;
+pub mod greedy;
+pub mod random;
+pub mod shopper;
+
+use rand::RngCore;
+
+use crate::solver::error::Result;
+use crate::solver::game_state::GameState;
+use crate::solver::round::{Action, Round};
+use crate::solver::scorer::Randomness;
+use crate::solver::shop::{OpenPack, Shop};
+use crate::solver::tag::Tag;
+
+/// Something that can play a run: picks what to do with each hand, and what
+/// to do between blinds.
+pub trait Strategy {
+    /// Choose the next play or discard in a round that isn't over yet.
+    fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action;
+
+    /// A consumable to use before the next action, by its index in
+    /// `GameState::consumables`, and the cards in hand to use it on. This is
+    /// asked again after each one's used, until it's `None`. By default
+    /// nothing is used.
+    fn use_consumable(
+        &mut self,
+        round: &Round,
+        rng: &mut dyn RngCore,
+    ) -> Option<(usize, Vec<usize>)> {
+        None
+    }
+
+    /// Whether to skip the upcoming Small or Big Blind for `tag`. By default
+    /// every blind is played.
+    fn skip_blind(&mut self, state: &GameState, tag: Tag, rng: &mut dyn RngCore) -> bool {
+        false
+    }
+
+    /// Take what's wanted from a pack opened by a tag or bought in the shop.
+    /// By default nothing is taken.
+    fn open_pack(&mut self, pack: &mut OpenPack, state: &mut GameState, rng: &mut dyn RngCore) {}
+
+    /// Shop between blinds, after the round's been cashed out. By default
+    /// nothing is bought.
+    fn visit_shop(&mut self, shop: &mut Shop, state: &mut GameState, rng: &mut dyn RngCore) {}
+}
+
+/// Let `strategy` use whatever consumables it wants, then take its next action.
+pub(crate) fn take_turn(
+    strategy: &mut impl Strategy,
+    round: &mut Round,
+    rng: &mut dyn RngCore,
+) -> Result<()> {
+    while let Some((index, selected)) = strategy.use_consumable(round, rng) {
+        round.state.use_consumable(index, &selected, rng)?;
+    }
+    let action = strategy.choose_action(round, rng);
+    round.apply(&action, Randomness::Sampled(rng))
+}
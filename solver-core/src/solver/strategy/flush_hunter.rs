@@ -0,0 +1,112 @@
+//! A player that only has eyes for flushes.
+
+use itertools::Itertools;
+use rand::RngCore;
+
+use crate::solver::game_state::GameState;
+use crate::solver::round::{Action, Round};
+use crate::solver::shop::{OpenPack, Shop};
+use crate::solver::strategy::greedy::Greedy;
+use crate::solver::strategy::Strategy;
+
+/// Discards the cards off its most common suit, lowest first, until it holds
+/// five of that suit or runs out of discards, then plays the highest-scoring
+/// hand. Everything else is left to `Greedy`.
+#[derive(Clone, Debug, Default)]
+pub struct FlushHunter {
+    pub greedy: Greedy,
+}
+
+impl Strategy for FlushHunter {
+    fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+        let hand = &round.state.hand;
+        let suit = hand
+            .iter()
+            .counts_by(|card| card.suit)
+            .into_iter()
+            .max_by_key(|(_, count)| *count);
+        if let Some((suit, count)) = suit {
+            if count < 5 && round.state.discards > 0 {
+                let discard = (0..hand.len())
+                    .filter(|index| hand[*index].suit != suit)
+                    .sorted_by_key(|index| hand[*index].rank)
+                    .take(5)
+                    .sorted()
+                    .collect_vec();
+                if !discard.is_empty() {
+                    return Action::Discard(discard);
+                }
+            }
+        }
+        self.greedy.choose_action(round, rng)
+    }
+
+    fn use_consumable(
+        &mut self,
+        round: &Round,
+        rng: &mut dyn RngCore,
+    ) -> Option<(usize, Vec<usize>)> {
+        self.greedy.use_consumable(round, rng)
+    }
+
+    fn open_pack(&mut self, pack: &mut OpenPack, state: &mut GameState, rng: &mut dyn RngCore) {
+        self.greedy.open_pack(pack, state, rng);
+    }
+
+    fn visit_shop(&mut self, shop: &mut Shop, state: &mut GameState, rng: &mut dyn RngCore) {
+        self.greedy.visit_shop(shop, state, rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardCollection, CardView, Deck};
+    use crate::solver::score::Score;
+
+    fn resumed(hand: &str, discards: u32) -> Round {
+        let state = GameState::from_parts(
+            Deck::base_deck(),
+            CardCollection::from_idents(hand).view().to_vec(),
+            4,
+            discards,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        );
+        Round::resume(state, Score::ZERO)
+    }
+
+    #[test]
+    fn flush_hunter_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut hunter = FlushHunter::default();
+
+        // Four hearts: throw away the rest, even the pair of kings
+        let round = resumed("2H KS 9H KC 4D JH 3H 8C", 3);
+        assert_eq!(
+            hunter.choose_action(&round, &mut rng),
+            Action::Discard(vec![1, 3, 4, 7])
+        );
+
+        // The flush is made, so play it
+        let round = resumed("2H KS 9H KC 5H JH 3H 8C", 3);
+        assert_eq!(
+            hunter.choose_action(&round, &mut rng),
+            Action::Play(vec![0, 2, 4, 5, 6])
+        );
+
+        // Without discards, the kings will have to do
+        let round = resumed("2H KS 9H KC 4D JH 3H 8C", 0);
+        assert_eq!(
+            hunter.choose_action(&round, &mut rng),
+            Action::Play(vec![1, 3])
+        );
+    }
+}
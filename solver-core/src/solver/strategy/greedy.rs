@@ -0,0 +1,103 @@
+//! The simplest sensible player: always go for the most points right now.
+
+use rand::RngCore;
+
+use crate::solver::advisor::Advisor;
+use crate::solver::consumable::Consumable;
+use crate::solver::game_state::GameState;
+use crate::solver::round::{Action, Round};
+use crate::solver::shop::{OpenPack, Shop, ShopItem};
+use crate::solver::strategy::Strategy;
+
+/// Plays the highest-scoring hand every time, and never discards or skips a
+/// blind. Between blinds it buys every joker it can afford and has room for,
+/// and every planet; it takes all it can from packs, and uses planets as soon
+/// as it has them.
+#[derive(Clone, Debug, Default)]
+pub struct Greedy {
+    /// Finds the highest-scoring hand
+    pub advisor: Advisor,
+}
+
+impl Strategy for Greedy {
+    fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+        let (play, _) = self.advisor.best_play(&round.state.hand, &round.state);
+        Action::Play(play)
+    }
+
+    fn use_consumable(
+        &mut self,
+        round: &Round,
+        rng: &mut dyn RngCore,
+    ) -> Option<(usize, Vec<usize>)> {
+        round
+            .state
+            .consumables
+            .iter()
+            .position(|consumable| matches!(consumable, Consumable::Planet(_)))
+            .map(|index| (index, Vec::new()))
+    }
+
+    fn open_pack(&mut self, pack: &mut OpenPack, state: &mut GameState, rng: &mut dyn RngCore) {
+        // Picking takes the item out of the pack, so the next one moves up
+        let mut index = 0;
+        while pack.picks > 0 && index < pack.items.len() {
+            if pack.pick(index, state).is_err() {
+                index += 1;
+            }
+        }
+    }
+
+    fn visit_shop(&mut self, shop: &mut Shop, state: &mut GameState, rng: &mut dyn RngCore) {
+        let mut index = 0;
+        while index < shop.cards.len() {
+            let wanted = matches!(
+                shop.cards[index],
+                ShopItem::Joker { .. } | ShopItem::Consumable(Consumable::Planet(_))
+            );
+            if !wanted || shop.buy(index, state).is_err() {
+                index += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::cards::HandKind;
+    use crate::solver::joker::registry::JokerId;
+    use crate::solver::joker::Stickers;
+    use crate::solver::run::RunSimulator;
+
+    #[test]
+    fn greedy_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = GameState::fresh_run(&mut rng);
+        state.money = 10;
+        let mut shop = Shop::generate(&state, &mut rng);
+        shop.cards = vec![
+            ShopItem::Consumable(Consumable::Planet(HandKind::Flush)),
+            ShopItem::Joker {
+                id: JokerId::Joker,
+                edition: None,
+                stickers: Stickers::empty(),
+            },
+        ];
+        Greedy::default().visit_shop(&mut shop, &mut state, &mut rng);
+        assert!(shop.cards.is_empty());
+        assert_eq!(state.jokers.len(), 1);
+        assert_eq!(state.consumables, vec![Consumable::Planet(HandKind::Flush)]);
+
+        // The first ante is easy enough to get through on raw scoring alone
+        let summary = RunSimulator::new(Greedy::default())
+            .with_winning_ante(1)
+            .run(GameState::fresh_run(&mut rng), &mut rng)
+            .unwrap();
+        assert!(summary.won);
+        assert_eq!(summary.discards_used, 0);
+    }
+}
@@ -0,0 +1,119 @@
+//! A player that does whatever, as a baseline for the others.
+
+use rand::seq::index;
+use rand::{Rng, RngCore};
+
+use crate::solver::consumable::Consumable;
+use crate::solver::game_state::GameState;
+use crate::solver::round::{Action, Round};
+use crate::solver::shop::{OpenPack, Shop};
+use crate::solver::strategy::Strategy;
+use crate::solver::tag::Tag;
+
+/// Does everything at random, but always within the rules: plays or discards
+/// one to five random cards, skips half the blinds it can, and buys, picks and
+/// uses things on a coin flip.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RandomPlayer;
+
+/// `count` different random indices below `len`.
+fn pick(rng: &mut dyn RngCore, len: usize, count: usize) -> Vec<usize> {
+    index::sample(rng, len, count).into_vec()
+}
+
+impl Strategy for RandomPlayer {
+    fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+        let len = round.state.hand.len();
+        let count = rng.gen_range(1..=len.clamp(1, 5));
+        let selected = pick(rng, len, count.min(len));
+        if round.state.discards > 0 && rng.gen_bool(0.5) {
+            Action::Discard(selected)
+        } else {
+            Action::Play(selected)
+        }
+    }
+
+    fn use_consumable(
+        &mut self,
+        round: &Round,
+        rng: &mut dyn RngCore,
+    ) -> Option<(usize, Vec<usize>)> {
+        let state = &round.state;
+        if state.consumables.is_empty() || !rng.gen_bool(0.5) {
+            return None;
+        }
+        let index = rng.gen_range(0..state.consumables.len());
+        match state.consumables[index] {
+            Consumable::Planet(_) => Some((index, Vec::new())),
+            Consumable::Tarot(tarot) => {
+                let (min, max) = tarot.selection();
+                if min > state.hand.len() {
+                    return None;
+                }
+                let count = rng.gen_range(min..=max.min(state.hand.len()));
+                let selected = pick(rng, state.hand.len(), count);
+                tarot
+                    .validate(state, &selected)
+                    .is_ok()
+                    .then_some((index, selected))
+            }
+        }
+    }
+
+    fn skip_blind(&mut self, state: &GameState, tag: Tag, rng: &mut dyn RngCore) -> bool {
+        rng.gen_bool(0.5)
+    }
+
+    fn open_pack(&mut self, pack: &mut OpenPack, state: &mut GameState, rng: &mut dyn RngCore) {
+        while pack.picks > 0 && !pack.items.is_empty() && rng.gen_bool(0.5) {
+            let index = rng.gen_range(0..pack.items.len());
+            // Taking something there's no room for just wastes the pick
+            if pack.pick(index, state).is_err() {
+                pack.picks -= 1;
+            }
+        }
+    }
+
+    fn visit_shop(&mut self, shop: &mut Shop, state: &mut GameState, rng: &mut dyn RngCore) {
+        while rng.gen_bool(0.5) {
+            match rng.gen_range(0..3) {
+                0 if !shop.cards.is_empty() => {
+                    let index = rng.gen_range(0..shop.cards.len());
+                    shop.buy(index, state).ok();
+                }
+                1 if !shop.packs.is_empty() => {
+                    let index = rng.gen_range(0..shop.packs.len());
+                    if let Ok(mut pack) = shop.buy_pack(index, state, rng) {
+                        self.open_pack(&mut pack, state, rng);
+                    }
+                }
+                2 if !shop.vouchers.is_empty() => {
+                    let index = rng.gen_range(0..shop.vouchers.len());
+                    shop.buy_voucher(index, state).ok();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::run::RunSimulator;
+
+    #[test]
+    fn random_test() {
+        // Whatever it does, it never breaks the rules
+        for seed in 0..20 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let state = GameState::fresh_run(&mut rng);
+            RunSimulator::new(RandomPlayer)
+                .run(state, &mut rng)
+                .unwrap();
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Any player, with its shopping done by a `ShopOptimizer`.
+
+use rand::RngCore;
+
+use crate::solver::game_state::GameState;
+use crate::solver::round::{Action, Round};
+use crate::solver::shop::{OpenPack, Shop};
+use crate::solver::shop_optimizer::ShopOptimizer;
+use crate::solver::strategy::Strategy;
+use crate::solver::tag::Tag;
+
+/// Leaves everything to `strategy`, except the shop, where it takes whatever
+/// `optimizer` ranks best until leaving is.
+#[derive(Clone, Debug, Default)]
+pub struct Shopper<S> {
+    pub strategy: S,
+    pub optimizer: ShopOptimizer,
+}
+
+impl<S> Shopper<S> {
+    pub fn new(strategy: S, optimizer: ShopOptimizer) -> Self {
+        Self {
+            strategy,
+            optimizer,
+        }
+    }
+}
+
+impl<S: Strategy> Strategy for Shopper<S> {
+    fn choose_action(&mut self, round: &Round, rng: &mut dyn RngCore) -> Action {
+        self.strategy.choose_action(round, rng)
+    }
+
+    fn use_consumable(
+        &mut self,
+        round: &Round,
+        rng: &mut dyn RngCore,
+    ) -> Option<(usize, Vec<usize>)> {
+        self.strategy.use_consumable(round, rng)
+    }
+
+    fn skip_blind(&mut self, state: &GameState, tag: Tag, rng: &mut dyn RngCore) -> bool {
+        self.strategy.skip_blind(state, tag, rng)
+    }
+
+    fn open_pack(&mut self, pack: &mut OpenPack, state: &mut GameState, rng: &mut dyn RngCore) {
+        self.strategy.open_pack(pack, state, rng);
+    }
+
+    fn visit_shop(&mut self, shop: &mut Shop, state: &mut GameState, rng: &mut dyn RngCore) {
+        self.optimizer.shop(shop, state, rng);
+    }
+}
@@ -0,0 +1,200 @@
+//! Tags, the rewards for skipping a Small or Big Blind.
+
+use rand::seq::IteratorRandom;
+use rand::RngCore;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
+
+use crate::solver::cards::HandKind;
+use crate::solver::game_state::GameState;
+use crate::solver::joker::registry::JokerId;
+use crate::solver::joker::Rarity;
+use crate::solver::shop::{BoosterPack, OpenPack, PackKind, PackSize};
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Tag {
+    /// The next shop has a free Uncommon joker
+    #[strum(serialize = "Uncommon Tag")]
+    Uncommon,
+    /// The next shop has a free Rare joker
+    #[strum(serialize = "Rare Tag")]
+    Rare,
+    /// The next plain joker in the shop is free and Negative
+    #[strum(serialize = "Negative Tag")]
+    Negative,
+    /// The next plain joker in the shop is free and Foil
+    #[strum(serialize = "Foil Tag")]
+    Foil,
+    /// The next plain joker in the shop is free and Holographic
+    #[strum(serialize = "Holographic Tag")]
+    Holographic,
+    /// The next plain joker in the shop is free and Polychrome
+    #[strum(serialize = "Polychrome Tag")]
+    Polychrome,
+    /// $25 after the next Boss Blind is beaten
+    #[strum(serialize = "Investment Tag")]
+    Investment,
+    /// The next shop has an extra voucher
+    #[strum(serialize = "Voucher Tag")]
+    Voucher,
+    /// Rerolls the Boss Blind, which isn't modeled
+    #[strum(serialize = "Boss Tag")]
+    Boss,
+    /// A free Mega Standard pack
+    #[strum(serialize = "Standard Tag")]
+    Standard,
+    /// A free Mega Arcana pack
+    #[strum(serialize = "Charm Tag")]
+    Charm,
+    /// A free Mega Celestial pack
+    #[strum(serialize = "Meteor Tag")]
+    Meteor,
+    /// A free Mega Buffoon pack
+    #[strum(serialize = "Buffoon Tag")]
+    Buffoon,
+    /// $1 for every hand played this run
+    #[strum(serialize = "Handy Tag")]
+    Handy,
+    /// $1 for every discard left unused this run
+    #[strum(serialize = "Garbage Tag")]
+    Garbage,
+    /// A free Spectral pack, which isn't modeled
+    #[strum(serialize = "Ethereal Tag")]
+    Ethereal,
+    /// The next shop's cards and packs are all free
+    #[strum(serialize = "Coupon Tag")]
+    Coupon,
+    /// The next tag gained is gained twice
+    #[strum(serialize = "Double Tag")]
+    Double,
+    /// +3 hand size for the next round
+    #[strum(serialize = "Juggle Tag")]
+    Juggle,
+    /// Rerolls start at $0 in the next shop
+    #[strum(serialize = "D6 Tag")]
+    D6,
+    /// Up to two Common jokers, if there's room
+    #[strum(serialize = "Top-up Tag")]
+    TopUp,
+    /// $5 for every blind skipped this run
+    #[strum(serialize = "Speed Tag")]
+    Speed,
+    /// Levels up a random poker hand three times
+    #[strum(serialize = "Orbital Tag")]
+    Orbital,
+    /// Doubles the money held, up to $40
+    #[strum(serialize = "Economy Tag")]
+    Economy,
+}
+
+impl Tag {
+    /// The first ante this tag can be offered in.
+    pub fn min_ante(self) -> u32 {
+        match self {
+            Self::Negative
+            | Self::Standard
+            | Self::Meteor
+            | Self::Buffoon
+            | Self::Handy
+            | Self::Garbage
+            | Self::Ethereal
+            | Self::TopUp
+            | Self::Orbital => 2,
+            _ => 1,
+        }
+    }
+
+    /// A random tag that can be offered for skipping a blind in `ante`.
+    pub fn random(ante: u32, rng: &mut dyn RngCore) -> Self {
+        Self::iter()
+            .filter(|tag| tag.min_ante() <= ante)
+            .choose(rng)
+            .unwrap()
+    }
+
+    /// The free pack this tag opens, if it's a pack tag.
+    fn pack(self) -> Option<BoosterPack> {
+        let kind = match self {
+            Self::Standard => PackKind::Standard,
+            Self::Charm => PackKind::Arcana,
+            Self::Meteor => PackKind::Celestial,
+            Self::Buffoon => PackKind::Buffoon,
+            _ => return None,
+        };
+        Some(BoosterPack {
+            kind,
+            size: PackSize::Mega,
+        })
+    }
+
+    /// Gain this tag: apply it right away if it's immediate, open its pack if
+    /// it's a pack tag, or save it in `GameState::tags` for later.
+    pub(crate) fn gain(self, state: &mut GameState, rng: &mut dyn RngCore) -> Option<OpenPack> {
+        if let Some(pack) = self.pack() {
+            return Some(pack.open(state, rng));
+        }
+        match self {
+            Self::Handy => {
+                let hands: u32 = HandKind::iter()
+                    .map(|kind| state.hand_levels.times_played(kind))
+                    .sum();
+                state.money += i64::from(hands);
+            }
+            Self::Garbage => state.money += i64::from(state.unused_discards),
+            Self::Speed => state.money += 5 * i64::from(state.blinds_skipped),
+            Self::Economy => state.money += state.money.clamp(0, 40),
+            Self::TopUp => {
+                for _ in 0..2.min(state.free_joker_slots()) {
+                    let id = JokerId::iter()
+                        .filter(|id| id.rarity() == Rarity::Common)
+                        .choose(rng)
+                        .unwrap();
+                    state.add_joker(id.joker());
+                }
+            }
+            Self::Orbital => {
+                let kind = HandKind::iter()
+                    .filter(|kind| *kind < HandKind::FiveOfAKind)
+                    .choose(rng)
+                    .unwrap();
+                for _ in 0..3 {
+                    state.hand_levels.level_up(kind);
+                }
+            }
+            Self::Boss | Self::Ethereal => {}
+            _ => state.tags.push(self),
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn random_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            assert_eq!(Tag::random(1, &mut rng).min_ante(), 1);
+        }
+        assert_eq!("speed tag".parse(), Ok(Tag::Speed));
+    }
+}
@@ -0,0 +1,406 @@
+//! Working towards a particular kind of hand: which cards to throw away, and
+//! which consumables to use, to make it, and how likely that is to work.
+
+use itertools::Itertools;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use strum::IntoEnumIterator;
+
+use crate::solver::advisor::selections;
+use crate::solver::cards::{Card, Hand, HandKind, Rank, Suit};
+use crate::solver::consumable::Consumable;
+use crate::solver::deck_tracker::DeckTracker;
+use crate::solver::game_state::GameState;
+use crate::solver::hand_evaluator::{HandEvaluator, Options};
+use crate::solver::round::Action;
+use crate::solver::scorer::ScoringContext;
+
+/// How to go about making a kind of hand, and how it's expected to go.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetPlan {
+    /// Consumables to use first, by slot, with the cards in hand to use them
+    /// on. They're listed from the last slot to the first, so each slot is
+    /// still right when it comes to be used.
+    pub consumables: Vec<(usize, Vec<usize>)>,
+    /// Then, the play that makes the hand if it's made already, or the first
+    /// discard towards it. `None` if it can't be made with what's left.
+    pub action: Option<Action>,
+    /// The chance of making the hand before the discards run out
+    pub probability: f64,
+    /// How many discards it takes on average, when it's made
+    pub expected_discards: f64,
+}
+
+/// Plans the cheapest way to a kind of hand. Suit-changing tarots are used
+/// on off-suit cards when going for a flush of any sort, then each discard
+/// throws away (up to five of) the cards that don't fit the most promising
+/// part of the hand towards the kind: the largest group of a rank, the most
+/// common suit, or the fullest stretch of a straight. The chances come from
+/// following that plan over a sample of draws.
+///
+/// Draws are sampled from a fixed seed, so the same situation always gets
+/// the same plan.
+#[derive(Copy, Clone, Debug)]
+pub struct TargetPlanner {
+    pub evaluator_options: Options,
+    /// How many sequences of draws to follow the plan through
+    pub samples: usize,
+}
+
+impl TargetPlanner {
+    pub const DEFAULT_SAMPLES: usize = 256;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_evaluator_options(self, evaluator_options: Options) -> Self {
+        Self {
+            evaluator_options,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_samples(self, samples: usize) -> Self {
+        Self { samples, ..self }
+    }
+
+    /// How to make `kind` from `hand`, with the consumables and discards left
+    /// in `state` and the cards that might be drawn.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn plan(
+        &self,
+        hand: &[Card],
+        tracker: &DeckTracker,
+        state: &GameState,
+        kind: HandKind,
+    ) -> TargetPlan {
+        let mut hand = hand.to_vec();
+        let mut consumables = Vec::new();
+        if self.made(&hand, kind).is_none() {
+            consumables = convert_suits(&mut hand, &state.consumables, kind);
+        }
+
+        let action = if let Some(play) = self.made(&hand, kind) {
+            Some(Action::Play(play))
+        } else if state.discards > 0 && tracker.count() > 0 {
+            discard(&hand, kind).map(Action::Discard)
+        } else {
+            None
+        };
+        let (probability, expected_discards) = match action {
+            Some(Action::Play(_)) => (1.0, 0.0),
+            Some(Action::Discard(_)) => {
+                let mut rng = SmallRng::seed_from_u64(0);
+                let samples = self.samples.max(1);
+                let discards = (0..samples)
+                    .filter_map(|_| self.follow(&hand, tracker, state.discards, kind, &mut rng))
+                    .collect_vec();
+                let made = discards.len().max(1) as f64;
+                (
+                    discards.len() as f64 / samples as f64,
+                    f64::from(discards.iter().sum::<u32>()) / made,
+                )
+            }
+            None => (0.0, 0.0),
+        };
+
+        TargetPlan {
+            consumables,
+            action,
+            probability,
+            expected_discards,
+        }
+    }
+
+    /// The cards to play from `cards` to make `kind`, if they can. The
+    /// fewest cards that make it are picked, the way `ScoringContext::contains`
+    /// sees it, so a flush house makes a flush too.
+    pub fn made(&self, cards: &[Card], kind: HandKind) -> Option<Vec<usize>> {
+        selections(cards.len()).find(|indices| {
+            let played = indices.iter().map(|&index| cards[index]).collect_vec();
+            Hand::from_slice(&played)
+                .ok()
+                .and_then(|hand| {
+                    let (made, _, _) =
+                        HandEvaluator::evaluate_played_hand(hand.clone(), self.evaluator_options)?;
+                    Some(ScoringContext::new(made, &hand).contains(kind))
+                })
+                .unwrap_or(false)
+        })
+    }
+
+    /// Follow the plan through one sequence of draws, returning how many
+    /// discards it took to make `kind`, if it was made at all.
+    fn follow(
+        &self,
+        hand: &[Card],
+        tracker: &DeckTracker,
+        discards: u32,
+        kind: HandKind,
+        rng: &mut SmallRng,
+    ) -> Option<u32> {
+        let mut hand = hand.to_vec();
+        let mut unseen = tracker.clone();
+        for used in 0..=discards {
+            if self.made(&hand, kind).is_some() {
+                return Some(used);
+            }
+            if used == discards {
+                break;
+            }
+            let thrown = discard(&hand, kind)?;
+            let drawn = unseen.sample(thrown.len(), rng);
+            for card in &drawn {
+                unseen.see(*card);
+            }
+            hand = hand
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !thrown.contains(index))
+                .map(|(_, card)| *card)
+                .chain(drawn)
+                .collect();
+        }
+        None
+    }
+}
+
+impl Default for TargetPlanner {
+    fn default() -> Self {
+        Self {
+            evaluator_options: Options::empty(),
+            samples: Self::DEFAULT_SAMPLES,
+        }
+    }
+}
+
+/// Whether `kind` needs five cards of one suit.
+fn is_flush(kind: HandKind) -> bool {
+    matches!(
+        kind,
+        HandKind::Flush | HandKind::StraightFlush | HandKind::FlushHouse | HandKind::FlushFive
+    )
+}
+
+/// Turn off-suit cards in `cards` into the most common suit with any held
+/// tarots that do that, when going for a flush of some sort. Returns the
+/// tarots used, as in `TargetPlan::consumables`.
+fn convert_suits(
+    cards: &mut [Card],
+    consumables: &[Consumable],
+    kind: HandKind,
+) -> Vec<(usize, Vec<usize>)> {
+    let mut used = Vec::new();
+    if !is_flush(kind) {
+        return used;
+    }
+    let all = (0..cards.len()).collect_vec();
+    let Some(suit) = top_suit(cards, &all) else {
+        return used;
+    };
+
+    for (slot, consumable) in consumables.iter().enumerate().rev() {
+        let Consumable::Tarot(tarot) = consumable else {
+            continue;
+        };
+        if tarot.suit() != Some(suit) {
+            continue;
+        }
+        let suited = cards.iter().filter(|card| card.suit == suit).count();
+        let (_, most) = tarot.selection();
+        let selected = (0..cards.len())
+            .filter(|index| cards[*index].suit != suit)
+            .take(most.min(5_usize.saturating_sub(suited)))
+            .collect_vec();
+        if selected.is_empty() {
+            break;
+        }
+        for &index in &selected {
+            cards[index].suit = suit;
+        }
+        used.push((slot, selected));
+    }
+    used
+}
+
+/// The cards to throw away from `cards` while going for `kind`: the ones not
+/// worth keeping, lowest rank first, up to five of them.
+fn discard(cards: &[Card], kind: HandKind) -> Option<Vec<usize>> {
+    let keep = keep(cards, kind);
+    let mut thrown = (0..cards.len())
+        .filter(|index| !keep.contains(index))
+        .sorted_by_key(|index| cards[*index].rank)
+        .take(5)
+        .collect_vec();
+    thrown.sort_unstable();
+    (!thrown.is_empty()).then_some(thrown)
+}
+
+/// The cards in `cards` worth keeping while going for `kind`, by index.
+fn keep(cards: &[Card], kind: HandKind) -> Vec<usize> {
+    let all = (0..cards.len()).collect_vec();
+    let suited = |among: &[usize]| {
+        top_suit(cards, among).map_or_else(Vec::new, |suit| {
+            among
+                .iter()
+                .copied()
+                .filter(|index| cards[*index].suit == suit)
+                .collect_vec()
+        })
+    };
+    match kind {
+        HandKind::HighCard => all,
+        HandKind::Pair | HandKind::ThreeOfAKind | HandKind::FourOfAKind | HandKind::FiveOfAKind => {
+            by_rank(cards, &all, 1)
+        }
+        HandKind::TwoPair | HandKind::FullHouse => by_rank(cards, &all, 2),
+        HandKind::Flush => suited(&all),
+        HandKind::Straight => straight(cards, &all),
+        HandKind::StraightFlush => Suit::iter()
+            .map(|suit| {
+                let among = all
+                    .iter()
+                    .copied()
+                    .filter(|index| cards[*index].suit == suit)
+                    .collect_vec();
+                straight(cards, &among)
+            })
+            .max_by_key(Vec::len)
+            .unwrap_or_default(),
+        HandKind::FlushHouse => by_rank(cards, &suited(&all), 2),
+        HandKind::FlushFive => by_rank(cards, &suited(&all), 1),
+    }
+}
+
+/// The most common suit among the cards at `among`, the highest on a tie.
+fn top_suit(cards: &[Card], among: &[usize]) -> Option<Suit> {
+    among
+        .iter()
+        .map(|index| cards[*index].suit)
+        .counts()
+        .into_iter()
+        .max_by_key(|(suit, count)| (*count, *suit))
+        .map(|(suit, _)| suit)
+}
+
+/// The cards at `among` of the `groups` ranks there are most of, the highest
+/// on a tie.
+fn by_rank(cards: &[Card], among: &[usize], groups: usize) -> Vec<usize> {
+    let ranks = among
+        .iter()
+        .map(|index| cards[*index].rank)
+        .counts()
+        .into_iter()
+        .sorted_by_key(|(rank, count)| std::cmp::Reverse((*count, *rank)))
+        .take(groups)
+        .map(|(rank, _)| rank)
+        .collect_vec();
+    among
+        .iter()
+        .copied()
+        .filter(|index| ranks.contains(&cards[*index].rank))
+        .collect()
+}
+
+/// One card at `among` for each rank in the stretch of five ranks that has
+/// the most of them, the highest stretch on a tie. Aces count low too.
+fn straight(cards: &[Card], among: &[usize]) -> Vec<usize> {
+    let ranks = Rank::iter().collect_vec();
+    // The wheel, A-2-3-4-5, then every stretch from 2-6 up to T-A
+    let stretches = std::iter::once([Rank::Ace, Rank::Deuce, Rank::Three, Rank::Four, Rank::Five])
+        .chain(ranks.windows(5).map(|window| window.try_into().unwrap()));
+    stretches
+        .map(|stretch| {
+            among
+                .iter()
+                .copied()
+                .filter(|index| stretch.contains(&cards[*index].rank))
+                .unique_by(|index| cards[*index].rank)
+                .collect_vec()
+        })
+        .rev()
+        .max_by_key(Vec::len)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{CardCollection, CardView, Deck};
+    use crate::solver::consumable::Tarot;
+
+    fn dealt(hand: &str, discards: u32, consumables: Vec<Consumable>) -> GameState {
+        GameState::from_parts(
+            Deck::base_deck(),
+            CardCollection::from_idents(hand).view().to_vec(),
+            4,
+            discards,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            consumables,
+        )
+    }
+
+    #[test]
+    fn made_test() {
+        let state = dealt("AS KS QS JS 2S TD 4C 5H", 3, Vec::new());
+        let tracker = DeckTracker::from_state(&state);
+        let plan = TargetPlanner::new().plan(&state.hand, &tracker, &state, HandKind::Flush);
+        assert_eq!(plan.action, Some(Action::Play(vec![0, 1, 2, 3, 4])));
+        assert!((plan.probability - 1.0).abs() < f64::EPSILON);
+        assert!(plan.consumables.is_empty());
+
+        // A straight is there too, but not a pair
+        let planner = TargetPlanner::new();
+        assert_eq!(
+            planner.made(&state.hand, HandKind::Straight),
+            Some(vec![0, 1, 2, 3, 5])
+        );
+        assert_eq!(planner.made(&state.hand, HandKind::Pair), None);
+    }
+
+    #[test]
+    fn discard_test() {
+        // Nine spades left in 44 cards: the chance of drawing at least one
+        // in four is 1 - C(35, 4) / C(44, 4)
+        let state = dealt("AS KS QS 2S 3D 4C 5H 9D", 1, Vec::new());
+        let tracker = DeckTracker::from_state(&state);
+        let planner = TargetPlanner::new().with_samples(1000);
+        let plan = planner.plan(&state.hand, &tracker, &state, HandKind::Flush);
+        assert_eq!(plan.action, Some(Action::Discard(vec![4, 5, 6, 7])));
+        assert!((plan.probability - (1.0 - 52360.0 / 135_751.0)).abs() < 0.05);
+        assert!((plan.expected_discards - 1.0).abs() < f64::EPSILON);
+
+        // Without discards, there's no way
+        let state = dealt("AS KS QS 2S 3D 4C 5H 9D", 0, Vec::new());
+        let plan = planner.plan(&state.hand, &tracker, &state, HandKind::Flush);
+        assert_eq!(plan.action, None);
+        assert!(plan.probability.abs() < f64::EPSILON);
+
+        // Throw away everything but the pair of kings
+        let state = dealt("KS KD 2S 3D 4C 5H 9D JC", 2, Vec::new());
+        let plan = planner.plan(&state.hand, &tracker, &state, HandKind::ThreeOfAKind);
+        assert_eq!(plan.action, Some(Action::Discard(vec![2, 3, 4, 5, 6])));
+    }
+
+    #[test]
+    fn consumable_test() {
+        // The World turns two cards into spades to finish the flush
+        let state = dealt(
+            "AS KS QS 2D 3D 4C 5H 7C",
+            0,
+            vec![Consumable::Tarot(Tarot::TheWorld)],
+        );
+        let tracker = DeckTracker::from_state(&state);
+        let plan = TargetPlanner::new().plan(&state.hand, &tracker, &state, HandKind::Flush);
+        assert_eq!(plan.consumables, vec![(0, vec![3, 4])]);
+        assert_eq!(plan.action, Some(Action::Play(vec![0, 1, 2, 3, 4])));
+        assert!((plan.probability - 1.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,215 @@
+//! Remembering the results the advisor works out over and over: the best
+//! poker hand in a set of cards, and what a discard is expected to score.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::solver::cards::{Card, CardCollection, CardView, Hand, HandKind};
+use crate::solver::cardset::CardSet;
+use crate::solver::deck_tracker::DeckTracker;
+use crate::solver::game_state::GameState;
+use crate::solver::hand_evaluator::{HandEvaluator, Options};
+use crate::solver::scorer::ScoringOptions;
+
+/// The best poker hand found in a set of cards, and the cards that score it.
+type Evaluation = Option<(HandKind, CardSet)>;
+
+/// The parts of a discard's situation that its expected score depends on,
+/// besides the jokers and hand levels.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+struct DiscardKey {
+    kept: CardSet,
+    unseen: CardSet,
+    drawn: usize,
+    hands: u32,
+    discards: u32,
+    money: i64,
+    evaluator_options: u32,
+    scoring_options: u32,
+}
+
+/// A memo of best poker hands, keyed by the set of cards they're found in,
+/// and of discard EVs, keyed by the cards kept and the cards that might be
+/// drawn. Cards a `CardSet` can't tell apart aren't remembered: duplicates,
+/// and for discard EVs, enhanced cards too.
+///
+/// Discard EVs also depend on the jokers and hand levels, which aren't part
+/// of the key, so a table should only be shared by states that agree on
+/// them, such as the turns of one round. It's safe to share between threads.
+#[derive(Debug, Default)]
+pub struct TranspositionTable {
+    evaluations: Mutex<HashMap<(CardSet, u32), Evaluation>>,
+    discard_evs: Mutex<HashMap<DiscardKey, f64>>,
+}
+
+impl TranspositionTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `HandEvaluator::find_best_poker_hand`, remembered. The scoring cards
+    /// come in the order they're in in `cards`.
+    pub fn find_best_poker_hand(
+        &self,
+        cards: &[Card],
+        options: Options,
+    ) -> Option<(HandKind, Hand)> {
+        let find = || HandEvaluator::find_best_poker_hand(CardCollection::from(cards), options);
+        let Some(cardset) = CardSet::of_distinct(cards) else {
+            return find();
+        };
+        let key = (cardset, options.bits());
+
+        let found = self.evaluations.lock().unwrap().get(&key).copied();
+        let found = found.unwrap_or_else(|| {
+            let found = find().map(|(kind, scoring)| (kind, CardSet::from(scoring)));
+            self.evaluations.lock().unwrap().insert(key, found);
+            found
+        });
+        found.map(|(kind, scoring)| {
+            let scoring = cards
+                .iter()
+                .filter(|card| scoring.contains(**card))
+                .copied()
+                .collect();
+            (kind, scoring)
+        })
+    }
+
+    /// The expected score of drawing `drawn` of the cards unseen by `tracker`
+    /// to add to `kept`, as worked out by `discard_ev` the first time.
+    #[allow(clippy::too_many_arguments)]
+    pub fn discard_ev(
+        &self,
+        kept: &[Card],
+        tracker: &DeckTracker,
+        drawn: usize,
+        state: &GameState,
+        evaluator_options: Options,
+        scoring_options: ScoringOptions,
+        discard_ev: impl FnOnce() -> f64,
+    ) -> f64 {
+        let (Some(kept), Some(unseen)) =
+            (CardSet::of_plain(kept), CardSet::of_plain(tracker.view()))
+        else {
+            return discard_ev();
+        };
+        let key = DiscardKey {
+            kept,
+            unseen,
+            drawn,
+            hands: state.hands,
+            discards: state.discards,
+            money: state.money,
+            evaluator_options: evaluator_options.bits(),
+            scoring_options: scoring_options.bits(),
+        };
+
+        let remembered = self.discard_evs.lock().unwrap().get(&key).copied();
+        remembered.unwrap_or_else(|| {
+            let expected = discard_ev();
+            self.discard_evs.lock().unwrap().insert(key, expected);
+            expected
+        })
+    }
+
+    /// How many results are remembered.
+    pub fn len(&self) -> usize {
+        self.evaluations.lock().unwrap().len() + self.discard_evs.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget everything, e.g. when the jokers change.
+    pub fn clear(&self) {
+        self.evaluations.lock().unwrap().clear();
+        self.discard_evs.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::solver::advisor::Advisor;
+    use crate::solver::blind::Blind;
+    use crate::solver::cards::{Deck, Enhancement};
+
+    #[test]
+    fn find_best_test() {
+        let table = TranspositionTable::new();
+        let cards = CardCollection::from_idents("2S KD 7C KH 9S 2H 3D");
+        let (kind, scoring) = table
+            .find_best_poker_hand(cards.view(), Options::empty())
+            .unwrap();
+        assert_eq!(kind, HandKind::TwoPair);
+        assert_eq!(
+            scoring,
+            CardCollection::from_idents("2S KD KH 2H")
+                .view()
+                .iter()
+                .copied()
+                .collect()
+        );
+        assert_eq!(table.len(), 1);
+
+        // Enhancements don't change the best hand, but the cards handed back
+        // are the ones asked about
+        let mut enhanced = cards.view().to_vec();
+        enhanced[1].enhancement = Some(Enhancement::Bonus);
+        let (_, scoring) = table
+            .find_best_poker_hand(&enhanced, Options::empty())
+            .unwrap();
+        assert!(scoring.view().contains(&enhanced[1]));
+        assert_eq!(table.len(), 1);
+
+        // Duplicates can't be told apart, so aren't remembered
+        let duplicated = CardCollection::from_idents("2S 2S KD");
+        assert_eq!(
+            table.find_best_poker_hand(duplicated.view(), Options::empty()),
+            HandEvaluator::find_best_poker_hand(duplicated, Options::empty())
+        );
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn discard_ev_test() {
+        let state = GameState::from_parts(
+            Deck::base_deck(),
+            CardCollection::from_idents("AS AD 7C").view().to_vec(),
+            4,
+            3,
+            0,
+            1,
+            Blind::Small,
+            Vec::new(),
+            Vec::new(),
+        );
+        let tracker = DeckTracker::new(vec![Card::from_ident("AH"), Card::from_ident("3C")]);
+        let table = Arc::new(TranspositionTable::new());
+        let advisor = Advisor::new().with_table(Arc::clone(&table));
+        let expected = advisor.discard_ev(&state.hand, &[2], &tracker, &state);
+        assert_relative_eq!(expected, 126.5);
+        assert!(!table.is_empty());
+
+        // The second time around, it's remembered
+        let remembered = table.discard_ev(
+            &state.hand[..2],
+            &tracker,
+            1,
+            &state,
+            Options::empty(),
+            ScoringOptions::empty(),
+            || unreachable!(),
+        );
+        assert_relative_eq!(remembered, expected);
+
+        table.clear();
+        assert!(table.is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+//! Vouchers: permanent upgrades bought from the shop, one per ante.
+
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
+
+use crate::solver::game_state::GameState;
+
+/// Every voucher, each base voucher followed by its upgrade. An upgrade can
+/// only be redeemed once its base voucher has been.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Voucher {
+    /// +1 card slot in the shop
+    Overstock,
+    #[strum(serialize = "Overstock Plus")]
+    OverstockPlus,
+    /// Everything in the shop is 25% off
+    #[strum(serialize = "Clearance Sale")]
+    ClearanceSale,
+    /// Everything in the shop is 50% off
+    Liquidation,
+    /// Jokers in the shop get editions twice as often
+    Hone,
+    /// Jokers in the shop get editions four times as often
+    #[strum(serialize = "Glow Up")]
+    GlowUp,
+    /// Rerolls cost $2 less
+    #[strum(serialize = "Reroll Surplus")]
+    RerollSurplus,
+    /// Rerolls cost another $2 less
+    #[strum(serialize = "Reroll Glut")]
+    RerollGlut,
+    /// +1 consumable slot
+    #[strum(serialize = "Crystal Ball")]
+    CrystalBall,
+    /// Spectral cards can show up in Arcana packs, which isn't modeled
+    #[strum(serialize = "Omen Globe")]
+    OmenGlobe,
+    /// Celestial packs always hold the planet for the most played hand
+    Telescope,
+    /// Planets held give x1.5 mult when their hand is played
+    Observatory,
+    /// +1 hand every round
+    Grabber,
+    #[strum(serialize = "Nacho Tong")]
+    NachoTong,
+    /// +1 discard every round
+    Wasteful,
+    Recyclomancy,
+    /// Tarots show up in the shop more often
+    #[strum(serialize = "Tarot Merchant")]
+    TarotMerchant,
+    #[strum(serialize = "Tarot Tycoon")]
+    TarotTycoon,
+    /// Planets show up in the shop more often
+    #[strum(serialize = "Planet Merchant")]
+    PlanetMerchant,
+    #[strum(serialize = "Planet Tycoon")]
+    PlanetTycoon,
+    /// Interest is capped at $10 a round instead of $5
+    #[strum(serialize = "Seed Money")]
+    SeedMoney,
+    /// Interest is capped at $20 a round
+    #[strum(serialize = "Money Tree")]
+    MoneyTree,
+    /// Does nothing
+    Blank,
+    /// +1 joker slot
+    Antimatter,
+    /// Playing cards can show up in the shop
+    #[strum(serialize = "Magic Trick")]
+    MagicTrick,
+    /// Playing cards in the shop can have enhancements and seals
+    Illusion,
+    /// -1 ante, -1 hand every round
+    Hieroglyph,
+    /// -1 ante, -1 discard every round
+    Petroglyph,
+    /// Reroll the Boss Blind once per ante, which isn't modeled
+    #[strum(serialize = "Director's Cut")]
+    DirectorsCut,
+    /// Reroll the Boss Blind any number of times, which isn't modeled
+    Retcon,
+    /// +1 hand size
+    #[strum(serialize = "Paint Brush")]
+    PaintBrush,
+    Palette,
+}
+
+impl Voucher {
+    /// The base voucher this one upgrades, if it's an upgrade.
+    pub fn requires(self) -> Option<Self> {
+        let base = match self {
+            Self::OverstockPlus => Self::Overstock,
+            Self::Liquidation => Self::ClearanceSale,
+            Self::GlowUp => Self::Hone,
+            Self::RerollGlut => Self::RerollSurplus,
+            Self::OmenGlobe => Self::CrystalBall,
+            Self::Observatory => Self::Telescope,
+            Self::NachoTong => Self::Grabber,
+            Self::Recyclomancy => Self::Wasteful,
+            Self::TarotTycoon => Self::TarotMerchant,
+            Self::PlanetTycoon => Self::PlanetMerchant,
+            Self::MoneyTree => Self::SeedMoney,
+            Self::Antimatter => Self::Blank,
+            Self::Illusion => Self::MagicTrick,
+            Self::Petroglyph => Self::Hieroglyph,
+            Self::Retcon => Self::DirectorsCut,
+            Self::Palette => Self::PaintBrush,
+            _ => return None,
+        };
+        Some(base)
+    }
+
+    /// The price of every voucher in the shop, before discounts.
+    pub const COST: u32 = 10;
+
+    /// Apply the parts of this voucher that change the state's counts. The
+    /// rest are looked up from `GameState::vouchers` when they matter.
+    pub(crate) fn redeem(self, state: &mut GameState) {
+        match self {
+            Self::Overstock | Self::OverstockPlus => state.shop_slots += 1,
+            Self::CrystalBall => state.consumable_slots += 1,
+            Self::Grabber | Self::NachoTong => state.hands_per_round += 1,
+            Self::Wasteful | Self::Recyclomancy => state.discards_per_round += 1,
+            Self::Antimatter => state.joker_slots += 1,
+            Self::Hieroglyph => {
+                state.ante = state.ante.saturating_sub(1);
+                state.hands_per_round = state.hands_per_round.saturating_sub(1);
+            }
+            Self::Petroglyph => {
+                state.ante = state.ante.saturating_sub(1);
+                state.discards_per_round = state.discards_per_round.saturating_sub(1);
+            }
+            Self::PaintBrush | Self::Palette => state.hand_size += 1,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn tree_test() {
+        // Every base voucher is followed by exactly its own upgrade
+        let vouchers: Vec<_> = Voucher::iter().collect();
+        for pair in vouchers.chunks(2) {
+            assert_eq!(pair[0].requires(), None);
+            assert_eq!(pair[1].requires(), Some(pair[0]));
+        }
+        assert_eq!("director's cut".parse(), Ok(Voucher::DirectorsCut));
+    }
+}
@@ -0,0 +1,73 @@
+#ifndef SOLVER_H
+#define SOLVER_H
+
+/* Generated by cbindgen from solver-ffi; don't edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Bumped whenever a function's signature or behaviour changes in a way that
+// would break existing callers.
+#define SOLVER_ABI_VERSION 1
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// The `SOLVER_ABI_VERSION` this library was built with, to check against the
+// header a caller was compiled with.
+uint32_t solver_abi_version(void);
+
+// The kind of poker hand one to five played cards make, like `"FullHouse"`,
+// or `NULL` if they aren't valid.
+//
+// # Safety
+//
+// `cards` must be `NULL` or point to a NUL-terminated string that lives for
+// the length of the call.
+char *solver_evaluate(const char *cards);
+
+// Write what one to five played cards score, with `jokers` held, to
+// `score`. Returns 0 on success, or -1 without touching `score` on failure.
+//
+// # Safety
+//
+// `cards` and `jokers` must be `NULL` or point to NUL-terminated strings
+// that live for the length of the call, and `score` must be `NULL` or valid
+// to write a `double` to.
+int32_t solver_score(const char *cards, const char *jokers, double *score);
+
+// The best thing to do with a hand, given as JSON like
+// `{"hand": ["AS", "KD", ...], "jokers": ["Joker"], "hands": 4,
+// "discards": 3, "seen": [], "samples": 200}`, where only `hand` is
+// required. The advice comes back as JSON like
+// `{"action": "discard", "cards": ["2C", "7D"], "expected": 312.5}`, or
+// `NULL` on failure.
+//
+// # Safety
+//
+// `request` must be `NULL` or point to a NUL-terminated string that lives
+// for the length of the call.
+char *solver_advise(const char *request);
+
+// Why the last call on this thread failed, or `NULL` if none has. The
+// string belongs to the library, and lasts until the next failure on this
+// thread; it must not be freed.
+const char *solver_last_error(void);
+
+// Free a string returned by this library. Does nothing given `NULL`.
+//
+// # Safety
+//
+// `text` must be `NULL` or a string returned by this library that hasn't
+// been freed yet.
+void solver_string_free(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SOLVER_H */
@@ -0,0 +1,252 @@
+//! The solver behind a C ABI, for tools and overlays written in C, C++ or C#.
+//! The header is `include/solver.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/solver.h` from this
+//! crate's directory; a test fails when it's out of date.
+//!
+//! Strings go in and come out as NUL-terminated UTF-8. Cards are their idents
+//! separated by spaces, like `"AS KD+glass 7C"`, and jokers their names
+//! separated by commas, like `"Joker, Greedy Joker"`. Anything more involved
+//! than that, like the state `solver_advise` looks at, is passed as JSON.
+//!
+//! Every string returned is owned by the caller, and must be given back to
+//! `solver_string_free`. When a function fails, it returns `NULL` or a
+//! negative number, and `solver_last_error` says why.
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use solver_core::solver::bindings::{self, parse_cards, parse_joker_list, AdviseRequest};
+
+/// Bumped whenever a function's signature or behaviour changes in a way that
+/// would break existing callers.
+pub const SOLVER_ABI_VERSION: u32 = 1;
+
+thread_local! {
+    /// Why the last call on this thread failed
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Run `call`, remembering why it failed, or that it panicked, for
+/// `solver_last_error`, and returning `failed` instead. Panics can't be
+/// allowed to unwind into C.
+fn guard<T>(failed: T, call: impl FnOnce() -> Result<T, Box<dyn Error>>) -> T {
+    let error = match panic::catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(value)) => return value,
+        Ok(Err(error)) => error.to_string(),
+        Err(_) => "the solver panicked".to_owned(),
+    };
+    // Interior NULs can only come from the caller's own strings
+    let error = CString::new(error.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(error));
+    failed
+}
+
+/// The string at `text`, which is `NULL` or as `solver_evaluate` describes.
+unsafe fn read_str<'a>(text: *const c_char) -> Result<&'a str, String> {
+    if text.is_null() {
+        return Err("a string argument was NULL".to_owned());
+    }
+    // SAFETY: the caller promises `text` is NUL-terminated and outlives the call
+    let text = unsafe { CStr::from_ptr(text) };
+    text.to_str()
+        .map_err(|_| "a string argument wasn't UTF-8".to_owned())
+}
+
+fn into_raw(text: String) -> Result<*mut c_char, Box<dyn Error>> {
+    Ok(CString::new(text)?.into_raw())
+}
+
+/// The `SOLVER_ABI_VERSION` this library was built with, to check against the
+/// header a caller was compiled with.
+#[no_mangle]
+pub extern "C" fn solver_abi_version() -> u32 {
+    SOLVER_ABI_VERSION
+}
+
+/// The kind of poker hand one to five played cards make, like `"FullHouse"`,
+/// or `NULL` if they aren't valid.
+///
+/// # Safety
+///
+/// `cards` must be `NULL` or point to a NUL-terminated string that lives for
+/// the length of the call.
+#[no_mangle]
+pub unsafe extern "C" fn solver_evaluate(cards: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        // SAFETY: passed on from the caller
+        let cards = parse_cards(unsafe { read_str(cards) }?)?;
+        let (kind, _, _) = bindings::evaluate(&cards)?;
+        into_raw(format!("{kind:?}"))
+    })
+}
+
+/// Write what one to five played cards score, with `jokers` held, to
+/// `score`. Returns 0 on success, or -1 without touching `score` on failure.
+///
+/// # Safety
+///
+/// `cards` and `jokers` must be `NULL` or point to NUL-terminated strings
+/// that live for the length of the call, and `score` must be `NULL` or valid
+/// to write a `double` to.
+#[no_mangle]
+pub unsafe extern "C" fn solver_score(
+    cards: *const c_char,
+    jokers: *const c_char,
+    score: *mut f64,
+) -> i32 {
+    guard(-1, || {
+        if score.is_null() {
+            return Err("the score pointer was NULL".into());
+        }
+        // SAFETY: passed on from the caller
+        let cards = parse_cards(unsafe { read_str(cards) }?)?;
+        // SAFETY: passed on from the caller
+        let jokers = parse_joker_list(unsafe { read_str(jokers) }?)?;
+        let value = bindings::score(&cards, &jokers)?.value();
+        // SAFETY: the caller promises `score` is valid to write to
+        unsafe {
+            score.write(value);
+        }
+        Ok(0)
+    })
+}
+
+/// The best thing to do with a hand, given as JSON like
+/// `{"hand": ["AS", "KD", ...], "jokers": ["Joker"], "hands": 4,
+/// "discards": 3, "seen": [], "samples": 200}`, where only `hand` is
+/// required. The advice comes back as JSON like
+/// `{"action": "discard", "cards": ["2C", "7D"], "expected": 312.5}`, or
+/// `NULL` on failure.
+///
+/// # Safety
+///
+/// `request` must be `NULL` or point to a NUL-terminated string that lives
+/// for the length of the call.
+#[no_mangle]
+pub unsafe extern "C" fn solver_advise(request: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        // SAFETY: passed on from the caller
+        let request: AdviseRequest = serde_json::from_str(unsafe { read_str(request) }?)
+            .map_err(|err| format!("invalid request: {err}"))?;
+        let advice = bindings::advise(&request)?;
+        into_raw(advice.to_json())
+    })
+}
+
+/// Why the last call on this thread failed, or `NULL` if none has. The
+/// string belongs to the library, and lasts until the next failure on this
+/// thread; it must not be freed.
+#[no_mangle]
+pub extern "C" fn solver_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |error| error.as_ptr())
+    })
+}
+
+/// Free a string returned by this library. Does nothing given `NULL`.
+///
+/// # Safety
+///
+/// `text` must be `NULL` or a string returned by this library that hasn't
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn solver_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: the caller promises this came from `CString::into_raw`
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    /// Take ownership of a string the library returned.
+    fn take(text: *mut c_char) -> String {
+        assert!(!text.is_null());
+        // SAFETY: only called on strings from `CString::into_raw`
+        let owned = unsafe { CString::from_raw(text) };
+        owned.into_string().unwrap()
+    }
+
+    fn last_error() -> String {
+        // SAFETY: `solver_last_error` is only called after a failure
+        let error = unsafe { CStr::from_ptr(solver_last_error()) };
+        error.to_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn evaluate_test() {
+        let cards = CString::new("AS AH AD KS KH").unwrap();
+        // SAFETY: `cards` is NUL-terminated
+        let kind = take(unsafe { solver_evaluate(cards.as_ptr()) });
+        assert_eq!(kind, "FullHouse");
+
+        let cards = CString::new("AS ZZ").unwrap();
+        // SAFETY: `cards` is NUL-terminated
+        assert!(unsafe { solver_evaluate(cards.as_ptr()) }.is_null());
+        assert!(last_error().contains("ZZ"));
+        // SAFETY: `NULL` is allowed
+        assert!(unsafe { solver_evaluate(ptr::null()) }.is_null());
+    }
+
+    #[test]
+    fn score_test() {
+        let cards = CString::new("AS AH").unwrap();
+        let jokers = CString::new("Joker").unwrap();
+        let mut score = 0.0;
+        // SAFETY: both strings are NUL-terminated, and `score` is a valid double
+        let status = unsafe { solver_score(cards.as_ptr(), jokers.as_ptr(), &raw mut score) };
+        assert_eq!(status, 0);
+        // (10 + 11 + 11) chips * (2 + 4) mult
+        assert_relative_eq!(score, 192.0);
+
+        let jokers = CString::new("Not A Joker").unwrap();
+        // SAFETY: as above
+        let status = unsafe { solver_score(cards.as_ptr(), jokers.as_ptr(), &raw mut score) };
+        assert_eq!(status, -1);
+        assert_eq!(last_error(), "unknown joker `Not A Joker`");
+    }
+
+    #[test]
+    fn header_test() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let config = cbindgen::Config::from_file(format!("{dir}/cbindgen.toml")).unwrap();
+        let mut header = Vec::new();
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(format!("{dir}/src/lib.rs"))
+            .generate()
+            .unwrap()
+            .write(&mut header);
+        let committed = std::fs::read_to_string(format!("{dir}/include/solver.h")).unwrap();
+        assert!(
+            String::from_utf8(header).unwrap() == committed,
+            "include/solver.h is out of date; regenerate it as cbindgen.toml says"
+        );
+    }
+
+    #[test]
+    fn advise_test() {
+        let request =
+            CString::new(r#"{"hand": ["AS", "KS", "QS", "JS", "TS", "2C", "3D", "4H"]}"#).unwrap();
+        // SAFETY: `request` is NUL-terminated
+        let advice = take(unsafe { solver_advise(request.as_ptr()) });
+        assert_eq!(
+            advice,
+            r#"{"action":"play","cards":["AS","KS","QS","JS","TS"],"expected":1208.0}"#
+        );
+
+        let request = CString::new(r#"{"hand": [], "stake": "gold"}"#).unwrap();
+        // SAFETY: as above
+        assert!(unsafe { solver_advise(request.as_ptr()) }.is_null());
+        assert!(last_error().starts_with("invalid request"));
+    }
+}
@@ -0,0 +1,127 @@
+//! The solver as a native Node.js module, so an overlay can call it in-process
+//! rather than starting the CLI for every decision. Cards are passed as their
+//! idents separated by spaces, like `"AS KD+glass 7C"`, and jokers as arrays
+//! of their names.
+//!
+//! Advising can take a while, so `advise` runs on libuv's thread pool and
+//! returns a promise; `adviseSync` blocks the event loop instead.
+
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, Task};
+use napi_derive::napi;
+
+use solver_core::prelude::Error;
+use solver_core::solver::bindings::{self, parse_cards, parse_jokers, AdviseRequest, Move};
+
+/// What's known about a hand to be advised on. Only the hand is required;
+/// the rest default to the start of a round with no jokers.
+#[napi(object)]
+pub struct AdviseOptions {
+    pub hand: String,
+    pub jokers: Option<Vec<String>>,
+    /// How many hands are left this round, counting the next one
+    pub hands: Option<u32>,
+    /// How many discards are left this round
+    pub discards: Option<u32>,
+    /// Cards already played or discarded this round, which can't be drawn
+    pub seen: Option<String>,
+    /// How many draws to sample for each discard, when there are too many to
+    /// try them all
+    pub samples: Option<u32>,
+}
+
+/// The best thing to do with a hand.
+#[napi(object)]
+pub struct Advice {
+    /// Either `"play"` or `"discard"`
+    pub action: String,
+    /// The cards to play or discard, in order
+    pub cards: Vec<String>,
+    /// The score of a play, or the expected score of a discard
+    pub expected: f64,
+}
+
+impl From<bindings::Advice> for Advice {
+    fn from(advice: bindings::Advice) -> Self {
+        let action = match advice.action {
+            Move::Play => "play",
+            Move::Discard => "discard",
+        };
+        Self {
+            action: action.to_owned(),
+            cards: advice.cards.iter().map(|card| card.ident()).collect(),
+            expected: advice.expected,
+        }
+    }
+}
+
+fn reason(err: &Error) -> napi::Error {
+    napi::Error::from_reason(err.to_string())
+}
+
+fn request(options: AdviseOptions) -> napi::Result<AdviseRequest> {
+    let defaults = AdviseRequest::default();
+    Ok(AdviseRequest {
+        hand: parse_cards(&options.hand).map_err(|err| reason(&err))?,
+        jokers: options.jokers.unwrap_or_default(),
+        hands: options.hands.unwrap_or(defaults.hands),
+        discards: options.discards.unwrap_or(defaults.discards),
+        seen: parse_cards(&options.seen.unwrap_or_default()).map_err(|err| reason(&err))?,
+        samples: options
+            .samples
+            .map_or(defaults.samples, |samples| samples as usize),
+    })
+}
+
+/// Advises on a request off the main thread, for `advise`.
+pub struct AdviseTask {
+    request: AdviseRequest,
+}
+
+impl Task for AdviseTask {
+    type Output = bindings::Advice;
+    type JsValue = Advice;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        bindings::advise(&self.request).map_err(|err| reason(&err))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// The kind of poker hand one to five played cards make, like `"FullHouse"`.
+#[napi]
+#[allow(clippy::needless_pass_by_value)] // N-API only passes owned strings
+pub fn evaluate(cards: String) -> napi::Result<String> {
+    let cards = parse_cards(&cards).map_err(|err| reason(&err))?;
+    let (kind, _, _) = bindings::evaluate(&cards).map_err(|err| reason(&err))?;
+    Ok(format!("{kind:?}"))
+}
+
+/// What one to five played cards score with `jokers` held.
+#[napi]
+#[allow(clippy::needless_pass_by_value)] // N-API only passes owned strings
+pub fn score(cards: String, jokers: Option<Vec<String>>) -> napi::Result<f64> {
+    let cards = parse_cards(&cards).map_err(|err| reason(&err))?;
+    let jokers = jokers.unwrap_or_default();
+    let jokers = parse_jokers(jokers.iter().map(String::as_str)).map_err(|err| reason(&err))?;
+    let score = bindings::score(&cards, &jokers).map_err(|err| reason(&err))?;
+    Ok(score.value())
+}
+
+/// A promise of the best thing to do with a hand.
+#[napi]
+pub fn advise(options: AdviseOptions) -> napi::Result<AsyncTask<AdviseTask>> {
+    Ok(AsyncTask::new(AdviseTask {
+        request: request(options)?,
+    }))
+}
+
+/// The best thing to do with a hand, blocking until it's worked out.
+#[napi]
+pub fn advise_sync(options: AdviseOptions) -> napi::Result<Advice> {
+    let advice = bindings::advise(&request(options)?).map_err(|err| reason(&err))?;
+    Ok(advice.into())
+}
@@ -0,0 +1,61 @@
+//! Loads the built module into Node and calls each function once, since the
+//! library can't be linked into a Rust test. Skipped where there's no `node`.
+
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::PathBuf;
+use std::process::Command;
+use std::{env, fs};
+
+const SCRIPT: &str = r#"
+const solver = require(process.argv[1]);
+const assert = require("assert");
+assert.strictEqual(solver.evaluate("AS AH AD KS KH"), "FullHouse");
+assert.strictEqual(solver.score("AS AH", ["Joker"]), 192);
+assert.throws(() => solver.evaluate("AS ZZ"));
+const advice = solver.adviseSync({ hand: "AS KS QS JS TS 2C 3D 4H" });
+assert.deepStrictEqual(advice, {
+  action: "play",
+  cards: ["AS", "KS", "QS", "JS", "TS"],
+  expected: 1208,
+});
+solver.advise({ hand: "AS KS QS JS TS 2C 3D 4H", discards: 0 }).then((advice) => {
+  assert.strictEqual(advice.action, "play");
+  console.log("ok");
+});
+"#;
+
+/// The library cargo built alongside this test, under `target/<profile>`.
+fn library() -> PathBuf {
+    let test = env::current_exe().unwrap();
+    let profile = test.parent().unwrap().parent().unwrap();
+    profile.join(format!("{DLL_PREFIX}solver_node{DLL_SUFFIX}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_test() {
+        if Command::new("node").arg("--version").output().is_err() {
+            eprintln!("node isn't installed, so the module can't be loaded");
+            return;
+        }
+        // Node only loads native modules ending in `.node`
+        let module = env::temp_dir().join(format!("solver-{}.node", std::process::id()));
+        fs::copy(library(), &module).unwrap();
+        let output = Command::new("node")
+            .arg("-e")
+            .arg(SCRIPT)
+            .arg(&module)
+            .output()
+            .unwrap();
+        fs::remove_file(&module).unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+    }
+}
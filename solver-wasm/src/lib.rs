@@ -0,0 +1,84 @@
+//! The solver exposed to JavaScript, so a hand calculator can run in the
+//! browser without a server. Cards are passed as their idents separated by
+//! spaces, like `"AS KD+glass 7C"`, and jokers as their names separated by
+//! commas, like `"Joker, Greedy Joker"`.
+
+use wasm_bindgen::prelude::*;
+
+use solver_core::solver::bindings::{self, parse_cards, parse_joker_list, AdviseRequest};
+
+/// How many draws `advise` samples for each discard, when there are too many
+/// to try them all. The same as the CLI's `best-play`.
+const SAMPLES: usize = 200;
+
+/// The kind of poker hand one to five played cards make, like `"FullHouse"`.
+#[wasm_bindgen]
+pub fn evaluate(cards: &str) -> Result<String, JsError> {
+    let (kind, _, _) = bindings::evaluate(&parse_cards(cards)?)?;
+    Ok(format!("{kind:?}"))
+}
+
+/// What one to five played cards score with `jokers` held.
+#[wasm_bindgen]
+pub fn score(cards: &str, jokers: &str) -> Result<f64, JsError> {
+    let score = bindings::score(&parse_cards(cards)?, &parse_joker_list(jokers)?)?;
+    Ok(score.value())
+}
+
+/// The best thing to do with `hand`, with `hands` and `discards` left and the
+/// `seen` cards already gone from the deck, as JSON like
+/// `{"action":"discard","cards":["2C","7D"],"expected":312.5}`.
+#[wasm_bindgen]
+pub fn advise(
+    hand: &str,
+    jokers: &str,
+    hands: u32,
+    discards: u32,
+    seen: &str,
+) -> Result<String, JsError> {
+    let request = AdviseRequest {
+        hand: parse_cards(hand)?,
+        jokers: jokers.split(',').map(str::to_owned).collect(),
+        hands,
+        discards,
+        seen: parse_cards(seen)?,
+        samples: SAMPLES,
+    };
+    let advice = bindings::advise(&request)?;
+    Ok(advice.to_json())
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    // Errors become `JsError`s, which can only be made in a JavaScript
+    // runtime, so only the successes are tested through the exports here
+
+    #[test]
+    fn evaluate_test() {
+        assert_eq!(evaluate("AS AH AD KS KH").unwrap(), "FullHouse");
+        assert_eq!(evaluate("7C").unwrap(), "HighCard");
+        assert!(bindings::evaluate(&parse_cards("AS AH AD KS KH 2C").unwrap()).is_err());
+        assert!(parse_cards("AS ZZ").is_err());
+    }
+
+    #[test]
+    fn score_test() {
+        // (10 + 11 + 11) chips * 2 mult
+        assert_relative_eq!(score("AS AH", "").unwrap(), 64.0);
+        assert_relative_eq!(score("AS AH", "Joker").unwrap(), 192.0);
+        assert_relative_eq!(score("AS AH", " Joker, Joker ").unwrap(), 320.0);
+        assert!(parse_joker_list("Joker, Not A Joker").is_err());
+    }
+
+    #[test]
+    fn advise_test() {
+        assert_eq!(
+            advise("AS KS QS JS TS 2C 3D 4H", "", 4, 3, "").unwrap(),
+            r#"{"action":"play","cards":["AS","KS","QS","JS","TS"],"expected":1208.0}"#
+        );
+    }
+}